@@ -2,4 +2,7 @@ extern crate build;
 fn main() {
     build::link("shell32", true);
     build::link("d2d1", true);
+    build::link("msimg32", true);
+    build::link("dwmapi", true);
+    build::link("wtsapi32", true);
 }
\ No newline at end of file
@@ -73,8 +73,9 @@ nwg_template!(
             let t = nwg::ButtonT {
                 text: text.get_text(),
                 position: (width_offset, height_offset), size: (195, 30), 
-                visible: true, disabled: false, 
-                parent:  MainWindow, font: Some(TextFont)
+                visible: true, disabled: false, tab_stop: true, group: false,
+                parent:  MainWindow, font: Some(TextFont),
+                command_link: false, note: None
             };
             ui.pack_control(&next_control_id, t);
 
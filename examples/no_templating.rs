@@ -32,6 +32,7 @@ pub fn setup_ui(ui: &Ui<AppId>) -> Result<(), Error> {
         family: "Arial", size: 27,
         weight: nwg::constants::FONT_WEIGHT_NORMAL,
         decoration: nwg::constants::FONT_DECO_NORMAL,
+        quality: nwg::constants::FontQuality::ClearType,
     };
 
     // nwg_font!(family="Arial"; size=17)
@@ -39,6 +40,7 @@ pub fn setup_ui(ui: &Ui<AppId>) -> Result<(), Error> {
         family: "Arial", size: 17,
         weight: nwg::constants::FONT_WEIGHT_NORMAL,
         decoration: nwg::constants::FONT_DECO_NORMAL,
+        quality: nwg::constants::FontQuality::ClearType,
     };
 
     // nwg_window!( title="Template Example"; size=(280, 105))
@@ -46,7 +48,7 @@ pub fn setup_ui(ui: &Ui<AppId>) -> Result<(), Error> {
         title: "No template",
         position: (100, 100), size: (280, 105),
         resizable: false, visible: true, disabled: false,
-        exit_on_close: true
+        exit_on_close: true, icon_big: None, icon_small: None
     };
 
     // nwg_label!( parent="MainWindow"; [...] font=Some("TextFont") )
@@ -55,14 +57,14 @@ pub fn setup_ui(ui: &Ui<AppId>) -> Result<(), Error> {
         position: (5,15), size: (80, 25),
         visible: true, disabled: false,
         align: nwg::constants::HTextAlign::Left,
-        parent: MainWindow, font: Some(TextFont)
+        parent: MainWindow, font: Some(TextFont), auto_tooltip: false
     };
 
     // nwg_textinput!( parent="MainWindow"; [..] font=Some("TextFont") )
     let tedit = nwg::TextInputT::<_, &'static str, _> {
         text: "",
         position: (85,13), size: (185,22),
-        visible: true, disabled: false, readonly: false, password: false,
+        visible: true, disabled: false, tab_stop: true, group: false, readonly: false, password: false,
         limit: 32_767, placeholder: None,
         parent: MainWindow, font: Some(TextFont)
     };
@@ -71,8 +73,9 @@ pub fn setup_ui(ui: &Ui<AppId>) -> Result<(), Error> {
     let hellbtn = nwg::ButtonT {
         text: "Hello World!",
         position: (5, 45), size: (270, 50),
-        visible: true, disabled: false,
-        parent: MainWindow, font: Some(MainFont)
+        visible: true, disabled: false, tab_stop: true, group: false,
+        parent: MainWindow, font: Some(MainFont),
+        command_link: false, note: None
     };
 
     // resources: 
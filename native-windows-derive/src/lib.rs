@@ -0,0 +1,147 @@
+/*!
+    Derive macros for native-windows-gui.
+
+    `#[derive(NwgId)]` implements the traits required by a `Ui` identifier (`Clone`, `Copy`, `PartialEq`, `Eq` and `Hash`)
+    on an enum without fields:
+
+    ```rust,ignore
+    #[macro_use] extern crate native_windows_derive;
+
+    #[derive(NwgId)]
+    enum MyId { MainWindow, OkButton, NameField }
+    ```
+
+    The derive expands to:
+
+    ```rust,ignore
+    impl Clone for MyId { fn clone(&self) -> MyId { *self } }
+    impl Copy for MyId {}
+    impl PartialEq for MyId { fn eq(&self, other: &MyId) -> bool { self.nwg_id_index() == other.nwg_id_index() } }
+    impl Eq for MyId {}
+    impl Hash for MyId { fn hash<H: Hasher>(&self, state: &mut H) { self.nwg_id_index().hash(state) } }
+    ```
+
+    where `nwg_id_index` returns the position of the variant in the enum.
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenTree, Delimiter};
+
+/**
+    Implement `Clone`, `Copy`, `PartialEq`, `Eq` and `Hash` on an enum without fields so that it can be used as a `Ui` identifier.
+*/
+#[proc_macro_derive(NwgId)]
+pub fn derive_nwg_id(input: TokenStream) -> TokenStream {
+    let (name, variants) = match parse_enum(input) {
+        Ok(v) => v,
+        Err(e) => { return compile_error(e); }
+    };
+
+    let arms: Vec<String> = variants.iter().enumerate()
+        .map(|(i, v)| format!("{}::{} => {}usize,", name, v, i))
+        .collect();
+
+    let code = format!("
+        impl {name} {{
+            #[doc(hidden)]
+            #[inline]
+            fn nwg_id_index(&self) -> usize {{
+                match *self {{ {arms} }}
+            }}
+        }}
+
+        impl ::std::clone::Clone for {name} {{
+            fn clone(&self) -> {name} {{ *self }}
+        }}
+
+        impl ::std::marker::Copy for {name} {{}}
+
+        impl ::std::cmp::PartialEq for {name} {{
+            fn eq(&self, other: &{name}) -> bool {{ self.nwg_id_index() == other.nwg_id_index() }}
+        }}
+
+        impl ::std::cmp::Eq for {name} {{}}
+
+        impl ::std::hash::Hash for {name} {{
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {{
+                ::std::hash::Hash::hash(&self.nwg_id_index(), state)
+            }}
+        }}
+    ", name=name, arms=arms.join(" "));
+
+    code.parse().expect("Failed to parse the NwgId expansion")
+}
+
+/**
+    Return the name and the variants of the enum. Attributes and visibility are skipped.
+    Return an error message if the input is not an enum, if it has generic parameters or if a variant has fields.
+*/
+fn parse_enum(input: TokenStream) -> Result<(String, Vec<String>), &'static str> {
+    let mut tokens = input.into_iter();
+
+    // Skip the attributes and the visibility until the `enum` keyword
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ref i)) if i.to_string() == "enum" => break,
+            Some(TokenTree::Ident(ref i)) if i.to_string() == "struct" || i.to_string() == "union" => {
+                return Err("NwgId can only be derived on an enum");
+            },
+            Some(_) => {},
+            None => { return Err("NwgId can only be derived on an enum"); }
+        }
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(i)) => i.to_string(),
+        _ => { return Err("Expected the name of the enum"); }
+    };
+
+    let body = match tokens.next() {
+        Some(TokenTree::Group(ref g)) if g.delimiter() == Delimiter::Brace => g.stream(),
+        _ => { return Err("NwgId cannot be derived on an enum with generic parameters"); }
+    };
+
+    let mut variants = Vec::new();
+    let mut expect_variant = true;
+    for token in body {
+        match token {
+            // Variant attributes (ex: doc comments) are a `#` followed by a bracket group
+            TokenTree::Punct(ref p) if p.as_char() == '#' => {},
+            TokenTree::Group(ref g) if g.delimiter() == Delimiter::Bracket => {},
+            TokenTree::Punct(ref p) if p.as_char() == ',' => { expect_variant = true; },
+            TokenTree::Ident(ref i) if expect_variant => {
+                variants.push(i.to_string());
+                expect_variant = false;
+            },
+            TokenTree::Group(_) => { return Err("NwgId can only be derived on an enum without fields"); },
+            TokenTree::Punct(ref p) if p.as_char() == '=' => { return Err("NwgId cannot be derived on an enum with explicit discriminants"); },
+            _ => { return Err("Unexpected token in the enum body"); }
+        }
+    }
+
+    if variants.is_empty() {
+        return Err("NwgId cannot be derived on an empty enum");
+    }
+
+    Ok((name, variants))
+}
+
+fn compile_error(msg: &'static str) -> TokenStream {
+    format!("compile_error!({:?});", msg).parse().unwrap()
+}
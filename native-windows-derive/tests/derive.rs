@@ -0,0 +1,43 @@
+#[macro_use] extern crate native_windows_derive;
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(NwgId, Debug)]
+pub enum AppId {
+    MainWindow,
+    /// Doc comments on the variants are allowed
+    OkButton,
+    NameField,
+}
+
+fn hash_of(id: &AppId) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_nwg_id_traits() {
+    let id = AppId::OkButton;
+    let copy = id;
+    let clone = Clone::clone(&id);
+
+    assert!(copy == AppId::OkButton && clone == AppId::OkButton, "Copies should be equal");
+    assert!(AppId::MainWindow != AppId::NameField, "Different variants should not be equal");
+    assert!(hash_of(&AppId::OkButton) == hash_of(&clone), "Equal values should have the same hash");
+    assert!(hash_of(&AppId::MainWindow) != hash_of(&AppId::NameField), "Hash should depend on the variant");
+}
+
+#[test]
+fn test_nwg_id_map_key() {
+    let mut map = HashMap::new();
+    map.insert(AppId::MainWindow, "window");
+    map.insert(AppId::OkButton, "button");
+    map.insert(AppId::OkButton, "button 2");
+
+    assert!(map.len() == 2, "Map size do not match");
+    assert!(map.get(&AppId::OkButton) == Some(&"button 2"), "Value do not match");
+    assert!(!map.contains_key(&AppId::NameField), "Value should not exist");
+}
@@ -0,0 +1,229 @@
+/*!
+    An animation control that plays AVI clips
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::ptr;
+
+use user32::SendMessageW;
+use winapi::{HWND, WPARAM, LPARAM};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::{Error, SystemError};
+use events::Event;
+use low::other_helper::to_utf16;
+
+/**
+    A template that creates an animation control. The animation control plays simple AVI clips (ex: the file copy animation).
+
+    The control can only open AVI clips that are uncompressed or compressed with RLE8 and that do not have a sound track.
+    Trying to open any other kind of clip returns `Error::System(SystemError::AnimationOpen)`.
+
+    Available events:  
+    Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `source`: The path to the AVI clip to open. If empty, no clip is opened.  
+    • `position`: The start position of the animation  
+    • `size`: The start size of the animation  
+    • `visible`: If the animation should be visible to the user  
+    • `disabled`: If the animation should be disabled  
+    • `autoplay`: If the clip should start playing (in a loop) as soon as it is opened  
+    • `center`: If the clip should be centered in the control. If false, the control is resized to the size of the clip.  
+    • `transparent`: If the background of the clip should be drawn using the background color of the parent  
+    • `parent`: The animation parent  
+*/
+#[derive(Clone)]
+pub struct AnimationT<S: Clone+Into<String>, ID: Hash+Clone> {
+    pub source: S,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub autoplay: bool,
+    pub center: bool,
+    pub transparent: bool,
+    pub parent: ID,
+}
+
+impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for AnimationT<S, ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<Animation>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, handle_of_window};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, ACS_AUTOPLAY, ACS_CENTER, ACS_TRANSPARENT};
+        use user32::DestroyWindow;
+
+        let flags: DWORD = WS_CHILD |
+        if self.visible     { WS_VISIBLE }      else { 0 } |
+        if self.disabled    { WS_DISABLED }     else { 0 } |
+        if self.autoplay    { ACS_AUTOPLAY }    else { 0 } |
+        if self.center      { ACS_CENTER }      else { 0 } |
+        if self.transparent { ACS_TRANSPARENT } else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of an animation must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysAnimate32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        let handle = match unsafe{ build_window(params) } {
+            Ok(h) => h,
+            Err(e) => { return Err(Error::System(e)); }
+        };
+
+        let source: String = self.source.clone().into();
+        if source.len() > 0 {
+            if let Err(e) = unsafe{ open_file(handle, &source) } {
+                unsafe{ DestroyWindow(handle); }
+                return Err(e);
+            }
+        }
+
+        Ok( Box::new(Animation{handle: handle}) )
+    }
+}
+
+/**
+    A control that plays simple AVI clips
+*/
+pub struct Animation {
+    handle: HWND
+}
+
+impl Animation {
+
+    /**
+        Open the AVI clip at `path`. If a clip was already opened, it is closed.  
+        If the clip cannot be opened (ex: the file does not exist or the clip format is not supported),
+        returns `Error::System(SystemError::AnimationOpen)`.
+    */
+    pub fn open_file<'a>(&self, path: &'a str) -> Result<(), Error> {
+        unsafe{ open_file(self.handle, path) }
+    }
+
+    /**
+        Open the AVI clip stored in the resources of the executable under the identifier `id`.  
+        If the clip cannot be opened, returns `Error::System(SystemError::AnimationOpen)`.
+    */
+    pub fn open_resource(&self, id: u16) -> Result<(), Error> {
+        use winapi::ACM_OPENW;
+        use kernel32::GetModuleHandleW;
+
+        let ok = unsafe{ 
+            let hinst = GetModuleHandleW(ptr::null());
+            SendMessageW(self.handle, ACM_OPENW, hinst as WPARAM, id as LPARAM)
+        };
+
+        match ok {
+            0 => Err(Error::System(SystemError::AnimationOpen)),
+            _ => Ok(())
+        }
+    }
+
+    /// Close the current clip. The control becomes blank.
+    pub fn close(&self) {
+        use winapi::ACM_OPENW;
+        unsafe{ SendMessageW(self.handle, ACM_OPENW, 0, 0); }
+    }
+
+    /// Play the current clip from the start to the end. If `looped` is true, the clip is repeated until `stop` is called.
+    pub fn play(&self, looped: bool) {
+        unsafe{ play(self.handle, if looped { -1 } else { 1 }, 0, 0xFFFF); }
+    }
+
+    /// Stop the clip. The current frame stays displayed.
+    pub fn stop(&self) {
+        use winapi::ACM_STOP;
+        unsafe{ SendMessageW(self.handle, ACM_STOP, 0, 0); }
+    }
+
+    /// Stop the clip and display the frame at `frame`
+    pub fn seek(&self, frame: u16) {
+        unsafe{ play(self.handle, 1, frame, frame); }
+    }
+
+    /// Return true if the clip is currently playing
+    pub fn is_playing(&self) -> bool {
+        use winapi::ACM_ISPLAYING;
+        unsafe{ SendMessageW(self.handle, ACM_ISPLAYING, 0, 0) != 0 }
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for Animation {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::Animation 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+// Private functions
+
+#[inline(always)]
+unsafe fn open_file<'a>(handle: HWND, path: &'a str) -> Result<(), Error> {
+    use winapi::ACM_OPENW;
+
+    let path = to_utf16(path);
+    match SendMessageW(handle, ACM_OPENW, 0, path.as_ptr() as LPARAM) {
+        0 => Err(Error::System(SystemError::AnimationOpen)),
+        _ => Ok(())
+    }
+}
+
+#[inline(always)]
+unsafe fn play(handle: HWND, repeat: i32, from: u16, to: u16) {
+    use winapi::ACM_PLAY;
+    let range = ((to as LPARAM) << 16) | (from as LPARAM);
+    SendMessageW(handle, ACM_PLAY, repeat as WPARAM, range);
+}
@@ -21,7 +21,10 @@
 use std::hash::Hash;
 use std::any::TypeId;
 
-use winapi::{HWND, HFONT};
+use std::mem;
+
+use winapi::{HWND, HFONT, WPARAM};
+use user32::SendMessageW;
 
 use ui::Ui;
 use controls::{Control, ControlT, ControlType, AnyHandle};
@@ -32,7 +35,7 @@ use events::Event;
     A template that creates a standard button
 
     Available events:  
-    Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `text`: The text of the button  
@@ -40,8 +43,12 @@ use events::Event;
     • `size`: The start size of the button  
     • `visible`: If the button should be visible to the user   
     • `disabled`: If the user can or can't click on the button  
+    • `tab_stop`: If the button can receive the keyboard focus with the TAB key  
+    • `group`: If the button is the first control of a group (`WS_GROUP`)  
     • `parent`: The button parent  
     • `font`: The button font. If None, use the system default  
+    • `command_link`: If the button should be created as a command link (`BS_COMMANDLINK`). Requires visual styles.  
+    • `note`: The description text displayed under the text of a command link. Ignored by regular buttons.  
 */
 #[derive(Clone)]
 pub struct ButtonT<S: Clone+Into<String>, ID: Hash+Clone> {
@@ -50,24 +57,31 @@ pub struct ButtonT<S: Clone+Into<String>, ID: Hash+Clone> {
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub parent: ID,
     pub font: Option<ID>,
+    pub command_link: bool,
+    pub note: Option<S>,
 }
 
 impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for ButtonT<S, ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<Button>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, BS_NOTIFY, BS_TEXT};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, BS_NOTIFY, BS_TEXT, BS_COMMANDLINK};
 
         let flags: DWORD = WS_CHILD | BS_NOTIFY | BS_TEXT |
-        if self.visible    { WS_VISIBLE }   else { 0 } |
-        if self.disabled   { WS_DISABLED }  else { 0 };
+        if self.visible      { WS_VISIBLE }     else { 0 } |
+        if self.disabled     { WS_DISABLED }    else { 0 } |
+        if self.tab_stop     { WS_TABSTOP }     else { 0 } |
+        if self.group        { WS_GROUP }       else { 0 } |
+        if self.command_link { BS_COMMANDLINK } else { 0 };
 
         // Get the parent handle
         let parent = match handle_of_window(ui, &self.parent, "The parent of a button must be a window-like control.") {
@@ -97,7 +111,13 @@ impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for ButtonT<S, ID> {
 
         match unsafe{ build_window(params) } {
             Ok(h) => {
-                unsafe{ set_window_font(h, font_handle, true); }
+                unsafe{ 
+                    set_window_font(h, font_handle, true);
+
+                    if let Some(note) = self.note.as_ref() {
+                        set_note(h, note.clone());
+                    }
+                }
                 Ok( Box::new(Button{handle: h}) )
             },
             Err(e) => Err(Error::System(e))
@@ -113,6 +133,59 @@ pub struct Button {
 }
 
 impl Button {
+
+    /**
+        Set the description text of a command link button. To remove the current note, send `""`.  
+        Has no effect if the button was not created with `command_link` set to `true`.
+    */
+    pub fn set_note<'a>(&self, note: &'a str) {
+        set_note(self.handle, note);
+    }
+
+    /**
+        Return the description text of a command link button.  
+        Returns an empty string if the button is not a command link or if it has no note.
+    */
+    pub fn get_note(&self) -> String {
+        use winapi::{BCM_GETNOTE, BCM_GETNOTELENGTH, DWORD};
+        use low::other_helper::from_utf16;
+
+        let length = unsafe{ SendMessageW(self.handle, BCM_GETNOTELENGTH, 0, 0) as DWORD };
+        if length == 0 {
+            return String::new();
+        }
+
+        let mut buffer_size: DWORD = length + 1;
+        let mut buffer: Vec<u16> = Vec::with_capacity(buffer_size as usize);
+        unsafe { buffer.set_len(buffer_size as usize); }
+
+        let ok = unsafe{ SendMessageW(self.handle, BCM_GETNOTE, mem::transmute(&mut buffer_size), mem::transmute(buffer.as_mut_ptr())) };
+        if ok == 0 {
+            String::new()
+        } else {
+            from_utf16(&buffer)
+        }
+    }
+
+    /**
+        Display or hide the UAC shield icon next to the button text.  
+        Use this on buttons that launch an action requiring elevation.
+    */
+    pub fn set_shield(&self, shield: bool) {
+        use winapi::BCM_SETSHIELD;
+        unsafe{ SendMessageW(self.handle, BCM_SETSHIELD, 0, shield as isize); }
+    }
+
+    /// Return `true` if the button is a command link or `false` otherwise
+    pub fn get_command_link(&self) -> bool {
+        use low::window_helper::get_window_long;
+        use winapi::{GWL_STYLE, BS_COMMANDLINK, BS_TYPEMASK};
+
+        let style = get_window_long(self.handle, GWL_STYLE) as u32;
+
+        (style & BS_TYPEMASK) == BS_COMMANDLINK
+    }
+
     pub fn get_text(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
     pub fn set_text<'a>(&self, text: &'a str) { unsafe{ ::low::window_helper::set_window_text(self.handle, text); } }
     pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
@@ -140,4 +213,14 @@ impl Control for Button {
         unsafe{ DestroyWindow(self.handle) };
     }
 
-}
\ No newline at end of file
+}
+
+/// Private button methods
+
+fn set_note<S: Into<String>>(handle: HWND, note: S) {
+    use winapi::BCM_SETNOTE;
+    use low::other_helper::to_utf16;
+
+    let text = to_utf16(note.into().as_str());
+    unsafe{ SendMessageW(handle, BCM_SETNOTE, 0 as WPARAM, mem::transmute(text.as_ptr()) ); }
+}
@@ -24,13 +24,16 @@ use std::ptr;
 use std::ops::{Deref, DerefMut};
 use std::marker::PhantomData;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use winapi::{HWND, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1SolidColorBrush, ID2D1StrokeStyle, 
   S_OK, D2D1_MATRIX_3X2_F};
 
 use controls::{Control, ControlType, AnyHandle};
+use resources::{Resource, Cursor, Icon};
 use error::{Error, SystemError};
-use super::{CanvasRenderer, RendererProtected, build_render_target, CANVAS_CLASS_NAME};
+use super::{CanvasRenderer, RendererProtected, CanvasState, CanvasStateProtected, build_render_target, child_style, window_style, CANVAS_CLASS_NAME};
+use super::{set_dialog_keys, dialog_keys};
 use defs;
 
 
@@ -52,6 +55,9 @@ pub struct Canvas<ID: Clone+Hash> {
     render_target: *mut ID2D1HwndRenderTarget,
     must_recreate_target: bool,
     resources: HashMap<u64, CanvasResources>,
+    state: Arc<CanvasState>,
+    topmost: bool,
+    icon: Option<Icon>,
     p: PhantomData<ID>
 }
 
@@ -138,16 +144,296 @@ impl<ID: Clone+Hash> Canvas<ID> {
     }
 
     /**
-        Redraw the canvas
+        Return the state of the canvas window. The state can be sent to a render thread to know when the canvas
+        is resized or destroyed. See `CanvasState` for the synchronization details.
+    */
+    pub fn state(&self) -> Arc<CanvasState> {
+        self.state.clone()
+    }
+
+    /**
+        Return the refresh rate in Hz of the monitor that displays the canvas.  
+        On a variable refresh rate display, this is the maximum rate of the current display mode.
+    */
+    pub fn refresh_rate(&self) -> f64 {
+        unsafe{ ::low::display_helper::refresh_rate(self.handle) }
+    }
+
+    /**
+        Return the presentation timing of the desktop compositor. Use it to schedule the next frame of a render loop.
+
+        Errors:  
+        • `Error::System` if the compositor is disabled or if the timing could not be read  
+    */
+    pub fn composition_timing(&self) -> Result<defs::CompositionTiming, Error> {
+        ::low::display_helper::composition_timing()
+    }
+
+    /**
+        Invalidate the whole canvas. The system sends a `Paint` event the next time the message queue is empty.  
+        The background is erased before the paint event. Use `invalidate_no_erase` if the whole canvas is painted by the renderer.
+    */
+    pub fn invalidate(&self) {
+        use user32::InvalidateRect;
+        unsafe { InvalidateRect(self.handle, ptr::null(), 1); }
+    }
+
+    /**
+        Invalidate the whole canvas without erasing its background. This removes the flicker of the background erase
+        on canvases that are fully painted by the renderer.
+    */
+    pub fn invalidate_no_erase(&self) {
+        use user32::InvalidateRect;
+        unsafe { InvalidateRect(self.handle, ptr::null(), 0); }
+    }
+
+    /**
+        Redraw the canvas immediately. The `Paint` event is raised before the function returns
+        (ex: to paint the canvas right before a screenshot, or to present a video frame as soon as it is decoded).
+        Use `invalidate` to queue the paint instead.  
+
+        The background is not erased because the `Paint` callback draws the whole canvas. Erasing it first would make the canvas flicker.  
+        Does nothing if the canvas is hidden or if its window is minimized.
     */
     pub fn redraw(&self) {
-        use user32::RedrawWindow;
-        use winapi::{RDW_ERASE, RDW_INVALIDATE};
+        use user32::{RedrawWindow, IsWindowVisible, IsIconic};
+        use winapi::{RDW_NOERASE, RDW_INVALIDATE, RDW_UPDATENOW};
+        use low::window_helper::get_root_window;
+
         unsafe { 
-            RedrawWindow(self.handle, ptr::null(), ptr::null_mut(), RDW_ERASE|RDW_INVALIDATE);
+            if IsWindowVisible(self.handle) == 0 || IsIconic(get_root_window(self.handle)) != 0 {
+                return;
+            }
+
+            RedrawWindow(self.handle, ptr::null(), ptr::null_mut(), RDW_INVALIDATE|RDW_UPDATENOW|RDW_NOERASE);
         }
     }
     
+    /**
+        Move the canvas into another window or, if `parent` is `None`, turn it into a top level window.  
+        The canvas keeps its window handle, so the render target, the resources and the shared state are preserved.  
+
+        The canvas stays at the same place on the screen and keeps its client size. The style is recomputed
+        for the new kind of window: a child canvas has no decoration and a top level canvas gets a caption and a resize frame.  
+        The visibility, the enabled state, the size limits and the topmost option of the canvas are kept.  
+        A tool window canvas (see `CanvasT::tool_window`) that leaves its parent is owned by the top level window of its old parent:
+        it stays above its owner and is minimized with it.
+
+        Errors:  
+        • `Error::BadParent` if the new parent is not a window-like control  
+        • `Error::System` if the system could not reparent the canvas  
+    */
+    pub fn set_parent(&self, parent: Option<&AnyHandle>) -> Result<(), Error> {
+        use user32::{SetParent, SetWindowPos, GetWindowRect, GetClientRect, MapWindowPoints, AdjustWindowRectEx};
+        use winapi::{RECT, POINT, DWORD, GWL_STYLE, GWL_EXSTYLE, GWL_HWNDPARENT, WS_VISIBLE, WS_DISABLED, WS_EX_TOOLWINDOW, SWP_NOZORDER, 
+          SWP_NOACTIVATE, SWP_FRAMECHANGED, HWND_TOP, HWND_TOPMOST};
+        use kernel32::{SetLastError, GetLastError};
+        use low::window_helper::{get_window_long, set_window_long, get_root_window};
+        use std::mem;
+
+        let parent = match parent {
+            Some(&AnyHandle::HWND(h)) => h,
+            Some(_) => { return Err(Error::BadParent("The parent of a canvas must be a window-like control.".to_string())); },
+            None => ptr::null_mut()
+        };
+
+        unsafe {
+            let old_style = get_window_long(self.handle, GWL_STYLE) as DWORD;
+            let (visible, disabled) = (old_style & WS_VISIBLE == WS_VISIBLE, old_style & WS_DISABLED == WS_DISABLED);
+
+            // Save the screen position and the client size before the frame changes
+            let mut rect: RECT = mem::uninitialized();
+            let mut client: RECT = mem::uninitialized();
+            GetWindowRect(self.handle, &mut rect);
+            GetClientRect(self.handle, &mut client);
+
+            // The owner of a tool window must be found while the canvas is still in its old parent
+            let ex_style = get_window_long(self.handle, GWL_EXSTYLE) as DWORD;
+            let old_root = get_root_window(self.handle);
+            let owner = if parent.is_null() && ex_style & WS_EX_TOOLWINDOW == WS_EX_TOOLWINDOW && old_root != self.handle {
+                old_root
+            } else {
+                ptr::null_mut()
+            };
+
+            // The style must be updated before the parent. See the `SetParent` documentation.
+            let style = if parent.is_null() { window_style(visible, disabled) } else { child_style(visible, disabled) };
+            set_window_long(self.handle, GWL_STYLE, style as usize);
+
+            // A top level window has no previous parent, so a null return is only an error if the last error is set
+            SetLastError(0);
+            if SetParent(self.handle, parent).is_null() && GetLastError() != 0 {
+                set_window_long(self.handle, GWL_STYLE, old_style as usize);
+                return Err(Error::from_last_error("SetParent"));
+            }
+
+            if !owner.is_null() {
+                set_window_long(self.handle, GWL_HWNDPARENT, owner as usize);
+            }
+
+            // Compute the new outer size of the canvas from its client size
+            let mut frame = RECT{left: 0, top: 0, right: client.right, bottom: client.bottom};
+            AdjustWindowRectEx(&mut frame, style, 0, ex_style);
+
+            // Map the screen position into the new parent client area
+            let mut pos = POINT{x: rect.left, y: rect.top};
+            if !parent.is_null() {
+                MapWindowPoints(ptr::null_mut(), parent, &mut pos, 1);
+            }
+
+            // A topmost canvas stays above its siblings or, if it is a top level window, above the other windows
+            let (after, zorder) = match (self.topmost, parent.is_null()) {
+                (true, true) => (HWND_TOPMOST, 0),
+                (true, false) => (HWND_TOP, 0),
+                (false, _) => (ptr::null_mut(), SWP_NOZORDER)
+            };
+
+            SetWindowPos(self.handle, after, pos.x, pos.y, frame.right-frame.left, frame.bottom-frame.top, 
+              zorder|SWP_NOACTIVATE|SWP_FRAMECHANGED);
+        }
+
+        Ok(())
+    }
+
+    /**
+        Return `true` if the canvas is a child of another window or `false` if it is a top level window.
+    */
+    pub fn is_child(&self) -> bool {
+        self.parent().is_some()
+    }
+
+    /**
+        Return the handle of the window that holds the canvas or `None` if the canvas is a top level window.
+    */
+    pub fn parent(&self) -> Option<AnyHandle> {
+        match unsafe{ ::low::window_helper::get_window_parent(self.handle) } {
+            Some(h) => Some(AnyHandle::HWND(h)),
+            None => None
+        }
+    }
+
+    /**
+        Set the icon of the canvas. The icon is displayed in the title bar and in the taskbar when the canvas is a top level window.
+        Use `None` to remove the icon.  
+        The canvas does not copy the icon, so the icon resource must not be freed while it is in use.  
+        The icon loaded from `CanvasT::icon_file` is kept until the canvas is freed.
+
+        Errors:  
+        • `Error::System` if the canvas window was destroyed or if the icon is not valid  
+    */
+    pub fn set_icon(&self, icon: Option<&Icon>) -> Result<(), Error> {
+        use low::window_helper::set_window_icon;
+        use winapi::{ICON_BIG, ICON_SMALL};
+
+        let icon_handle = match icon.map(|i| i.handle()) {
+            Some(AnyHandle::HICON(h)) => Some(h),
+            _ => None
+        };
+
+        for &size in [ICON_BIG, ICON_SMALL].iter() {
+            if let Err(e) = unsafe{ set_window_icon(self.handle, size as usize, icon_handle) } {
+                return Err(Error::System(e));
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+        Return the icon of the canvas, if any. The returned icon is shared: freeing it does not destroy the canvas icon.
+
+        Errors:  
+        • `Error::System` if the canvas window was destroyed  
+    */
+    pub fn icon(&self) -> Result<Option<Icon>, Error> {
+        use low::window_helper::get_window_icon;
+        use winapi::ICON_BIG;
+
+        match unsafe{ get_window_icon(self.handle, ICON_BIG as usize) } {
+            Ok(icon) => Ok(icon.map(|h| Icon::from_hicon(h, false))),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+
+    /**
+        Set the cursor displayed when the mouse is over the canvas. Use `None` to restore the default cursor (the arrow).  
+        The canvas does not copy the cursor, so the cursor resource must not be freed while it is in use.
+    */
+    pub fn set_cursor(&self, cursor: Option<&Cursor>) {
+        let cursor_handle = match cursor.map(|c| c.handle()) {
+            Some(AnyHandle::HCURSOR(h)) => Some(h),
+            _ => None
+        };
+
+        unsafe{ ::low::window_helper::set_window_cursor(self.handle, cursor_handle); }
+    }
+
+    /**
+        Hide or show the cursor when the mouse is over the canvas. A hidden cursor takes precedence over the cursor set with `set_cursor`.
+    */
+    pub fn set_cursor_visible(&self, visible: bool) {
+        unsafe{ ::low::window_helper::set_window_cursor_hidden(self.handle, !visible); }
+    }
+
+    /// Return `true` if the cursor is visible when the mouse is over the canvas
+    pub fn get_cursor_visible(&self) -> bool {
+        unsafe{ !::low::window_helper::get_window_cursor_hidden(self.handle) }
+    }
+
+    /**
+        Select the keys that the canvas handles itself (`WM_GETDLGCODE`). By default, the dialog navigation uses
+        the TAB and the arrow keys to move the focus, and the canvas never receives them.
+
+        Arguments:  
+        • `keys`: A combination of the `DIALOG_KEYS_*` constants. Use `DIALOG_KEYS_NONE` to restore the default behaviour.  
+    */
+    pub fn set_dialog_keys(&self, keys: u32) {
+        unsafe{ set_dialog_keys(self.handle, keys); }
+    }
+
+    /// Return the keys that the canvas handles itself (a combination of the `DIALOG_KEYS_*` constants)
+    pub fn dialog_keys(&self) -> u32 {
+        unsafe{ dialog_keys(self.handle) }
+    }
+
+    /**
+        Install a handler called when the system wants to erase the canvas background (`WM_ERASEBKGND`).  
+        If the handler returns `false`, the background is not erased. This removes the flicker when
+        the canvas is resized. If the handler returns `true`, the system erases the background as usual.
+
+        Use `None` to remove the handler.
+    */
+    pub fn on_erase_background(&self, handler: Option<fn() -> bool>) {
+        use low::window_helper::set_window_long;
+        use winapi::GWL_USERDATA;
+
+        let handler_ptr: usize = match handler {
+            Some(h) => h as usize,
+            None => 0
+        };
+
+        set_window_long(self.handle, GWL_USERDATA, handler_ptr);
+    }
+
+    /**
+        Return the size of the drawable area of the canvas in pixels. The caption and the borders of a
+        top level canvas are not included. Use this value to size the render target or a swapchain.
+    */
+    pub fn physical_size(&self) -> (u32, u32) {
+        let (_, _, w, h) = unsafe{ ::low::window_helper::get_window_client_rect(self.handle) };
+        (w, h)
+    }
+
+    /**
+        Return the scale factor of the monitor that displays the canvas (1.0 at 96 DPI, 1.5 at 144 DPI).  
+        Multiply the logical sizes by this value to match the physical pixels of the canvas.
+    */
+    pub fn scale_factor(&self) -> f32 {
+        use low::defs::USER_DEFAULT_SCREEN_DPI;
+        let dpi = unsafe{ ::low::other_helper::window_dpi(self.handle) };
+        (dpi as f32) / (USER_DEFAULT_SCREEN_DPI as f32)
+    }
+
     /**
         Set the render target resolution.  
         If the control size do not match the render target size, the result will be upscaled or downscaled
@@ -189,6 +475,16 @@ impl<ID: Clone+Hash> Canvas<ID> {
         s1.finish()
     }
 
+    /**
+        Set the title of the canvas, truncated to `max_length` UTF-16 units. The title is never cut in the middle of a character.  
+        If `ellipsis` is true and the title is truncated, it ends with `…`. The title is only displayed when the canvas is a top level window.
+    */
+    pub fn set_title_truncated<'a>(&self, text: &'a str, max_length: usize, ellipsis: bool) {
+        unsafe{ ::low::window_helper::set_window_text_truncated(self.handle, text, max_length, ellipsis); }
+    }
+
+    pub fn get_title(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
+    pub fn set_title<'a>(&self, text: &'a str) { unsafe{ ::low::window_helper::set_window_text(self.handle, text); } }
     pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
     pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
     pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
@@ -215,6 +511,9 @@ impl<ID: Clone+Hash> Control for Canvas<ID> {
             use kernel32::GetModuleHandleW;
             use low::other_helper::to_utf16;
 
+            // Tell the render threads to stop before the render target is released
+            self.state.close();
+
             let factory = &mut *self.factory;
             let render_target = &mut *self.render_target;
 
@@ -229,6 +528,11 @@ impl<ID: Clone+Hash> Control for Canvas<ID> {
             factory.Release();
             DestroyWindow(self.handle);
 
+            // The icon can only be destroyed once the window stopped using it
+            if let Some(mut icon) = self.icon.take() {
+                icon.free();
+            }
+
             let cls = to_utf16(CANVAS_CLASS_NAME);
             let hmod = GetModuleHandleW(ptr::null_mut());
             UnregisterClassW(cls.as_ptr(), hmod);
@@ -256,13 +560,44 @@ impl<'a, ID: Clone+Hash> DerefMut for Canvas<ID> {
 
 }
 
+/**
+    Expose the canvas window to graphics libraries (wgpu, vulkan, opengl loaders) without any unsafe glue.
+    A canvas always owns a window, so there is no unbound state to check here.
+*/
+#[cfg(feature = "raw-window-handle")]
+unsafe impl<ID: Clone+Hash> ::raw_window_handle::HasRawWindowHandle for Canvas<ID> {
+
+    fn raw_window_handle(&self) -> ::raw_window_handle::RawWindowHandle {
+        use raw_window_handle::{RawWindowHandle, Win32WindowHandle};
+        use kernel32::GetModuleHandleW;
+
+        let mut handle = Win32WindowHandle::empty();
+        handle.hwnd = self.handle as *mut _;
+        handle.hinstance = unsafe{ GetModuleHandleW(ptr::null()) } as *mut _;
+
+        RawWindowHandle::Win32(handle)
+    }
+
+}
+
+#[cfg(feature = "raw-window-handle")]
+unsafe impl<ID: Clone+Hash> ::raw_window_handle::HasRawDisplayHandle for Canvas<ID> {
+
+    fn raw_display_handle(&self) -> ::raw_window_handle::RawDisplayHandle {
+        use raw_window_handle::{RawDisplayHandle, WindowsDisplayHandle};
+        RawDisplayHandle::Windows(WindowsDisplayHandle::empty())
+    }
+
+}
+
 /**
     Protected renderer method (only available in the canvas control module)
 */
 pub trait CanvasProtected<ID: Clone+Hash>  {
     fn get_must_recreate_target(&mut self) -> bool;
     fn set_must_recreate_target(&mut self, recreate: bool);
-    fn create(h: HWND, f: *mut ID2D1Factory, r: *mut ID2D1HwndRenderTarget) -> Canvas<ID>;
+    fn create(h: HWND, f: *mut ID2D1Factory, r: *mut ID2D1HwndRenderTarget, s: Arc<CanvasState>, topmost: bool, icon: Option<Icon>) -> Canvas<ID>;
+    fn apply_owned_icon(&self) -> Result<(), Error>;
     fn rebuild(&mut self) -> Result<(), SystemError>;
     fn get_resource(&mut self, id: &ID) -> Result<CanvasResources, Error>;
 }
@@ -277,17 +612,28 @@ impl<ID: Clone+Hash> CanvasProtected<ID> for Canvas<ID> {
         self.must_recreate_target = recreate;
     }
 
-    fn create(h: HWND, f: *mut ID2D1Factory, r: *mut ID2D1HwndRenderTarget) -> Canvas<ID> {
+    fn create(h: HWND, f: *mut ID2D1Factory, r: *mut ID2D1HwndRenderTarget, s: Arc<CanvasState>, topmost: bool, icon: Option<Icon>) -> Canvas<ID> {
          Canvas::<ID>{
             handle: h,
             factory: f,
             render_target: r,
             must_recreate_target: false,
             resources: HashMap::with_capacity(10),
+            state: s,
+            topmost: topmost,
+            icon: icon,
             p: PhantomData
         }
     }
 
+    /// Display the icon owned by the canvas (if any)
+    fn apply_owned_icon(&self) -> Result<(), Error> {
+        match self.icon.as_ref() {
+            Some(icon) => self.set_icon(Some(icon)),
+            None => Ok(())
+        }
+    }
+
     /// Rebuild the canvas renderer
     fn rebuild(&mut self) -> Result<(), SystemError> {
         let result = unsafe{ build_render_target(self.handle,  &mut *self.factory) };
@@ -25,24 +25,53 @@ pub const CANVAS_CLASS_NAME: &'static str = "NWG_BUILTIN_D2DCANVAS";
 use std::hash::Hash;
 use std::any::TypeId;
 use std::ptr;
+use std::sync::Arc;
 
-use winapi::{HWND, ID2D1Factory, ID2D1HwndRenderTarget};
+use winapi::{HWND, BOOL, DWORD, ID2D1Factory, ID2D1HwndRenderTarget};
 
 use ui::Ui;
 use controls::{Control, ControlT};
 use error::{Error, SystemError};
 use events::Event;
-use super::{Canvas, CanvasProtected};
+use super::{Canvas, CanvasProtected, CanvasState, CanvasStateProtected};
+
+/// Name of the window property that holds the shared state of a canvas
+const STATE_PROP_NAME: &'static str = "NWG_CANVAS_STATE";
+
+/// Name of the window property that holds the size limits of a canvas
+const LIMITS_PROP_NAME: &'static str = "NWG_CANVAS_LIMITS";
+
+/// Name of the window property that holds the keys handled by a canvas (see `Canvas::set_dialog_keys`)
+const DIALOG_KEYS_PROP_NAME: &'static str = "NWG_CANVAS_DIALOG_KEYS";
+
+/// Minimum and maximum size of a canvas
+type SizeLimits = (Option<(u32, u32)>, Option<(u32, u32)>);
 
 /**
     A template that creates a canvas
 
+    Events:  
+    Event::Destroyed, Event::KeyDown, Event::KeyUp, Event::Char, Event::MouseDown, Event::MouseUp, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Paint, Event::WorkAreaChanged, Event::DpiChanged, Event::Raw  
+
+    `DpiChanged` is only raised when the canvas is a top level window. It receives `EventArgs::Dpi` with the new DPI, the new scale factor
+    and the window rectangle suggested by the system. The canvas is not resized: move it to the suggested rectangle and resize the render target in the handler.
+
     Members:  
     • `parent`: The canvas parent.
     • `position` : Starting posiion of the canvas after it is created  
     • `size` : Starting size of the canvas after it is created  
     • `visible` : If the user can see the canvas or not  
     • `disabled` : If the canvas is enabled or not. A disabled canvas do not process events  
+    • `min_size` : The minimum size of the canvas when the user resizes it. Only used when the canvas is a top level window  
+    • `max_size` : The maximum size of the canvas when the user resizes it. Only used when the canvas is a top level window  
+    • `topmost` : If the canvas is placed above its siblings. When the canvas becomes a top level window (see `Canvas::set_parent`), it stays above the other windows  
+    • `alpha` : The opacity of the canvas (0 is transparent, 255 is opaque). The canvas is created as a layered window. Child layered windows require Windows 8  
+    • `center` : If the canvas is centered in its parent. Overrides `position`  
+    • `icon_file` : The path of a `.ico` file to use as the canvas icon. The icon is owned by the canvas and freed with it  
+    • `tool_window` : If the canvas is a tool window (`WS_EX_TOOLWINDOW`). When it is a top level window, a tool window has a smaller title bar and
+       is not displayed in the taskbar or in the Alt-Tab list  
+    • `owned` : If the canvas is created as a top level window owned by `parent` instead of a child of `parent`. An owned canvas is not clipped
+       by its owner, always stays above it and is minimized with it. Use it for palettes and non modal dialogs. `position` is in screen coordinates  
 */
 pub struct CanvasT<ID: Hash+Clone> {
     pub parent: ID,
@@ -50,6 +79,14 @@ pub struct CanvasT<ID: Hash+Clone> {
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub min_size: Option<(u32, u32)>,
+    pub max_size: Option<(u32, u32)>,
+    pub topmost: bool,
+    pub alpha: Option<u8>,
+    pub center: bool,
+    pub icon_file: Option<String>,
+    pub tool_window: bool,
+    pub owned: bool,
 }
 
 impl<ID: Hash+Clone+'static> ControlT<ID> for CanvasT<ID> {
@@ -57,17 +94,38 @@ impl<ID: Hash+Clone+'static> ControlT<ID> for CanvasT<ID> {
 
     fn events(&self) -> Vec<Event> {
         vec![Event::Destroyed, Event::KeyDown, Event::KeyUp, Event::Char, Event::MouseDown, Event::MouseUp,
-             Event::Moved, Event::Resized, Event::Paint, Event::Raw]
+             Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Paint, Event::WorkAreaChanged,
+             Event::DpiChanged, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+       use resources::{Icon, Resource};
+
+       // Load the icon first, so that a bad file do not leave a half built canvas
+       let icon = match self.icon_file.as_ref() {
+            Some(path) => match Icon::from_file(path, (0, 0)) {
+                Ok(i) => Some(i),
+                Err(e) => { return Err(e); }
+            },
+            None => None
+       };
+
        unsafe{
             if let Err(e) = build_sysclass() { return Err(e); }
             match build_window(ui, &self) {
-                Ok((h, (f, r))) => { Ok( Box::new( 
-                    Canvas::<ID>::create(h, f, r)
-                ) as Box<Control> ) },
-                Err(e) => Err(e)
+                Ok((h, (f, r))) => { 
+                    let mut canvas = Canvas::<ID>::create(h, f, r, attach_state(h), self.topmost, icon);
+                    if let Err(e) = canvas.apply_owned_icon() {
+                        canvas.free();
+                        return Err(e);
+                    }
+
+                    Ok( Box::new(canvas) as Box<Control> )
+                },
+                Err(e) => {
+                    if let Some(mut i) = icon { i.free(); }
+                    Err(e)
+                }
             }
         } // unsafe
     }
@@ -82,15 +140,76 @@ type RenderOut = (*mut ID2D1Factory, *mut ID2D1HwndRenderTarget);
 
 #[allow(unused_variables)]
 unsafe extern "system" fn canvas_sysproc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
-    use winapi::{WM_CREATE, WM_CLOSE};
-    use user32::{DefWindowProcW, ShowWindow};
+    use winapi::{WM_CREATE, WM_CLOSE, WM_ERASEBKGND, WM_SIZE, WM_DESTROY, WM_GETMINMAXINFO, WM_GETDLGCODE, GWL_USERDATA, LOWORD, HIWORD, MINMAXINFO};
+    use user32::{DefWindowProcW, ShowWindow, GetPropW, RemovePropW};
+    use low::window_helper::get_window_long;
+    use low::other_helper::to_utf16;
+    use std::mem;
 
     let handled = match msg {
         WM_CREATE => true,
+        WM_SIZE => {
+            let prop_name = to_utf16(STATE_PROP_NAME);
+            let state: *const CanvasState = mem::transmute(GetPropW(hwnd, prop_name.as_ptr()));
+            if !state.is_null() {
+                (&*state).resized(LOWORD(l as u32) as u32, HIWORD(l as u32) as u32);
+            }
+            false
+        },
+        WM_DESTROY => {
+            // Release the reference held by the window
+            let prop_name = to_utf16(STATE_PROP_NAME);
+            let state: *const CanvasState = mem::transmute(RemovePropW(hwnd, prop_name.as_ptr()));
+            if !state.is_null() {
+                let state = Arc::from_raw(state);
+                state.close();
+            }
+
+            let prop_name = to_utf16(LIMITS_PROP_NAME);
+            let limits: *mut SizeLimits = mem::transmute(RemovePropW(hwnd, prop_name.as_ptr()));
+            if !limits.is_null() {
+                drop(Box::from_raw(limits));
+            }
+
+            let prop_name = to_utf16(DIALOG_KEYS_PROP_NAME);
+            RemovePropW(hwnd, prop_name.as_ptr());
+            false
+        },
+        WM_GETDLGCODE => {
+            // Also queried by `IsDialogMessage`, so the keys are not used for the dialog navigation
+            return DefWindowProcW(hwnd, msg, w, l) | (dialog_keys(hwnd) as LRESULT);
+        },
+        WM_GETMINMAXINFO => {
+            let prop_name = to_utf16(LIMITS_PROP_NAME);
+            let limits: *const SizeLimits = mem::transmute(GetPropW(hwnd, prop_name.as_ptr()));
+            if !limits.is_null() {
+                let info: &mut MINMAXINFO = mem::transmute(l);
+                if let Some((w, h)) = (&*limits).0 {
+                    info.ptMinTrackSize.x = w as i32;
+                    info.ptMinTrackSize.y = h as i32;
+                }
+                if let Some((w, h)) = (&*limits).1 {
+                    info.ptMaxTrackSize.x = w as i32;
+                    info.ptMaxTrackSize.y = h as i32;
+                }
+            }
+            false
+        },
         WM_CLOSE => {
             ShowWindow(hwnd, 0);
             true
-        }
+        },
+        WM_ERASEBKGND => {
+            // The erase background handler is saved in the window user data (see `Canvas::on_erase_background`)
+            let handler_ptr = get_window_long(hwnd, GWL_USERDATA) as usize;
+            if handler_ptr != 0 {
+                let handler: fn() -> bool = mem::transmute(handler_ptr);
+                if !handler() {
+                    return 1;
+                }
+            }
+            false
+        },
         _ => false
     };
 
@@ -101,15 +220,76 @@ unsafe extern "system" fn canvas_sysproc(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
     }
 }
 
+/**
+    Update the activation flag of the canvas states found in `root`, in its children and in the windows it owns.
+    Called by the ui when a top level window is activated or deactivated.
+*/
+pub unsafe fn update_canvas_activation(root: HWND, active: bool) {
+    use user32::{EnumChildWindows, EnumThreadWindows, GetWindowThreadProcessId};
+    use winapi::LPARAM;
+    use std::ptr;
+
+    // The root can be a top level canvas
+    update_child_activation(root, active as LPARAM);
+    EnumChildWindows(root, Some(update_child_activation), active as LPARAM);
+
+    // The owned canvases are top level windows, so they are not enumerated with the children of the root
+    let owner = (root, active);
+    EnumThreadWindows(GetWindowThreadProcessId(root, ptr::null_mut()), Some(update_owned_activation), &owner as *const (HWND, bool) as LPARAM);
+}
+
+unsafe extern "system" fn update_owned_activation(hwnd: HWND, owner: LPARAM) -> BOOL {
+    use user32::GetWindow;
+    use winapi::GW_OWNER;
+
+    let &(root, active) = &*(owner as *const (HWND, bool));
+    if GetWindow(hwnd, GW_OWNER) == root {
+        update_canvas_activation(hwnd, active);
+    }
+
+    1
+}
+
+unsafe extern "system" fn update_child_activation(hwnd: HWND, active: LPARAM) -> BOOL {
+    use user32::GetPropW;
+    use low::other_helper::to_utf16;
+    use std::mem;
+
+    let prop_name = to_utf16(STATE_PROP_NAME);
+    let state: *const CanvasState = mem::transmute(GetPropW(hwnd, prop_name.as_ptr()));
+    if !state.is_null() {
+        (&*state).activated(active != 0);
+    }
+
+    1
+}
+
+/// Create the shared state of a canvas. The window keeps a reference to the state until it is destroyed.
+unsafe fn attach_state(hwnd: HWND) -> Arc<CanvasState> {
+    use low::window_helper::get_window_size;
+    use low::other_helper::to_utf16;
+    use user32::SetPropW;
+    use std::mem;
+
+    let (w, h) = get_window_size(hwnd);
+    let state = Arc::new(CanvasState::create(w, h));
+
+    let prop_name = to_utf16(STATE_PROP_NAME);
+    SetPropW(hwnd, prop_name.as_ptr(), mem::transmute(Arc::into_raw(state.clone())));
+
+    state
+}
+
 #[inline(always)]
 unsafe fn build_sysclass() -> Result<(), Error> {
-    use low::window_helper::{SysclassParams, build_sysclass};
+    use low::window_helper::{SysclassParams, build_sysclass, NO_BACKGROUND_BRUSH};
     use winapi::{CS_HREDRAW, CS_VREDRAW};
 
+    // The class is registered with the arrow cursor. Use `Canvas::set_cursor` to change it.
     let params = SysclassParams { 
         class_name: CANVAS_CLASS_NAME,
         sysproc: Some(canvas_sysproc),
-        background: Some(ptr::null_mut()),
+        background: Some(NO_BACKGROUND_BRUSH),
         style: Some(CS_HREDRAW | CS_VREDRAW)
     };
     
@@ -191,15 +371,96 @@ unsafe fn build_renderer(hwnd: HWND) -> Result<RenderOut, SystemError> {
     }
 }
 
+/**
+    Return the window style of a child canvas. The style is always computed from a clean base so that
+    no window decoration (caption, system menu, resize frame) ends up on the child.
+*/
+pub fn child_style(visible: bool, disabled: bool) -> DWORD {
+    use winapi::{WS_VISIBLE, WS_DISABLED, WS_CHILD};
+
+    WS_CHILD | 
+    if visible    { WS_VISIBLE }   else { 0 } |
+    if disabled   { WS_DISABLED }  else { 0 }
+}
+
+/**
+    Return the window style of a top level canvas (a canvas without parent).
+*/
+pub fn window_style(visible: bool, disabled: bool) -> DWORD {
+    use winapi::{WS_VISIBLE, WS_DISABLED, WS_OVERLAPPEDWINDOW, WS_CLIPCHILDREN};
+
+    WS_OVERLAPPEDWINDOW | WS_CLIPCHILDREN |
+    if visible    { WS_VISIBLE }   else { 0 } |
+    if disabled   { WS_DISABLED }  else { 0 }
+}
+
+/// Save the keys handled by a canvas (`DIALOG_KEYS_*`). The property is removed if `keys` is 0.
+pub unsafe fn set_dialog_keys(hwnd: HWND, keys: u32) {
+    use low::other_helper::to_utf16;
+    use user32::{SetPropW, RemovePropW};
+    use std::mem;
+
+    let prop_name = to_utf16(DIALOG_KEYS_PROP_NAME);
+    if keys == 0 {
+        RemovePropW(hwnd, prop_name.as_ptr());
+    } else {
+        SetPropW(hwnd, prop_name.as_ptr(), mem::transmute(keys as usize));
+    }
+}
+
+/// Return the keys handled by a canvas (`DIALOG_KEYS_*`)
+pub unsafe fn dialog_keys(hwnd: HWND) -> u32 {
+    use low::other_helper::to_utf16;
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(DIALOG_KEYS_PROP_NAME);
+    GetPropW(hwnd, prop_name.as_ptr()) as usize as u32
+}
+
+/// Save the size limits of a canvas. The limits are freed when the window is destroyed.
+unsafe fn attach_limits(hwnd: HWND, limits: SizeLimits) {
+    use low::other_helper::to_utf16;
+    use user32::SetPropW;
+    use std::mem;
+
+    let prop_name = to_utf16(LIMITS_PROP_NAME);
+    SetPropW(hwnd, prop_name.as_ptr(), mem::transmute(Box::into_raw(Box::new(limits))));
+}
+
+/// Center a window in its parent client area. An owned window is centered over its owner, in screen coordinates.
+unsafe fn center_window(hwnd: HWND, parent: HWND, owned: bool) {
+    use low::window_helper::{get_window_client_rect, get_window_screen_rect, set_window_position};
+    use user32::GetWindowRect;
+    use winapi::RECT;
+    use std::mem;
+
+    let (px, py, pw, ph) = if owned {
+        let (left, top, right, bottom) = get_window_screen_rect(parent);
+        (left, top, (right-left) as u32, (bottom-top) as u32)
+    } else {
+        get_window_client_rect(parent)
+    };
+
+    let mut rect: RECT = mem::uninitialized();
+    GetWindowRect(hwnd, &mut rect);
+    let (w, h) = (rect.right-rect.left, rect.bottom-rect.top);
+
+    set_window_position(hwnd, px + ((pw as i32)-w)/2, py + ((ph as i32)-h)/2);
+}
+
 #[inline(always)]
 unsafe fn build_window<ID: Hash+Clone>(ui: &Ui<ID>, t: &CanvasT<ID>) -> Result<(HWND, RenderOut), Error> {
-    use low::window_helper::{WindowParams, build_window, handle_of_window};
-    use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD};
-    use user32::DestroyWindow;   
-
-    let flags: DWORD = WS_CHILD | 
-    if t.visible    { WS_VISIBLE }   else { 0 } |
-    if t.disabled   { WS_DISABLED }  else { 0 };
+    use low::window_helper::{WindowParams, build_window, handle_of_window, set_window_visibility};
+    use user32::{DestroyWindow, SetLayeredWindowAttributes, SetWindowPos, AdjustWindowRectEx};
+    use winapi::{RECT, WS_EX_COMPOSITED, WS_EX_LAYERED, WS_EX_TOOLWINDOW, LWA_ALPHA, HWND_TOP, SWP_NOMOVE, SWP_NOSIZE, SWP_NOACTIVATE};
+
+    // The canvas is shown once it is fully configured.
+    // An owned canvas is a top level window: the parent handle passed to `CreateWindowExW` becomes its owner
+    let flags = if t.owned { window_style(false, t.disabled) } else { child_style(false, t.disabled) };
+    let ex_flags = match t.alpha {
+        Some(_) => WS_EX_LAYERED,
+        None => WS_EX_COMPOSITED
+    } | if t.tool_window { WS_EX_TOOLWINDOW } else { 0 };
 
     // Get the parent handle
     let parent = match handle_of_window(ui, &t.parent, "The parent of a canvas must be a window-like control.") {
@@ -207,13 +468,22 @@ unsafe fn build_window<ID: Hash+Clone>(ui: &Ui<ID>, t: &CanvasT<ID>) -> Result<(
         Err(e) => { return Err(e); }
     };
 
+    // The size of the template is the client size. The frame of an owned canvas is added to it.
+    let size = if t.owned {
+        let mut frame = RECT{left: 0, top: 0, right: t.size.0 as i32, bottom: t.size.1 as i32};
+        AdjustWindowRectEx(&mut frame, flags, 0, ex_flags);
+        ((frame.right-frame.left) as u32, (frame.bottom-frame.top) as u32)
+    } else {
+        t.size.clone()
+    };
+
     let params = WindowParams {
         title:  "",
         class_name: CANVAS_CLASS_NAME,
         position: t.position.clone(),
-        size: t.size.clone(),
+        size: size,
         flags: flags,
-        ex_flags: None,
+        ex_flags: Some(ex_flags),
         parent: parent
     };
 
@@ -222,6 +492,23 @@ unsafe fn build_window<ID: Hash+Clone>(ui: &Ui<ID>, t: &CanvasT<ID>) -> Result<(
         Err(e) => { return Err(Error::System(e)); }
     };
 
+    if let Some(alpha) = t.alpha {
+        SetLayeredWindowAttributes(handle, 0, alpha, LWA_ALPHA);
+    }
+
+    if t.min_size.is_some() || t.max_size.is_some() {
+        attach_limits(handle, (t.min_size, t.max_size));
+    }
+
+    if t.topmost {
+        SetWindowPos(handle, HWND_TOP, 0, 0, 0, 0, SWP_NOMOVE|SWP_NOSIZE|SWP_NOACTIVATE);
+    }
+
+    // Centering needs the final size of the canvas
+    if t.center {
+        center_window(handle, parent, t.owned);
+    }
+
     let renderer = match build_renderer(handle) {
         Ok(r) => r,
         Err(e) => {
@@ -230,5 +517,9 @@ unsafe fn build_window<ID: Hash+Clone>(ui: &Ui<ID>, t: &CanvasT<ID>) -> Result<(
         }
     };
 
+    if t.visible {
+        set_window_visibility(handle, true);
+    }
+
     Ok((handle, renderer))
 }
\ No newline at end of file
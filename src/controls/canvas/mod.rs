@@ -1,7 +1,10 @@
 mod canvas;
 mod renderer;
 mod canvas_t;
+mod state;
 
 pub use self::canvas::{Canvas, CanvasProtected, CanvasResources};
 pub use self::renderer::{CanvasRenderer, RendererProtected};
-pub use self::canvas_t::{CanvasT, build_render_target, CANVAS_CLASS_NAME};
+pub use self::canvas_t::{CanvasT, build_render_target, update_canvas_activation, child_style, window_style,
+  set_dialog_keys, dialog_keys, CANVAS_CLASS_NAME};
+pub use self::state::{CanvasState, CanvasStateProtected};
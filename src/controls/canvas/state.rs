@@ -0,0 +1,126 @@
+/*!
+    A canvas control where the user can draw stuff.
+    This defines the canvas state shared with render threads
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering, fence};
+
+/**
+    The state of a canvas window, shared between the ui thread and a render thread (see `Canvas::state`).
+
+    The ui thread updates the state when the canvas is resized or destroyed. A render thread reads it before presenting
+    a frame to know if its surface must be resized or if it must stop rendering altogether.
+
+    Memory ordering:  
+    • The size and the resize generation are written as a sequence lock: the ui thread bumps an internal sequence number
+      before and after writing the size (`Release`), and the readers load it before and after reading the size (`Acquire`).
+      `size` retries until it reads the same even sequence twice, so a width and a height from different resizes are never mixed.  
+    • The `closing` flag is set with `Release` before the canvas window and its render target are destroyed and read with `Acquire`.
+      Once a render thread sees `is_closing() == true`, it must stop presenting; the flag is never reset.  
+    • The `active` flag follows the activation of the top level window of the canvas. It is only a hint, so it uses `Relaxed`.
+      A render thread can lower its frame rate while `is_active() == false`.
+*/
+pub struct CanvasState {
+    sequence: AtomicUsize,
+    width: AtomicUsize,
+    height: AtomicUsize,
+    closing: AtomicBool,
+    active: AtomicBool
+}
+
+impl CanvasState {
+
+    /// Return the last known client size of the canvas
+    pub fn size(&self) -> (u32, u32) {
+        loop {
+            let s1 = self.sequence.load(Ordering::Acquire);
+            let w = self.width.load(Ordering::Relaxed);
+            let h = self.height.load(Ordering::Relaxed);
+            fence(Ordering::Acquire);
+            let s2 = self.sequence.load(Ordering::Relaxed);
+
+            if s1 == s2 && s1 % 2 == 0 {
+                return (w as u32, h as u32);
+            }
+        }
+    }
+
+    /**
+        Return the resize generation of the canvas. The generation is increased by one each time the canvas is resized.  
+        A render thread can compare it with the generation of its surface to know if the surface must be resized.
+    */
+    pub fn generation(&self) -> usize {
+        self.sequence.load(Ordering::Acquire) / 2
+    }
+
+    /// Return `true` if the canvas is being destroyed.
+    pub fn is_closing(&self) -> bool {
+        self.closing.load(Ordering::Acquire)
+    }
+
+    /// Return `false` if the top level window of the canvas is inactive or minimized.
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+}
+
+/**
+    Protected canvas state methods (only available in the canvas control module)
+*/
+pub trait CanvasStateProtected {
+    fn create(w: u32, h: u32) -> CanvasState;
+    fn resized(&self, w: u32, h: u32);
+    fn close(&self);
+    fn activated(&self, active: bool);
+}
+
+impl CanvasStateProtected for CanvasState {
+
+    fn create(w: u32, h: u32) -> CanvasState {
+        CanvasState {
+            sequence: AtomicUsize::new(0),
+            width: AtomicUsize::new(w as usize),
+            height: AtomicUsize::new(h as usize),
+            closing: AtomicBool::new(false),
+            active: AtomicBool::new(true)
+        }
+    }
+
+    /// Must only be called from the ui thread
+    fn resized(&self, w: u32, h: u32) {
+        let s = self.sequence.load(Ordering::Relaxed);
+        self.sequence.store(s.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        self.width.store(w as usize, Ordering::Relaxed);
+        self.height.store(h as usize, Ordering::Relaxed);
+
+        self.sequence.store(s.wrapping_add(2), Ordering::Release);
+    }
+
+    fn close(&self) {
+        self.closing.store(true, Ordering::Release);
+    }
+
+    fn activated(&self, active: bool) {
+        self.active.store(active, Ordering::Relaxed);
+    }
+
+}
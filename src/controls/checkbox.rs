@@ -34,7 +34,7 @@ use defs::CheckState;
     A template that creates a standard checkbox
 
     Available events:  
-    Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `text`: The text of the checkbox  
@@ -42,6 +42,8 @@ use defs::CheckState;
     • `size`: The start size of the checkbox  
     • `visible`: If the checkbox should be visible to the user32  
     • `disabled`: If the user can or can't click on the checkbox  
+    • `tab_stop`: If the checkbox can receive the keyboard focus with the TAB key  
+    • `group`: If the checkbox is the first control of a group (`WS_GROUP`)  
     • `parent`: The checkbox parent  
     • `checkstate`: The starting checkstate  
     • `tristate`: If the checkbox should have three states  
@@ -54,6 +56,8 @@ pub struct CheckBoxT<S: Clone+Into<String>, ID: Hash+Clone> {
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub parent: ID,
     pub checkstate: CheckState,
     pub tristate: bool,
@@ -64,16 +68,18 @@ impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for CheckBoxT<S, ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<CheckBox>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, BS_NOTIFY, BS_AUTO3STATE, BS_AUTOCHECKBOX, BS_TEXT};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, BS_NOTIFY, BS_AUTO3STATE, BS_AUTOCHECKBOX, BS_TEXT};
 
         let flags: DWORD = WS_CHILD | BS_NOTIFY | BS_TEXT |
         if self.visible    { WS_VISIBLE }   else { 0 } |
         if self.disabled   { WS_DISABLED }  else { 0 } |
+        if self.tab_stop   { WS_TABSTOP }   else { 0 } |
+        if self.group      { WS_GROUP }     else { 0 } |
         if self.tristate   { BS_AUTO3STATE } else { BS_AUTOCHECKBOX };
 
         // Get the parent handle
@@ -35,7 +35,7 @@ use low::other_helper::{to_utf16, from_utf16};
     Template that creates a combobox control
 
     Available events:  
-    Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `collection`: Item collection of the combobox. The item type must implement `Display`  
@@ -43,6 +43,11 @@ use low::other_helper::{to_utf16, from_utf16};
     • `size`: The start size of the combobox  
     • `visible`: If the combobox should be visible to the user  
     • `disabled`: If the user can or can't use the combobox   
+    • `tab_stop`: If the combobox can receive the keyboard focus with the TAB key  
+    • `group`: If the combobox is the first control of a group (`WS_GROUP`)  
+    • `placeholder`: Text displayed in the combobox when no item is selected  
+    • `visible_items`: The number of items shown in the dropdown before it scrolls. If None, use the system default (30)  
+    • `dropdown_width`: The minimum width of the dropdown. If None, the dropdown is as wide as the combobox  
     • `parent`: The combobox parent  
     • `font`: The combobox font. If None, use the system default  
 */
@@ -53,7 +58,11 @@ pub struct ComboBoxT<D: Clone+Display+'static, ID: Hash+Clone, S: Clone+Into<Str
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub placeholder: Option<S>,
+    pub visible_items: Option<u32>,
+    pub dropdown_width: Option<u32>,
     pub parent: ID,
     pub font: Option<ID>,
 }
@@ -62,17 +71,19 @@ impl<D: Clone+Display+'static, ID: Hash+Clone, S: Clone+Into<String>> ControlT<I
     fn resource_type_id(&self) -> TypeId { TypeId::of::<ComboBox<D>>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
-        use low::defs::{CBS_DROPDOWNLIST, CBS_HASSTRINGS, CB_ADDSTRING};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD};
+        use low::defs::{CBS_DROPDOWNLIST, CBS_HASSTRINGS, CB_ADDSTRING, CB_SETMINVISIBLE, CB_SETDROPPEDWIDTH};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP};
 
         let flags: DWORD = WS_CHILD | CBS_HASSTRINGS | CBS_DROPDOWNLIST |
         if self.visible      { WS_VISIBLE }      else { 0 } |
-        if self.disabled     { WS_DISABLED }     else { 0 };
+        if self.disabled     { WS_DISABLED }     else { 0 } |
+        if self.tab_stop     { WS_TABSTOP }      else { 0 } |
+        if self.group        { WS_GROUP }        else { 0 };
 
         // Get the parent handle
         let parent = match handle_of_window(ui, &self.parent, "The parent of a combobox must be a window-like control.") {
@@ -112,6 +123,15 @@ impl<D: Clone+Display+'static, ID: Hash+Clone, S: Clone+Into<String>> ControlT<I
                         _ => {}
                     }
 
+                    // Set the dropdown size
+                    if let Some(count) = self.visible_items {
+                        SendMessageW(h, CB_SETMINVISIBLE, count as WPARAM, 0);
+                    }
+
+                    if let Some(width) = self.dropdown_width {
+                        SendMessageW(h, CB_SETDROPPEDWIDTH, width as WPARAM, 0);
+                    }
+
                     // Init collection
                     let collection: Vec<D> = self.collection.iter().map(
                         |s|{  
@@ -286,6 +306,35 @@ impl<D: Clone+Display> ComboBox<D> {
         unsafe{ SendMessageW(self.handle, CB_SHOWDROPDOWN, dropped as WPARAM, 0); }
     }
 
+    /**
+        Set the number of items shown in the dropdown before it scrolls.  
+        Requires the visual styles (comctl32 version 6). Returns `false` if the count could not be set.
+    */
+    pub fn set_visible_item_count(&self, count: u32) -> bool {
+        use low::defs::CB_SETMINVISIBLE;
+        unsafe{ SendMessageW(self.handle, CB_SETMINVISIBLE, count as WPARAM, 0) != 0 }
+    }
+
+    /// Return the number of items shown in the dropdown before it scrolls
+    pub fn get_visible_item_count(&self) -> u32 {
+        use low::defs::CB_GETMINVISIBLE;
+        unsafe{ SendMessageW(self.handle, CB_GETMINVISIBLE, 0, 0) as u32 }
+    }
+
+    /**
+        Set the minimum width of the dropdown in pixels. The dropdown is never narrower than the combobox.
+    */
+    pub fn set_dropdown_width(&self, width: u32) {
+        use low::defs::CB_SETDROPPEDWIDTH;
+        unsafe{ SendMessageW(self.handle, CB_SETDROPPEDWIDTH, width as WPARAM, 0); }
+    }
+
+    /// Return the width of the dropdown in pixels
+    pub fn get_dropdown_width(&self) -> u32 {
+        use low::defs::CB_GETDROPPEDWIDTH;
+        unsafe{ SendMessageW(self.handle, CB_GETDROPPEDWIDTH, 0, 0) as u32 }
+    }
+
     /// Remove every item in the inner collection and in the combobox
     pub fn clear(&mut self) {
         use low::defs::CB_RESETCONTENT;
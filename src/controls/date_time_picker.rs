@@ -0,0 +1,305 @@
+/*!
+    Date time picker control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+
+use winapi::{HWND, HFONT, SYSTEMTIME};
+use user32::SendMessageW;
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use defs::{PickerDateTime, DateTimePickerFlags};
+use low::other_helper::to_utf16;
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDateTime;
+
+/// Format used by a `DateTimePickerFlags::DateTime` picker if the template do not define one
+const DEFAULT_DATETIME_FORMAT: &'static str = "yyyy'-'MM'-'dd' 'HH':'mm':'ss";
+
+/**
+    A template that creates a date time picker (dtp). Unlike the `DatePicker`, the control can also edit the time.  
+
+    The format string uses the syntax of the `DatePicker` format string, with the time elements added:  
+    "h" 	The one- or two-digit hour in 12-hour format.  
+    "hh" 	The two-digit hour in 12-hour format. Single-digit values are preceded by a zero.  
+    "H" 	The one- or two-digit hour in 24-hour format.  
+    "HH" 	The two-digit hour in 24-hour format. Single-digit values are preceded by a zero.  
+    "m" 	The one- or two-digit minute.  
+    "mm" 	The two-digit minute. Single-digit values are preceded by a zero.  
+    "s" 	The one- or two-digit second.  
+    "ss" 	The two-digit second. Single-digit values are preceded by a zero.  
+
+    Events:  
+    Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw, Event::DateTimeChanged  
+
+    Members:  
+    • `value`: The value of the dtp. If None, either use the current system time or show nothing (if optional is true)  
+    • `position`: The start position of the dtp  
+    • `size`: The start size of the dtp  
+    • `visible`: If the dtp should be visible to the user   
+    • `disabled`: If the user can or can't edit the value of the dtp  
+    • `tab_stop`: If the dtp can receive the keyboard focus with the TAB key  
+    • `parent`: The dtp parent  
+    • `font`: The dtp font. If None, use the system default  
+    • `flags`: If the dtp edits the date, the time or both  
+    • `format`: The dtp format string. If None, use the default system locale format of the date or of the time.
+       A `DateTimePickerFlags::DateTime` dtp uses the `yyyy-MM-dd HH:mm:ss` format by default.  
+    • `optional`: If the dtp has a checkbox to leave it empty (`DTS_SHOWNONE`)  
+*/
+#[derive(Clone)]
+pub struct DateTimePickerT<S: Clone+Into<String>, ID: Hash+Clone> {
+    pub value: Option<PickerDateTime>,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub parent: ID,
+    pub font: Option<ID>,
+    pub flags: DateTimePickerFlags,
+    pub format: Option<S>,
+    pub optional: bool,
+}
+
+impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for DateTimePickerT<S, ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<DateTimePicker>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw, Event::DateTimeChanged]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, DTS_SHOWNONE, DTS_SHORTDATEFORMAT, DTS_TIMEFORMAT};
+
+        let flags: DWORD = WS_CHILD |
+        if self.visible  { WS_VISIBLE }   else { 0 } |
+        if self.disabled { WS_DISABLED }  else { 0 } |
+        if self.tab_stop { WS_TABSTOP }   else { 0 } |
+        if self.optional { DTS_SHOWNONE } else { 0 } |
+        match self.flags {
+            DateTimePickerFlags::Date | DateTimePickerFlags::DateTime => DTS_SHORTDATEFORMAT,
+            DateTimePickerFlags::Time => DTS_TIMEFORMAT
+        };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a date time picker must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) =>    
+                match handle_of_font(ui, &font_id, "The font of a date time picker must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysDateTimePick32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{ 
+                    set_window_font(h, font_handle, true); 
+                }
+                let dtp = DateTimePicker{handle: h};
+
+                match (self.format.as_ref(), self.flags) {
+                    (Some(format), _) => {
+                        let format: String = format.clone().into();
+                        dtp.set_format(Some(format.as_str()));
+                    },
+                    (None, DateTimePickerFlags::DateTime) => dtp.set_format(Some(DEFAULT_DATETIME_FORMAT)),
+                    (None, _) => {}
+                }
+
+                if self.value.is_some() || self.optional {
+                    dtp.set_value(&self.value);
+                }
+
+                Ok( Box::new(dtp) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A date time picker control. Do not handle timezone.  
+    With the `chrono` feature, the value can also be read and written as a `chrono::NaiveDateTime`.
+*/
+pub struct DateTimePicker {
+    handle: HWND,
+}
+
+impl DateTimePicker {
+
+    /// Set the format of the date time picker. If `None`, use the default system locale format.
+    pub fn set_format<'a>(&self, format: Option<&'a str>) {
+        use winapi::{DTM_SETFORMATW, LPARAM};
+        use std::ptr;
+
+        unsafe{
+            match format {
+                Some(format) => {
+                    let format = to_utf16(format);
+                    SendMessageW(self.handle, DTM_SETFORMATW, 0, format.as_ptr() as LPARAM);
+                },
+                None => {
+                    SendMessageW(self.handle, DTM_SETFORMATW, 0, ptr::null::<u16>() as LPARAM);
+                }
+            }
+        }
+    }
+
+    /**
+        Return `true` if the checkbox of the control is checked. If the date time picker is not optional, 
+        the checkbox is not displayed and this always returns `true`.
+    */
+    pub fn is_checked(&self) -> bool {
+        self.get_value().is_some()
+    }
+
+    /**
+        Return the value of the control in a `PickerDateTime` structure.  
+        Return None if `optional` was set and the checkbox is not checked.  
+        Note: use `get_value_string` to get the text value of the control.
+    */
+    pub fn get_value(&self) -> Option<PickerDateTime> {
+        use winapi::{DTM_GETSYSTEMTIME, GDT_VALID};
+        let mut syst: SYSTEMTIME = unsafe{ mem::zeroed() };
+
+        let r = unsafe{ SendMessageW(self.handle, DTM_GETSYSTEMTIME, 0, mem::transmute(&mut syst)) };
+        match r {
+            GDT_VALID => Some(PickerDateTime{
+                year: syst.wYear,
+                month: syst.wMonth,
+                day: syst.wDay,
+                hour: syst.wHour,
+                minute: syst.wMinute,
+                second: syst.wSecond
+            }),
+            _ => None
+        }
+    }
+
+    /**
+        Set the value of the control from a `PickerDateTime` structure.  
+        If `None` is passed, clears the checkbox.
+    */
+    pub fn set_value(&self, value: &Option<PickerDateTime>) {
+        use winapi::{DTM_SETSYSTEMTIME, GDT_VALID, GDT_NONE, WPARAM};
+        unsafe{
+            match value {
+                &Some(ref v) => {
+                    let syst: SYSTEMTIME = SYSTEMTIME{ 
+                        wYear: v.year, 
+                        wMonth: v.month, 
+                        wDay: v.day, 
+                        wDayOfWeek: 0, wHour: v.hour, wMinute: v.minute, wSecond: v.second, wMilliseconds: 0 
+                    };
+                    SendMessageW(self.handle, DTM_SETSYSTEMTIME, GDT_VALID as WPARAM, mem::transmute(&syst));
+                },
+                &None => { 
+                    SendMessageW(self.handle, DTM_SETSYSTEMTIME, GDT_NONE as WPARAM, 0); 
+                }
+            };
+        }
+    }
+
+    /**
+        Return the value of the control as a `NaiveDateTime`.  
+        Return None if `optional` was set and the checkbox is not checked.
+    */
+    #[cfg(feature = "chrono")]
+    pub fn value(&self) -> Option<NaiveDateTime> {
+        use chrono::NaiveDate;
+
+        match self.get_value() {
+            Some(v) => NaiveDate::from_ymd_opt(v.year as i32, v.month as u32, v.day as u32)
+                .and_then(|d| d.and_hms_opt(v.hour as u32, v.minute as u32, v.second as u32)),
+            None => None
+        }
+    }
+
+    /**
+        Set the value of the control from a `NaiveDateTime`. The fractions of second are ignored.  
+        If `None` is passed, clears the checkbox.
+    */
+    #[cfg(feature = "chrono")]
+    pub fn set_naive_value(&self, value: Option<NaiveDateTime>) {
+        use chrono::{Datelike, Timelike};
+
+        let value = value.map(|dt| PickerDateTime{
+            year: dt.year() as u16,
+            month: dt.month() as u16,
+            day: dt.day() as u16,
+            hour: dt.hour() as u16,
+            minute: dt.minute() as u16,
+            second: dt.second() as u16
+        });
+
+        self.set_value(&value);
+    }
+
+    pub fn get_value_string(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for DateTimePicker {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::DateTimePicker 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
@@ -57,6 +57,8 @@ use low::other_helper::to_utf16;
     • `size`: The start size of the dtp  
     • `visible`: If the dtp should be visible to the user   
     • `disabled`: If the user can or can't edit the value of the dtp  
+    • `tab_stop`: If the dtp can receive the keyboard focus with the TAB key  
+    • `group`: If the dtp is the first control of a group (`WS_GROUP`)  
     • `parent`: The dtp parent  
     • `font`: The dtp font. If None, use the system default  
     • `align`: The alignment of the dtp control,
@@ -70,6 +72,8 @@ pub struct DatePickerT<S: Clone+Into<String>, ID: Hash+Clone> {
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub parent: ID,
     pub font: Option<ID>,
     pub align: HTextAlign,
@@ -87,11 +91,13 @@ impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for DatePickerT<S, ID>
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, DTS_SHOWNONE, DTS_RIGHTALIGN};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, DTS_SHOWNONE, DTS_RIGHTALIGN};
 
         let flags: DWORD = WS_CHILD |
         if self.visible  { WS_VISIBLE }   else { 0 } |
         if self.disabled { WS_DISABLED }  else { 0 } |
+        if self.tab_stop { WS_TABSTOP }   else { 0 } |
+        if self.group    { WS_GROUP }     else { 0 } |
         if self.optional { DTS_SHOWNONE } else { 0 } |
         match self.align   { 
             HTextAlign::Center=>{ return Err(Error::UserError("The date time picker control do not support centered text.".to_string())); }, 
@@ -33,7 +33,7 @@ use defs::HTextAlign;
     A template that creates a standard groupbox
 
     Available events:  
-    Event::Destroyed, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `text`: The text of the groupbox  
@@ -60,7 +60,7 @@ impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for GroupBoxT<S, ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<GroupBox>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
@@ -0,0 +1,222 @@
+/*!
+    A hotkey input control
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+
+use user32::SendMessageW;
+use winapi::{HWND, HFONT, WPARAM, LPARAM};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use defs::{Shortcut, HotkeyCombination};
+
+/**
+    A template that creates a hotkey input. The user can press a key combination in the control
+    and the control displays it.
+
+    Available events:  
+    Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `position`: The start position of the hotkey input  
+    • `size`: The start size of the hotkey input  
+    • `visible`: If the hotkey input should be visible to the user  
+    • `disabled`: If the user can or can't use the hotkey input  
+    • `tab_stop`: If the hotkey input can receive the keyboard focus with the TAB key  
+    • `group`: If the hotkey input is the first control of a group (`WS_GROUP`)  
+    • `hotkey`: The starting shortcut displayed by the control  
+    • `parent`: The hotkey input parent  
+    • `font`: The hotkey input font. If None, use the system default  
+*/
+#[derive(Clone)]
+pub struct HotkeyInputT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
+    pub hotkey: Option<Shortcut>,
+    pub parent: ID,
+    pub font: Option<ID>,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for HotkeyInputT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<HotkeyInput>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP};
+
+        let flags: DWORD = WS_CHILD |
+        if self.visible  { WS_VISIBLE }  else { 0 } |
+        if self.disabled { WS_DISABLED } else { 0 } |
+        if self.tab_stop { WS_TABSTOP }  else { 0 } |
+        if self.group    { WS_GROUP }    else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a hotkey input must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of a hotkey input must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "msctls_hotkey32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{
+                    set_window_font(h, font_handle, true);
+                    set_hotkey(h, self.hotkey.as_ref());
+                }
+                Ok( Box::new(HotkeyInput{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A control that let the user enter a keyboard shortcut
+*/
+pub struct HotkeyInput {
+    handle: HWND
+}
+
+impl HotkeyInput {
+
+    /// Return the shortcut displayed by the control or `None` if the control is empty
+    pub fn get_hotkey(&self) -> Option<Shortcut> {
+        use winapi::{HKM_GETHOTKEY, HOTKEYF_CONTROL, HOTKEYF_ALT, HOTKEYF_SHIFT};
+
+        let value = unsafe{ SendMessageW(self.handle, HKM_GETHOTKEY, 0, 0) as u16 };
+        let (key, modifiers) = ((value & 0xFF) as u32, (value >> 8) as u8);
+        if key == 0 {
+            return None;
+        }
+
+        Some(Shortcut {
+            key: key,
+            ctrl: (modifiers & HOTKEYF_CONTROL) != 0,
+            alt: (modifiers & HOTKEYF_ALT) != 0,
+            shift: (modifiers & HOTKEYF_SHIFT) != 0,
+        })
+    }
+
+    /// Set the shortcut displayed by the control. If `hotkey` is `None`, clear the control.
+    pub fn set_hotkey(&self, hotkey: Option<Shortcut>) {
+        unsafe{ set_hotkey(self.handle, hotkey.as_ref()); }
+    }
+
+    /**
+        Set the modifiers combinations that the user is not allowed to enter.  
+        When the user enters one of the `invalid` combinations, the control replace the modifiers with the modifiers
+        of `replacement` (the key of `replacement` is ignored).
+
+        Ex: `set_rules(&[HotkeyCombination::None, HotkeyCombination::Shift], Shortcut{key: 0, ctrl: true, alt: false, shift: false})`
+        forces the user to press at least control or alt.
+    */
+    pub fn set_rules(&self, invalid: &[HotkeyCombination], replacement: Shortcut) {
+        use winapi::{HKM_SETRULES, HKCOMB_NONE, HKCOMB_S, HKCOMB_C, HKCOMB_A, HKCOMB_SC, HKCOMB_SA, HKCOMB_CA, HKCOMB_SCA};
+
+        let combinations = invalid.iter().fold(0, |acc, c| acc | match c {
+            &HotkeyCombination::None => HKCOMB_NONE,
+            &HotkeyCombination::Shift => HKCOMB_S,
+            &HotkeyCombination::Ctrl => HKCOMB_C,
+            &HotkeyCombination::Alt => HKCOMB_A,
+            &HotkeyCombination::ShiftCtrl => HKCOMB_SC,
+            &HotkeyCombination::ShiftAlt => HKCOMB_SA,
+            &HotkeyCombination::CtrlAlt => HKCOMB_CA,
+            &HotkeyCombination::ShiftCtrlAlt => HKCOMB_SCA,
+        });
+
+        unsafe{ SendMessageW(self.handle, HKM_SETRULES, combinations, modifiers_of(&replacement) as LPARAM); }
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for HotkeyInput {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::HotkeyInput 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+// Private functions
+
+#[inline(always)]
+fn modifiers_of(hotkey: &Shortcut) -> u8 {
+    use winapi::{HOTKEYF_CONTROL, HOTKEYF_ALT, HOTKEYF_SHIFT};
+    if hotkey.ctrl  { HOTKEYF_CONTROL } else { 0 } |
+    if hotkey.alt   { HOTKEYF_ALT }     else { 0 } |
+    if hotkey.shift { HOTKEYF_SHIFT }   else { 0 }
+}
+
+#[inline(always)]
+unsafe fn set_hotkey(handle: HWND, hotkey: Option<&Shortcut>) {
+    use winapi::HKM_SETHOTKEY;
+    let value = match hotkey {
+        Some(k) => ((modifiers_of(k) as WPARAM) << 8) | ((k.key as WPARAM) & 0xFF),
+        None => 0
+    };
+    SendMessageW(handle, HKM_SETHOTKEY, value, 0);
+}
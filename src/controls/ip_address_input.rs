@@ -0,0 +1,212 @@
+/*!
+    An IP address input control
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+
+use user32::SendMessageW;
+use winapi::{HWND, HFONT, DWORD, WPARAM, LPARAM};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+
+/**
+    A template that creates an IP address input. The control has 4 fields, one for each byte of an IPv4 address.
+
+    Available events:  
+    Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `position`: The start position of the ip address input  
+    • `size`: The start size of the ip address input  
+    • `visible`: If the ip address input should be visible to the user  
+    • `disabled`: If the user can or can't use the ip address input  
+    • `tab_stop`: If the ip address input can receive the keyboard focus with the TAB key  
+    • `group`: If the ip address input is the first control of a group (`WS_GROUP`)  
+    • `address`: The starting address. If None, the fields are empty.  
+    • `parent`: The ip address input parent  
+    • `font`: The ip address input font. If None, use the system default  
+*/
+#[derive(Clone)]
+pub struct IpAddressInputT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
+    pub address: Option<[u8; 4]>,
+    pub parent: ID,
+    pub font: Option<ID>,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for IpAddressInputT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<IpAddressInput>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use winapi::{WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP};
+
+        let flags: DWORD = WS_CHILD |
+        if self.visible  { WS_VISIBLE }  else { 0 } |
+        if self.disabled { WS_DISABLED } else { 0 } |
+        if self.tab_stop { WS_TABSTOP }  else { 0 } |
+        if self.group    { WS_GROUP }    else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of an ip address input must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of an ip address input must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysIPAddress32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{
+                    set_window_font(h, font_handle, true);
+                    if let Some(address) = self.address.as_ref() {
+                        set_address(h, address);
+                    }
+                }
+                Ok( Box::new(IpAddressInput{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A control that let the user enter an IPv4 address
+*/
+pub struct IpAddressInput {
+    handle: HWND
+}
+
+impl IpAddressInput {
+
+    /// Return the address in the control. Empty fields are returned as 0.
+    pub fn get_address(&self) -> [u8; 4] {
+        use winapi::IPM_GETADDRESS;
+
+        let mut address: DWORD = 0;
+        unsafe{ SendMessageW(self.handle, IPM_GETADDRESS, 0, mem::transmute(&mut address)); }
+
+        [(address >> 24) as u8, (address >> 16) as u8, (address >> 8) as u8, address as u8]
+    }
+
+    /// Set the address in the control
+    pub fn set_address(&self, address: [u8; 4]) {
+        unsafe{ set_address(self.handle, &address); }
+    }
+
+    /// Clear the fields of the control
+    pub fn clear(&self) {
+        use winapi::IPM_CLEARADDRESS;
+        unsafe{ SendMessageW(self.handle, IPM_CLEARADDRESS, 0, 0); }
+    }
+
+    /// Return true if all the fields of the control are empty
+    pub fn is_blank(&self) -> bool {
+        use winapi::IPM_ISBLANK;
+        unsafe{ SendMessageW(self.handle, IPM_ISBLANK, 0, 0) != 0 }
+    }
+
+    /**
+        Limit the values that the user can enter in the field at `field` (0 to 3).  
+        If `field` is greater than 3 or if `min` is greater than `max`, an error is returned.
+    */
+    pub fn set_field_range(&self, field: u8, min: u8, max: u8) -> Result<(), Error> {
+        use winapi::IPM_SETRANGE;
+
+        if field > 3 {
+            return Err(Error::UserError("An ip address only has 4 fields".to_string()));
+        }
+
+        if min > max {
+            return Err(Error::UserError("The field range maximum value must be greater or equal than the minimum value".to_string()));
+        }
+
+        let range = ((max as LPARAM) << 8) | (min as LPARAM);
+        unsafe{ SendMessageW(self.handle, IPM_SETRANGE, field as WPARAM, range); }
+
+        Ok(())
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for IpAddressInput {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::IpAddressInput 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+// Private functions
+
+#[inline(always)]
+unsafe fn set_address(handle: HWND, address: &[u8; 4]) {
+    use winapi::IPM_SETADDRESS;
+    let value = ((address[0] as LPARAM) << 24) | ((address[1] as LPARAM) << 16) | ((address[2] as LPARAM) << 8) | (address[3] as LPARAM);
+    SendMessageW(handle, IPM_SETADDRESS, 0, value);
+}
@@ -33,7 +33,7 @@ use defs::HTextAlign;
     A template that creates a standard label
 
     Available events:  
-    Event::Destroyed, Event::Click, Event::DoubleClick, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::Click, Event::DoubleClick, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `text`: The text of the label  
@@ -44,6 +44,7 @@ use defs::HTextAlign;
     • `align`: The text align of the label
     • `parent`: The label parent  
     • `font`: The label font. If None, use the system default  
+    • `auto_tooltip`: If the label text should end with an ellipsis when it does not fit the label and show the full text in a tooltip on hover  
 */
 #[derive(Clone)]
 pub struct LabelT<S: Clone+Into<String>, ID: Hash+Clone> {
@@ -55,23 +56,26 @@ pub struct LabelT<S: Clone+Into<String>, ID: Hash+Clone> {
     pub align: HTextAlign,
     pub parent: ID,
     pub font: Option<ID>,
+    pub auto_tooltip: bool,
 }
 
 impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for LabelT<S, ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<Label>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
-        use low::defs::{SS_NOTIFY, SS_NOPREFIX, SS_LEFT, SS_RIGHT, SS_CENTER};
+        use low::tooltip_helper::set_auto_tooltip;
+        use low::defs::{SS_NOTIFY, SS_NOPREFIX, SS_LEFT, SS_RIGHT, SS_CENTER, SS_ENDELLIPSIS};
         use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD};
 
         let flags: DWORD = WS_CHILD | SS_NOTIFY | SS_NOPREFIX | 
         if self.visible    { WS_VISIBLE }   else { 0 } |
         if self.disabled   { WS_DISABLED }  else { 0 } |
+        if self.auto_tooltip { SS_ENDELLIPSIS } else { 0 } |
         match self.align   { HTextAlign::Center=>SS_CENTER, HTextAlign::Left=>SS_LEFT, HTextAlign::Right=>SS_RIGHT };
 
         // Get the parent handle
@@ -103,6 +107,14 @@ impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for LabelT<S, ID> {
         match unsafe{ build_window(params) } {
             Ok(h) => {
                 unsafe{ set_window_font(h, font_handle, true); }
+
+                if self.auto_tooltip {
+                    if let Err(e) = unsafe{ set_auto_tooltip(h, parent, label_truncated_text) } {
+                        unsafe{ ::user32::DestroyWindow(h); }
+                        return Err(e);
+                    }
+                }
+
                 Ok( Box::new(Label{handle: h}) )
             },
             Err(e) => Err(Error::System(e))
@@ -145,4 +157,20 @@ impl Control for Label {
         unsafe{ DestroyWindow(self.handle) };
     }
 
-}
\ No newline at end of file
+}
+
+
+// Private functions
+
+/// The text of a label created with `auto_tooltip` is truncated if it is wider than the label
+unsafe fn label_truncated_text(handle: HWND, _x: i32, _y: i32) -> Option<String> {
+    use low::window_helper::{get_window_text, get_window_client_rect};
+    use low::tooltip_helper::text_width;
+
+    let text = get_window_text(handle);
+    let (_, _, width, _) = get_window_client_rect(handle);
+    match text_width(handle, &text) {
+        Some(w) if w > width => Some(text),
+        _ => None
+    }
+}
@@ -0,0 +1,455 @@
+/*!
+    List view control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::cmp::Ordering;
+use std::mem;
+
+use user32::SendMessageW;
+use winapi::{HWND, HFONT, WPARAM, LPARAM, c_int};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use defs::ListViewStyle;
+use low::other_helper::{to_utf16, from_utf16};
+
+/**
+    Template that creates a list view control
+
+    Available events:  
+    Event::Destroyed, Event::SelectionChanged, Event::ColumnClick, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    `ColumnClick` receives `EventArgs::Column` with the index of the clicked column header. It is only raised in the `Report` style.
+
+    Members:  
+    • `position`: The start position of the list view  
+    • `size`: The start size of the list view  
+    • `visible`: If the list view should be visible to the user  
+    • `disabled`: If the user can or can't use the list view  
+    • `tab_stop`: If the list view can receive the keyboard focus with the TAB key  
+    • `group`: If the list view is the first control of a group (`WS_GROUP`)  
+    • `style`: How the items are displayed (`Icon`, `SmallIcon`, `List` or `Report`)  
+    • `parent`: The list view parent  
+    • `font`: The list view font. If None, use the system default  
+    • `auto_tooltip`: If a tooltip should show the full text of a cell that is wider than the cell when the mouse is over it  
+*/
+#[derive(Clone)]
+pub struct ListViewT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
+    pub style: ListViewStyle,
+    pub parent: ID,
+    pub font: Option<ID>,
+    pub auto_tooltip: bool,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for ListViewT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<ListView>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::SelectionChanged, Event::ColumnClick, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use low::tooltip_helper::set_auto_tooltip;
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, WS_BORDER, LVS_SHOWSELALWAYS,
+          LVS_EX_FULLROWSELECT, LVM_SETEXTENDEDLISTVIEWSTYLE};
+
+        let flags: DWORD = WS_CHILD | WS_BORDER | LVS_SHOWSELALWAYS | style_flags(&self.style) |
+        if self.visible      { WS_VISIBLE }    else { 0 } |
+        if self.disabled     { WS_DISABLED }   else { 0 } |
+        if self.tab_stop     { WS_TABSTOP }    else { 0 } |
+        if self.group        { WS_GROUP }      else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a list view must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of a list view must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysListView32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{ 
+                    set_window_font(h, font_handle, true);
+                    SendMessageW(h, LVM_SETEXTENDEDLISTVIEWSTYLE, LVS_EX_FULLROWSELECT as WPARAM, LVS_EX_FULLROWSELECT as LPARAM);
+
+                    if self.auto_tooltip {
+                        if let Err(e) = set_auto_tooltip(h, parent, list_view_truncated_text) {
+                            ::user32::DestroyWindow(h);
+                            return Err(e);
+                        }
+                    }
+                }
+                Ok( Box::new(ListView{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A control that displays a list of items with an optional grid of sub items (in the `Report` style)
+*/
+pub struct ListView {
+    handle: HWND
+}
+
+impl ListView {
+
+    /**
+        Insert a column at `index` with a width of `width` pixels. `text` is the column title.  
+        The columns are only displayed in the `Report` style.
+    */
+    pub fn add_column<'a>(&self, index: usize, width: u32, text: &'a str) {
+        unsafe{ insert_column(self.handle, index, width, text) }
+    }
+
+    /**
+        Set the text of the cell at `row` and `col`.  
+        If `col` is 0, a new item is inserted at `row`. Otherwise, the text of the sub item of the existing item at `row` is set.
+    */
+    pub fn insert_item<'a>(&self, row: usize, col: usize, text: &'a str) {
+        unsafe{ set_item_text(self.handle, row, col, text) }
+    }
+
+    /// Remove the item at `index`. Do nothing if the index is out of bounds.
+    pub fn remove_item(&self, index: usize) {
+        use winapi::LVM_DELETEITEM;
+        unsafe{ SendMessageW(self.handle, LVM_DELETEITEM, index as WPARAM, 0); }
+    }
+
+    /// Remove every item in the list view. The columns are kept.
+    pub fn clear(&self) {
+        use winapi::LVM_DELETEALLITEMS;
+        unsafe{ SendMessageW(self.handle, LVM_DELETEALLITEMS, 0, 0); }
+    }
+
+    /// Return the number of items in the list view
+    pub fn len(&self) -> usize {
+        unsafe{ item_count(self.handle) }
+    }
+
+    /**
+        Return the text of the cell at `row` and `col`.  
+        Return an empty string if the cell does not exist.
+    */
+    pub fn get_item_text(&self, row: usize, col: usize) -> String {
+        unsafe{ item_text(self.handle, row, col) }
+    }
+
+    /// Return the index of the first selected item or None if no item is selected
+    pub fn get_selected_index(&self) -> Option<usize> {
+        unsafe{ next_selected_item(self.handle, None) }
+    }
+
+    /// Return the indexes of every selected items
+    pub fn get_selected_indexes(&self) -> Vec<usize> {
+        unsafe{ selected_items(self.handle) }
+    }
+
+    /// Select or unselect the item at `index`
+    pub fn set_selected(&self, index: usize, selected: bool) {
+        unsafe{ set_item_selected(self.handle, index, selected) }
+    }
+
+    /**
+        Start an OLE drag and drop operation with the selected rows. The dragged data is the text of the first column
+        of the rows, one row per line. The system shows a ghost of the selected rows (icons and text) under the cursor.
+        Requires the `drag-drop` feature.
+
+        The function only returns once the operation is done. Returns `Ok(true)` if the rows were dropped on a target and
+        `Ok(false)` if the operation was canceled or if no row is selected.
+
+        Errors:  
+        • `Error::System` if the drag and drop operation could not be started  
+    */
+    #[cfg(feature = "drag-drop")]
+    pub fn drag_selection(&self) -> Result<bool, Error> {
+        use low::drag_helper::drag_text;
+
+        let rows: Vec<String> = self.get_selected_indexes().iter().map(|&row| self.get_item_text(row, 0)).collect();
+        if rows.is_empty() {
+            return Ok(false);
+        }
+
+        unsafe{ drag_text(self.handle, &rows.join("\r\n")) }
+    }
+
+    /**
+        Sort the items using the text of the column `col`. The comparison ignores the case.  
+        The list view do not keep the items sorted, so this must be called again after new items are inserted.
+    */
+    pub fn sort_column(&self, col: usize, ascending: bool) {
+        use winapi::LVM_SORTITEMSEX;
+
+        let sort = SortParams { handle: self.handle, col: col, ascending: ascending };
+        let compare: unsafe extern "system" fn(LPARAM, LPARAM, LPARAM) -> c_int = compare_items;
+
+        unsafe{ SendMessageW(self.handle, LVM_SORTITEMSEX, mem::transmute(&sort), mem::transmute(compare)); }
+    }
+
+    /// Return the display style of the list view
+    pub fn get_style(&self) -> ListViewStyle {
+        use low::window_helper::get_window_long;
+        use winapi::{GWL_STYLE, LVS_TYPEMASK, LVS_REPORT, LVS_SMALLICON, LVS_LIST};
+
+        match (get_window_long(self.handle, GWL_STYLE) as u32) & LVS_TYPEMASK {
+            LVS_REPORT => ListViewStyle::Report,
+            LVS_SMALLICON => ListViewStyle::SmallIcon,
+            LVS_LIST => ListViewStyle::List,
+            _ => ListViewStyle::Icon
+        }
+    }
+
+    /// Set the display style of the list view
+    pub fn set_style(&self, style: ListViewStyle) {
+        use low::window_helper::{get_window_long, set_window_long};
+        use winapi::{GWL_STYLE, LVS_TYPEMASK};
+
+        let old_style = get_window_long(self.handle, GWL_STYLE) as u32;
+        let new_style = (old_style & !LVS_TYPEMASK) | style_flags(&style);
+        set_window_long(self.handle, GWL_STYLE, new_style as usize);
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for ListView {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::ListView 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+/// Parameters passed to `compare_items` by `sort_column`
+struct SortParams {
+    handle: HWND,
+    col: usize,
+    ascending: bool
+}
+
+/// Return the `LVS_*` flag of a list view style
+fn style_flags(style: &ListViewStyle) -> u32 {
+    use winapi::{LVS_ICON, LVS_REPORT, LVS_SMALLICON, LVS_LIST};
+
+    match style {
+        &ListViewStyle::Icon => LVS_ICON,
+        &ListViewStyle::Report => LVS_REPORT,
+        &ListViewStyle::SmallIcon => LVS_SMALLICON,
+        &ListViewStyle::List => LVS_LIST,
+    }
+}
+
+/**
+    Return the text of the cell under the point (x, y) of a list view if the text is wider than the cell.
+    Also used by the multi column listbox, which is a list view in the `Report` style.
+*/
+pub unsafe fn list_view_truncated_text(handle: HWND, x: i32, y: i32) -> Option<String> {
+    use low::defs::LVHITTESTINFO;
+    use low::tooltip_helper::text_width;
+    use winapi::{POINT, RECT, LVM_SUBITEMHITTEST, LVM_GETSUBITEMRECT, LVIR_LABEL};
+
+    let mut info = LVHITTESTINFO{ pt: POINT{x: x, y: y}, flags: 0, iItem: -1, iSubItem: 0, iGroup: 0 };
+    SendMessageW(handle, LVM_SUBITEMHITTEST, 0, mem::transmute(&mut info));
+    if info.iItem < 0 {
+        return None;
+    }
+
+    // The sub item and the part of the cell are passed in the rect
+    let mut rect = RECT{ left: LVIR_LABEL, top: info.iSubItem, right: 0, bottom: 0 };
+    if SendMessageW(handle, LVM_GETSUBITEMRECT, info.iItem as WPARAM, mem::transmute(&mut rect)) == 0 {
+        return None;
+    }
+
+    let text = item_text(handle, info.iItem as usize, info.iSubItem as usize);
+    match text_width(handle, &text) {
+        Some(w) if (w as i32) > rect.right - rect.left => Some(text),
+        _ => None
+    }
+}
+
+/**
+    Insert a column at `index` in a list view. Also used by the multi column listbox.
+*/
+pub unsafe fn insert_column<'a>(handle: HWND, index: usize, width: u32, text: &'a str) {
+    use winapi::{LVM_INSERTCOLUMNW, LVCOLUMNW, LVCF_WIDTH, LVCF_TEXT, LVCF_SUBITEM};
+
+    let mut text = to_utf16(text);
+    let mut column: LVCOLUMNW = mem::zeroed();
+    column.mask = LVCF_WIDTH | LVCF_TEXT | LVCF_SUBITEM;
+    column.cx = width as c_int;
+    column.pszText = text.as_mut_ptr();
+    column.iSubItem = index as c_int;
+
+    SendMessageW(handle, LVM_INSERTCOLUMNW, index as WPARAM, mem::transmute(&column));
+}
+
+/**
+    Set the text of the cell at `row` and `col` of a list view. If `col` is 0, a new item is inserted at `row`.
+    Also used by the multi column listbox.
+*/
+pub unsafe fn set_item_text<'a>(handle: HWND, row: usize, col: usize, text: &'a str) {
+    use winapi::{LVM_INSERTITEMW, LVM_SETITEMTEXTW, LVITEMW, LVIF_TEXT};
+
+    let mut text = to_utf16(text);
+    let mut item: LVITEMW = mem::zeroed();
+    item.mask = LVIF_TEXT;
+    item.iItem = row as c_int;
+    item.iSubItem = col as c_int;
+    item.pszText = text.as_mut_ptr();
+
+    if col == 0 {
+        SendMessageW(handle, LVM_INSERTITEMW, 0, mem::transmute(&item));
+    } else {
+        SendMessageW(handle, LVM_SETITEMTEXTW, row as WPARAM, mem::transmute(&item));
+    }
+}
+
+/// Return the number of items in a list view
+pub unsafe fn item_count(handle: HWND) -> usize {
+    use winapi::LVM_GETITEMCOUNT;
+    SendMessageW(handle, LVM_GETITEMCOUNT, 0, 0) as usize
+}
+
+/// Return the index of the first selected item of a list view after `start`, or the first selected item if `start` is None
+pub unsafe fn next_selected_item(handle: HWND, start: Option<usize>) -> Option<usize> {
+    use winapi::{LVM_GETNEXTITEM, LVNI_SELECTED};
+
+    let start: c_int = match start { Some(i) => i as c_int, None => -1 };
+    let index = SendMessageW(handle, LVM_GETNEXTITEM, start as WPARAM, LVNI_SELECTED) as c_int;
+    if index == -1 {
+        None
+    } else {
+        Some(index as usize)
+    }
+}
+
+/// Return the indexes of every selected items of a list view
+pub unsafe fn selected_items(handle: HWND) -> Vec<usize> {
+    let mut selected = Vec::new();
+    let mut next = next_selected_item(handle, None);
+    while let Some(index) = next {
+        selected.push(index);
+        next = next_selected_item(handle, Some(index));
+    }
+
+    selected
+}
+
+/// Select or unselect the item at `index` of a list view
+pub unsafe fn set_item_selected(handle: HWND, index: usize, selected: bool) {
+    use winapi::{LVM_SETITEMSTATE, LVITEMW, LVIF_STATE, LVIS_SELECTED};
+
+    let mut item: LVITEMW = mem::zeroed();
+    item.mask = LVIF_STATE;
+    item.state = if selected { LVIS_SELECTED } else { 0 };
+    item.stateMask = LVIS_SELECTED;
+
+    SendMessageW(handle, LVM_SETITEMSTATE, index as WPARAM, mem::transmute(&item));
+}
+
+/**
+    Return the text of the cell at `row` and `col` of a list view. Return an empty string if the cell does not exist.
+    Also used by the multi column listbox.
+*/
+pub unsafe fn item_text(handle: HWND, row: usize, col: usize) -> String {
+    use winapi::{LVM_GETITEMTEXTW, LVITEMW};
+
+    let mut buffer_size: usize = 256;
+    loop {
+        let mut buffer: Vec<u16> = vec![0; buffer_size];
+
+        let mut item: LVITEMW = mem::zeroed();
+        item.iSubItem = col as c_int;
+        item.pszText = buffer.as_mut_ptr();
+        item.cchTextMax = buffer_size as c_int;
+
+        let length = SendMessageW(handle, LVM_GETITEMTEXTW, row as WPARAM, mem::transmute(&mut item)) as usize;
+        if length + 1 < buffer_size {
+            return from_utf16(&buffer[0..length]);
+        }
+
+        // The text was truncated
+        buffer_size *= 2;
+    }
+}
+
+/**
+    Sort callback of `LVM_SORTITEMSEX`. `index1` and `index2` are the indices of the items to compare
+    and `params` is a pointer to the `SortParams` of the sort.
+*/
+unsafe extern "system" fn compare_items(index1: LPARAM, index2: LPARAM, params: LPARAM) -> c_int {
+    let params: &SortParams = &*(params as *const SortParams);
+    let text1 = item_text(params.handle, index1 as usize, params.col).to_lowercase();
+    let text2 = item_text(params.handle, index2 as usize, params.col).to_lowercase();
+
+    let order = if params.ascending { text1.cmp(&text2) } else { text2.cmp(&text1) };
+    match order {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1
+    }
+}
@@ -36,7 +36,7 @@ use low::other_helper::{to_utf16, from_utf16};
     Template that creates a listbox control
 
     Available events:  
-    Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `collection`: Item collection of the listbox. The item type must implement `Display`  
@@ -44,10 +44,14 @@ use low::other_helper::{to_utf16, from_utf16};
     • `size`: The start size of the listbox  
     • `visible`: If the listbox should be visible to the user  
     • `disabled`: If the user can or can't use the listbox  
+    • `tab_stop`: If the listbox can receive the keyboard focus with the TAB key  
+    • `group`: If the listbox is the first control of a group (`WS_GROUP`)  
     • `readonly` : If true, the user won't be able to select items in the listbox  
     • `multi_select`: If true, allow the user to select more than one item  
+    • `tab_stops`: If true, tab characters in the items are expanded to the tab stops (see `ListBox::set_tab_stops`)  
     • `parent`: The listbox parent  
     • `font`: The listbox font. If None, use the system default  
+    • `auto_tooltip`: If a tooltip should show the full text of an item that is wider than the listbox when the mouse is over it  
 */
 #[derive(Clone)]
 pub struct ListBoxT<D: Clone+Display+'static, ID: Hash+Clone> {
@@ -56,29 +60,37 @@ pub struct ListBoxT<D: Clone+Display+'static, ID: Hash+Clone> {
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub readonly: bool,
     pub multi_select: bool,
+    pub tab_stops: bool,
     pub parent: ID,
     pub font: Option<ID>,
+    pub auto_tooltip: bool,
 }
 
 impl<D: Clone+Display+'static, ID: Hash+Clone> ControlT<ID> for ListBoxT<D, ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<ListBox<D>>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::SelectionChanged, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
-        use low::defs::{LB_ADDSTRING, LBS_HASSTRINGS, LBS_MULTIPLESEL, LBS_NOSEL, LBS_NOTIFY};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_BORDER, WS_VSCROLL, WS_HSCROLL};
+        use low::tooltip_helper::set_auto_tooltip;
+        use low::defs::{LB_ADDSTRING, LBS_HASSTRINGS, LBS_MULTIPLESEL, LBS_NOSEL, LBS_NOTIFY, LBS_USETABSTOPS};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, WS_BORDER, WS_VSCROLL, WS_HSCROLL};
 
         let flags: DWORD = WS_CHILD | WS_BORDER | LBS_HASSTRINGS | WS_VSCROLL | WS_HSCROLL | LBS_NOTIFY |
         if self.visible      { WS_VISIBLE }      else { 0 } |
         if self.disabled     { WS_DISABLED }     else { 0 } |
+        if self.tab_stop     { WS_TABSTOP }      else { 0 } |
+        if self.group        { WS_GROUP }        else { 0 } |
         if self.multi_select { LBS_MULTIPLESEL } else { 0 } |
-        if self.readonly     { LBS_NOSEL }       else { 0 };
+        if self.readonly     { LBS_NOSEL }       else { 0 } |
+        if self.tab_stops    { LBS_USETABSTOPS } else { 0 };
 
         // Get the parent handle
         let parent = match handle_of_window(ui, &self.parent, "The parent of a listbox must be a window-like control.") {
@@ -121,6 +133,13 @@ impl<D: Clone+Display+'static, ID: Hash+Clone> ControlT<ID> for ListBoxT<D, ID>
                         } 
                     ).collect();
 
+                    if self.auto_tooltip {
+                        if let Err(e) = set_auto_tooltip(h, parent, list_box_truncated_text) {
+                            ::user32::DestroyWindow(h);
+                            return Err(e);
+                        }
+                    }
+
                     Ok( Box::new(ListBox{handle: h, collection: collection}) )
                 }
             },
@@ -259,6 +278,24 @@ impl<D: Clone+Display> ListBox<D> {
         unsafe{ SendMessageW(self.handle, LB_SELITEMRANGEEX, min, max); }
     }
 
+    /**
+        Set the tab stops of the listbox. The stops are in dialog units, use `dialog_units_to_pixels` to convert them
+        to pixels. If `stops` has a single value, a tab stop is set every `stops[0]` units.  
+        The listbox must be created with `tab_stops` set to `true`, otherwise the tab characters are not expanded.
+
+        Returns `false` if the tab stops could not be set.
+    */
+    pub fn set_tab_stops(&self, stops: &[u32]) -> bool {
+        use low::defs::LB_SETTABSTOPS;
+        unsafe{ SendMessageW(self.handle, LB_SETTABSTOPS, stops.len() as WPARAM, mem::transmute(stops.as_ptr())) != 0 }
+    }
+
+    /// Restore the default tab stops of the listbox (one every 32 dialog units)
+    pub fn clear_tab_stops(&self) {
+        use low::defs::LB_SETTABSTOPS;
+        unsafe{ SendMessageW(self.handle, LB_SETTABSTOPS, 0, 0); }
+    }
+
     /// Return the number of selected items.
     pub fn len_selected(&self) -> usize {
         use low::defs::LB_GETSELCOUNT;
@@ -296,20 +333,7 @@ impl<D: Clone+Display> ListBox<D> {
 
     /// Return the item text at the provided index. Returns None if the index is not valid.
     pub fn get_string(&self, index: usize) -> Option<String> {
-        use low::defs::{LB_GETTEXT, LB_GETTEXTLEN};
-
-        let length = unsafe{ SendMessageW(self.handle, LB_GETTEXTLEN, index as WPARAM, 0) };
-        if length == -1 { return None; }
-
-        let length = (length+1) as usize;
-        let mut buffer: Vec<u16> = Vec::with_capacity(length);
-        unsafe {
-            buffer.set_len(length);
-            let err = SendMessageW(self.handle, LB_GETTEXT, index as WPARAM, mem::transmute( buffer.as_mut_ptr() ));
-            if err == -1 { return None; }
-        }
-
-       Some( from_utf16(&buffer[..]) )
+        unsafe{ item_string(self.handle, index) }
     }
 
     /// Return true if the listbox is currently in a readonly mode, false otherwise.
@@ -387,4 +411,50 @@ impl<D: Clone+Display> Control for ListBox<D> {
         unsafe{ DestroyWindow(self.handle) };
     }
 
-}
\ No newline at end of file
+}
+
+
+// Private functions
+
+unsafe fn item_string(handle: HWND, index: usize) -> Option<String> {
+    use low::defs::{LB_GETTEXT, LB_GETTEXTLEN};
+
+    let length = SendMessageW(handle, LB_GETTEXTLEN, index as WPARAM, 0);
+    if length == -1 { return None; }
+
+    let length = (length+1) as usize;
+    let mut buffer: Vec<u16> = Vec::with_capacity(length);
+    buffer.set_len(length);
+    let err = SendMessageW(handle, LB_GETTEXT, index as WPARAM, mem::transmute( buffer.as_mut_ptr() ));
+    if err == -1 { return None; }
+
+    Some( from_utf16(&buffer[..]) )
+}
+
+/// The text of an item is truncated if it is wider than the item
+unsafe fn list_box_truncated_text(handle: HWND, x: i32, y: i32) -> Option<String> {
+    use low::defs::{LB_ITEMFROMPOINT, LB_GETITEMRECT};
+    use low::tooltip_helper::text_width;
+    use winapi::{RECT, LPARAM, DWORD, LOWORD, HIWORD};
+
+    // The high word of the result is 1 if the point is outside of the items
+    let pos = (((y as u32) << 16) | ((x as u32) & 0xFFFF)) as LPARAM;
+    let result = SendMessageW(handle, LB_ITEMFROMPOINT, 0, pos) as DWORD;
+    if HIWORD(result) != 0 {
+        return None;
+    }
+
+    let index = LOWORD(result) as usize;
+    let mut rect: RECT = mem::zeroed();
+    if SendMessageW(handle, LB_GETITEMRECT, index as WPARAM, mem::transmute(&mut rect)) == -1 {
+        return None;
+    }
+
+    match item_string(handle, index) {
+        Some(text) => match text_width(handle, &text) {
+            Some(w) if (w as i32) > rect.right - rect.left => Some(text),
+            _ => None
+        },
+        None => None
+    }
+}
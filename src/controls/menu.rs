@@ -281,6 +281,9 @@ unsafe fn build_menu<S: Clone+Into<String>, ID: Clone+Hash>(ui: &Ui<ID>, t: &Men
         },
         AnyHandle::HMENU_ITEM(_, _) => Err(Error::BadParent("Window or menu parent required, got MenuItem".to_string())),
         AnyHandle::HFONT(_) =>  Err(Error::BadParent("Window or menu parent required, got Font".to_string())),
+        AnyHandle::HICON(_) =>  Err(Error::BadParent("Window or menu parent required, got Icon".to_string())),
+        AnyHandle::HBITMAP(_) =>  Err(Error::BadParent("Window or menu parent required, got Bitmap".to_string())),
+        AnyHandle::HCURSOR(_) =>  Err(Error::BadParent("Window or menu parent required, got Cursor".to_string())),
         AnyHandle::Custom(_, _) =>  Err(Error::BadParent("Window or menu parent required, got custom control".to_string())),
    }
 }
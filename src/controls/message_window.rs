@@ -0,0 +1,126 @@
+/*!
+    A message-only window control
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+
+use winapi::{HWND, UINT, WPARAM, LPARAM, LRESULT};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+
+/**
+    A template that creates a message-only window. A message-only window is not visible, does not receive 
+    input or paint messages and is not enumerated by the system. It only receives the messages sent or posted to it 
+    (ex: `WM_COPYDATA` from another process), which can be handled with the `Raw` event.
+
+    Like any other control, the window belongs to the thread of its Ui. To receive messages without blocking the main thread,
+    create a Ui on a worker thread, pack the message window in it and call `dispatch_events` from that thread.
+
+    Available events:  
+    Event::Destroyed, Event::Raw  
+
+    Members:  
+    • `title`: The title of the window. Can be used to find the window with `FindWindowEx`  
+*/
+#[derive(Clone)]
+pub struct MessageWindowT<S: Clone+Into<String>> {
+    pub title: S,
+}
+
+impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for MessageWindowT<S> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<MessageWindow>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::Raw]
+    }
+
+    #[allow(unused_variables)]
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, SysclassParams, build_sysclass, build_window};
+        use winapi::HWND_MESSAGE;
+
+        let params = SysclassParams { 
+            class_name: MESSAGE_WINDOW_CLASS_NAME,
+            sysproc: Some(message_window_sysproc),
+            background: None, style: None
+        };
+
+        if let Err(e) = unsafe{ build_sysclass(params) } {
+            return Err(Error::System(e));
+        }
+
+        let params = WindowParams {
+            title: self.title.clone().into(),
+            class_name: MESSAGE_WINDOW_CLASS_NAME,
+            position: (0, 0),
+            size: (0, 0),
+            flags: 0,
+            ex_flags: Some(0),
+            parent: HWND_MESSAGE
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => Ok( Box::new(MessageWindow{handle: h}) ),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A message-only window
+*/
+pub struct MessageWindow {
+    handle: HWND
+}
+
+impl MessageWindow {
+    pub fn get_title(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
+    pub fn set_title<'a>(&self, text: &'a str) { unsafe{ ::low::window_helper::set_window_text(self.handle, text); } }
+}
+
+impl Control for MessageWindow {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::MessageWindow 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+
+// Private methods
+
+const MESSAGE_WINDOW_CLASS_NAME: &'static str = "NWG_BUILTIN_MESSAGEWINDOW";
+
+unsafe extern "system" fn message_window_sysproc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use user32::DefWindowProcW;
+    DefWindowProcW(hwnd, msg, w, l)
+}
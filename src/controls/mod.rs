@@ -34,11 +34,29 @@ pub mod datepicker;
 pub mod file_dialog;
 pub mod timer;
 pub mod canvas;
+pub mod static_image;
+pub mod multi_column_listbox;
+pub mod hotkey_input;
+pub mod ip_address_input;
+pub mod animation;
+pub mod pager;
+pub mod rebar;
+pub mod message_window;
+pub mod list_view;
+pub mod tab_control;
+pub mod rich_text_box;
+pub mod date_time_picker;
+pub mod month_calendar;
+pub mod numeric_updown;
+pub mod scroll_bar;
+
+#[cfg(feature = "tree-view")]
+pub mod tree_view;
 
 use std::any::TypeId;
 use std::hash::Hash;
 
-use winapi::{HWND, HMENU, UINT, HFONT};
+use winapi::{HWND, HMENU, UINT, HFONT, HICON, HBITMAP, HCURSOR};
 
 pub use controls::window::{WindowT, Window};
 pub use controls::menu::{MenuT, Menu, MenuItemT, MenuItem, SeparatorT, Separator};
@@ -54,9 +72,28 @@ pub use controls::groupbox::{GroupBoxT, GroupBox};
 pub use controls::progress_bar::{ProgressBarT, ProgressBar};
 pub use controls::file_dialog::{FileDialogT, FileDialog};
 pub use controls::timer::{TimerT, Timer};
-pub use controls::canvas::{CanvasT, Canvas, CanvasRenderer};
+pub use controls::canvas::{CanvasT, Canvas, CanvasRenderer, CanvasState};
 pub use controls::datepicker::{DatePickerT, DatePicker};
+pub use controls::static_image::{StaticImageT, StaticImage};
+pub use controls::multi_column_listbox::{MultiColumnListBoxT, MultiColumnListBox};
+pub use controls::hotkey_input::{HotkeyInputT, HotkeyInput};
+pub use controls::ip_address_input::{IpAddressInputT, IpAddressInput};
+pub use controls::animation::{AnimationT, Animation};
+pub use controls::pager::{PagerT, Pager};
+pub use controls::rebar::{RebarT, Rebar};
+pub use controls::message_window::{MessageWindowT, MessageWindow};
+pub use controls::list_view::{ListViewT, ListView};
+pub use controls::tab_control::{TabControlT, TabControl, TabItemT, TabItem};
+pub use controls::rich_text_box::{RichTextBoxT, RichTextBox};
+pub use controls::date_time_picker::{DateTimePickerT, DateTimePicker};
+pub use controls::month_calendar::{MonthCalendarT, MonthCalendar};
+pub use controls::numeric_updown::{NumericUpDownT, NumericUpDown};
+pub use controls::scroll_bar::{HorizontalScrollBarT, HorizontalScrollBar, VerticalScrollBarT, VerticalScrollBar};
+
+#[cfg(feature = "tree-view")]
+pub use controls::tree_view::{TreeViewT, TreeView, TreeItem};
 use ui::Ui;
+use defs::{Anchor, Dock, RelativePlacement};
 use events::Event;
 use error::Error;
 
@@ -70,9 +107,131 @@ pub enum AnyHandle {
     HMENU(HMENU),
     HMENU_ITEM(HMENU, UINT),
     HFONT(HFONT),
+    HICON(HICON),
+    HBITMAP(HBITMAP),
+    HCURSOR(HCURSOR),
     Custom(TypeId, usize)
 }
 
+impl AnyHandle {
+
+    /**
+        Return the parent window of a window handle. Return `None` if the window is a top level window
+        or if the handle is not a window handle.
+    */
+    pub fn parent(&self) -> Option<AnyHandle> {
+        match self {
+            &AnyHandle::HWND(h) => unsafe{ ::low::window_helper::get_window_parent(h).map(|p| AnyHandle::HWND(p)) },
+            _ => None
+        }
+    }
+
+    /**
+        Return the top level window that contains a window handle. If the window is a top level window, return
+        a copy of the handle. Non window handles are returned as is.
+    */
+    pub fn root_window(&self) -> AnyHandle {
+        match self {
+            &AnyHandle::HWND(h) => AnyHandle::HWND(unsafe{ ::low::window_helper::get_root_window(h) }),
+            h => h.clone()
+        }
+    }
+
+    /**
+        Return true if the handle is a window handle that is a child or a descendant of `ancestor`.  
+        Return false if one of the handles is not a window handle.
+    */
+    pub fn is_child_of(&self, ancestor: &AnyHandle) -> bool {
+        match (self, ancestor) {
+            (&AnyHandle::HWND(h), &AnyHandle::HWND(a)) => unsafe{ ::low::window_helper::is_window_child_of(h, a) },
+            _ => false
+        }
+    }
+
+    /**
+        Return the rectangle of a window handle in screen coordinates as `(left, top, right, bottom)`.  
+        Return `None` if the handle is not a window handle.
+    */
+    pub fn screen_rect(&self) -> Option<(i32, i32, i32, i32)> {
+        match self {
+            &AnyHandle::HWND(h) => Some(unsafe{ ::low::window_helper::get_window_screen_rect(h) }),
+            _ => None
+        }
+    }
+
+    /**
+        Return the client area of a window handle in client coordinates as `(x, y, width, height)`.  
+        Return `None` if the handle is not a window handle.
+    */
+    pub fn client_rect(&self) -> Option<(i32, i32, u32, u32)> {
+        match self {
+            &AnyHandle::HWND(h) => Some(unsafe{ ::low::window_helper::get_window_client_rect(h) }),
+            _ => None
+        }
+    }
+
+    /**
+        Anchor a control to the edges of its parent client area (see `Anchor`). Use `None` to remove the anchor.  
+        The distances to the edges are captured when the function is called, so call it again after moving the control manually.  
+        Return `false` if the handle is not a window handle or if the window has no parent.
+    */
+    pub fn set_anchor(&self, anchor: Option<Anchor>) -> bool {
+        match self {
+            &AnyHandle::HWND(h) => unsafe{ ::low::layout_helper::set_window_anchor(h, anchor) },
+            _ => false
+        }
+    }
+
+    /// Return the anchor of a control set with `set_anchor`
+    pub fn anchor(&self) -> Option<Anchor> {
+        match self {
+            &AnyHandle::HWND(h) => unsafe{ ::low::layout_helper::get_window_anchor(h) },
+            _ => None
+        }
+    }
+
+    /**
+        Dock a control to an edge of its parent client area (see `Dock`). Use `None` to undock the control.  
+        The docked controls are processed from the bottom of the Z order to the top, so the controls created last are docked first.  
+        Return `false` if the handle is not a window handle or if the window has no parent.
+    */
+    pub fn set_dock(&self, dock: Option<Dock>) -> bool {
+        match self {
+            &AnyHandle::HWND(h) => unsafe{ ::low::layout_helper::set_window_dock(h, dock) },
+            _ => false
+        }
+    }
+
+    /// Return the dock of a control set with `set_dock`
+    pub fn dock(&self) -> Option<Dock> {
+        match self {
+            &AnyHandle::HWND(h) => unsafe{ ::low::layout_helper::get_window_dock(h) },
+            _ => None
+        }
+    }
+
+    /**
+        Place a control relatively to the client area of its parent (ex: `Length::Percent(50.0)` for half the parent width).
+        The control is moved immediately and every time its parent is resized. Use `None` to remove the relative placement.  
+        Return `false` if the handle is not a window handle or if the window has no parent.
+    */
+    pub fn set_relative_placement(&self, placement: Option<RelativePlacement>) -> bool {
+        match self {
+            &AnyHandle::HWND(h) => unsafe{ ::low::layout_helper::set_window_relative_placement(h, placement) },
+            _ => false
+        }
+    }
+
+    /// Return the relative placement of a control set with `set_relative_placement`
+    pub fn relative_placement(&self) -> Option<RelativePlacement> {
+        match self {
+            &AnyHandle::HWND(h) => unsafe{ ::low::layout_helper::get_window_relative_placement(h) },
+            _ => None
+        }
+    }
+
+}
+
 /**
     An enum that list type names for the common controls.
 
@@ -98,6 +257,23 @@ pub enum ControlType {
     DatePicker,
     FileDialog,
     Canvas,
+    StaticImage,
+    MultiColumnListBox,
+    HotkeyInput,
+    IpAddressInput,
+    Animation,
+    Pager,
+    Rebar,
+    MessageWindow,
+    TreeView,
+    ListView,
+    TabControl,
+    TabItem,
+    RichTextBox,
+    DateTimePicker,
+    MonthCalendar,
+    NumericUpDown,
+    ScrollBar,
     Undefined  // Control is not a common control
 }
 
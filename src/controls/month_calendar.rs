@@ -0,0 +1,310 @@
+/*!
+    Month calendar control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+
+use winapi::{HWND, HFONT, WPARAM, SYSTEMTIME};
+use user32::SendMessageW;
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use defs::PickerDate;
+
+/**
+    A template that creates a month calendar. A month calendar displays the days of a month and lets the user select a date
+    or, with `multi_select`, a range of dates.
+
+    Events:  
+    Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw, Event::SelectionChanged  
+
+    Members:  
+    • `value`: The selected date. If None, select the current date  
+    • `position`: The start position of the calendar  
+    • `size`: The start size of the calendar  
+    • `visible`: If the calendar should be visible to the user   
+    • `disabled`: If the user can or can't select a date  
+    • `tab_stop`: If the calendar can receive the keyboard focus with the TAB key  
+    • `parent`: The calendar parent  
+    • `font`: The calendar font. If None, use the system default  
+    • `multi_select`: If the user can select a range of dates (`MCS_MULTISELECT`)  
+    • `week_numbers`: If the week numbers are displayed to the left of each row of days  
+    • `range`: The first and the last date that can be selected. If None, there is no limit  
+*/
+#[derive(Clone)]
+pub struct MonthCalendarT<ID: Hash+Clone> {
+    pub value: Option<PickerDate>,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub parent: ID,
+    pub font: Option<ID>,
+    pub multi_select: bool,
+    pub week_numbers: bool,
+    pub range: (Option<PickerDate>, Option<PickerDate>)
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for MonthCalendarT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<MonthCalendar>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw, Event::SelectionChanged]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, MCS_MULTISELECT, MCS_WEEKNUMBERS};
+
+        let flags: DWORD = WS_CHILD |
+        if self.visible  { WS_VISIBLE }   else { 0 } |
+        if self.disabled { WS_DISABLED }  else { 0 } |
+        if self.tab_stop { WS_TABSTOP }   else { 0 } |
+        if self.multi_select { MCS_MULTISELECT } else { 0 } |
+        if self.week_numbers { MCS_WEEKNUMBERS } else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a month calendar must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) =>    
+                match handle_of_font(ui, &font_id, "The font of a month calendar must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysMonthCal32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{ 
+                    set_window_font(h, font_handle, true); 
+                }
+                let cal = MonthCalendar{handle: h};
+
+                // The range must be set first, a selected date outside of the range is refused
+                cal.set_range(&self.range.0, &self.range.1);
+                if let Some(ref value) = self.value {
+                    cal.set_selected_date(value);
+                }
+
+                Ok( Box::new(cal) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A month calendar control. Do not handle timezone.
+*/
+pub struct MonthCalendar {
+    handle: HWND,
+}
+
+impl MonthCalendar {
+
+    /**
+        Return the selected date. If the calendar is in multi select mode, return the first date of the selected range.
+    */
+    pub fn selected_date(&self) -> PickerDate {
+        self.selected_range().0
+    }
+
+    /**
+        Select a date. If the calendar is in multi select mode, select a range of one day.  
+        Do nothing if the date is outside of the calendar range.
+    */
+    pub fn set_selected_date(&self, date: &PickerDate) {
+        use winapi::MCM_SETCURSEL;
+
+        if self.is_multi_select() {
+            self.set_selected_range(date, date);
+        } else {
+            let st = systemtime(date);
+            unsafe{ SendMessageW(self.handle, MCM_SETCURSEL, 0, mem::transmute(&st)); }
+        }
+    }
+
+    /**
+        Return the first and the last date of the selected range. If the calendar is not in multi select mode,
+        both dates are the selected date.
+    */
+    pub fn selected_range(&self) -> (PickerDate, PickerDate) {
+        use winapi::{MCM_GETCURSEL, MCM_GETSELRANGE};
+
+        if self.is_multi_select() {
+            let mut range: [SYSTEMTIME; 2] = unsafe{ mem::zeroed() };
+            unsafe{ SendMessageW(self.handle, MCM_GETSELRANGE, 0, mem::transmute(range.as_mut_ptr())); }
+            (picker_date(&range[0]), picker_date(&range[1]))
+        } else {
+            let mut st: SYSTEMTIME = unsafe{ mem::zeroed() };
+            unsafe{ SendMessageW(self.handle, MCM_GETCURSEL, 0, mem::transmute(&mut st)); }
+            (picker_date(&st), picker_date(&st))
+        }
+    }
+
+    /**
+        Select the dates from `start` to `end`. Only works in multi select mode.  
+        Do nothing if the range is longer than the maximum selection (7 days by default, see `set_max_selection`)
+        or if it is outside of the calendar range.
+    */
+    pub fn set_selected_range(&self, start: &PickerDate, end: &PickerDate) {
+        use winapi::MCM_SETSELRANGE;
+
+        let range = [systemtime(start), systemtime(end)];
+        unsafe{ SendMessageW(self.handle, MCM_SETSELRANGE, 0, mem::transmute(range.as_ptr())); }
+    }
+
+    /// Set the maximum number of days that can be selected in multi select mode
+    pub fn set_max_selection(&self, days: u32) {
+        use winapi::MCM_SETMAXSELCOUNT;
+        unsafe{ SendMessageW(self.handle, MCM_SETMAXSELCOUNT, days as WPARAM, 0); }
+    }
+
+    /// Return the date that the calendar displays as today
+    pub fn today(&self) -> PickerDate {
+        use winapi::MCM_GETTODAY;
+
+        let mut st: SYSTEMTIME = unsafe{ mem::zeroed() };
+        unsafe{ SendMessageW(self.handle, MCM_GETTODAY, 0, mem::transmute(&mut st)); }
+        picker_date(&st)
+    }
+
+    /// Set the date that the calendar displays as today. The default is the current system date.
+    pub fn set_today(&self, date: &PickerDate) {
+        use winapi::MCM_SETTODAY;
+
+        let st = systemtime(date);
+        unsafe{ SendMessageW(self.handle, MCM_SETTODAY, 0, mem::transmute(&st)); }
+    }
+
+    /// Return the first date that can be selected or None if there is no limit
+    pub fn min_date(&self) -> Option<PickerDate> {
+        use winapi::GDTR_MIN;
+        let (flags, range) = self.range();
+        if flags & GDTR_MIN == GDTR_MIN { Some(picker_date(&range[0])) } else { None }
+    }
+
+    /// Return the last date that can be selected or None if there is no limit
+    pub fn max_date(&self) -> Option<PickerDate> {
+        use winapi::GDTR_MAX;
+        let (flags, range) = self.range();
+        if flags & GDTR_MAX == GDTR_MAX { Some(picker_date(&range[1])) } else { None }
+    }
+
+    /// Set the first and the last date that can be selected. Use None to remove a limit.
+    pub fn set_range(&self, min: &Option<PickerDate>, max: &Option<PickerDate>) {
+        use winapi::{MCM_SETRANGE, GDTR_MIN, GDTR_MAX};
+
+        let mut range: [SYSTEMTIME; 2] = unsafe{ mem::zeroed() };
+        let mut flags: WPARAM = 0;
+
+        if let &Some(ref min) = min {
+            range[0] = systemtime(min);
+            flags |= GDTR_MIN;
+        }
+
+        if let &Some(ref max) = max {
+            range[1] = systemtime(max);
+            flags |= GDTR_MAX;
+        }
+
+        unsafe{ SendMessageW(self.handle, MCM_SETRANGE, flags, mem::transmute(range.as_ptr())); }
+    }
+
+    /// Return `true` if the user can select a range of dates
+    pub fn is_multi_select(&self) -> bool {
+        use low::window_helper::get_window_long;
+        use winapi::{GWL_STYLE, MCS_MULTISELECT};
+
+        let style = get_window_long(self.handle, GWL_STYLE) as u32;
+        style & MCS_MULTISELECT == MCS_MULTISELECT
+    }
+
+    /// Return the range flags (`GDTR_*`) and the range of the calendar
+    fn range(&self) -> (WPARAM, [SYSTEMTIME; 2]) {
+        use winapi::MCM_GETRANGE;
+
+        let mut range: [SYSTEMTIME; 2] = unsafe{ mem::zeroed() };
+        let flags = unsafe{ SendMessageW(self.handle, MCM_GETRANGE, 0, mem::transmute(range.as_mut_ptr())) };
+        (flags as WPARAM, range)
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for MonthCalendar {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::MonthCalendar 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+// Private functions
+
+#[inline(always)]
+fn systemtime(date: &PickerDate) -> SYSTEMTIME {
+    SYSTEMTIME{ 
+        wYear: date.year, wMonth: date.month, wDay: date.day, 
+        wDayOfWeek: 0, wHour: 0, wMinute: 0, wSecond: 0, wMilliseconds: 0 
+    }
+}
+
+#[inline(always)]
+fn picker_date(st: &SYSTEMTIME) -> PickerDate {
+    PickerDate{ year: st.wYear, month: st.wMonth, day: st.wDay }
+}
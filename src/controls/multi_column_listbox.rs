@@ -0,0 +1,249 @@
+/*!
+    Multi column listbox control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+
+use user32::SendMessageW;
+use winapi::{HWND, HFONT, WPARAM, LPARAM};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use controls::list_view::{insert_column, set_item_text, item_count, item_text, next_selected_item, selected_items, set_item_selected};
+
+/**
+    Template that creates a multi column listbox control. The control is a ListView in report mode
+    without the column headers.
+
+    Available events:  
+    Event::Destroyed, Event::SelectionChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `position`: The start position of the listbox  
+    • `size`: The start size of the listbox  
+    • `visible`: If the listbox should be visible to the user  
+    • `disabled`: If the user can or can't use the listbox  
+    • `tab_stop`: If the listbox can receive the keyboard focus with the TAB key  
+    • `group`: If the listbox is the first control of a group (`WS_GROUP`)  
+    • `multi_select`: If true, allow the user to select more than one row  
+    • `parent`: The listbox parent  
+    • `font`: The listbox font. If None, use the system default  
+    • `auto_tooltip`: If a tooltip should show the full text of a cell that is wider than the cell when the mouse is over it  
+*/
+#[derive(Clone)]
+pub struct MultiColumnListBoxT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
+    pub multi_select: bool,
+    pub parent: ID,
+    pub font: Option<ID>,
+    pub auto_tooltip: bool,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for MultiColumnListBoxT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<MultiColumnListBox>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::SelectionChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use low::tooltip_helper::set_auto_tooltip;
+        use controls::list_view::list_view_truncated_text;
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, WS_BORDER, LVS_REPORT, LVS_NOCOLUMNHEADER, LVS_SHOWSELALWAYS,
+          LVS_SINGLESEL, LVS_EX_FULLROWSELECT, LVM_SETEXTENDEDLISTVIEWSTYLE};
+
+        let flags: DWORD = WS_CHILD | WS_BORDER | LVS_REPORT | LVS_NOCOLUMNHEADER | LVS_SHOWSELALWAYS |
+        if self.visible      { WS_VISIBLE }    else { 0 } |
+        if self.disabled     { WS_DISABLED }   else { 0 } |
+        if self.tab_stop     { WS_TABSTOP }    else { 0 } |
+        if self.group        { WS_GROUP }      else { 0 } |
+        if self.multi_select { 0 }             else { LVS_SINGLESEL };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a multi column listbox must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of a multi column listbox must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysListView32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{
+                    set_window_font(h, font_handle, true);
+                    SendMessageW(h, LVM_SETEXTENDEDLISTVIEWSTYLE, LVS_EX_FULLROWSELECT as WPARAM, LVS_EX_FULLROWSELECT as LPARAM);
+
+                    if self.auto_tooltip {
+                        if let Err(e) = set_auto_tooltip(h, parent, list_view_truncated_text) {
+                            ::user32::DestroyWindow(h);
+                            return Err(e);
+                        }
+                    }
+                }
+                Ok( Box::new(MultiColumnListBox{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A listbox that can display more than one column of text per row
+*/
+pub struct MultiColumnListBox {
+    handle: HWND
+}
+
+impl MultiColumnListBox {
+
+    /**
+        Insert a column at `index` with a width of `width` pixels. `text` is the column title.  
+        The title is only used for accessibility because the column headers are hidden.
+    */
+    pub fn add_column<'a>(&self, index: usize, width: u32, text: &'a str) {
+        unsafe{ insert_column(self.handle, index, width, text) }
+    }
+
+    /**
+        Add a row at the end of the listbox. Each value of `row` is displayed in its own column.  
+        Values without a matching column are ignored.
+    */
+    pub fn add_item(&self, row: Vec<String>) {
+        let index = self.len();
+        for (col, value) in row.iter().enumerate() {
+            unsafe{ set_item_text(self.handle, index, col, value) }
+        }
+    }
+
+    /// Remove the row at `index`. Do nothing if the index is out of bounds.
+    pub fn remove_item(&self, index: usize) {
+        use winapi::LVM_DELETEITEM;
+        unsafe{ SendMessageW(self.handle, LVM_DELETEITEM, index as WPARAM, 0); }
+    }
+
+    /// Remove every row in the listbox. The columns are kept.
+    pub fn clear(&self) {
+        use winapi::LVM_DELETEALLITEMS;
+        unsafe{ SendMessageW(self.handle, LVM_DELETEALLITEMS, 0, 0); }
+    }
+
+    /// Return the number of rows in the listbox
+    pub fn len(&self) -> usize {
+        unsafe{ item_count(self.handle) }
+    }
+
+    /**
+        Return the text of the cell at `row` and `col`.  
+        Return an empty string if the cell does not exist.
+    */
+    pub fn get_item_text(&self, row: usize, col: usize) -> String {
+        unsafe{ item_text(self.handle, row, col) }
+    }
+
+    /// Return the index of the first selected row or None if no row is selected
+    pub fn get_selected_index(&self) -> Option<usize> {
+        unsafe{ next_selected_item(self.handle, None) }
+    }
+
+    /// Return the indexes of every selected rows
+    pub fn get_selected_indexes(&self) -> Vec<usize> {
+        unsafe{ selected_items(self.handle) }
+    }
+
+    /// Select or unselect the row at `index`
+    pub fn set_selected(&self, index: usize, selected: bool) {
+        unsafe{ set_item_selected(self.handle, index, selected) }
+    }
+
+    /**
+        Start an OLE drag and drop operation with the selected rows. The dragged data is the text of the first column
+        of the rows, one row per line. The system shows a ghost of the selected rows under the cursor.
+        Requires the `drag-drop` feature.
+
+        The function only returns once the operation is done. Returns `Ok(true)` if the rows were dropped on a target and
+        `Ok(false)` if the operation was canceled or if no row is selected.
+
+        Errors:  
+        • `Error::System` if the drag and drop operation could not be started  
+    */
+    #[cfg(feature = "drag-drop")]
+    pub fn drag_selection(&self) -> Result<bool, Error> {
+        use low::drag_helper::drag_text;
+
+        let rows: Vec<String> = self.get_selected_indexes().iter().map(|&row| self.get_item_text(row, 0)).collect();
+        if rows.is_empty() {
+            return Ok(false);
+        }
+
+        unsafe{ drag_text(self.handle, &rows.join("\r\n")) }
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for MultiColumnListBox {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::MultiColumnListBox 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
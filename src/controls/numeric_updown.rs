@@ -0,0 +1,359 @@
+/*!
+    Numeric up-down control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+use std::ptr;
+
+use winapi::{HWND, HFONT, WPARAM, LPARAM, UDACCEL};
+use user32::SendMessageW;
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+
+/// System class of the up-down control
+const UPDOWN_CLASS_NAME: &'static str = "msctls_updown32";
+
+/**
+    A template that creates a numeric up-down control. A numeric up-down is an edit control (the buddy) with a pair of
+    arrow buttons that increase or decrease its value.
+
+    Events:  
+    Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Raw  
+
+    `ValueChanged` is raised when the user clicks an arrow or uses the arrow keys, before the value is updated.
+    It receives `EventArgs::Delta` with the new value and the change. Typing a value in the edit does not raise `ValueChanged`.
+
+    Members:  
+    • `value`: The starting value  
+    • `min`: The minimum value  
+    • `max`: The maximum value  
+    • `step`: The value added or removed when an arrow is clicked. Must be greater than 0  
+    • `wrap`: If the value wraps around to `min` when it goes past `max` (and to `max` when it goes past `min`)  
+    • `position`: The start position of the control  
+    • `size`: The start size of the control, including the arrows  
+    • `visible`: If the control should be visible to the user   
+    • `disabled`: If the user can or can't change the value  
+    • `tab_stop`: If the edit can receive the keyboard focus with the TAB key  
+    • `parent`: The control parent  
+    • `font`: The font of the edit. If None, use the system default  
+*/
+#[derive(Clone)]
+pub struct NumericUpDownT<ID: Hash+Clone> {
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+    pub step: i32,
+    pub wrap: bool,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub parent: ID,
+    pub font: Option<ID>,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for NumericUpDownT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<NumericUpDown>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use low::defs::ES_AUTOHSCROLL;
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_EX_CLIENTEDGE, UDS_WRAP, UDS_SETBUDDYINT, UDS_ALIGNRIGHT,
+          UDS_ARROWKEYS, UDS_NOTHOUSANDS};
+        use user32::DestroyWindow;
+
+        if self.min > self.max {
+            return Err(Error::UserError("The minimum value of a numeric up-down must be smaller than its maximum value.".to_string()));
+        }
+
+        if self.step <= 0 {
+            return Err(Error::UserError("The step of a numeric up-down must be greater than 0.".to_string()));
+        }
+
+        let common_flags: DWORD = WS_CHILD |
+        if self.visible  { WS_VISIBLE }  else { 0 } |
+        if self.disabled { WS_DISABLED } else { 0 };
+
+        let edit_flags = common_flags | ES_AUTOHSCROLL | if self.tab_stop { WS_TABSTOP } else { 0 };
+        let updown_flags = common_flags | UDS_SETBUDDYINT | UDS_ALIGNRIGHT | UDS_ARROWKEYS | UDS_NOTHOUSANDS |
+        if self.wrap { UDS_WRAP } else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a numeric up-down must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of a numeric up-down must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        // The buddy edit takes the whole size. The up-down control shrinks it when it is attached.
+        let params = WindowParams {
+            title: "",
+            class_name: "EDIT",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: edit_flags,
+            ex_flags: Some(WS_EX_CLIENTEDGE),
+            parent: parent
+        };
+
+        let edit = match unsafe{ build_window(params) } {
+            Ok(h) => h,
+            Err(e) => { return Err(Error::System(e)); }
+        };
+
+        unsafe{ set_window_font(edit, font_handle, true); }
+
+        let params = WindowParams {
+            title: "",
+            class_name: UPDOWN_CLASS_NAME,
+            position: (0, 0),
+            size: (0, 0),
+            flags: updown_flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                let updown = NumericUpDown{handle: h, edit: edit};
+                updown.attach_buddy();
+                updown.set_range(self.min, self.max);
+                updown.set_step(self.step);
+                updown.set_value(self.value);
+                Ok( Box::new(updown) )
+            },
+            Err(e) => {
+                unsafe{ DestroyWindow(edit); }
+                Err(Error::System(e))
+            }
+        }
+    }
+}
+
+/**
+    An edit control with a pair of arrow buttons that increase or decrease its value.  
+    The control handle is the handle of the up-down control. Use `edit_handle` to get the handle of the edit.
+*/
+pub struct NumericUpDown {
+    handle: HWND,
+    edit: HWND
+}
+
+impl NumericUpDown {
+
+    /// Return the value of the control. If the text of the edit is not a valid number, return the last valid value.
+    pub fn value(&self) -> i32 {
+        use winapi::{UDM_GETPOS32, BOOL};
+
+        let mut error: BOOL = 0;
+        unsafe{ SendMessageW(self.handle, UDM_GETPOS32, 0, mem::transmute(&mut error)) as i32 }
+    }
+
+    /// Set the value of the control. The value is clamped to the range of the control.
+    pub fn set_value(&self, value: i32) {
+        use winapi::UDM_SETPOS32;
+        unsafe{ SendMessageW(self.handle, UDM_SETPOS32, 0, value as LPARAM); }
+    }
+
+    /// Return the minimum value
+    pub fn min(&self) -> i32 {
+        self.range().0
+    }
+
+    /// Return the maximum value
+    pub fn max(&self) -> i32 {
+        self.range().1
+    }
+
+    /// Set the minimum and the maximum value. The current value is clamped to the new range.
+    pub fn set_range(&self, min: i32, max: i32) {
+        use winapi::UDM_SETRANGE32;
+
+        let value = self.value();
+        unsafe{ SendMessageW(self.handle, UDM_SETRANGE32, min as WPARAM, max as LPARAM); }
+
+        // The up-down control do not update its position when the range changes
+        self.set_value(if value < min { min } else if value > max { max } else { value });
+    }
+
+    /// Return the value added or removed when an arrow is clicked
+    pub fn step(&self) -> i32 {
+        use winapi::UDM_GETACCEL;
+
+        let mut accel = UDACCEL{nSec: 0, nInc: 1};
+        unsafe{ SendMessageW(self.handle, UDM_GETACCEL, 1, mem::transmute(&mut accel)); }
+        accel.nInc as i32
+    }
+
+    /// Set the value added or removed when an arrow is clicked. A step smaller than 1 is set to 1.
+    pub fn set_step(&self, step: i32) {
+        use winapi::UDM_SETACCEL;
+
+        let step = if step < 1 { 1 } else { step };
+        let accel = UDACCEL{nSec: 0, nInc: step as u32};
+        unsafe{ SendMessageW(self.handle, UDM_SETACCEL, 1, mem::transmute(&accel)); }
+    }
+
+    /// Return `true` if the value wraps around when it goes past the range
+    pub fn get_wrap(&self) -> bool {
+        use low::window_helper::get_window_long;
+        use winapi::{GWL_STYLE, UDS_WRAP};
+
+        let style = get_window_long(self.handle, GWL_STYLE) as u32;
+        style & UDS_WRAP == UDS_WRAP
+    }
+
+    /// Return the handle of the edit control that displays the value
+    pub fn edit_handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.edit)
+    }
+
+    /// Return the position of the control (the position of the edit)
+    pub fn get_position(&self) -> (i32, i32) { 
+        unsafe{ ::low::window_helper::get_window_position(self.edit) } 
+    }
+
+    /// Move the control. The arrows follow the edit.
+    pub fn set_position(&self, x: i32, y: i32) {
+        use low::window_helper::{get_window_position, set_window_position};
+
+        unsafe{
+            let (ex, ey) = get_window_position(self.edit);
+            let (ux, uy) = get_window_position(self.handle);
+            set_window_position(self.edit, x, y);
+            set_window_position(self.handle, ux + (x-ex), uy + (y-ey));
+        }
+    }
+
+    /// Return the size of the control, including the arrows
+    pub fn get_size(&self) -> (u32, u32) {
+        use low::window_helper::get_window_screen_rect;
+
+        let (left, top, _, _) = unsafe{ get_window_screen_rect(self.edit) };
+        let (_, _, right, bottom) = unsafe{ get_window_screen_rect(self.handle) };
+        ((right-left) as u32, (bottom-top) as u32)
+    }
+
+    /// Resize the control. The size includes the arrows, which keep their width.
+    pub fn set_size(&self, w: u32, h: u32) {
+        use low::window_helper::{get_window_position, set_window_position, get_window_screen_rect, set_window_size};
+
+        unsafe{
+            let (left, _, right, _) = get_window_screen_rect(self.handle);
+            let arrows_width = (right-left) as u32;
+            let edit_width = if w > arrows_width { w - arrows_width } else { 0 };
+
+            let (ex, ey) = get_window_position(self.edit);
+            set_window_size(self.edit, edit_width, h, false);
+            set_window_size(self.handle, arrows_width, h, false);
+            set_window_position(self.handle, ex + (edit_width as i32), ey);
+        }
+    }
+
+    pub fn get_text(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.edit) } }
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+
+    pub fn set_visibility(&self, visible: bool) { 
+        unsafe{ 
+            ::low::window_helper::set_window_visibility(self.edit, visible);
+            ::low::window_helper::set_window_visibility(self.handle, visible); 
+        }
+    }
+
+    pub fn set_enabled(&self, e:bool) { 
+        unsafe{ 
+            ::low::window_helper::set_window_enabled(self.edit, e);
+            ::low::window_helper::set_window_enabled(self.handle, e); 
+        } 
+    }
+
+    /// Attach the edit to the up-down control. This places the arrows inside the right side of the edit and shrinks the edit.
+    fn attach_buddy(&self) {
+        use winapi::UDM_SETBUDDY;
+        unsafe{ SendMessageW(self.handle, UDM_SETBUDDY, self.edit as WPARAM, 0); }
+    }
+
+    /// Return the minimum and the maximum value
+    fn range(&self) -> (i32, i32) {
+        use winapi::UDM_GETRANGE32;
+
+        let (mut min, mut max) = (0i32, 0i32);
+        unsafe{ SendMessageW(self.handle, UDM_GETRANGE32, mem::transmute(&mut min), mem::transmute(&mut max)); }
+        (min, max)
+    }
+}
+
+impl Control for NumericUpDown {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::NumericUpDown 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ 
+            DestroyWindow(self.handle);
+            DestroyWindow(self.edit);
+        }
+    }
+
+}
+
+/**
+    Called from the events dispatcher when the user clicks an arrow (`UDN_DELTAPOS`).
+    Return the value of the control after the change.
+*/
+pub unsafe fn updown_delta(handle: HWND, position: i32, delta: i32) -> i32 {
+    let updown = NumericUpDown{handle: handle, edit: ptr::null_mut()};
+    let (min, max) = updown.range();
+
+    let value = position.saturating_add(delta);
+    match (value < min, value > max, updown.get_wrap()) {
+        (true, _, true) => max,
+        (_, true, true) => min,
+        (true, _, false) => min,
+        (_, true, false) => max,
+        _ => value
+    }
+}
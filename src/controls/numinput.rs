@@ -59,7 +59,7 @@ impl<ID: Hash+Clone> ControlT<ID> for NumericInputT<ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<NumericInput>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::Moved, Event::Resized]
+        vec![Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
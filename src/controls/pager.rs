@@ -0,0 +1,218 @@
+/*!
+    A pager control that scrolls a child control wider than itself
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+
+use user32::SendMessageW;
+use winapi::{HWND, LPARAM};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+
+/**
+    A template that creates a pager. A pager hosts a single child control (ex: a toolbar) that is larger than
+    the visible area of the pager and displays arrows to scroll it.
+
+    To set the contained control, create it with the pager as parent and call `Pager::set_child`.
+
+    Available events:  
+    Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `position`: The start position of the pager  
+    • `size`: The start size of the pager  
+    • `visible`: If the pager should be visible to the user  
+    • `disabled`: If the user can or can't use the pager  
+    • `vertical`: If true, the child is scrolled vertically. Otherwise, the child is scrolled horizontally.  
+    • `autoscroll`: If true, the child is scrolled when the mouse hover the arrows  
+    • `parent`: The pager parent  
+*/
+#[derive(Clone)]
+pub struct PagerT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub vertical: bool,
+    pub autoscroll: bool,
+    pub parent: ID,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for PagerT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<Pager>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, handle_of_window};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, PGS_HORZ, PGS_VERT, PGS_AUTOSCROLL};
+
+        let flags: DWORD = WS_CHILD |
+        if self.visible    { WS_VISIBLE }     else { 0 } |
+        if self.disabled   { WS_DISABLED }    else { 0 } |
+        if self.vertical   { PGS_VERT }       else { PGS_HORZ } |
+        if self.autoscroll { PGS_AUTOSCROLL } else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a pager must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysPager",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => Ok( Box::new(Pager{handle: h}) ),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A container that scrolls a child control using arrow buttons
+*/
+pub struct Pager {
+    handle: HWND
+}
+
+impl Pager {
+
+    /**
+        Set the control hosted by the pager. The control must be a window-like control created with the pager as parent.
+        Use `ui.handle_of` to get the handle of a control.
+
+        Errors:  
+        • `Error::ControlRequired` if the handle is not a window handle  
+    */
+    pub fn set_child(&self, child: &AnyHandle) -> Result<(), Error> {
+        use winapi::PGM_SETCHILD;
+
+        match child {
+            &AnyHandle::HWND(h) => {
+                unsafe{ SendMessageW(self.handle, PGM_SETCHILD, 0, h as LPARAM); }
+                Ok(())
+            },
+            _ => Err(Error::ControlRequired)
+        }
+    }
+
+    /// Force the pager to recompute the size of its child. Call this after the child was resized.
+    pub fn recalc_size(&self) {
+        use winapi::PGM_RECALCSIZE;
+        unsafe{ SendMessageW(self.handle, PGM_RECALCSIZE, 0, 0); }
+    }
+
+    /// Return the current scroll position of the child, in pixels
+    pub fn get_scroll(&self) -> u32 {
+        use winapi::PGM_GETPOS;
+        unsafe{ SendMessageW(self.handle, PGM_GETPOS, 0, 0) as u32 }
+    }
+
+    /// Set the current scroll position of the child, in pixels
+    pub fn set_scroll(&self, pos: u32) {
+        use winapi::PGM_SETPOS;
+        unsafe{ SendMessageW(self.handle, PGM_SETPOS, 0, pos as LPARAM); }
+    }
+
+    /// Return the size of the scroll arrows, in pixels
+    pub fn get_button_size(&self) -> u32 {
+        use winapi::PGM_GETBUTTONSIZE;
+        unsafe{ SendMessageW(self.handle, PGM_GETBUTTONSIZE, 0, 0) as u32 }
+    }
+
+    /// Set the size of the scroll arrows, in pixels
+    pub fn set_button_size(&self, size: u32) {
+        use winapi::PGM_SETBUTTONSIZE;
+        unsafe{ SendMessageW(self.handle, PGM_SETBUTTONSIZE, 0, size as LPARAM); }
+    }
+
+    /// Set the size of the border between the edges of the pager and its child, in pixels
+    pub fn set_border(&self, size: u32) {
+        use winapi::PGM_SETBORDER;
+        unsafe{ SendMessageW(self.handle, PGM_SETBORDER, 0, size as LPARAM); }
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for Pager {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::Pager 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+// Private functions
+
+/**
+    Answer the `PGN_CALCSIZE` notification sent by a pager to its parent. The pager
+    receives the size of its child window.
+*/
+pub unsafe fn calc_pager_size(calc: &mut ::winapi::NMPGCALCSIZE) {
+    use user32::{GetWindow, GetWindowRect};
+    use winapi::{RECT, GW_CHILD, PGF_CALCWIDTH, PGF_CALCHEIGHT};
+    use std::mem;
+
+    let child = GetWindow(calc.hdr.hwndFrom, GW_CHILD);
+    if child.is_null() {
+        return;
+    }
+
+    let mut r: RECT = mem::uninitialized();
+    GetWindowRect(child, &mut r);
+
+    if (calc.dwFlag & PGF_CALCWIDTH) != 0 {
+        calc.iWidth = r.right - r.left;
+    }
+
+    if (calc.dwFlag & PGF_CALCHEIGHT) != 0 {
+        calc.iHeight = r.bottom - r.top;
+    }
+}
@@ -35,7 +35,7 @@ use defs::ProgressBarState;
     A template that creates a progress bar
 
     Events:  
-    Event::Destroyed, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `position`: The start position of the progressbar  
@@ -67,7 +67,7 @@ impl<ID: Hash+Clone> ControlT<ID> for ProgressBarT<ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<ProgressBar>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
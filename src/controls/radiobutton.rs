@@ -34,7 +34,7 @@ use defs::CheckState;
     A template that creates a standard radio button
 
     Events:  
-    Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `text`: The text of the radio button  
@@ -42,6 +42,8 @@ use defs::CheckState;
     • `size`: The start size of the radio button  
     • `visible`: If the radio button should be visible to the user32  
     • `disabled`: If the user can or can't click on the radio button  
+    • `tab_stop`: If the radio button can receive the keyboard focus with the TAB key  
+    • `group`: If the radio button is the first control of a group (`WS_GROUP`)  
     • `parent`: The radio button parent  
     • `checkstate`: The starting checkstate  
     • `tristate`: If the radio button should have three states  
@@ -54,6 +56,8 @@ pub struct RadioButtonT<S: Clone+Into<String>, ID: Hash+Clone> {
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub parent: ID,
     pub checkstate: CheckState,
     pub font: Option<ID>,
@@ -63,16 +67,18 @@ impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for RadioButtonT<S, ID>
     fn resource_type_id(&self) -> TypeId { TypeId::of::<RadioButton>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::Focus, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, BS_NOTIFY, BS_AUTORADIOBUTTON, BS_TEXT};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, BS_NOTIFY, BS_AUTORADIOBUTTON, BS_TEXT};
 
         let flags: DWORD = WS_CHILD | BS_NOTIFY | BS_TEXT | BS_AUTORADIOBUTTON |
         if self.visible    { WS_VISIBLE }   else { 0 } |
-        if self.disabled   { WS_DISABLED }  else { 0 } ;
+        if self.disabled   { WS_DISABLED }  else { 0 } |
+        if self.tab_stop   { WS_TABSTOP }   else { 0 } |
+        if self.group      { WS_GROUP }     else { 0 } ;
 
         // Get the parent handle
         let parent = match handle_of_window(ui, &self.parent, "The parent of a checkbox must be a window-like control.") {
@@ -0,0 +1,219 @@
+/*!
+    A rebar control that hosts its children in movable bands
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+
+use user32::SendMessageW;
+use winapi::{HWND, UINT, WPARAM, LPARAM, REBARBANDINFOW};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use low::other_helper::to_utf16;
+
+/**
+    A template that creates a rebar. A rebar hosts child controls (ex: toolbars or comboboxes) in bands
+    that the user can drag to rearrange and double click to collapse.
+
+    To add a band, create a control with the rebar as parent and call `Rebar::add_band`.
+
+    Available events:  
+    Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `position`: The start position of the rebar  
+    • `size`: The start size of the rebar  
+    • `visible`: If the rebar should be visible to the user  
+    • `disabled`: If the user can or can't use the rebar  
+    • `fixed_order`: If true, the user can move the bands but cannot change their order  
+    • `band_borders`: If true, draw a line between the bands  
+    • `parent`: The rebar parent  
+*/
+#[derive(Clone)]
+pub struct RebarT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub fixed_order: bool,
+    pub band_borders: bool,
+    pub parent: ID,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for RebarT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<Rebar>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::ValueChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, handle_of_window};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_CLIPCHILDREN, WS_CLIPSIBLINGS, RBS_VARHEIGHT, RBS_FIXEDORDER,
+          RBS_BANDBORDERS, RBS_DBLCLKTOGGLE, CCS_NODIVIDER, CCS_NOPARENTALIGN};
+
+        let flags: DWORD = WS_CHILD | WS_CLIPCHILDREN | WS_CLIPSIBLINGS | RBS_VARHEIGHT | RBS_DBLCLKTOGGLE | CCS_NODIVIDER | CCS_NOPARENTALIGN |
+        if self.visible      { WS_VISIBLE }      else { 0 } |
+        if self.disabled     { WS_DISABLED }     else { 0 } |
+        if self.fixed_order  { RBS_FIXEDORDER }  else { 0 } |
+        if self.band_borders { RBS_BANDBORDERS } else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a rebar must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "ReBarWindow32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => Ok( Box::new(Rebar{handle: h}) ),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A container that hosts controls in bands
+*/
+pub struct Rebar {
+    handle: HWND
+}
+
+impl Rebar {
+
+    /**
+        Add a band at the end of the rebar that holds `child`. The child must be a window-like control created with the rebar as parent.
+        Use `ui.handle_of` to get the handle of a control.  
+        `text` is displayed before the child. If `break_line` is true, the band starts a new row.
+
+        The starting size of the child is used as the minimum size of the band.
+
+        Errors:  
+        • `Error::ControlRequired` if the handle is not a window handle  
+    */
+    pub fn add_band<'a>(&self, child: &AnyHandle, text: &'a str, break_line: bool) -> Result<(), Error> {
+        use winapi::{RB_INSERTBANDW, RBBIM_STYLE, RBBIM_TEXT, RBBIM_CHILD, RBBIM_CHILDSIZE, RBBIM_SIZE, RBBS_BREAK, RBBS_CHILDEDGE,
+          RBBS_GRIPPERALWAYS};
+        use low::window_helper::get_window_size;
+
+        let child = match child {
+            &AnyHandle::HWND(h) => h,
+            _ => { return Err(Error::ControlRequired); }
+        };
+
+        let (w, h) = unsafe{ get_window_size(child) };
+        let mut text = to_utf16(text);
+
+        let mut band: REBARBANDINFOW = unsafe{ mem::zeroed() };
+        band.cbSize = mem::size_of::<REBARBANDINFOW>() as UINT;
+        band.fMask = RBBIM_STYLE | RBBIM_TEXT | RBBIM_CHILD | RBBIM_CHILDSIZE | RBBIM_SIZE;
+        band.fStyle = RBBS_CHILDEDGE | RBBS_GRIPPERALWAYS | if break_line { RBBS_BREAK } else { 0 };
+        band.lpText = text.as_mut_ptr();
+        band.hwndChild = child;
+        band.cxMinChild = w as UINT;
+        band.cyMinChild = h as UINT;
+        band.cx = w as UINT;
+
+        // Inserting at -1 adds the band at the end
+        unsafe{ SendMessageW(self.handle, RB_INSERTBANDW, -1isize as WPARAM, mem::transmute(&band)); }
+
+        Ok(())
+    }
+
+    /// Remove the band at `index`. The child of the band is not destroyed.
+    pub fn remove_band(&self, index: u32) {
+        use winapi::RB_DELETEBAND;
+        unsafe{ SendMessageW(self.handle, RB_DELETEBAND, index as WPARAM, 0); }
+    }
+
+    /// Return the number of bands in the rebar
+    pub fn band_count(&self) -> u32 {
+        use winapi::RB_GETBANDCOUNT;
+        unsafe{ SendMessageW(self.handle, RB_GETBANDCOUNT, 0, 0) as u32 }
+    }
+
+    /// Return the number of rows of bands in the rebar
+    pub fn row_count(&self) -> u32 {
+        use winapi::RB_GETROWCOUNT;
+        unsafe{ SendMessageW(self.handle, RB_GETROWCOUNT, 0, 0) as u32 }
+    }
+
+    /// Move the band at `from` to `to`
+    pub fn move_band(&self, from: u32, to: u32) {
+        use winapi::RB_MOVEBAND;
+        unsafe{ SendMessageW(self.handle, RB_MOVEBAND, from as WPARAM, to as LPARAM); }
+    }
+
+    /// Collapse the band at `index` to its smallest size
+    pub fn collapse_band(&self, index: u32) {
+        use winapi::RB_MINIMIZEBAND;
+        unsafe{ SendMessageW(self.handle, RB_MINIMIZEBAND, index as WPARAM, 0); }
+    }
+
+    /// Expand the band at `index` to its largest size
+    pub fn expand_band(&self, index: u32) {
+        use winapi::RB_MAXIMIZEBAND;
+        unsafe{ SendMessageW(self.handle, RB_MAXIMIZEBAND, index as WPARAM, 0); }
+    }
+
+    /// Show or hide the band at `index`
+    pub fn set_band_visibility(&self, index: u32, visible: bool) {
+        use winapi::RB_SHOWBAND;
+        unsafe{ SendMessageW(self.handle, RB_SHOWBAND, index as WPARAM, visible as LPARAM); }
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for Rebar {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::Rebar 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
@@ -0,0 +1,457 @@
+/*!
+    Rich text box control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+
+use winapi::{HWND, HFONT, WPARAM, LPARAM, DWORD, DWORD_PTR, LONG, BYTE};
+use user32::SendMessageW;
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::{Error, SystemError};
+use events::Event;
+use low::defs::{EDITSTREAM, CHARFORMATW, CHARRANGE};
+
+/// System class of the rich edit 4.1 control, defined in `msftedit.dll`
+const RICH_EDIT_CLASS_NAME: &'static str = "RICHEDIT50W";
+
+/**
+    A template that creates a rich text box. A rich text box is a multi line text box that can hold formatted text (RTF).
+
+    Events:  
+    Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::ValueChanged, Event::SelectionChanged, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `text`: The starting text (unformatted) of the rich text box  
+    • `position`: The start position of the rich text box  
+    • `size`: The start size of the rich text box  
+    • `visible`: If the rich text box should be visible to the user   
+    • `disabled`: If the user can or can't use the rich text box  
+    • `tab_stop`: If the rich text box can receive the keyboard focus with the TAB key  
+    • `readonly`: If the user can copy the text but can't edit the content of the rich text box  
+    • `scrollbars`: A tuple to defined whether to show scrollbars or not (show horizontal, show vertical)  
+    • `parent`: The rich text box parent  
+    • `font`: The default font of the text. If None, use the system default  
+*/
+#[derive(Clone)]
+pub struct RichTextBoxT<S1: Clone+Into<String>, ID: Hash+Clone> {
+    pub text: S1,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub readonly: bool,
+    pub scrollbars: (bool, bool),
+    pub parent: ID,
+    pub font: Option<ID>,
+}
+
+impl<S1: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for RichTextBoxT<S1, ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<RichTextBox>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp,
+             Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::ValueChanged, Event::SelectionChanged, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use low::defs::{ES_AUTOHSCROLL, ES_AUTOVSCROLL, ES_READONLY, ES_MULTILINE, ES_WANTRETURN, ES_NOHIDESEL, EM_SETEVENTMASK, 
+          ENM_CHANGE, ENM_SELCHANGE};
+        use winapi::{WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_BORDER, WS_HSCROLL, WS_VSCROLL};
+
+        let flags: DWORD = WS_CHILD | WS_BORDER | ES_MULTILINE | ES_AUTOVSCROLL | ES_AUTOHSCROLL | ES_WANTRETURN | ES_NOHIDESEL |
+        if self.readonly { ES_READONLY } else { 0 } |
+        if self.visible  { WS_VISIBLE }  else { 0 } |
+        if self.scrollbars.0 { WS_HSCROLL } else { 0 } |
+        if self.scrollbars.1 { WS_VSCROLL } else { 0 } |
+        if self.disabled { WS_DISABLED } else { 0 } |
+        if self.tab_stop { WS_TABSTOP }  else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a rich text box must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of a rich text box must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        if let Err(e) = unsafe{ load_rich_edit() } {
+            return Err(Error::System(e));
+        }
+
+        let params = WindowParams {
+            title: self.text.clone().into(),
+            class_name: RICH_EDIT_CLASS_NAME,
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{ 
+                    set_window_font(h, font_handle, true); 
+
+                    // The rich edit control only sends the notifications that are enabled in its event mask
+                    SendMessageW(h, EM_SETEVENTMASK, 0, (ENM_CHANGE | ENM_SELCHANGE) as LPARAM);
+                };
+
+                Ok( Box::new(RichTextBox{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A multi line text box that can hold formatted text
+*/
+pub struct RichTextBox {
+    handle: HWND
+}
+
+impl RichTextBox {
+
+    /**
+        Replace the content of the rich text box with the RTF document `rtf`.
+        The characters that do not fit in a byte are written as RTF unicode escapes (`\uN?`).
+
+        Errors:  
+        • `Error::System(SystemError::SystemCall{..})` if the control could not read the document  
+    */
+    pub fn load_rtf<'a>(&self, rtf: &'a str) -> Result<(), Error> {
+        use low::defs::{EM_STREAMIN, SF_RTF};
+
+        let rtf = rtf_to_bytes(rtf);
+        let mut data = &rtf[..];
+        let mut read = |buffer: &mut [u8]| -> usize {
+            let count = if buffer.len() < data.len() { buffer.len() } else { data.len() };
+            buffer[0..count].copy_from_slice(&data[0..count]);
+            data = &data[count..];
+            count
+        };
+
+        unsafe{ stream(self.handle, EM_STREAMIN, SF_RTF, &mut read) }
+    }
+
+    /**
+        Return the content of the rich text box as a RTF document.
+        The document bytes are decoded as Latin-1, so `load_rtf` reads back the exact same document.
+
+        Errors:  
+        • `Error::System(SystemError::SystemCall{..})` if the control could not write the document  
+    */
+    pub fn save_rtf(&self) -> Result<String, Error> {
+        use low::defs::{EM_STREAMOUT, SF_RTF};
+
+        let mut data: Vec<u8> = Vec::new();
+        let result = {
+            let mut write = |buffer: &mut [u8]| -> usize {
+                data.extend_from_slice(buffer);
+                buffer.len()
+            };
+
+            unsafe{ stream(self.handle, EM_STREAMOUT, SF_RTF, &mut write) }
+        };
+
+        match result {
+            Ok(_) => Ok(data.iter().map(|&b| b as char).collect()),
+            Err(e) => Err(e)
+        }
+    }
+
+    /// Add `text` at the end of the rich text box. The text uses the format of the end of the document.
+    pub fn append_text<'a>(&self, text: &'a str) {
+        use low::defs::{EM_EXSETSEL, EM_EXGETSEL};
+        use low::other_helper::to_utf16;
+        use winapi::EM_REPLACESEL;
+
+        let text = to_utf16(text);
+        let mut old_selection = CHARRANGE{cpMin: 0, cpMax: 0};
+        let end = CHARRANGE{cpMin: -1, cpMax: -1};
+
+        unsafe {
+            SendMessageW(self.handle, EM_EXGETSEL, 0, mem::transmute(&mut old_selection));
+            SendMessageW(self.handle, EM_EXSETSEL, 0, mem::transmute(&end));
+            SendMessageW(self.handle, EM_REPLACESEL as u32, 0, mem::transmute(text.as_ptr()));
+            SendMessageW(self.handle, EM_EXSETSEL, 0, mem::transmute(&old_selection));
+        }
+    }
+
+    /// Return the selected range of characters as `(start, end)`. If nothing is selected, start is equal to end and is the position of the caret.
+    pub fn get_selection(&self) -> (u32, u32) {
+        use low::defs::EM_EXGETSEL;
+
+        let mut range = CHARRANGE{cpMin: 0, cpMax: 0};
+        unsafe{ SendMessageW(self.handle, EM_EXGETSEL, 0, mem::transmute(&mut range)); }
+        (range.cpMin as u32, range.cpMax as u32)
+    }
+
+    /// Select the characters from `start` to `end`. Use `(0, -1)` to select every character.
+    pub fn set_selection(&self, start: i32, end: i32) {
+        use low::defs::EM_EXSETSEL;
+
+        let range = CHARRANGE{cpMin: start as LONG, cpMax: end as LONG};
+        unsafe{ SendMessageW(self.handle, EM_EXSETSEL, 0, mem::transmute(&range)); }
+    }
+
+    /// Return true if every selected character is bold
+    pub fn selection_bold(&self) -> bool {
+        use low::defs::{CFM_BOLD, CFE_BOLD};
+        self.selection_effect(CFM_BOLD, CFE_BOLD)
+    }
+
+    /// Make the selected characters bold or remove the bold style from them
+    pub fn set_selection_bold(&self, bold: bool) {
+        use low::defs::{CFM_BOLD, CFE_BOLD};
+        self.set_selection_effect(CFM_BOLD, if bold { CFE_BOLD } else { 0 }, 0);
+    }
+
+    /// Return true if every selected character is italic
+    pub fn selection_italic(&self) -> bool {
+        use low::defs::{CFM_ITALIC, CFE_ITALIC};
+        self.selection_effect(CFM_ITALIC, CFE_ITALIC)
+    }
+
+    /// Make the selected characters italic or remove the italic style from them
+    pub fn set_selection_italic(&self, italic: bool) {
+        use low::defs::{CFM_ITALIC, CFE_ITALIC};
+        self.set_selection_effect(CFM_ITALIC, if italic { CFE_ITALIC } else { 0 }, 0);
+    }
+
+    /// Return true if every selected character is underlined
+    pub fn selection_underline(&self) -> bool {
+        use low::defs::{CFM_UNDERLINE, CFE_UNDERLINE};
+        self.selection_effect(CFM_UNDERLINE, CFE_UNDERLINE)
+    }
+
+    /// Underline the selected characters or remove the underline from them
+    pub fn set_selection_underline(&self, underline: bool) {
+        use low::defs::{CFM_UNDERLINE, CFE_UNDERLINE};
+        self.set_selection_effect(CFM_UNDERLINE, if underline { CFE_UNDERLINE } else { 0 }, 0);
+    }
+
+    /**
+        Return the color of the selected characters in the (red, green, blue) format.  
+        Return `None` if the selected characters use the default text color or do not have the same color.
+    */
+    pub fn selection_color(&self) -> Option<(u8, u8, u8)> {
+        use low::defs::{CFM_COLOR, CFE_AUTOCOLOR};
+
+        let format = self.selection_format();
+        if format.dwMask & CFM_COLOR != CFM_COLOR || format.dwEffects & CFE_AUTOCOLOR == CFE_AUTOCOLOR {
+            return None;
+        }
+
+        let c = format.crTextColor;
+        Some(((c & 0xFF) as u8, ((c >> 8) & 0xFF) as u8, ((c >> 16) & 0xFF) as u8))
+    }
+
+    /// Set the color of the selected characters. Use `None` to restore the default text color.
+    pub fn set_selection_color(&self, color: Option<(u8, u8, u8)>) {
+        use low::defs::{CFM_COLOR, CFE_AUTOCOLOR};
+        use low::gdi_helper::colorref;
+
+        match color {
+            Some(c) => self.set_selection_effect(CFM_COLOR, 0, colorref(c)),
+            None => self.set_selection_effect(CFM_COLOR, CFE_AUTOCOLOR, 0)
+        }
+    }
+
+    /// Set or unset the readonly status on the control
+    pub fn set_readonly(&self, readonly: bool) {
+        use winapi::EM_SETREADONLY;
+        unsafe{ SendMessageW(self.handle, EM_SETREADONLY as u32, readonly as WPARAM, 0); }
+    }
+
+    /// Return `true` if the user cannot edit the content of the control or `false` if the user can
+    pub fn get_readonly(&self) -> bool {
+        use low::window_helper::get_window_long;
+        use low::defs::ES_READONLY;
+        use winapi::GWL_STYLE;
+
+        let style = get_window_long(self.handle, GWL_STYLE) as u32;
+
+        (style & ES_READONLY) == ES_READONLY
+    }
+
+    /// Return the character format of the selection
+    fn selection_format(&self) -> CHARFORMATW {
+        use low::defs::{EM_GETCHARFORMAT, SCF_SELECTION};
+
+        let mut format: CHARFORMATW = unsafe{ mem::zeroed() };
+        format.cbSize = mem::size_of::<CHARFORMATW>() as u32;
+        unsafe{ SendMessageW(self.handle, EM_GETCHARFORMAT, SCF_SELECTION, mem::transmute(&mut format)); }
+
+        format
+    }
+
+    /// Return true if the effect `effect` (`CFE_*`) of the mask `mask` (`CFM_*`) is set on every selected character
+    fn selection_effect(&self, mask: DWORD, effect: DWORD) -> bool {
+        let format = self.selection_format();
+        format.dwMask & mask == mask && format.dwEffects & effect == effect
+    }
+
+    /// Set the effects of the mask `mask` on the selected characters
+    fn set_selection_effect(&self, mask: DWORD, effects: DWORD, color: DWORD) {
+        use low::defs::{EM_SETCHARFORMAT, SCF_SELECTION};
+
+        let mut format: CHARFORMATW = unsafe{ mem::zeroed() };
+        format.cbSize = mem::size_of::<CHARFORMATW>() as u32;
+        format.dwMask = mask;
+        format.dwEffects = effects;
+        format.crTextColor = color;
+
+        unsafe{ SendMessageW(self.handle, EM_SETCHARFORMAT, SCF_SELECTION, mem::transmute(&format)); }
+    }
+
+    pub fn get_text(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
+    pub fn set_text<'a>(&self, text: &'a str) { unsafe{ ::low::window_helper::set_window_text(self.handle, text); } }
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for RichTextBox {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::RichTextBox 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+
+// Private methods
+
+/// Closure called by `stream` for each block of data. It fills or reads the buffer and returns the number of bytes it used.
+type StreamCallback<'a> = &'a mut FnMut(&mut [u8]) -> usize;
+
+/**
+    Load the library of the rich edit control. The library is loaded once and stays loaded until the process exits,
+    because the control class is unregistered when the library is freed.
+*/
+unsafe fn load_rich_edit() -> Result<(), SystemError> {
+    use kernel32::{GetModuleHandleW, LoadLibraryW};
+    use low::other_helper::to_utf16;
+
+    let name = to_utf16("msftedit.dll");
+    if !GetModuleHandleW(name.as_ptr()).is_null() {
+        return Ok(());
+    }
+
+    if LoadLibraryW(name.as_ptr()).is_null() {
+        Err(SystemError::from_last_error("LoadLibraryW"))
+    } else {
+        Ok(())
+    }
+}
+
+/**
+    Encode a RTF document for `EM_STREAMIN`. RTF is a 8 bits format, so the characters up to U+00FF are written
+    as a single byte (the reverse of the Latin-1 decoding of `save_rtf`) and the other characters are escaped with `\uN?`.
+*/
+fn rtf_to_bytes<'a>(rtf: &'a str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(rtf.len());
+    for c in rtf.chars() {
+        if (c as u32) <= 0xFF {
+            bytes.push(c as u8);
+        } else {
+            let mut units = [0u16; 2];
+            for unit in c.encode_utf16(&mut units).iter() {
+                // The value of `\u` is a signed 16 bits number
+                bytes.extend_from_slice(format!("\\u{}?", *unit as i16).as_bytes());
+            }
+        }
+    }
+
+    bytes
+}
+
+/**
+    Send `EM_STREAMIN` or `EM_STREAMOUT` (`msg`) to a rich edit control. `callback` is called by the control,
+    before `stream` returns, for each block of data.
+*/
+unsafe fn stream(handle: HWND, msg: u32, format: usize, mut callback: StreamCallback) -> Result<(), Error> {
+    use low::defs::EM_STREAMIN;
+
+    let cookie: *mut StreamCallback = &mut callback;
+    let mut edit_stream = EDITSTREAM {
+        dwCookie: cookie as DWORD_PTR,
+        dwError: 0,
+        pfnCallback: stream_proc
+    };
+
+    SendMessageW(handle, msg, format as WPARAM, mem::transmute(&mut edit_stream));
+
+    let error = edit_stream.dwError;
+    if error == 0 {
+        Ok(())
+    } else {
+        use kernel32::SetLastError;
+
+        // The control reports the error in the stream structure, not with the thread last error
+        let function = if msg == EM_STREAMIN { "EM_STREAMIN" } else { "EM_STREAMOUT" };
+        SetLastError(error);
+        Err(Error::from_last_error(function))
+    }
+}
+
+unsafe extern "system" fn stream_proc(cookie: DWORD_PTR, buffer: *mut BYTE, size: LONG, transferred: *mut LONG) -> DWORD {
+    use std::slice;
+
+    let callback = &mut *(cookie as *mut StreamCallback);
+    let buffer = if buffer.is_null() || size <= 0 { 
+        &mut [] 
+    } else { 
+        slice::from_raw_parts_mut(buffer, size as usize) 
+    };
+
+    *transferred = callback(buffer) as LONG;
+    0
+}
@@ -0,0 +1,334 @@
+/*!
+    Scroll bar controls definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+
+use winapi::{HWND, UINT, c_int};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use defs::ScrollType;
+
+/**
+    Define a scroll bar template and its control. The horizontal and the vertical scroll bars only differ by their style.
+*/
+macro_rules! scroll_bar {
+    ($(#[$t_attr:meta])* template $t_name:ident; $(#[$c_attr:meta])* control $c_name:ident; style $style:ident) => {
+        $(#[$t_attr])*
+        #[derive(Clone)]
+        pub struct $t_name<ID: Hash+Clone> {
+            pub position: (i32, i32),
+            pub size: (u32, u32),
+            pub visible: bool,
+            pub disabled: bool,
+            pub range: (u32, u32),
+            pub value: u32,
+            pub page_size: u32,
+            pub parent: ID,
+        }
+
+        impl<ID: Hash+Clone> ControlT<ID> for $t_name<ID> {
+            fn resource_type_id(&self) -> TypeId { TypeId::of::<$c_name>() }
+
+            fn events(&self) -> Vec<Event> {
+                vec![Event::Destroyed, Event::Scroll, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+            }
+
+            fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+                use low::defs::$style;
+
+                let params = ScrollBarParams {
+                    position: self.position, size: self.size, visible: self.visible, disabled: self.disabled,
+                    range: self.range, value: self.value, page_size: self.page_size
+                };
+
+                match unsafe{ build_scroll_bar(ui, &self.parent, params, $style) } {
+                    Ok(h) => Ok( Box::new($c_name{handle: h}) ),
+                    Err(e) => Err(e)
+                }
+            }
+        }
+
+        $(#[$c_attr])*
+        pub struct $c_name {
+            handle: HWND
+        }
+
+        impl $c_name {
+
+            /// Return the value of the scroll bar
+            pub fn value(&self) -> u32 { unsafe{ get_value(self.handle) } }
+
+            /// Set the value of the scroll bar. The value is clamped to the range of the scroll bar.
+            pub fn set_value(&self, v: u32) { unsafe{ set_value(self.handle, v); } }
+
+            /// Return the minimum and the maximum value of the scroll bar
+            pub fn range(&self) -> (u32, u32) { unsafe{ get_range(self.handle) } }
+
+            /// Set the minimum and the maximum value of the scroll bar. If `min` is greater than `max`, an error is returned.
+            pub fn set_range(&self, min: u32, max: u32) -> Result<(), Error> { unsafe{ set_range(self.handle, min, max) } }
+
+            /// Return the page size of the scroll bar
+            pub fn page_size(&self) -> u32 { unsafe{ get_page_size(self.handle) } }
+
+            /// Set the page size of the scroll bar
+            pub fn set_page_size(&self, s: u32) { unsafe{ set_page_size(self.handle, s); } }
+
+            /**
+                Set the range, the value and the page size of the scroll bar in a single call, so that the scroll bar is only redrawn once.
+                The value is clamped to the new range. If `min` is greater than `max`, an error is returned.
+            */
+            pub fn set_track_info(&self, min: i32, max: i32, pos: i32, page: u32) -> Result<(), Error> { unsafe{ set_track_info(self.handle, min, max, pos, page) } }
+
+            pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+            pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+            pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+            pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+            pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+            pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+            pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+            pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+        }
+
+        impl Control for $c_name {
+
+            fn handle(&self) -> AnyHandle {
+                AnyHandle::HWND(self.handle)
+            }
+
+            fn control_type(&self) -> ControlType { 
+                ControlType::ScrollBar 
+            }
+
+            fn free(&mut self) {
+                use user32::DestroyWindow;
+                unsafe{ DestroyWindow(self.handle) };
+            }
+
+        }
+    }
+}
+
+scroll_bar! {
+    /**
+        A template that creates a standalone horizontal scroll bar
+
+        Events:  
+        Event::Destroyed, Event::Scroll, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+        `Scroll` receives `EventArgs::Scroll` with the kind of scroll and the new value. The value is updated before the callbacks are called.
+
+        Members:  
+        • `position`: The start position of the scroll bar  
+        • `size`: The start size of the scroll bar  
+        • `visible`: If the scroll bar should be visible to the user  
+        • `disabled`: If the user can or can't use the scroll bar  
+        • `range`: The minimum and the maximum value of the scroll bar  
+        • `value`: The starting value of the scroll bar  
+        • `page_size`: The size of a page. The thumb is sized according to the page and a large scroll moves by one page  
+        • `parent`: The scroll bar parent  
+    */
+    template HorizontalScrollBarT;
+
+    /**
+        A standalone horizontal scroll bar.  
+        If the page size is not 0, the greatest value that can be reached is `max - page_size + 1`.
+    */
+    control HorizontalScrollBar;
+
+    style SBS_HORZ
+}
+
+scroll_bar! {
+    /**
+        A template that creates a standalone vertical scroll bar
+
+        Events:  
+        Event::Destroyed, Event::Scroll, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+        `Scroll` receives `EventArgs::Scroll` with the kind of scroll and the new value. The value is updated before the callbacks are called.
+
+        Members:  
+        • `position`: The start position of the scroll bar  
+        • `size`: The start size of the scroll bar  
+        • `visible`: If the scroll bar should be visible to the user  
+        • `disabled`: If the user can or can't use the scroll bar  
+        • `range`: The minimum and the maximum value of the scroll bar  
+        • `value`: The starting value of the scroll bar  
+        • `page_size`: The size of a page. The thumb is sized according to the page and a large scroll moves by one page  
+        • `parent`: The scroll bar parent  
+    */
+    template VerticalScrollBarT;
+
+    /**
+        A standalone vertical scroll bar.  
+        If the page size is not 0, the greatest value that can be reached is `max - page_size + 1`.
+    */
+    control VerticalScrollBar;
+
+    style SBS_VERT
+}
+
+/**
+    Called from the events dispatcher when the parent of a scroll bar receives `WM_HSCROLL` or `WM_VSCROLL`.
+    The scroll bar control do not move its thumb by itself, so the new value is computed and set here.  
+    Return the kind of scroll and the new value, or `None` if the request is not handled.
+*/
+pub unsafe fn scroll_bar_scrolled(handle: HWND, request: c_int) -> Option<(ScrollType, u32)> {
+    use user32::{GetScrollInfo, SetScrollPos};
+    use winapi::{SCROLLINFO, SIF_ALL, SB_CTL, SB_LINEUP, SB_LINEDOWN, SB_PAGEUP, SB_PAGEDOWN, SB_THUMBTRACK,
+      SB_THUMBPOSITION, SB_ENDSCROLL, TRUE};
+
+    let mut info: SCROLLINFO = mem::zeroed();
+    info.cbSize = mem::size_of::<SCROLLINFO>() as UINT;
+    info.fMask = SIF_ALL;
+    GetScrollInfo(handle, SB_CTL, &mut info);
+
+    let page = if info.nPage == 0 { 1 } else { info.nPage as c_int };
+    let (kind, pos) = match request {
+        SB_LINEUP => (ScrollType::SmallDec, info.nPos.saturating_sub(1)),
+        SB_LINEDOWN => (ScrollType::SmallInc, info.nPos.saturating_add(1)),
+        SB_PAGEUP => (ScrollType::LargeDec, info.nPos.saturating_sub(page)),
+        SB_PAGEDOWN => (ScrollType::LargeInc, info.nPos.saturating_add(page)),
+        SB_THUMBTRACK => (ScrollType::ThumbTrack, info.nTrackPos),
+        SB_THUMBPOSITION => (ScrollType::ThumbPosition, info.nTrackPos),
+        SB_ENDSCROLL => (ScrollType::EndScroll, info.nPos),
+        _ => { return None; }
+    };
+
+    // The system clamps the position to the range of the scroll bar
+    SetScrollPos(handle, SB_CTL, pos, TRUE);
+
+    Some((kind, get_value(handle)))
+}
+
+// Private functions
+
+/// Values shared by the two scroll bar templates
+struct ScrollBarParams {
+    position: (i32, i32),
+    size: (u32, u32),
+    visible: bool,
+    disabled: bool,
+    range: (u32, u32),
+    value: u32,
+    page_size: u32
+}
+
+unsafe fn build_scroll_bar<ID: Hash+Clone>(ui: &Ui<ID>, parent: &ID, p: ScrollBarParams, bar_style: UINT) -> Result<HWND, Error> {
+    use low::window_helper::{WindowParams, build_window, handle_of_window, set_window_scroll_info};
+    use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, SB_CTL};
+
+    if p.range.1 < p.range.0 {
+        let msg = "The scroll bar range maximum value must be greater or equal to the minimum value";
+        return Err(Error::UserError(msg.to_string()));
+    }
+
+    let flags: DWORD = WS_CHILD | bar_style |
+    if p.visible  { WS_VISIBLE }   else { 0 } |
+    if p.disabled { WS_DISABLED }  else { 0 };
+
+    // Get the parent handle
+    let parent = match handle_of_window(ui, parent, "The parent of a scroll bar must be a window-like control.") {
+        Ok(h) => h,
+        Err(e) => { return Err(e); }
+    };
+
+    let params = WindowParams {
+        title: "",
+        class_name: "SCROLLBAR",
+        position: p.position,
+        size: p.size,
+        flags: flags,
+        ex_flags: Some(0),
+        parent: parent
+    };
+
+    match build_window(params) {
+        Ok(h) => {
+            set_window_scroll_info(h, SB_CTL, p.range.0 as i32, p.range.1 as i32, p.value as i32, p.page_size);
+            Ok(h)
+        },
+        Err(e) => Err(Error::System(e))
+    }
+}
+
+#[inline(always)]
+unsafe fn get_value(handle: HWND) -> u32 {
+    use user32::GetScrollPos;
+    use winapi::SB_CTL;
+    GetScrollPos(handle, SB_CTL) as u32
+}
+
+#[inline(always)]
+unsafe fn set_value(handle: HWND, value: u32) {
+    use user32::SetScrollPos;
+    use winapi::{SB_CTL, TRUE};
+    SetScrollPos(handle, SB_CTL, value as c_int, TRUE);
+}
+
+#[inline(always)]
+unsafe fn get_range(handle: HWND) -> (u32, u32) {
+    use low::window_helper::get_window_scroll_info;
+    use winapi::SB_CTL;
+    let (min, max, _, _) = get_window_scroll_info(handle, SB_CTL);
+    (min as u32, max as u32)
+}
+
+unsafe fn set_range(handle: HWND, min: u32, max: u32) -> Result<(), Error> {
+    use low::window_helper::get_window_scroll_info;
+    use winapi::SB_CTL;
+
+    let (_, _, pos, page) = get_window_scroll_info(handle, SB_CTL);
+    set_track_info(handle, min as i32, max as i32, pos, page)
+}
+
+unsafe fn set_track_info(handle: HWND, min: i32, max: i32, pos: i32, page: u32) -> Result<(), Error> {
+    use low::window_helper::set_window_scroll_info;
+    use winapi::SB_CTL;
+
+    if max < min {
+        let msg = "The scroll bar range maximum value must be greater or equal to the minimum value";
+        return Err(Error::UserError(msg.to_string()));
+    }
+
+    set_window_scroll_info(handle, SB_CTL, min, max, pos, page);
+    Ok(())
+}
+
+#[inline(always)]
+unsafe fn get_page_size(handle: HWND) -> u32 {
+    use low::window_helper::get_window_scroll_info;
+    use winapi::SB_CTL;
+    let (_, _, _, page) = get_window_scroll_info(handle, SB_CTL);
+    page
+}
+
+unsafe fn set_page_size(handle: HWND, page: u32) {
+    use low::window_helper::{get_window_scroll_info, set_window_scroll_info};
+    use winapi::SB_CTL;
+    let (min, max, pos, _) = get_window_scroll_info(handle, SB_CTL);
+    set_window_scroll_info(handle, SB_CTL, min, max, pos, page);
+}
@@ -0,0 +1,299 @@
+/*!
+    Static image control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+
+use winapi::{HWND, HANDLE, UINT, HBITMAP, HICON};
+use user32::SendMessageW;
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use resources::{Bitmap, Icon, Resource};
+use error::Error;
+use events::Event;
+use defs::ImageType;
+use low::other_helper::to_utf16;
+
+/// Name of the window property that holds the last image handle passed to the control
+const IMAGE_PROP_NAME: &'static str = "NWG_STATIC_IMAGE";
+
+/**
+    A template that creates a static control displaying a bitmap or an icon
+
+    Available events:  
+    Event::Destroyed, Event::Click, Event::DoubleClick, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `image`: The image displayed by the control. Must be a bitmap or an icon resource. If None, the control starts empty  
+    • `position`: The start position of the control  
+    • `size`: The start size of the control  
+    • `visible`: If the control should be visible to the user  
+    • `disabled`: If the user can or can't click on the control  
+    • `center`: If the image should be centered in the control. If false, the control is resized to fit the image  
+    • `parent`: The control parent  
+*/
+#[derive(Clone)]
+pub struct StaticImageT<ID: Hash+Clone> {
+    pub image: Option<ID>,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub center: bool,
+    pub parent: ID,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for StaticImageT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<StaticImage>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::Click, Event::DoubleClick, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, handle_of_window};
+        use low::defs::{SS_NOTIFY, SS_BITMAP, SS_ICON, SS_CENTERIMAGE};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD};
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a static image must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the image handle (if any)
+        let image: Option<(ImageType, HANDLE)> = match self.image.as_ref() {
+            Some(image_id) => match ui.handle_of(image_id) {
+                Ok(AnyHandle::HBITMAP(h)) => Some((ImageType::Bitmap, h as HANDLE)),
+                Ok(AnyHandle::HICON(h)) => Some((ImageType::Icon, h as HANDLE)),
+                Ok(_) => { return Err(Error::BadResource("The image of a static image must be a bitmap or an icon resource.".to_string())); },
+                Err(e) => { return Err(e); }
+            },
+            None => None
+        };
+
+        let image_style = match image.as_ref() {
+            Some(&(ImageType::Icon, _)) => SS_ICON,
+            _ => SS_BITMAP
+        };
+
+        let flags: DWORD = WS_CHILD | SS_NOTIFY | image_style |
+        if self.visible    { WS_VISIBLE }     else { 0 } |
+        if self.disabled   { WS_DISABLED }    else { 0 } |
+        if self.center     { SS_CENTERIMAGE } else { 0 };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "STATIC",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                if let Some((image_type, image_handle)) = image {
+                    unsafe{ set_image(h, image_type, image_handle); }
+                }
+                Ok( Box::new(StaticImage{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    A static control that displays a bitmap or an icon.  
+    The control do not own the displayed image. The image resource must outlive the time it is displayed.
+*/
+pub struct StaticImage {
+    handle: HWND
+}
+
+impl StaticImage {
+
+    /// Display a bitmap in the control. If `bitmap` is None, clear the current image.
+    pub fn set_bitmap(&self, bitmap: Option<&Bitmap>) {
+        let handle = match bitmap.map(|b| b.handle()) {
+            Some(AnyHandle::HBITMAP(h)) => h as HANDLE,
+            _ => ::std::ptr::null_mut()
+        };
+
+        unsafe{ set_image(self.handle, ImageType::Bitmap, handle); }
+    }
+
+    /// Display an icon in the control. If `icon` is None, clear the current image.
+    pub fn set_icon(&self, icon: Option<&Icon>) {
+        let handle = match icon.map(|i| i.handle()) {
+            Some(AnyHandle::HICON(h)) => h as HANDLE,
+            _ => ::std::ptr::null_mut()
+        };
+
+        unsafe{ set_image(self.handle, ImageType::Icon, handle); }
+    }
+
+    /// Return the type of image the control displays
+    pub fn get_image_type(&self) -> ImageType {
+        use low::window_helper::get_window_long;
+        use low::defs::{SS_ICON, SS_TYPEMASK};
+        use winapi::GWL_STYLE;
+
+        let style = get_window_long(self.handle, GWL_STYLE) as UINT;
+        match style & SS_TYPEMASK {
+            SS_ICON => ImageType::Icon,
+            _ => ImageType::Bitmap
+        }
+    }
+
+    /**
+        Return the size in pixels of the displayed image. This is not the size of the control: the image is clipped
+        or centered in the control. Return (0, 0) if the control does not display an image.
+    */
+    pub fn size(&self) -> (u32, u32) {
+        unsafe{ image_size(self.handle, self.get_image_type()) }
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for StaticImage {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::StaticImage 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+
+        // Free the copy of the image made by the control (if any)
+        unsafe{
+            set_image(self.handle, ImageType::Bitmap, ::std::ptr::null_mut());
+            DestroyWindow(self.handle);
+        }
+    }
+
+}
+
+/// Private static image methods
+
+/**
+    Display `image` in the control. If the control returns an image that is not the last image passed to this function,
+    the image is a copy made by the control (ex: a 32 bits bitmap with an alpha channel) and it is freed.
+*/
+unsafe fn set_image(handle: HWND, image_type: ImageType, image: HANDLE) {
+    use user32::{GetPropW, SetPropW, RemovePropW, DestroyIcon};
+    use gdi32::DeleteObject;
+    use low::window_helper::{get_window_long, set_window_long};
+    use low::defs::{STM_SETIMAGE, SS_BITMAP, SS_ICON, SS_TYPEMASK};
+    use winapi::{GWL_STYLE, IMAGE_BITMAP, IMAGE_ICON, WPARAM, LPARAM, UINT};
+
+    let (image_style, image_flag) = match image_type {
+        ImageType::Bitmap => (SS_BITMAP, IMAGE_BITMAP),
+        ImageType::Icon => (SS_ICON, IMAGE_ICON)
+    };
+
+    // The control style must match the type of the image
+    let old_style = get_window_long(handle, GWL_STYLE) as usize;
+    set_window_long(handle, GWL_STYLE, (old_style & !(SS_TYPEMASK as usize)) | (image_style as usize));
+
+    let prop_name = to_utf16(IMAGE_PROP_NAME);
+    let user_image = GetPropW(handle, prop_name.as_ptr());
+
+    let old_image = SendMessageW(handle, STM_SETIMAGE, image_flag as WPARAM, image as LPARAM) as HANDLE;
+    if !old_image.is_null() && old_image != user_image {
+        match (old_style as UINT) & SS_TYPEMASK {
+            SS_ICON => { DestroyIcon(old_image as HICON); },
+            _ => { DeleteObject(mem::transmute(old_image)); }
+        }
+    }
+
+    if image.is_null() {
+        RemovePropW(handle, prop_name.as_ptr());
+    } else {
+        SetPropW(handle, prop_name.as_ptr(), image);
+    }
+}
+
+unsafe fn bitmap_size(bitmap: HBITMAP) -> (u32, u32) {
+    use low::defs::GetObjectW;
+    use winapi::{BITMAP, c_int};
+
+    let mut info: BITMAP = mem::zeroed();
+    if GetObjectW(mem::transmute(bitmap), mem::size_of::<BITMAP>() as c_int, mem::transmute(&mut info)) == 0 {
+        (0, 0)
+    } else {
+        (info.bmWidth as u32, info.bmHeight.abs() as u32)
+    }
+}
+
+unsafe fn image_size(handle: HWND, image_type: ImageType) -> (u32, u32) {
+    use user32::GetIconInfo;
+    use gdi32::DeleteObject;
+    use low::defs::STM_GETIMAGE;
+    use winapi::{IMAGE_BITMAP, IMAGE_ICON, WPARAM, ICONINFO};
+
+    let image_flag = match image_type {
+        ImageType::Bitmap => IMAGE_BITMAP,
+        ImageType::Icon => IMAGE_ICON
+    };
+
+    let image = SendMessageW(handle, STM_GETIMAGE, image_flag as WPARAM, 0);
+    if image == 0 {
+        return (0, 0);
+    }
+
+    match image_type {
+        ImageType::Bitmap => bitmap_size(image as HBITMAP),
+        ImageType::Icon => {
+            let mut info: ICONINFO = mem::zeroed();
+            if GetIconInfo(image as HICON, &mut info) == 0 {
+                return (0, 0);
+            }
+
+            // A monochrome icon stores the AND and the XOR masks in the same bitmap, one over the other
+            let size = if info.hbmColor.is_null() {
+                let (w, h) = bitmap_size(info.hbmMask);
+                (w, h / 2)
+            } else {
+                bitmap_size(info.hbmColor)
+            };
+
+            if !info.hbmMask.is_null() { DeleteObject(mem::transmute(info.hbmMask)); }
+            if !info.hbmColor.is_null() { DeleteObject(mem::transmute(info.hbmColor)); }
+
+            size
+        }
+    }
+}
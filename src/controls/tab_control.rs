@@ -0,0 +1,547 @@
+/*!
+    Tab control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+
+use std::hash::Hash;
+use std::any::TypeId;
+use std::mem;
+use std::ptr;
+
+use user32::SendMessageW;
+use winapi::{HWND, HFONT, UINT, WPARAM, LPARAM, LRESULT, UINT_PTR, DWORD_PTR, TCITEMW, c_int};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use low::other_helper::{to_utf16, from_utf16};
+
+/**
+    Template that creates a tab control. The pages of the tab control are created with `TabItemT` or with `TabControl::add_tab`.
+
+    Available events:  
+    Event::Destroyed, Event::TabChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    `TabChanged` is raised when the user selects another tab. It is not raised by `set_selected_tab`.
+
+    Members:  
+    • `position`: The start position of the tab control  
+    • `size`: The start size of the tab control  
+    • `visible`: If the tab control should be visible to the user  
+    • `disabled`: If the user can or can't use the tab control  
+    • `tab_stop`: If the tab control can receive the keyboard focus with the TAB key  
+    • `group`: If the tab control is the first control of a group (`WS_GROUP`)  
+    • `multi_line`: If the tabs are displayed on more than one row when they do not fit the width of the control  
+    • `buttons`: If the tabs are displayed as buttons  
+    • `parent`: The tab control parent  
+    • `font`: The font of the tabs. If None, use the system default  
+*/
+#[derive(Clone)]
+pub struct TabControlT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
+    pub multi_line: bool,
+    pub buttons: bool,
+    pub parent: ID,
+    pub font: Option<ID>,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for TabControlT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<TabControl>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::TabChanged, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use user32::DestroyWindow;
+        use comctl32::SetWindowSubclass;
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, WS_CLIPCHILDREN, WS_CLIPSIBLINGS, TCS_MULTILINE, TCS_BUTTONS};
+
+        let flags: DWORD = WS_CHILD | WS_CLIPCHILDREN | WS_CLIPSIBLINGS |
+        if self.visible      { WS_VISIBLE }    else { 0 } |
+        if self.disabled     { WS_DISABLED }   else { 0 } |
+        if self.tab_stop     { WS_TABSTOP }    else { 0 } |
+        if self.group        { WS_GROUP }      else { 0 } |
+        if self.multi_line   { TCS_MULTILINE } else { 0 } |
+        if self.buttons      { TCS_BUTTONS }   else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a tab control must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of a tab control must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysTabControl32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => unsafe {
+                set_window_font(h, font_handle, true);
+
+                // The pages must follow the size of the tab control
+                if SetWindowSubclass(h, Some(tab_control_proc), TAB_CONTROL_ID, 0) == 0 {
+                    let error = Error::from_last_error("SetWindowSubclass");
+                    DestroyWindow(h);
+                    return Err(error);
+                }
+
+                Ok( Box::new(TabControl{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    Template that creates a page in a tab control. The controls created with the tab item as parent
+    are only visible when the tab of the page is selected.
+
+    Available events:  
+    Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Raw  
+
+    Members:  
+    • `label`: The text of the tab  
+    • `parent`: The tab control that holds the page. Must be a `TabControl`  
+*/
+#[derive(Clone)]
+pub struct TabItemT<S: Clone+Into<String>, ID: Hash+Clone> {
+    pub label: S,
+    pub parent: ID,
+}
+
+impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for TabItemT<S, ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<TabItem>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::handle_of_window;
+
+        let err = "The parent of a tab item must be a tab control.";
+        let tab = match handle_of_window(ui, &self.parent, err) {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        if !unsafe{ is_tab_control(tab) } {
+            return Err(Error::BadParent(err.to_string()));
+        }
+
+        let label: String = self.label.clone().into();
+        match unsafe{ insert_page(tab, &label) } {
+            Ok((_, page)) => Ok( Box::new(TabItem{handle: page, tab: tab}) ),
+            Err(e) => Err(e)
+        }
+    }
+}
+
+/**
+    A control that displays a page of controls for each of its tabs. Only the page of the selected tab is visible.
+*/
+pub struct TabControl {
+    handle: HWND
+}
+
+impl TabControl {
+
+    /**
+        Add a tab with the text `label` at the end of the tab control and return its index.  
+        The page of the tab is owned by the tab control. Use `page` to get its handle and create child controls in it.
+
+        Errors:  
+        • `Error::System` if the system could not create the page or insert the tab  
+    */
+    pub fn add_tab<'a>(&self, label: &'a str) -> Result<usize, Error> {
+        match unsafe{ insert_page(self.handle, label) } {
+            Ok((index, _)) => Ok(index),
+            Err(e) => Err(e)
+        }
+    }
+
+    /**
+        Remove the tab at `index` and destroy its page with the controls in it. Do nothing if the index is out of bounds.  
+        The pages created by a `TabItemT` should be removed by unpacking the tab item instead.
+    */
+    pub fn remove_tab(&self, index: usize) {
+        unsafe{ remove_page(self.handle, index); }
+    }
+
+    /// Return the number of tabs in the tab control
+    pub fn tab_count(&self) -> usize {
+        use winapi::TCM_GETITEMCOUNT;
+        unsafe{ SendMessageW(self.handle, TCM_GETITEMCOUNT, 0, 0) as usize }
+    }
+
+    /// Return the index of the selected tab or None if the tab control is empty
+    pub fn selected_tab(&self) -> Option<usize> {
+        use winapi::TCM_GETCURSEL;
+
+        let index = unsafe{ SendMessageW(self.handle, TCM_GETCURSEL, 0, 0) };
+        if index < 0 { None } else { Some(index as usize) }
+    }
+
+    /// Select the tab at `index` and display its page. `Event::TabChanged` is not raised.
+    pub fn set_selected_tab(&self, index: usize) {
+        use winapi::TCM_SETCURSEL;
+
+        unsafe { 
+            SendMessageW(self.handle, TCM_SETCURSEL, index as WPARAM, 0);
+            update_pages(self.handle);
+        }
+    }
+
+    /// Return the text of the tab at `index`. Return an empty string if the index is out of bounds.
+    pub fn tab_label(&self, index: usize) -> String {
+        unsafe{ tab_label(self.handle, index) }
+    }
+
+    /// Set the text of the tab at `index`
+    pub fn set_tab_label<'a>(&self, index: usize, label: &'a str) {
+        unsafe{ set_tab_label(self.handle, index, label); }
+    }
+
+    /// Return the handle of the page of the tab at `index` or None if the index is out of bounds
+    pub fn page(&self, index: usize) -> Option<AnyHandle> {
+        let page = unsafe{ page_of(self.handle, index) };
+        if page.is_null() { None } else { Some(AnyHandle::HWND(page)) }
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for TabControl {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::TabControl 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+/**
+    A page of a tab control. The handle of the tab item is the handle of the page.
+*/
+pub struct TabItem {
+    handle: HWND,
+    tab: HWND
+}
+
+impl TabItem {
+
+    /// Return the index of the tab of the page in its tab control
+    pub fn index(&self) -> Option<usize> {
+        unsafe{ index_of_page(self.tab, self.handle) }
+    }
+
+    /// Return the text of the tab
+    pub fn get_label(&self) -> String {
+        match self.index() {
+            Some(i) => unsafe{ tab_label(self.tab, i) },
+            None => String::new()
+        }
+    }
+
+    /// Set the text of the tab
+    pub fn set_label<'a>(&self, label: &'a str) {
+        if let Some(i) = self.index() {
+            unsafe{ set_tab_label(self.tab, i, label); }
+        }
+    }
+
+    /// Return true if the page is displayed (its tab is selected)
+    pub fn is_selected(&self) -> bool {
+        use winapi::TCM_GETCURSEL;
+
+        let selected = unsafe{ SendMessageW(self.tab, TCM_GETCURSEL, 0, 0) };
+        self.index().map(|i| i as LRESULT == selected).unwrap_or(false)
+    }
+
+}
+
+impl Control for TabItem {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::TabItem 
+    }
+
+    fn free(&mut self) {
+        use user32::{IsWindow, DestroyWindow};
+
+        unsafe {
+            // The page may have been destroyed with its tab control
+            if IsWindow(self.tab) != 0 {
+                match index_of_page(self.tab, self.handle) {
+                    Some(i) => remove_page(self.tab, i),
+                    None => { DestroyWindow(self.handle); }
+                }
+            }
+        }
+    }
+
+}
+
+
+// Private methods
+
+const TAB_PAGE_CLASS_NAME: &'static str = "NWG_BUILTIN_TABPAGE";
+const TAB_CONTROL_ID: UINT_PTR = 5676;
+
+/**
+    Called from the events dispatcher when the user selects another tab (`TCN_SELCHANGE`).
+    Display the page of the selected tab.
+*/
+pub unsafe fn tab_selection_changed(tab: HWND) {
+    update_pages(tab);
+}
+
+/// Return true if `handle` is a system tab control
+unsafe fn is_tab_control(handle: HWND) -> bool {
+    use user32::GetClassNameW;
+
+    let mut buffer: [u16; 32] = [0; 32];
+    let length = GetClassNameW(handle, buffer.as_mut_ptr(), buffer.len() as c_int) as usize;
+    from_utf16(&buffer[0..length]) == "SysTabControl32"
+}
+
+/// Create a page and add a tab for it at the end of the tab control. Return the index of the tab and the page.
+unsafe fn insert_page(tab: HWND, label: &str) -> Result<(usize, HWND), Error> {
+    use low::window_helper::{WindowParams, SysclassParams, build_sysclass, build_window};
+    use user32::DestroyWindow;
+    use winapi::{WS_CHILD, WS_CLIPCHILDREN, WS_CLIPSIBLINGS, WS_EX_CONTROLPARENT, COLOR_BTNFACE, HBRUSH, TCM_INSERTITEMW, 
+      TCM_GETITEMCOUNT, TCIF_TEXT, TCIF_PARAM};
+
+    let background: HBRUSH = mem::transmute((COLOR_BTNFACE + 1) as usize);
+    let params = SysclassParams { 
+        class_name: TAB_PAGE_CLASS_NAME,
+        sysproc: Some(tab_page_sysproc),
+        background: Some(background), style: None
+    };
+
+    if let Err(e) = build_sysclass(params) {
+        return Err(Error::System(e));
+    }
+
+    let params = WindowParams {
+        title: "",
+        class_name: TAB_PAGE_CLASS_NAME,
+        position: (0, 0),
+        size: (0, 0),
+        flags: WS_CHILD | WS_CLIPCHILDREN | WS_CLIPSIBLINGS,
+        ex_flags: Some(WS_EX_CONTROLPARENT),
+        parent: tab
+    };
+
+    let page = match build_window(params) {
+        Ok(h) => h,
+        Err(e) => { return Err(Error::System(e)); }
+    };
+
+    let mut text = to_utf16(label);
+    let mut item: TCITEMW = mem::zeroed();
+    item.mask = TCIF_TEXT | TCIF_PARAM;
+    item.pszText = text.as_mut_ptr();
+    item.lParam = page as LPARAM;
+
+    let count = SendMessageW(tab, TCM_GETITEMCOUNT, 0, 0);
+    let index = SendMessageW(tab, TCM_INSERTITEMW, count as WPARAM, mem::transmute(&item));
+    if index < 0 {
+        let error = Error::from_last_error("TCM_INSERTITEMW");
+        DestroyWindow(page);
+        return Err(error);
+    }
+
+    update_pages(tab);
+
+    Ok((index as usize, page))
+}
+
+/// Remove the tab at `index` and destroy its page. If the tab was selected, the next tab is selected.
+unsafe fn remove_page(tab: HWND, index: usize) {
+    use user32::DestroyWindow;
+    use winapi::{TCM_DELETEITEM, TCM_GETCURSEL, TCM_SETCURSEL, TCM_GETITEMCOUNT};
+
+    let page = page_of(tab, index);
+    if page.is_null() {
+        return;
+    }
+
+    let selected = SendMessageW(tab, TCM_GETCURSEL, 0, 0);
+    SendMessageW(tab, TCM_DELETEITEM, index as WPARAM, 0);
+    DestroyWindow(page);
+
+    let count = SendMessageW(tab, TCM_GETITEMCOUNT, 0, 0);
+    if selected == index as LRESULT && count > 0 {
+        let next = if (index as LRESULT) < count { index as LRESULT } else { count - 1 };
+        SendMessageW(tab, TCM_SETCURSEL, next as WPARAM, 0);
+    }
+
+    update_pages(tab);
+}
+
+/// Return the page of the tab at `index` or a null handle if the index is out of bounds
+unsafe fn page_of(tab: HWND, index: usize) -> HWND {
+    use winapi::{TCM_GETITEMW, TCIF_PARAM};
+
+    let mut item: TCITEMW = mem::zeroed();
+    item.mask = TCIF_PARAM;
+
+    if SendMessageW(tab, TCM_GETITEMW, index as WPARAM, mem::transmute(&mut item)) == 0 {
+        ptr::null_mut()
+    } else {
+        item.lParam as HWND
+    }
+}
+
+/// Return the index of the tab of `page`
+unsafe fn index_of_page(tab: HWND, page: HWND) -> Option<usize> {
+    use winapi::TCM_GETITEMCOUNT;
+
+    let count = SendMessageW(tab, TCM_GETITEMCOUNT, 0, 0) as usize;
+    (0..count).find(|&i| page_of(tab, i) == page)
+}
+
+unsafe fn tab_label(tab: HWND, index: usize) -> String {
+    use winapi::{TCM_GETITEMW, TCIF_TEXT};
+
+    let mut buffer_size: usize = 256;
+    loop {
+        let mut buffer: Vec<u16> = vec![0; buffer_size];
+
+        let mut item: TCITEMW = mem::zeroed();
+        item.mask = TCIF_TEXT;
+        item.pszText = buffer.as_mut_ptr();
+        item.cchTextMax = buffer_size as c_int;
+
+        if SendMessageW(tab, TCM_GETITEMW, index as WPARAM, mem::transmute(&mut item)) == 0 {
+            return String::new();
+        }
+
+        // The control may return a pointer to its own buffer instead of copying the text
+        let text = item.pszText;
+        let mut length = 0;
+        while *text.offset(length as isize) != 0 && length < buffer_size { length += 1; }
+
+        if length + 1 < buffer_size {
+            return from_utf16(::std::slice::from_raw_parts(text, length));
+        }
+
+        // The text was truncated
+        buffer_size *= 2;
+    }
+}
+
+unsafe fn set_tab_label(tab: HWND, index: usize, label: &str) {
+    use winapi::{TCM_SETITEMW, TCIF_TEXT};
+
+    let mut text = to_utf16(label);
+    let mut item: TCITEMW = mem::zeroed();
+    item.mask = TCIF_TEXT;
+    item.pszText = text.as_mut_ptr();
+
+    SendMessageW(tab, TCM_SETITEMW, index as WPARAM, mem::transmute(&item));
+}
+
+/// Fit the pages into the display area of the tab control and only show the page of the selected tab
+unsafe fn update_pages(tab: HWND) {
+    use user32::{GetClientRect, SetWindowPos};
+    use winapi::{RECT, TCM_GETCURSEL, TCM_GETITEMCOUNT, TCM_ADJUSTRECT, SWP_NOZORDER, SWP_NOACTIVATE, SWP_SHOWWINDOW, SWP_HIDEWINDOW};
+
+    let mut rect: RECT = mem::zeroed();
+    GetClientRect(tab, &mut rect);
+    SendMessageW(tab, TCM_ADJUSTRECT, 0, mem::transmute(&mut rect));
+
+    let selected = SendMessageW(tab, TCM_GETCURSEL, 0, 0);
+    let count = SendMessageW(tab, TCM_GETITEMCOUNT, 0, 0) as usize;
+    for i in 0..count {
+        let page = page_of(tab, i);
+        let visibility = if i as LRESULT == selected { SWP_SHOWWINDOW } else { SWP_HIDEWINDOW };
+        SetWindowPos(page, ptr::null_mut(), rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top,
+          SWP_NOZORDER | SWP_NOACTIVATE | visibility);
+    }
+}
+
+unsafe extern "system" fn tab_control_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, _id: UINT_PTR, _data: DWORD_PTR) -> LRESULT {
+    use comctl32::{DefSubclassProc, RemoveWindowSubclass};
+    use winapi::{WM_SIZE, WM_NCDESTROY};
+
+    match msg {
+        WM_SIZE => {
+            // The display area is only updated once the tab control processed the message
+            let result = DefSubclassProc(hwnd, msg, w, l);
+            update_pages(hwnd);
+            return result;
+        },
+        WM_NCDESTROY => {
+            RemoveWindowSubclass(hwnd, Some(tab_control_proc), TAB_CONTROL_ID);
+        },
+        _ => {}
+    }
+
+    DefSubclassProc(hwnd, msg, w, l)
+}
+
+unsafe extern "system" fn tab_page_sysproc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use user32::DefWindowProcW;
+    DefWindowProcW(hwnd, msg, w, l)
+}
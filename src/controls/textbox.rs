@@ -38,7 +38,7 @@ use events::Event;
     A template that creates a multi line textinput control
 
     Events:  
-    Event: Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp, Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw  
+    Event: Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `text`: The text of the textbox  
@@ -46,6 +46,8 @@ use events::Event;
     • `size`: The start size of the textbox  
     • `visible`: If the textbox should be visible to the user   
     • `disabled`: If the user can or can't click on the textbox  
+    • `tab_stop`: If the textbox can receive the keyboard focus with the TAB key  
+    • `group`: If the textbox is the first control of a group (`WS_GROUP`)  
     • `readonly`: If the user can copty the text but can't edit the textbox content  
     • `limit`: The maximum number of characters that the control can hold  
     • `scrollbars`: A tuple to defined whether to show scrollbars or not (show horizontal, show vertical)
@@ -59,6 +61,8 @@ pub struct TextBoxT<S1: Clone+Into<String>, ID: Hash+Clone> {
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub readonly: bool,
     pub limit: u32,
     pub scrollbars: (bool, bool),
@@ -71,20 +75,22 @@ impl<S1: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for TextBoxT<S1, ID> {
 
     fn events(&self) -> Vec<Event> {
         vec![Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp,
-             Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw]
+             Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
         use low::defs::{ES_AUTOHSCROLL, ES_AUTOVSCROLL, ES_READONLY, EM_LIMITTEXT, ES_MULTILINE};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_BORDER, WS_HSCROLL, WS_VSCROLL};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, WS_BORDER, WS_HSCROLL, WS_VSCROLL};
 
         let flags: DWORD = WS_CHILD | WS_BORDER | ES_AUTOHSCROLL | ES_MULTILINE | ES_AUTOVSCROLL |
         if self.readonly { ES_READONLY } else { 0 } |
         if self.visible  { WS_VISIBLE }  else { 0 } |
         if self.scrollbars.0 { WS_HSCROLL } else { 0 } |
         if self.scrollbars.1 { WS_VSCROLL } else { 0 } |
-        if self.disabled { WS_DISABLED } else { 0 };
+        if self.disabled { WS_DISABLED } else { 0 } |
+        if self.tab_stop { WS_TABSTOP }  else { 0 } |
+        if self.group    { WS_GROUP }    else { 0 };
 
         // Get the parent handle
         let parent = match handle_of_window(ui, &self.parent, "The parent of a textinput must be a window-like control.") {
@@ -172,6 +178,15 @@ impl TextBox {
         unsafe{ SendMessageW(self.handle, EM_GETLIMITTEXT, 0, 0) as u32 }
     }
 
+    /**
+        Set a function that transforms the clipboard text before it is pasted in the TextBox. Use `None` to remove the filter.  
+        For example, a filter can remove the control characters or normalize the line endings. A filter that returns
+        its input unchanged behaves like the default paste.
+    */
+    pub fn set_paste_filter(&self, filter: Option<fn(&str) -> String>) {
+        unsafe{ ::low::window_helper::set_window_paste_filter(self.handle, filter); }
+    }
+
     pub fn get_text(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
     pub fn set_text<'a>(&self, text: &'a str) { unsafe{ ::low::window_helper::set_window_text(self.handle, text); } }
     pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
@@ -36,7 +36,7 @@ use events::Event;
     A template that creates a single line textinput control
 
     Events:  
-    Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp, Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw  
+    Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw  
 
     Members:  
     • `text`: The text of the textinput  
@@ -44,6 +44,8 @@ use events::Event;
     • `size`: The start size of the textinput  
     • `visible`: If the textinput should be visible to the user   
     • `disabled`: If the user can or can't click on the textinput  
+    • `tab_stop`: If the textinput can receive the keyboard focus with the TAB key  
+    • `group`: If the textinput is the first control of a group (`WS_GROUP`)  
     • `readonly`: If the user can copty the text but can't edit the textinput content  
     • `password`: If the textinput should hide its content  
     • `placeholder`: Some text that is displayed when the actual value is empty  
@@ -58,6 +60,8 @@ pub struct TextInputT<S1: Clone+Into<String>, S2: Clone+Into<String>, ID: Hash+C
     pub size: (u32, u32),
     pub visible: bool,
     pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
     pub readonly: bool,
     pub password: bool,
     pub placeholder: Option<S2>,
@@ -71,19 +75,21 @@ impl<S1: Clone+Into<String>, S2: Clone+Into<String>, ID: Hash+Clone> ControlT<ID
 
     fn events(&self) -> Vec<Event> {
         vec![Event::Destroyed, Event::Focus, Event::Char, Event::KeyDown, Event::KeyUp, Event::MouseDown, Event::MouseUp,
-             Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw]
+             Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::ValueChanged, Event::Moved, Event::Resized, Event::Raw]
     }
 
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
         use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
         use low::defs::{ES_AUTOHSCROLL, ES_READONLY, ES_PASSWORD, EM_LIMITTEXT};
-        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_BORDER};
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, WS_BORDER};
 
         let flags: DWORD = WS_CHILD | WS_BORDER | ES_AUTOHSCROLL | 
         if self.readonly { ES_READONLY } else { 0 } |
         if self.password { ES_PASSWORD } else { 0 } |
         if self.visible  { WS_VISIBLE }  else { 0 } |
-        if self.disabled { WS_DISABLED } else { 0 };
+        if self.disabled { WS_DISABLED } else { 0 } |
+        if self.tab_stop { WS_TABSTOP }  else { 0 } |
+        if self.group    { WS_GROUP }    else { 0 };
 
         // Get the parent handle
         let parent = match handle_of_window(ui, &self.parent, "The parent of a textinput must be a window-like control.") {
@@ -224,6 +230,15 @@ impl TextInput {
     }*/
     
 
+    /**
+        Set a function that transforms the clipboard text before it is pasted in the TextInput. Use `None` to remove the filter.  
+        For example, a filter can remove the control characters or normalize the line endings. A filter that returns
+        its input unchanged behaves like the default paste.
+    */
+    pub fn set_paste_filter(&self, filter: Option<fn(&str) -> String>) {
+        unsafe{ ::low::window_helper::set_window_paste_filter(self.handle, filter); }
+    }
+
     pub fn get_text(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
     pub fn set_text<'a>(&self, text: &'a str) { unsafe{ ::low::window_helper::set_window_text(self.handle, text); } }
     pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
@@ -0,0 +1,410 @@
+/*!
+    Tree view control definition
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::hash::Hash;
+use std::any::{TypeId, Any};
+use std::mem;
+use std::ptr;
+
+use user32::SendMessageW;
+use winapi::{HWND, HFONT, HTREEITEM, WPARAM, LPARAM, c_int};
+
+use ui::Ui;
+use controls::{Control, ControlT, ControlType, AnyHandle};
+use error::Error;
+use events::Event;
+use low::other_helper::{to_utf16, from_utf16};
+
+/**
+    Template that creates a tree view control. Requires the `tree-view` feature.
+
+    Available events:  
+    Event::Destroyed, Event::TreeItemSelected, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    Members:  
+    • `position`: The start position of the tree view  
+    • `size`: The start size of the tree view  
+    • `visible`: If the tree view should be visible to the user  
+    • `disabled`: If the user can or can't use the tree view  
+    • `tab_stop`: If the tree view can receive the keyboard focus with the TAB key  
+    • `group`: If the tree view is the first control of a group (`WS_GROUP`)  
+    • `lines`: If lines are drawn between the items  
+    • `buttons`: If a button to expand or collapse the items is drawn next to the items that have children  
+    • `parent`: The tree view parent  
+    • `font`: The tree view font. If None, use the system default  
+    • `auto_tooltip`: If a tooltip should show the full text of an item that goes past the tree view when the mouse is over it (replaces the tree view own tooltips)  
+*/
+#[derive(Clone)]
+pub struct TreeViewT<ID: Hash+Clone> {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
+    pub lines: bool,
+    pub buttons: bool,
+    pub parent: ID,
+    pub font: Option<ID>,
+    pub auto_tooltip: bool,
+}
+
+impl<ID: Hash+Clone> ControlT<ID> for TreeViewT<ID> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<TreeView>() }
+
+    fn events(&self) -> Vec<Event> {
+        vec![Event::Destroyed, Event::TreeItemSelected, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
+    }
+
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::{WindowParams, build_window, set_window_font, handle_of_window, handle_of_font};
+        use low::tooltip_helper::set_auto_tooltip;
+        use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_CHILD, WS_TABSTOP, WS_GROUP, WS_BORDER, TVS_HASLINES, TVS_LINESATROOT, TVS_HASBUTTONS,
+          TVS_SHOWSELALWAYS, TVS_NOTOOLTIPS};
+
+        let flags: DWORD = WS_CHILD | WS_BORDER | TVS_SHOWSELALWAYS |
+        if self.visible      { WS_VISIBLE }    else { 0 } |
+        if self.disabled     { WS_DISABLED }   else { 0 } |
+        if self.tab_stop     { WS_TABSTOP }    else { 0 } |
+        if self.group        { WS_GROUP }      else { 0 } |
+        if self.lines        { TVS_HASLINES | TVS_LINESATROOT } else { 0 } |
+        if self.buttons      { TVS_HASBUTTONS } else { 0 } |
+        if self.auto_tooltip { TVS_NOTOOLTIPS } else { 0 };
+
+        // Get the parent handle
+        let parent = match handle_of_window(ui, &self.parent, "The parent of a tree view must be a window-like control.") {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        // Get the font handle (if any)
+        let font_handle: Option<HFONT> = match self.font.as_ref() {
+            Some(font_id) => 
+                match handle_of_font(ui, &font_id, "The font of a tree view must be a font resource.") {
+                    Ok(h) => Some(h),
+                    Err(e) => { return Err(e); }
+                },
+            None => None
+        };
+
+        let params = WindowParams {
+            title: "",
+            class_name: "SysTreeView32",
+            position: self.position.clone(),
+            size: self.size.clone(),
+            flags: flags,
+            ex_flags: Some(0),
+            parent: parent
+        };
+
+        match unsafe{ build_window(params) } {
+            Ok(h) => {
+                unsafe{ set_window_font(h, font_handle, true); }
+
+                if self.auto_tooltip {
+                    if let Err(e) = unsafe{ set_auto_tooltip(h, parent, tree_view_truncated_text) } {
+                        unsafe{ ::user32::DestroyWindow(h); }
+                        return Err(e);
+                    }
+                }
+
+                Ok( Box::new(TreeView{handle: h}) )
+            },
+            Err(e) => Err(Error::System(e))
+        }
+    }
+}
+
+/**
+    An item in a tree view. The handle stays valid until the item is removed from the tree view.
+*/
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TreeItem(pub HTREEITEM);
+
+impl TreeItem {
+    /// Return true if the item handle is null (ex: the item could not be inserted)
+    pub fn is_null(&self) -> bool { self.0.is_null() }
+}
+
+/**
+    A control that displays a hierarchy of items. Each item can hold a value of any type.
+*/
+pub struct TreeView {
+    handle: HWND
+}
+
+impl TreeView {
+
+    /**
+        Add an item with the text `label` at the end of the children of `parent`. If `parent` is `None`, the item is added at the root.  
+        `data` is saved with the item and can be read with `item_data`. It is dropped when the item is removed.
+
+        Return the new item. The item is null if the system could not insert it.
+    */
+    pub fn insert_item<'a, T: 'static>(&self, parent: Option<TreeItem>, label: &'a str, data: Option<T>) -> TreeItem {
+        use winapi::{TVM_INSERTITEMW, TVINSERTSTRUCTW, TVI_ROOT, TVI_LAST, TVIF_TEXT, TVIF_PARAM};
+
+        let data: LPARAM = match data {
+            Some(d) => {
+                let data: Box<Box<Any>> = Box::new(Box::new(d));
+                Box::into_raw(data) as LPARAM
+            },
+            None => 0
+        };
+
+        let mut text = to_utf16(label);
+        let mut insert: TVINSERTSTRUCTW = unsafe{ mem::zeroed() };
+        insert.hParent = parent.map(|p| p.0).unwrap_or(TVI_ROOT);
+        insert.hInsertAfter = TVI_LAST;
+
+        unsafe {
+            let item = insert.item_mut();
+            item.mask = TVIF_TEXT | TVIF_PARAM;
+            item.pszText = text.as_mut_ptr();
+            item.lParam = data;
+        }
+
+        let handle: HTREEITEM = unsafe{ mem::transmute(SendMessageW(self.handle, TVM_INSERTITEMW, 0, mem::transmute(&insert))) };
+        if handle.is_null() {
+            unsafe{ free_item_data(data); }
+        }
+
+        TreeItem(handle)
+    }
+
+    /// Remove `item` and its children from the tree view. The data of the removed items is dropped.
+    pub fn remove_item(&self, item: TreeItem) {
+        use winapi::TVM_DELETEITEM;
+
+        unsafe {
+            self.free_data(item);
+            SendMessageW(self.handle, TVM_DELETEITEM, 0, mem::transmute(item.0));
+        }
+    }
+
+    /// Remove every item from the tree view
+    pub fn clear(&self) {
+        for item in self.children(None) {
+            self.remove_item(item);
+        }
+    }
+
+    /// Return the number of items in the tree view
+    pub fn len(&self) -> usize {
+        use winapi::TVM_GETCOUNT;
+        unsafe{ SendMessageW(self.handle, TVM_GETCOUNT, 0, 0) as usize }
+    }
+
+    /// Return the children of `item` or, if `item` is `None`, the items at the root.
+    pub fn children(&self, item: Option<TreeItem>) -> Vec<TreeItem> {
+        use winapi::{TVM_GETNEXTITEM, TVGN_CHILD, TVGN_NEXT, TVGN_ROOT};
+
+        let mut children = Vec::new();
+        let mut next: HTREEITEM = unsafe { match item {
+            Some(i) => mem::transmute(SendMessageW(self.handle, TVM_GETNEXTITEM, TVGN_CHILD, mem::transmute(i.0))),
+            None => mem::transmute(SendMessageW(self.handle, TVM_GETNEXTITEM, TVGN_ROOT, 0))
+        }};
+
+        while !next.is_null() {
+            children.push(TreeItem(next));
+            next = unsafe{ mem::transmute(SendMessageW(self.handle, TVM_GETNEXTITEM, TVGN_NEXT, mem::transmute(next))) };
+        }
+
+        children
+    }
+
+    /// Return the text of `item`
+    pub fn item_text(&self, item: TreeItem) -> String {
+        unsafe{ item_text(self.handle, item.0) }
+    }
+
+    /// Set the text of `item`
+    pub fn set_item_text<'a>(&self, item: TreeItem, text: &'a str) {
+        use winapi::{TVM_SETITEMW, TVITEMW, TVIF_TEXT, TVIF_HANDLE};
+
+        let mut text = to_utf16(text);
+        let mut tv_item: TVITEMW = unsafe{ mem::zeroed() };
+        tv_item.mask = TVIF_TEXT | TVIF_HANDLE;
+        tv_item.hItem = item.0;
+        tv_item.pszText = text.as_mut_ptr();
+
+        unsafe{ SendMessageW(self.handle, TVM_SETITEMW, 0, mem::transmute(&tv_item)); }
+    }
+
+    /**
+        Return a copy of the data saved with `item`.  
+        Return `None` if the item has no data or if the data is not of type `T`.
+    */
+    pub fn item_data<T: Clone+'static>(&self, item: TreeItem) -> Option<T> {
+        let data = unsafe{ self.item_param(item) };
+        if data == 0 {
+            return None;
+        }
+
+        let data: &Box<Any> = unsafe{ &*(data as *const Box<Any>) };
+        data.downcast_ref::<T>().map(|d| d.clone())
+    }
+
+    /// Show the children of `item`
+    pub fn expand(&self, item: TreeItem) {
+        use winapi::{TVM_EXPAND, TVE_EXPAND};
+        unsafe{ SendMessageW(self.handle, TVM_EXPAND, TVE_EXPAND, mem::transmute(item.0)); }
+    }
+
+    /// Hide the children of `item`
+    pub fn collapse(&self, item: TreeItem) {
+        use winapi::{TVM_EXPAND, TVE_COLLAPSE};
+        unsafe{ SendMessageW(self.handle, TVM_EXPAND, TVE_COLLAPSE, mem::transmute(item.0)); }
+    }
+
+    /// Return true if the children of `item` are visible
+    pub fn is_expanded(&self, item: TreeItem) -> bool {
+        use winapi::{TVM_GETITEMSTATE, TVIS_EXPANDED};
+        let state = unsafe{ SendMessageW(self.handle, TVM_GETITEMSTATE, mem::transmute(item.0), TVIS_EXPANDED as LPARAM) as u32 };
+        state & TVIS_EXPANDED == TVIS_EXPANDED
+    }
+
+    /// Return the selected item or None if no item is selected
+    pub fn selected_item(&self) -> Option<TreeItem> {
+        use winapi::{TVM_GETNEXTITEM, TVGN_CARET};
+
+        let item: HTREEITEM = unsafe{ mem::transmute(SendMessageW(self.handle, TVM_GETNEXTITEM, TVGN_CARET, 0)) };
+        if item.is_null() { None } else { Some(TreeItem(item)) }
+    }
+
+    /// Select `item`. If `item` is `None`, the selection is removed.
+    pub fn set_selected_item(&self, item: Option<TreeItem>) {
+        use winapi::{TVM_SELECTITEM, TVGN_CARET};
+
+        let item = item.map(|i| i.0).unwrap_or(ptr::null_mut());
+        unsafe{ SendMessageW(self.handle, TVM_SELECTITEM, TVGN_CARET, mem::transmute(item)); }
+    }
+
+    /// Return the `lParam` of `item`, where the item data is saved
+    unsafe fn item_param(&self, item: TreeItem) -> LPARAM {
+        use winapi::{TVM_GETITEMW, TVITEMW, TVIF_PARAM, TVIF_HANDLE};
+
+        let mut tv_item: TVITEMW = mem::zeroed();
+        tv_item.mask = TVIF_PARAM | TVIF_HANDLE;
+        tv_item.hItem = item.0;
+
+        if SendMessageW(self.handle, TVM_GETITEMW, 0, mem::transmute(&mut tv_item)) == 0 {
+            0
+        } else {
+            tv_item.lParam
+        }
+    }
+
+    /// Drop the data of `item` and of its children
+    unsafe fn free_data(&self, item: TreeItem) {
+        for child in self.children(Some(item)) {
+            self.free_data(child);
+        }
+
+        free_item_data(self.item_param(item));
+    }
+
+    pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
+    pub fn set_visibility(&self, visible: bool) { unsafe{ ::low::window_helper::set_window_visibility(self.handle, visible); }}
+    pub fn get_position(&self) -> (i32, i32) { unsafe{ ::low::window_helper::get_window_position(self.handle) } }
+    pub fn set_position(&self, x: i32, y: i32) { unsafe{ ::low::window_helper::set_window_position(self.handle, x, y); }}
+    pub fn get_size(&self) -> (u32, u32) { unsafe{ ::low::window_helper::get_window_size(self.handle) } }
+    pub fn set_size(&self, w: u32, h: u32) { unsafe{ ::low::window_helper::set_window_size(self.handle, w, h, false); } }
+    pub fn get_enabled(&self) -> bool { unsafe{ ::low::window_helper::get_window_enabled(self.handle) } }
+    pub fn set_enabled(&self, e:bool) { unsafe{ ::low::window_helper::set_window_enabled(self.handle, e); } }
+}
+
+impl Control for TreeView {
+
+    fn handle(&self) -> AnyHandle {
+        AnyHandle::HWND(self.handle)
+    }
+
+    fn control_type(&self) -> ControlType { 
+        ControlType::TreeView 
+    }
+
+    fn free(&mut self) {
+        use user32::DestroyWindow;
+
+        // The items data must be dropped before the items are destroyed
+        for item in self.children(None) {
+            unsafe{ self.free_data(item); }
+        }
+
+        unsafe{ DestroyWindow(self.handle) };
+    }
+
+}
+
+/// Drop the data saved in the `lParam` of an item
+unsafe fn item_text(handle: HWND, item: HTREEITEM) -> String {
+    use winapi::{TVM_GETITEMW, TVITEMW, TVIF_TEXT, TVIF_HANDLE};
+
+    let mut buffer_size: usize = 256;
+    loop {
+        let mut buffer: Vec<u16> = vec![0; buffer_size];
+
+        let mut tv_item: TVITEMW = mem::zeroed();
+        tv_item.mask = TVIF_TEXT | TVIF_HANDLE;
+        tv_item.hItem = item;
+        tv_item.pszText = buffer.as_mut_ptr();
+        tv_item.cchTextMax = buffer_size as c_int;
+
+        SendMessageW(handle, TVM_GETITEMW, 0, mem::transmute(&mut tv_item));
+
+        let length = buffer.iter().position(|&c| c == 0).unwrap_or(buffer_size);
+        if length + 1 < buffer_size {
+            return from_utf16(&buffer[0..length]);
+        }
+
+        // The text was truncated
+        buffer_size *= 2;
+    }
+}
+
+/// The text of an item is truncated if it goes past the right side of the tree view
+unsafe fn tree_view_truncated_text(handle: HWND, x: i32, y: i32) -> Option<String> {
+    use low::defs::TVHITTESTINFO;
+    use low::window_helper::get_window_client_rect;
+    use winapi::{POINT, RECT, TVM_HITTEST, TVM_GETITEMRECT, TVHT_ONITEM, TRUE};
+
+    let mut info = TVHITTESTINFO{ pt: POINT{x: x, y: y}, flags: 0, hItem: ptr::null_mut() };
+    SendMessageW(handle, TVM_HITTEST, 0, mem::transmute(&mut info));
+    if info.hItem.is_null() || info.flags & TVHT_ONITEM == 0 {
+        return None;
+    }
+
+    // The item handle is passed in the rect. With TRUE, the rect only covers the item text.
+    let mut rect: RECT = mem::zeroed();
+    *(&mut rect as *mut RECT as *mut HTREEITEM) = info.hItem;
+    if SendMessageW(handle, TVM_GETITEMRECT, TRUE as WPARAM, mem::transmute(&mut rect)) == 0 {
+        return None;
+    }
+
+    let (_, _, width, _) = get_window_client_rect(handle);
+    if rect.right > width as i32 { Some(item_text(handle, info.hItem)) } else { None }
+}
+
+unsafe fn free_item_data(data: LPARAM) {
+    if data != 0 {
+        drop(Box::from_raw(data as *mut Box<Any>));
+    }
+}
@@ -20,22 +20,40 @@
 
 use std::any::TypeId;
 use std::hash::Hash;
+use std::mem;
 
-use winapi::HWND;
+use winapi::{HWND, HBITMAP, HICON, GUID};
 
 use ui::Ui;
 use controls::{Control, ControlT, ControlType, AnyHandle};
+use resources::{Icon, Bitmap, Cursor, Resource};
+use defs::{BackgroundMode, WindowPlacement, PlacementState};
 use error::Error;
 use events::Event;
+use low::window_helper::{set_window_icon, get_window_icon};
 
 /// System class identifier
 const WINDOW_CLASS_NAME: &'static str = "NWG_BUILTIN_WINDOW";
 
+/// Name of the window property that holds the background image of a window
+const BACKGROUND_PROP_NAME: &'static str = "NWG_BACKGROUND_IMAGE";
+
 /**
     A template that will create a window.
 
     Events:  
-    Event::Destroyed, Event::KeyDown, Event::KeyUp, Event::Char, Event::Closed, Event::MouseDown, Event::MouseUp, Event::Moved, Event::Resized, Event::Raw
+    Event::Destroyed, Event::KeyDown, Event::KeyUp, Event::Char, Event::Closed, Event::Activated, Event::Deactivated, Event::AppActivation, Event::Suspended, Event::Resumed, Event::SessionLocked, Event::SessionUnlocked, Event::DeviceArrival, Event::DeviceRemoval, Event::DevicesChanged, Event::DisplayChanged, Event::WorkAreaChanged, Event::DpiChanged, Event::MouseDown, Event::MouseUp, Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw  
+
+    `Activated` and `Deactivated` receive `EventArgs::Activation`: `click` is true if the window was activated by a mouse click and `minimized`
+    is true if the window is minimized (a window that gets minimized is also deactivated). `AppActivation` receives `EventArgs::Focus(true)`
+    when one of the application windows becomes active and `EventArgs::Focus(false)` when the application loses the focus.
+    `SessionLocked` and `SessionUnlocked` are only raised after `Window::set_session_notifications(true)`.
+    `DeviceArrival` and `DeviceRemoval` receive `EventArgs::Device` with the device type of the notification. Volumes and ports are broadcast
+    to every top level window, device interfaces must be registered with `Window::register_device_notifications`.
+    `DisplayChanged` receives `EventArgs::DisplayMode` with the new resolution and color depth of the display.
+    `WorkAreaChanged` receives `EventArgs::WorkArea` with the new work area of the window monitor. A maximized window is resized to the new work area before the event.
+    `DpiChanged` receives `EventArgs::Dpi` with the new DPI of the window, its scale factor and the window rectangle suggested by the system.
+    It is only raised for a per monitor DPI aware application. The window is not resized automatically.
 
     Members:  
       • `title` : The title of the window (in the title bar)  
@@ -45,32 +63,58 @@ const WINDOW_CLASS_NAME: &'static str = "NWG_BUILTIN_WINDOW";
       • `visible` : If the user can see the window or not  
       • `disabled` : If the window is enabled or not. A disabled window do not process events  
       • `exit_on_close` : If NWG should break the event processing loop when this window is closed  
+      • `icon_big` : The icon displayed in the Alt+Tab dialog (32x32). Must be an icon resource  
+      • `icon_small` : The icon displayed in the title bar and in the taskbar (16x16). Must be an icon resource  
 */
 #[derive(Clone)]
-pub struct WindowT<S: Clone+Into<String>> {
+pub struct WindowT<S: Clone+Into<String>, ID: Hash+Clone> {
     pub title: S,
     pub position: (i32, i32),
     pub size: (u32, u32),
     pub resizable: bool,
     pub visible: bool,
     pub disabled: bool,
-    pub exit_on_close: bool
+    pub exit_on_close: bool,
+    pub icon_big: Option<ID>,
+    pub icon_small: Option<ID>
 }
 
-impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for WindowT<S> {
+impl<S: Clone+Into<String>, ID: Hash+Clone> ControlT<ID> for WindowT<S, ID> {
     fn resource_type_id(&self) -> TypeId { TypeId::of::<Window>() }
 
     fn events(&self) -> Vec<Event> {
-        vec![Event::Destroyed, Event::KeyDown, Event::KeyUp, Event::Char, Event::Closed, Event::MouseDown, Event::MouseUp,
-             Event::Moved, Event::Resized, Event::Raw]
+        vec![Event::Destroyed, Event::KeyDown, Event::KeyUp, Event::Char, Event::Closed, Event::Activated, Event::Deactivated,
+             Event::AppActivation, Event::Suspended, Event::Resumed, Event::SessionLocked, Event::SessionUnlocked, Event::DeviceArrival,
+             Event::DeviceRemoval, Event::DevicesChanged, Event::DisplayChanged, Event::WorkAreaChanged, Event::DpiChanged, Event::MouseDown, Event::MouseUp,
+             Event::MouseEnter, Event::MouseLeave, Event::MouseHover, Event::Moved, Event::Resized, Event::Raw]
     }
 
-    #[allow(unused_variables)]
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Control>, Error> {
+        use low::window_helper::handle_of_icon;
+        use winapi::{ICON_BIG, ICON_SMALL};
+
+        let mut icons: Vec<(usize, HICON)> = Vec::with_capacity(2);
+        for &(size, icon) in [(ICON_BIG, &self.icon_big), (ICON_SMALL, &self.icon_small)].iter() {
+            if let &Some(ref icon_id) = icon {
+                match handle_of_icon(ui, icon_id, "The icons of a window must be icon resources.") {
+                    Ok(h) => icons.push((size as usize, h)),
+                    Err(e) => { return Err(e); }
+                }
+            }
+        }
+
         unsafe{
             if let Err(e) = build_sysclass() { return Err(e); }
             match build_window(&self) {
-                Ok(h) => { Ok( Box::new(Window{handle: h}) as Box<Control> ) },
+                Ok(h) => { 
+                    for &(size, icon) in icons.iter() {
+                        if let Err(e) = set_window_icon(h, size, Some(icon)) {
+                            ::user32::DestroyWindow(h);
+                            return Err(Error::System(e));
+                        }
+                    }
+                    Ok( Box::new(Window{handle: h}) as Box<Control> ) 
+                },
                 Err(e) => Err(e)
             }
         } // unsafe
@@ -103,6 +147,313 @@ impl Window {
         SetForegroundWindow(self.handle); 
     } }
 
+    /**
+        Enable or disable `Event::SessionLocked` and `Event::SessionUnlocked` for this window.  
+        The window is unregistered automatically when it is destroyed. Return `false` if the system could not register the window.
+    */
+    pub fn set_session_notifications(&self, enabled: bool) -> bool {
+        unsafe{ ::low::window_helper::set_window_session_notifications(self.handle, enabled) }
+    }
+
+    /**
+        Raise `Event::DeviceArrival` and `Event::DeviceRemoval` for the device interfaces of `class`, or for every device interface if `class` is `None`.  
+        A previous registration is replaced and the window is unregistered automatically when it is destroyed.  
+        Return `false` if the system could not register the window.
+    */
+    pub fn register_device_notifications(&self, class: Option<&GUID>) -> bool {
+        unsafe{ ::low::window_helper::register_window_device_notifications(self.handle, class) }
+    }
+
+    /// Stop the device interface notifications registered with `register_device_notifications`
+    pub fn unregister_device_notifications(&self) {
+        unsafe{ ::low::window_helper::unregister_window_device_notifications(self.handle) }
+    }
+
+    /**
+        Set a small overlay icon over the window button in the taskbar. Use `None` to remove the current overlay.  
+        `description` is an accessibility text describing the status conveyed by the overlay.
+
+        The overlay is automatically reapplied if the taskbar is recreated (ex: if explorer restarts).
+    */
+    pub fn set_overlay_icon<'a>(&self, icon: Option<&Icon>, description: &'a str) -> Result<(), Error> {
+        use low::taskbar_helper::set_overlay_icon;
+
+        let icon_handle = match icon.map(|i| i.handle()) {
+            Some(AnyHandle::HICON(h)) => Some(h),
+            _ => None
+        };
+
+        unsafe{ set_overlay_icon(self.handle, icon_handle, description) }
+    }
+
+    /**
+        Draw the window with a 32 bits bitmap, using its alpha channel for the transparency. The window becomes a layered window
+        and is resized to the size of the bitmap. The color channels of the bitmap must be premultiplied by the alpha channel
+        (see `custom::premultiply_alpha`). `alpha` is the opacity applied to the whole window (255 is opaque).
+
+        Errors:  
+        • `Error::BadResource` if the bitmap is not a 32 bits bitmap  
+        • `Error::System` if the window could not be updated  
+    */
+    pub fn update_layered(&self, bitmap: &Bitmap, alpha: u8) -> Result<(), Error> {
+        use low::layered_helper::update_layered_window;
+
+        let bitmap_handle = match bitmap.handle() {
+            AnyHandle::HBITMAP(h) => h,
+            _ => unreachable!()
+        };
+
+        unsafe{ update_layered_window(self.handle, bitmap_handle, alpha) }
+    }
+
+    /**
+        Let the clicks go through the pixels of a layered window (see `update_layered`) with an alpha lower than `threshold`.
+        The hit test always uses the last bitmap drawn on the window. Use `None` to disable the hit test.
+    */
+    pub fn set_hit_test_threshold(&self, threshold: Option<u8>) {
+        unsafe{ ::low::layered_helper::set_layered_hit_threshold(self.handle, threshold); }
+    }
+
+    /// Return the hit test threshold of the window, if any
+    pub fn hit_test_threshold(&self) -> Option<u8> {
+        unsafe{ ::low::layered_helper::get_layered_hit_threshold(self.handle) }
+    }
+
+    /**
+        Draw an image in the background of the window. Use `None` to remove the current background image.  
+        A copy of the bitmap is kept by the window, so the bitmap resource can be freed after the call.
+
+        Setting a background image also sets the `WS_CLIPCHILDREN` style on the window so that the children are drawn over the image.
+    */
+    pub fn set_background_image(&self, image: Option<&Bitmap>, mode: BackgroundMode) {
+        use low::window_helper::{get_window_long, set_window_long};
+        use user32::InvalidateRect;
+        use winapi::{GWL_STYLE, WS_CLIPCHILDREN, TRUE};
+
+        let image_handle = match image.map(|i| i.handle()) {
+            Some(AnyHandle::HBITMAP(h)) => Some(h),
+            _ => None
+        };
+
+        unsafe{ 
+            set_background_image(self.handle, image_handle, mode);
+            if image_handle.is_some() {
+                let old_style = get_window_long(self.handle, GWL_STYLE) as usize;
+                set_window_long(self.handle, GWL_STYLE, old_style | (WS_CLIPCHILDREN as usize));
+            }
+            InvalidateRect(self.handle, ::std::ptr::null(), TRUE);
+        }
+    }
+
+    /**
+        Return the placement of the window. The returned value keeps the restored position and size of the window,
+        even if the window is currently minimized or maximized.
+    */
+    pub fn get_placement(&self) -> WindowPlacement {
+        use user32::GetWindowPlacement;
+        use winapi::{WINDOWPLACEMENT, UINT, SW_SHOWMINIMIZED, SW_SHOWMAXIMIZED, SW_SHOWMINNOACTIVE, SW_MINIMIZE};
+        use low::defs::WPF_RESTORETOMAXIMIZED;
+
+        let mut placement: WINDOWPLACEMENT = unsafe{ mem::zeroed() };
+        placement.length = mem::size_of::<WINDOWPLACEMENT>() as UINT;
+        unsafe{ GetWindowPlacement(self.handle, &mut placement); }
+
+        let state = match placement.showCmd as i32 {
+            SW_SHOWMINIMIZED | SW_SHOWMINNOACTIVE | SW_MINIMIZE => PlacementState::Minimized,
+            SW_SHOWMAXIMIZED => PlacementState::Maximized,
+            _ => PlacementState::Normal
+        };
+
+        let r = placement.rcNormalPosition;
+        WindowPlacement {
+            state: state,
+            position: (r.left, r.top),
+            size: ((r.right - r.left) as u32, (r.bottom - r.top) as u32),
+            restore_maximized: (placement.flags & WPF_RESTORETOMAXIMIZED) != 0
+        }
+    }
+
+    /**
+        Set the placement of the window. This can be used with `get_placement` to restore a window
+        to the place it was when the application was closed.
+    */
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        use user32::SetWindowPlacement;
+        use winapi::{WINDOWPLACEMENT, UINT, RECT, POINT, SW_SHOWNORMAL, SW_SHOWMINIMIZED, SW_SHOWMAXIMIZED};
+        use low::defs::WPF_RESTORETOMAXIMIZED;
+
+        let show = match placement.state {
+            PlacementState::Normal => SW_SHOWNORMAL,
+            PlacementState::Minimized => SW_SHOWMINIMIZED,
+            PlacementState::Maximized => SW_SHOWMAXIMIZED
+        };
+
+        let (x, y) = placement.position;
+        let (w, h) = placement.size;
+        let p = WINDOWPLACEMENT {
+            length: mem::size_of::<WINDOWPLACEMENT>() as UINT,
+            flags: if placement.restore_maximized { WPF_RESTORETOMAXIMIZED } else { 0 },
+            showCmd: show as UINT,
+            ptMinPosition: POINT{x: -1, y: -1},
+            ptMaxPosition: POINT{x: -1, y: -1},
+            rcNormalPosition: RECT{left: x, top: y, right: x + (w as i32), bottom: y + (h as i32)}
+        };
+
+        unsafe{ SetWindowPlacement(self.handle, &p); }
+    }
+
+    /// Return the show state of the window (`WINDOWPLACEMENT.showCmd`)
+    pub fn show_state(&self) -> PlacementState {
+        self.get_placement().state
+    }
+
+    /**
+        Minimize, maximize or restore the window. The window is shown if it was hidden.  
+        The restored position and size of the window are kept, see `normal_rect`.
+    */
+    pub fn set_show_state(&self, state: PlacementState) {
+        use user32::ShowWindow;
+        use winapi::{SW_SHOWNORMAL, SW_MINIMIZE, SW_MAXIMIZE};
+
+        let show = match state {
+            PlacementState::Normal => SW_SHOWNORMAL,
+            PlacementState::Minimized => SW_MINIMIZE,
+            PlacementState::Maximized => SW_MAXIMIZE
+        };
+
+        unsafe{ ShowWindow(self.handle, show); }
+    }
+
+    /**
+        Return the position and the size of the window when it is restored, even if the window is currently minimized or maximized.  
+        The position is in workspace coordinates.
+    */
+    pub fn normal_rect(&self) -> ((i32, i32), (u32, u32)) {
+        let placement = self.get_placement();
+        (placement.position, placement.size)
+    }
+
+    /**
+        Set the cursor displayed when the mouse is over the window. Use `None` to restore the default cursor.  
+        The window does not copy the cursor, so the cursor resource must not be freed while it is in use.
+    */
+    pub fn set_cursor(&self, cursor: Option<&Cursor>) {
+        let cursor_handle = match cursor.map(|c| c.handle()) {
+            Some(AnyHandle::HCURSOR(h)) => Some(h),
+            _ => None
+        };
+
+        unsafe{ ::low::window_helper::set_window_cursor(self.handle, cursor_handle); }
+    }
+
+    /**
+        Set the icon displayed in the Alt+Tab dialog (32x32). Use `None` to remove the icon.  
+        The window does not copy the icon, so the icon resource must not be freed while it is in use.
+
+        Errors:  
+        • `Error::System` if the window was destroyed or if the icon is not valid  
+    */
+    pub fn set_icon_big(&self, icon: Option<&Icon>) -> Result<(), Error> {
+        use winapi::ICON_BIG;
+        match unsafe{ set_window_icon(self.handle, ICON_BIG as usize, icon_handle(icon)) } {
+            Ok(()) => Ok(()),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+
+    /**
+        Set the icon displayed in the title bar and in the taskbar (16x16). Use `None` to remove the icon.  
+        The window does not copy the icon, so the icon resource must not be freed while it is in use.
+
+        Errors:  
+        • `Error::System` if the window was destroyed or if the icon is not valid  
+    */
+    pub fn set_icon_small(&self, icon: Option<&Icon>) -> Result<(), Error> {
+        use winapi::ICON_SMALL;
+        match unsafe{ set_window_icon(self.handle, ICON_SMALL as usize, icon_handle(icon)) } {
+            Ok(()) => Ok(()),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+
+    /**
+        Return the big icon of the window, if any. The returned icon is shared: freeing it does not destroy the window icon.
+
+        Errors:  
+        • `Error::System` if the window was destroyed  
+    */
+    pub fn get_icon_big(&self) -> Result<Option<Icon>, Error> {
+        use winapi::ICON_BIG;
+        match unsafe{ get_window_icon(self.handle, ICON_BIG as usize) } {
+            Ok(icon) => Ok(icon.map(|h| Icon::from_hicon(h, false))),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+
+    /**
+        Return the small icon of the window, if any. The returned icon is shared: freeing it does not destroy the window icon.
+
+        Errors:  
+        • `Error::System` if the window was destroyed  
+    */
+    pub fn get_icon_small(&self) -> Result<Option<Icon>, Error> {
+        use winapi::ICON_SMALL;
+        match unsafe{ get_window_icon(self.handle, ICON_SMALL as usize) } {
+            Ok(icon) => Ok(icon.map(|h| Icon::from_hicon(h, false))),
+            Err(e) => Err(Error::System(e))
+        }
+    }
+
+    /**
+        Set the time in milliseconds the mouse must stay still over the window before `Event::MouseHover` is raised.  
+        Use `None` (or `HOVER_DEFAULT`) to restore the system default.
+    */
+    pub fn set_hover_time(&self, time: Option<u32>) {
+        unsafe{ ::low::window_helper::set_window_hover_time(self.handle, time); }
+    }
+
+    /// Return the hover time of the window in milliseconds. Return `HOVER_DEFAULT` (0xFFFFFFFF) if the window uses the system default.
+    pub fn get_hover_time(&self) -> u32 {
+        unsafe{ ::low::window_helper::get_window_hover_time(self.handle) }
+    }
+
+    /**
+        Set the range, the position and the page size of the standard scroll bar of the window in a single call, so that the
+        scroll bar is only redrawn once. The scroll bar is shown if the range is greater than the page.  
+        If `vertical` is true, the vertical scroll bar is updated. Otherwise, the horizontal scroll bar is updated.
+
+        Errors:  
+        • `Error::UserError` if `min` is greater than `max`  
+    */
+    pub fn set_track_info(&self, vertical: bool, min: i32, max: i32, pos: i32, page: u32) -> Result<(), Error> {
+        use winapi::{SB_HORZ, SB_VERT};
+
+        if max < min {
+            let msg = "The scroll bar range maximum value must be greater or equal to the minimum value";
+            return Err(Error::UserError(msg.to_string()));
+        }
+
+        let bar = if vertical { SB_VERT } else { SB_HORZ };
+        unsafe{ ::low::window_helper::set_window_scroll_info(self.handle, bar, min, max, pos, page); }
+        Ok(())
+    }
+
+    /// Return the range, the position and the page size of a standard scroll bar of the window as `(min, max, pos, page)`
+    pub fn track_info(&self, vertical: bool) -> (i32, i32, i32, u32) {
+        use winapi::{SB_HORZ, SB_VERT};
+
+        let bar = if vertical { SB_VERT } else { SB_HORZ };
+        unsafe{ ::low::window_helper::get_window_scroll_info(self.handle, bar) }
+    }
+
+    /**
+        Set the title of the window, truncated to `max_length` UTF-16 units. The title is never cut in the middle of a character.  
+        If `ellipsis` is true and the title is truncated, it ends with `…`. Use it for long dynamic titles, like a file path.
+    */
+    pub fn set_title_truncated<'a>(&self, text: &'a str, max_length: usize, ellipsis: bool) {
+        unsafe{ ::low::window_helper::set_window_text_truncated(self.handle, text, max_length, ellipsis); }
+    }
+
     pub fn get_title(&self) -> String { unsafe{ ::low::window_helper::get_window_text(self.handle) } }
     pub fn set_title<'a>(&self, text: &'a str) { unsafe{ ::low::window_helper::set_window_text(self.handle, text); } }
     pub fn get_visibility(&self) -> bool { unsafe{ ::low::window_helper::get_window_visibility(self.handle) } }
@@ -141,9 +492,18 @@ use winapi::{UINT, WPARAM, LPARAM, LRESULT};
 
 #[allow(unused_variables)]
 unsafe extern "system" fn window_sysproc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
-    use winapi::{WM_CREATE, WM_CLOSE, GWL_USERDATA};
+    use winapi::{WM_CREATE, WM_CLOSE, WM_DESTROY, WM_ERASEBKGND, GWL_USERDATA, HDC};
     use user32::{DefWindowProcW, PostQuitMessage, ShowWindow};
     use low::window_helper::get_window_long;
+    use low::taskbar_helper::{taskbar_created_message, reapply_overlay_icon, free_overlay_icon};
+
+    if msg == taskbar_created_message() {
+        reapply_overlay_icon(hwnd);
+    }
+
+    if msg == WM_ERASEBKGND && draw_background_image(hwnd, w as HDC) {
+        return 1;
+    }
 
     let handled = match msg {
         WM_CREATE => true,
@@ -155,7 +515,12 @@ unsafe extern "system" fn window_sysproc(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
                 PostQuitMessage(0);
             }
             true
-        }
+        },
+        WM_DESTROY => {
+            free_overlay_icon(hwnd);
+            set_background_image(hwnd, None, BackgroundMode::Tile);
+            false
+        },
         _ => false
     };
 
@@ -166,6 +531,111 @@ unsafe extern "system" fn window_sysproc(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
     }
 }
 
+/// Return the raw handle of an optional icon resource
+fn icon_handle(icon: Option<&Icon>) -> Option<HICON> {
+    match icon.map(|i| i.handle()) {
+        Some(AnyHandle::HICON(h)) => Some(h),
+        _ => None
+    }
+}
+
+/**
+    Data saved on a window that has a background image
+*/
+struct BackgroundData {
+    image: HBITMAP,
+    mode: BackgroundMode
+}
+
+/**
+    Replace the background image saved on a window. The old image is freed.
+*/
+unsafe fn set_background_image(hwnd: HWND, image: Option<HBITMAP>, mode: BackgroundMode) {
+    use user32::{SetPropW, RemovePropW, CopyImage};
+    use gdi32::DeleteObject;
+    use winapi::IMAGE_BITMAP;
+    use low::other_helper::to_utf16;
+
+    let prop_name = to_utf16(BACKGROUND_PROP_NAME);
+    let old_data: *mut BackgroundData = mem::transmute(RemovePropW(hwnd, prop_name.as_ptr()));
+    if !old_data.is_null() {
+        let old_data = Box::from_raw(old_data);
+        DeleteObject(mem::transmute(old_data.image));
+    }
+
+    if let Some(image) = image {
+        let image_copy = CopyImage(mem::transmute(image), IMAGE_BITMAP, 0, 0, 0);
+        if !image_copy.is_null() {
+            let data = Box::into_raw(Box::new(BackgroundData{ image: mem::transmute(image_copy), mode: mode }));
+            SetPropW(hwnd, prop_name.as_ptr(), mem::transmute(data));
+        }
+    }
+}
+
+/**
+    Draw the background image of a window (if any) on `WM_ERASEBKGND`. Return `false` if the window has no background image.
+*/
+unsafe fn draw_background_image(hwnd: HWND, hdc: HDC) -> bool {
+    use user32::{GetPropW, GetClientRect, GetScrollPos, DefWindowProcW};
+    use gdi32::{CreateCompatibleDC, SelectObject, DeleteDC, BitBlt};
+    use winapi::{RECT, BITMAP, SRCCOPY, SB_HORZ, SB_VERT, WM_ERASEBKGND, c_int};
+    use low::defs::{GetObjectW, StretchBlt, SetStretchBltMode, HALFTONE};
+    use low::other_helper::to_utf16;
+
+    let prop_name = to_utf16(BACKGROUND_PROP_NAME);
+    let data: *mut BackgroundData = mem::transmute(GetPropW(hwnd, prop_name.as_ptr()));
+    if data.is_null() {
+        return false;
+    }
+
+    let data = &*data;
+
+    let mut bitmap: BITMAP = mem::zeroed();
+    GetObjectW(mem::transmute(data.image), mem::size_of::<BITMAP>() as c_int, mem::transmute(&mut bitmap));
+    let (bw, bh) = (bitmap.bmWidth as c_int, bitmap.bmHeight as c_int);
+    if bw == 0 || bh == 0 {
+        return false;
+    }
+
+    let mut rect: RECT = mem::uninitialized();
+    GetClientRect(hwnd, &mut rect);
+    let (cw, ch) = (rect.right - rect.left, rect.bottom - rect.top);
+
+    let src = CreateCompatibleDC(hdc);
+    let old_bitmap = SelectObject(src, mem::transmute(data.image));
+
+    match data.mode {
+        BackgroundMode::Stretch => {
+            SetStretchBltMode(hdc, HALFTONE);
+            StretchBlt(hdc, 0, 0, cw, ch, src, 0, 0, bw, bh, SRCCOPY);
+        },
+        BackgroundMode::Center => {
+            // Erase the area not covered by the image with the class background
+            DefWindowProcW(hwnd, WM_ERASEBKGND, hdc as WPARAM, 0);
+            BitBlt(hdc, (cw - bw) / 2, (ch - bh) / 2, bw, bh, src, 0, 0, SRCCOPY);
+        },
+        BackgroundMode::Tile => {
+            // Start the tiling from the scroll offset so that the tiles stay aligned with the content
+            let offset_x = GetScrollPos(hwnd, SB_HORZ) % bw;
+            let offset_y = GetScrollPos(hwnd, SB_VERT) % bh;
+            let mut y = -offset_y;
+            while y < ch {
+                let mut x = -offset_x;
+                while x < cw {
+                    BitBlt(hdc, x, y, bw, bh, src, 0, 0, SRCCOPY);
+                    x += bw;
+                }
+                y += bh;
+            }
+        }
+    }
+
+    SelectObject(src, old_bitmap);
+    DeleteDC(src);
+
+    true
+}
+
 #[inline(always)]
 unsafe fn build_sysclass() -> Result<(), Error> {
     use low::window_helper::{SysclassParams, build_sysclass};
@@ -183,7 +653,7 @@ unsafe fn build_sysclass() -> Result<(), Error> {
 }
 
 #[inline(always)]
-unsafe fn build_window<S: Clone+Into<String>>(t: &WindowT<S>) -> Result<HWND, Error> {
+unsafe fn build_window<S: Clone+Into<String>, ID: Hash+Clone>(t: &WindowT<S, ID>) -> Result<HWND, Error> {
     use low::window_helper::{WindowParams, build_window, set_window_long};
     use winapi::{DWORD, WS_VISIBLE, WS_DISABLED, WS_OVERLAPPEDWINDOW, WS_CAPTION, WS_OVERLAPPED, WS_MINIMIZEBOX,
       WS_MAXIMIZEBOX, WS_SYSMENU, GWL_USERDATA, WS_CLIPCHILDREN};
@@ -18,7 +18,10 @@
     along with this program.  If not, see <http://www.gnu.org/licenses/>.
 */
 
-use winapi::{c_int, DWORD, CW_USEDEFAULT};
+use winapi::{c_int, DWORD, UINT, CW_USEDEFAULT, DT_LEFT, DT_CENTER, DT_RIGHT, DT_TOP, DT_VCENTER, DT_BOTTOM, DT_WORDBREAK,
+  DT_SINGLELINE, DT_NOPREFIX, DT_END_ELLIPSIS};
+
+use low::defs::{DLGC_WANTARROWS, DLGC_WANTTAB, DLGC_WANTALLKEYS, DLGC_WANTCHARS};
 
 pub use winapi::SYSTEMTIME;
 
@@ -26,6 +29,7 @@ pub use winapi::SYSTEMTIME;
     Checkbox checkstate
 */
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CheckState {
     Checked,
     Unchecked,
@@ -104,6 +108,180 @@ pub enum MessageChoice {
     Yes
 }
 
+/**
+    What `keep_awake` prevents the system from doing
+
+    Members:  
+    * `System`: The system cannot sleep, but the display can turn off  
+    * `Display`: The system cannot sleep and the display stays on  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeepAwake {
+    System,
+    Display
+}
+
+/**
+    The DPI awareness of the windows created by a thread. See `dpi_awareness_context`.
+
+    Members:  
+    * `Unaware`: The windows are scaled by the system (bitmap stretching)  
+    * `SystemAware`: The windows are scaled using the DPI of the primary monitor when the user logged in  
+    * `PerMonitorAware`: The application scales its windows when the DPI of the monitor changes  
+    * `PerMonitorAwareV2`: Like `PerMonitorAware`, but the system also scales the non client area, the menus and the dialogs  
+    * `UnawareGdiScaled`: Like `Unaware`, but the GDI content is rendered at the monitor DPI  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DpiAwareness {
+    Unaware,
+    SystemAware,
+    PerMonitorAware,
+    PerMonitorAwareV2,
+    UnawareGdiScaled
+}
+
+/**
+    The edges of the parent client area that a control keeps at the same distance when the parent is resized.
+    See `AnyHandle::set_anchor`.
+
+    A control anchored to both the left and the right edges (or the top and the bottom edges) is stretched with its parent.
+    A control anchored to neither edge of an axis keeps its position on that axis. The default anchor is top left.
+
+    Members:  
+    * `left`: Keep the distance to the left edge  
+    * `top`: Keep the distance to the top edge  
+    * `right`: Keep the distance to the right edge  
+    * `bottom`: Keep the distance to the bottom edge  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Anchor {
+    pub left: bool,
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool
+}
+
+impl Default for Anchor {
+    fn default() -> Anchor {
+        Anchor{ left: true, top: true, right: false, bottom: false }
+    }
+}
+
+/**
+    The value of an input control saved by `Ui::form_state`.
+
+    With the `serde` feature, the values can be serialized in a settings file.
+
+    Members:  
+    * `Text`: The text of a text input or a text box  
+    * `Check`: The check state of a check box or a radio button  
+    * `Selection`: The selected indexes of a combo box or a list box. Empty if nothing is selected.  
+*/
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FormValue {
+    Text(String),
+    Check(CheckState),
+    Selection(Vec<usize>)
+}
+
+/**
+    A length resolved against the client area of the parent of a control. See `RelativePlacement`.
+
+    Members:  
+    * `Px`: A length in pixels  
+    * `Percent`: A percentage of the parent client width (for x and width) or height (for y and height). `50.0` is half the parent.  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Length {
+    Px(i32),
+    Percent(f32)
+}
+
+/**
+    The position and the size of a control relative to the client area of its parent. See `AnyHandle::set_relative_placement`.
+
+    Members:  
+    * `x`: The distance between the left edge of the parent and the control  
+    * `y`: The distance between the top edge of the parent and the control  
+    * `width`: The width of the control  
+    * `height`: The height of the control  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RelativePlacement {
+    pub x: Length,
+    pub y: Length,
+    pub width: Length,
+    pub height: Length
+}
+
+/**
+    The type of control created from a `ControlSpec`
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ControlKind {
+    Button,
+    Label,
+    TextInput,
+    TextBox,
+    CheckBox,
+    RadioButton,
+    GroupBox,
+    ComboBox,
+    ListBox
+}
+
+/**
+    A declarative description of a control used by `Ui::build_ui` to create many controls in one call.
+    The other members of the control template use their default values (same as the template macros).
+    The label and the group box controls ignore the `tab_stop` and `group` flags.
+    The combo box and the list box use each line of `text` as an item of their collection.
+
+    Members:  
+    * `kind`: The type of control to create  
+    * `id`: The id of the control in the ui  
+    * `text`: The text of the control  
+    * `position`: The position of the control in its parent  
+    * `size`: The size of the control  
+    * `visible`: If the control should be visible  
+    * `disabled`: If the control should be disabled  
+    * `tab_stop`: If the control can be selected using tab navigation  
+    * `group`: If the control is the first of a group of controls (see the template `group` member)  
+    * `placement`: The layout cell of the control in its parent. Overrides `position` and `size` once the control is created.  
+*/
+#[derive(Clone, Debug)]
+pub struct ControlSpec<ID: Clone> {
+    pub kind: ControlKind,
+    pub id: ID,
+    pub text: &'static str,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub visible: bool,
+    pub disabled: bool,
+    pub tab_stop: bool,
+    pub group: bool,
+    pub placement: Option<RelativePlacement>
+}
+
+/**
+    The edge of the parent client area where a control is docked. See `AnyHandle::set_dock`.
+
+    Members:  
+    * `Top`: The control keeps its height and takes the whole width at the top of the parent  
+    * `Bottom`: The control keeps its height and takes the whole width at the bottom of the parent  
+    * `Left`: The control keeps its width and takes the whole height at the left of the parent  
+    * `Right`: The control keeps its width and takes the whole height at the right of the parent  
+    * `Fill`: The control takes the space left by the other docked controls  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Dock {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Fill
+}
+
 /**
     A date struct that can be passed to a date time picker control.
 */
@@ -114,6 +292,57 @@ pub struct PickerDate {
     pub day: u16
 }
 
+/**
+    A date and time struct that can be passed to a `DateTimePicker` control.
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub struct PickerDateTime {
+    pub year: u16,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16
+}
+
+/**
+    Define what a `DateTimePicker` control edits
+
+    Members:  
+    * `Date`: The date. A calendar is displayed when the dropdown button is clicked  
+    * `Time`: The time. The value is edited with an up-down control  
+    * `DateTime`: The date and the time. A calendar is displayed when the dropdown button is clicked  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DateTimePickerFlags {
+    Date,
+    Time,
+    DateTime,
+}
+
+/**
+    The kind of scroll that raised a `Scroll` event on a scroll bar
+
+    Members:  
+    * `SmallInc`: The user clicked the right (or the down) arrow  
+    * `SmallDec`: The user clicked the left (or the up) arrow  
+    * `LargeInc`: The user clicked the scroll bar shaft after the thumb  
+    * `LargeDec`: The user clicked the scroll bar shaft before the thumb  
+    * `ThumbTrack`: The user is dragging the thumb  
+    * `ThumbPosition`: The user released the thumb after dragging it  
+    * `EndScroll`: The user released the mouse after a scroll. The value is not changed  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScrollType {
+    SmallInc,
+    SmallDec,
+    LargeInc,
+    LargeDec,
+    ThumbTrack,
+    ThumbPosition,
+    EndScroll,
+}
+
 /**
     A structure that defines how a messagebox should look and behave. 
 
@@ -131,6 +360,24 @@ pub struct MessageParams<'a> {
     pub icons: MessageIcons
 }
 
+/**
+    Extra options of a message box (to use with `message_ex`). `MessageOptions::default()` 
+    gives the same behaviour as `message`.
+
+    Members:  
+    * `default_button`: The button selected by default, from 1 to 3. Any other value selects the first button  
+    * `topmost`: If the message box should be created with the `WS_EX_TOPMOST` style  
+    * `right_align`: If the text of the message box should be right aligned  
+    * `lang`: The language of the text in the buttons. `0` uses the current language of the system  
+*/
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct MessageOptions {
+    pub default_button: u8,
+    pub topmost: bool,
+    pub right_align: bool,
+    pub lang: u16
+}
+
 /**
     A enum that dictates how a file dialog should behave
 
@@ -161,6 +408,236 @@ pub enum ProgressBarState {
     Error,
 }
 
+/**
+    Define the type of image displayed by a StaticImage control
+
+    Members:  
+    * `Bitmap`: The control displays a bitmap  
+    * `Icon`: The control displays an icon  
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub enum ImageType {
+    Bitmap,
+    Icon,
+}
+
+/**
+    Define how the items of a ListView control are displayed
+
+    Members:  
+    * `Icon`: Each item is displayed with a large icon and its label below  
+    * `SmallIcon`: Each item is displayed with a small icon and its label to the right  
+    * `List`: Like `SmallIcon`, but the items are arranged in columns  
+    * `Report`: Each item is displayed on its own row with its sub items in columns. The column headers are displayed  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ListViewStyle {
+    Icon,
+    SmallIcon,
+    List,
+    Report,
+}
+
+/**
+    Define how the characters of a font are smoothed
+
+    Members:  
+    * `Default`: Use the smoothing selected by the user in the system settings  
+    * `ClearType`: Smooth the characters using the subpixels of the screen. Gives the sharpest text on opaque surfaces  
+    * `Grayscale`: Smooth the characters with shades of gray. Use it to draw on a transparent or layered surface,
+      where ClearType produces colored fringes around the characters  
+    * `None`: Do not smooth the characters  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FontQuality {
+    Default,
+    ClearType,
+    Grayscale,
+    None,
+}
+
+/**
+    Define how a background image is drawn in a window
+
+    Members:  
+    * `Tile`: The image is repeated to fill the window  
+    * `Stretch`: The image is stretched to fill the window  
+    * `Center`: The image is drawn at the center of the window, using its original size  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BackgroundMode {
+    Tile,
+    Stretch,
+    Center,
+}
+
+/**
+    Define the direction of a gradient
+
+    Members:  
+    * `Horizontal`: The gradient goes from the left to the right  
+    * `Vertical`: The gradient goes from the top to the bottom  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GradientDirection {
+    Horizontal,
+    Vertical,
+}
+
+/**
+    The fonts used by the system to draw the non client area of the windows
+
+    Members:  
+    * `Caption`: The font of the window title bars  
+    * `SmallCaption`: The font of the small title bars (ex: tool windows)  
+    * `Menu`: The font of the menu bars  
+    * `Status`: The font of the status bars and tooltips  
+    * `Message`: The font of the message boxes. This is the font used by most native controls.  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SystemFont {
+    Caption,
+    SmallCaption,
+    Menu,
+    Status,
+    Message,
+}
+
+/**
+    The standard cursors of the system
+
+    Members:  
+    * `Arrow`: The standard arrow  
+    * `IBeam`: The text selection cursor  
+    * `Wait`: The hourglass  
+    * `Cross`: A crosshair  
+    * `UpArrow`: A vertical arrow  
+    * `SizeNWSE`: A double-pointed arrow pointing northwest and southeast  
+    * `SizeNESW`: A double-pointed arrow pointing northeast and southwest  
+    * `SizeWE`: A double-pointed arrow pointing west and east  
+    * `SizeNS`: A double-pointed arrow pointing north and south  
+    * `SizeAll`: A four-pointed arrow  
+    * `No`: A slashed circle  
+    * `Hand`: A hand, used over links  
+    * `AppStarting`: The standard arrow with a small hourglass  
+    * `Help`: The standard arrow with a question mark  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SystemCursor {
+    Arrow,
+    IBeam,
+    Wait,
+    Cross,
+    UpArrow,
+    SizeNWSE,
+    SizeNESW,
+    SizeWE,
+    SizeNS,
+    SizeAll,
+    No,
+    Hand,
+    AppStarting,
+    Help,
+}
+
+/**
+    A keyboard shortcut. Used by the HotkeyInput control.
+
+    Members:  
+    * `key`: The virtual key code of the shortcut  
+    * `ctrl`: If the control key must be pressed  
+    * `alt`: If the alt key must be pressed  
+    * `shift`: If the shift key must be pressed  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Shortcut {
+    pub key: u32,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+/**
+    Modifier combinations that can be forbidden in a HotkeyInput control
+
+    Members:  
+    * `None`: No modifiers  
+    * `Shift`: Shift only  
+    * `Ctrl`: Control only  
+    * `Alt`: Alt only  
+    * `ShiftCtrl`: Shift and control  
+    * `ShiftAlt`: Shift and alt  
+    * `CtrlAlt`: Control and alt  
+    * `ShiftCtrlAlt`: Shift, control and alt  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HotkeyCombination {
+    None,
+    Shift,
+    Ctrl,
+    Alt,
+    ShiftCtrl,
+    ShiftAlt,
+    CtrlAlt,
+    ShiftCtrlAlt,
+}
+
+/**
+    The show state of a window saved in a WindowPlacement
+
+    Members:  
+    * `Normal`: The window is neither minimized or maximized  
+    * `Minimized`: The window is minimized  
+    * `Maximized`: The window is maximized  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PlacementState {
+    Normal,
+    Minimized,
+    Maximized,
+}
+
+/**
+    The placement of a top level window. Unlike the window position and size, the placement
+    keeps the restored position and size of the window even if it is minimized or maximized.
+
+    With the `serde` feature, the placement can be serialized in a settings file.
+
+    Members:  
+    * `state`: The show state of the window  
+    * `position`: The position of the window when it is restored, in workspace coordinates  
+    * `size`: The size of the window when it is restored  
+    * `restore_maximized`: If a minimized window should be maximized when it is restored  
+*/
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowPlacement {
+    pub state: PlacementState,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub restore_maximized: bool,
+}
+
+/**
+    Presentation timing of the desktop compositor. Every time is a value of the performance counter (`QueryPerformanceCounter`).
+
+    Members:  
+    * `qpc_frequency`: The frequency of the performance counter in ticks per second  
+    * `refresh_period`: The number of ticks between two refreshes of the display  
+    * `last_vblank`: The time of the last vertical blank  
+    * `next_vblank`: The estimated time of the next vertical blank (`last_vblank + refresh_period`)  
+    * `last_compose`: The time of the last composition pass  
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub struct CompositionTiming {
+    pub qpc_frequency: u64,
+    pub refresh_period: u64,
+    pub last_vblank: u64,
+    pub next_vblank: u64,
+    pub last_compose: u64,
+}
+
 /**
     Define a rectangle shape that can be used with canvases
 */
@@ -276,6 +753,25 @@ pub const FONT_DECO_ITALIC: DWORD = 0x01;
 pub const FONT_DECO_UNDERLINE: DWORD = 0x02;
 pub const FONT_DECO_STRIKEOUT: DWORD = 0x04;
 
+// Text drawing flags (see `custom::draw_text`)
+pub const TEXT_LEFT: UINT = DT_LEFT;
+pub const TEXT_CENTER: UINT = DT_CENTER;
+pub const TEXT_RIGHT: UINT = DT_RIGHT;
+pub const TEXT_TOP: UINT = DT_TOP;
+pub const TEXT_VCENTER: UINT = DT_VCENTER;  // Only works with TEXT_SINGLE_LINE
+pub const TEXT_BOTTOM: UINT = DT_BOTTOM;    // Only works with TEXT_SINGLE_LINE
+pub const TEXT_WORD_BREAK: UINT = DT_WORDBREAK;
+pub const TEXT_SINGLE_LINE: UINT = DT_SINGLELINE;
+pub const TEXT_NO_PREFIX: UINT = DT_NOPREFIX;
+pub const TEXT_END_ELLIPSIS: UINT = DT_END_ELLIPSIS;
+
+// Keys that a canvas handles itself instead of the dialog navigation (see `Canvas::set_dialog_keys`)
+pub const DIALOG_KEYS_NONE: u32 = 0;
+pub const DIALOG_KEYS_ARROWS: u32 = DLGC_WANTARROWS;
+pub const DIALOG_KEYS_TAB: u32 = DLGC_WANTTAB;
+pub const DIALOG_KEYS_CHARS: u32 = DLGC_WANTCHARS;
+pub const DIALOG_KEYS_ALL: u32 = DLGC_WANTALLKEYS;
+
 pub mod keys {
     //! Windows virtual key code
     
@@ -29,13 +29,31 @@ use events::Event;
 pub enum SystemError {
     SystemClassCreation,
     WindowCreationFail,
+    ParentNotFound,
     UiCreation,
     FontCreation,
+    ImageCreation,
+    VisualStyles,
+    WindowSubclass,
+    AnimationOpen,
     ComInstanceCreation(String),
     ComError(String),
+    SystemCall{function: String, code: u32, message: String},
 }
 
 impl SystemError {
+
+    /**
+        Capture the last error raised by the system (`GetLastError`) right after the system function `function` failed.  
+        The system message is read immediately, so the error stays accurate even if it is only displayed later.
+    */
+    pub fn from_last_error<'a>(function: &'a str) -> SystemError {
+        use low::other_helper::get_system_error;
+
+        let (code, message) = unsafe{ get_system_error() };
+        SystemError::SystemCall{ function: function.to_string(), code: code as u32, message: message.trim().to_string() }
+    }
+
     fn translate(&self) -> String {
         use low::other_helper::get_system_error;
 
@@ -43,10 +61,19 @@ impl SystemError {
         let tr = match self {
             &SystemError::SystemClassCreation => format!("Failed to create a system class for a control"),
             &SystemError::WindowCreationFail => format!("Failed to create a system window for a control"),
+            &SystemError::ParentNotFound => format!("Failed to create a system window for a control, the parent window does not exist"),
             &SystemError::UiCreation => format!("The system could not initialize the Ui"),
             &SystemError::FontCreation => format!("Failed to create a system font"),
+            &SystemError::ImageCreation => format!("Failed to load a system image"),
+            &SystemError::VisualStyles => format!("Failed to activate a context that enables the visual styles"),
+            &SystemError::WindowSubclass => format!("Failed to subclass a system window"),
+            &SystemError::AnimationOpen => format!("Failed to open an animation clip. Only uncompressed or RLE8 AVI clips without sound are supported"),
             &SystemError::ComInstanceCreation(ref name) => format!("Failed to create a COM instance for {}", name),
             &SystemError::ComError(ref details) => format!("An error ocurred while executing a COM method, {}", details),
+            // The error was captured when it was raised
+            &SystemError::SystemCall{ref function, code, ref message} => {
+                return format!("{} failed: {} ({})", function, message, code);
+            }
         };
 
         format!("{}.\nID {:?} - {}", tr, code, code_txt)
@@ -103,6 +130,15 @@ impl Error {
     }
 }
 
+impl Error {
+
+    /// Shortcut for `Error::System(SystemError::from_last_error(function))`
+    pub fn from_last_error<'a>(function: &'a str) -> Error {
+        Error::System(SystemError::from_last_error(function))
+    }
+
+}
+
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.translate())
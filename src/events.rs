@@ -21,7 +21,8 @@
 use std::time::Duration;
 
 use ui::Ui;
-use defs::MouseButton;
+use controls::AnyHandle;
+use defs::{MouseButton, ScrollType};
 
 /**
 The function signature for the event callback
@@ -34,22 +35,73 @@ Arguments:
 */
 pub type EventCallback<ID> = Fn(&Ui<ID>, &ID, &Event, &EventArgs) -> ();
 
+/**
+    The control that raised an event, passed to the callbacks bound with `Ui::bind_typed`
+
+    Members:  
+    * `Control`: A reference to the control, if it has the type expected by the callback  
+    * `Handle`: The handle of the control, if it does not have the expected type or if it could not be borrowed  
+*/
+pub enum Sender<'a, T: 'a> {
+    Control(&'a T),
+    Handle(AnyHandle)
+}
+
+impl<'a, T: 'a> Sender<'a, T> {
+
+    /// Return the control if it has the type expected by the callback
+    pub fn control(&self) -> Option<&'a T> {
+        match self {
+            &Sender::Control(c) => Some(c),
+            &Sender::Handle(_) => None
+        }
+    }
+
+}
+
 /**
     Events name definition
+
+    When a control is unpacked, its children are removed with it in two passes:  
+    1. `DestroyNotice` is raised on the control, then on its children (parents before children). Every control of the
+       group is still alive, so custom controls can release their external resources (GL contexts, file handles) while their data is valid.  
+    2. `Destroyed` is raised on the children, then on the control (children before parents). Each control is freed right after
+       its callbacks return.  
+
+    The system `WM_DESTROY` message is sent when the window is freed, after both events. It is not dispatched to the callbacks.  
+    Every control supports `DestroyNotice`.
 */
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Event {
 
     // NWG special events
     Destroyed,
+    DestroyNotice,
 
     // System events
     KeyDown,
     KeyUp,
     Char,
     Closed,
+    Activated,
+    Deactivated,
+    AppActivation,
+    Suspended,
+    Resumed,
+    SessionLocked,
+    SessionUnlocked,
+    DeviceArrival,
+    DeviceRemoval,
+    DevicesChanged,
+    DisplayChanged,
+    WorkAreaChanged,
+    DpiChanged,
+    TreeItemSelected,
     MouseUp,
     MouseDown,
+    MouseEnter,
+    MouseLeave,
+    MouseHover,
     Moved,
     Resized,
     Paint,
@@ -66,7 +118,11 @@ pub enum Event {
     Focus,
     Tick,
     Triggered,
-    DateChanged
+    DateChanged,
+    ColumnClick,
+    TabChanged,
+    DateTimeChanged,
+    Scroll
 }
 
 /**
@@ -77,9 +133,17 @@ pub enum EventArgs {
     Char(char),
     MouseClick{btn: MouseButton, pos: (i32, i32)},
     Focus(bool),
+    Activation{click: bool, minimized: bool},
+    Device(u32),   // The device type of the notification (DBT_DEVTYP_*)
+    DisplayMode{size: (u32, u32), bits_per_pixel: u32},
+    WorkArea{position: (i32, i32), size: (u32, u32)},
+    Dpi{dpi: u32, scale: f32, position: (i32, i32), size: (u32, u32)},  // The suggested window rectangle in screen coordinates
     Tick(Duration),
     Position(i32, i32),
     Size(u32, u32),
+    Column(i32),
+    Delta{value: i32, delta: i32},  // The new value and the change
+    Scroll{kind: ScrollType, value: u32},
     Raw(u32, usize, usize), // MSG, WPARAM, LPARAM
     None
 }
\ No newline at end of file
@@ -1,5 +1,50 @@
 /*!
     A very high level native gui library for Windows.
+
+    Controls, resources, events and user values are packed in a `Ui` under an identifier chosen by the application. 
+    The identifier type is the generic parameter of `Ui<ID>` and only has to implement `Hash` and `Clone`. 
+    The simplest choice is a plain enum:
+
+    ```rust,ignore
+    #[macro_use] extern crate native_windows_gui as nwg;
+
+    use nwg::{Ui, Event};
+
+    #[derive(Debug, Clone, Hash)]
+    pub enum AppId {
+        MainWindow,
+        NameInput,
+        HelloButton,
+        Label(u8),   // Variants can hold data to create identifiers dynamically
+    }
+
+    fn main() {
+        let ui: Ui<AppId> = Ui::new().expect("Failed to initialize the Ui");
+        ui.pack_control(&AppId::MainWindow, nwg_window!());
+        ui.pack_control(&AppId::HelloButton, nwg_button!(parent=AppId::MainWindow; text="Hello"));
+        ui.commit().expect("Failed to create the controls");
+
+        nwg::dispatch_events();
+    }
+    ```
+
+    Strings (`&'static str`) and integers also work as identifiers, which is useful for quick prototypes or 
+    for user interfaces built at runtime.
+
+    With the `derive` feature, `#[derive(NwgId)]` (from the `native-windows-derive` crate) implements the traits
+    of an identifier on an enum without fields. The enum is also `Copy` and `Eq`:
+
+    ```rust,ignore
+    #[derive(NwgId)]
+    pub enum AppId { MainWindow, OkButton, NameField }
+
+    // Expands to:
+    impl Clone for AppId { fn clone(&self) -> AppId { *self } }
+    impl Copy for AppId {}
+    impl PartialEq for AppId { fn eq(&self, other: &AppId) -> bool { self.nwg_id_index() == other.nwg_id_index() } }
+    impl Eq for AppId {}
+    impl Hash for AppId { fn hash<H: Hasher>(&self, state: &mut H) { self.nwg_id_index().hash(state) } }
+    ```
 */
 /*
     Copyright (C) 2016  Gabriel Dubé
@@ -26,6 +71,25 @@ extern crate comctl32;
 extern crate gdi32;
 extern crate ole32;
 
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+#[cfg(feature = "raw-window-handle")]
+extern crate raw_window_handle;
+
+#[cfg(feature = "chrono")]
+extern crate chrono;
+
+#[cfg(feature = "derive")]
+extern crate native_windows_derive;
+
+#[cfg(feature = "derive")]
+pub use native_windows_derive::NwgId;
+
+#[macro_use]
+pub mod templates;
+
 mod low;
 mod defs;
 mod error;
@@ -34,18 +98,64 @@ mod controls;
 mod resources;
 mod ui;
 
-pub mod templates;
-
 pub mod custom {
     /*!
         Custom control creation resources
     */
     pub use controls::{ControlT, Control, AnyHandle};
     pub use resources::{ResourceT, Resource};
-    pub use low::window_helper::{build_window, build_sysclass, SysclassParams, WindowParams, set_window_long, get_window_long,
-    get_window_text, set_window_text, get_window_visibility, set_window_visibility, get_window_position, set_window_position,
-    get_window_size, set_window_size, get_window_enabled, set_window_enabled};
+    pub use low::window_helper::{build_window, build_sysclass, SysclassParams, WindowParams, NO_BACKGROUND_BRUSH, set_window_long, get_window_long,
+    get_window_text, set_window_text, set_window_text_truncated, get_window_visibility, set_window_visibility, get_window_position, set_window_position,
+    get_window_size, set_window_size, get_window_screen_rect, get_window_client_rect, get_window_enabled, set_window_enabled, get_window_parent, get_root_window, is_window_child_of,
+    dialog_units_to_pixels, set_window_scroll_info, get_window_scroll_info,
+    set_window_cursor, set_window_cursor_hidden, get_window_cursor_hidden, SubclassGuard,
+    set_window_user_data, window_user_data, clear_window_user_data, set_window_hover_time, get_window_hover_time,
+    set_window_session_notifications, register_window_device_notifications, unregister_window_device_notifications};
+    pub use low::events::{is_window_hooked, set_window_pre_translate, has_window_pre_translate, pre_translate_message};
+    pub use winapi::MSG;
+    pub use low::display_helper::{work_area, fit_maximized_window};
+    pub use low::layered_helper::{update_layered_window, set_layered_hit_threshold, get_layered_hit_threshold};
+    pub use low::tooltip_helper::{set_auto_tooltip, auto_tooltip_text, text_width, TruncatedText};
+    #[cfg(feature = "drag-drop")]
+    pub use low::drag_helper::drag_text;
+    pub use low::gdi_helper::{gradient_fill, draw_bitmap_alpha, premultiply_alpha, draw_text, draw_text_cached, cached_pen, cached_brush, cached_font,
+    clear_gdi_cache, gdi_cache_len};
+    pub use low::layout_helper::{set_window_anchor, get_window_anchor, update_anchored_children, set_window_dock, get_window_dock,
+    update_docked_children, set_window_relative_placement, get_window_relative_placement, update_relative_children,
+    update_window_layout, invalidate_window_layout, flush_window_layouts};
+
+}
+
+pub mod keyboard {
+    /*!
+        Keyboard state helpers
+    */
+    pub use low::keyboard_helper::{KeyModifiers, is_key_down, ctrl_down, shift_down, alt_down, win_key_down, current_key_modifiers};
+}
+
+pub mod mouse {
+    /*!
+        Mouse cursor position helpers and mouse settings
+    */
+    pub use low::mouse_helper::{cursor_position, cursor_client_position, is_cursor_inside, is_cursor_within_margin,
+      system_double_click_time, system_drag_threshold};
+}
 
+#[cfg(feature = "debug-messages")]
+pub mod debug {
+    /*!
+        Window messages logging. Requires the `debug-messages` feature.
+    */
+    pub use low::message_log::{MessageLogger, message_name, set_message_logger};
+}
+
+#[cfg(feature = "testing")]
+pub mod testing {
+    /*!
+        Simulated user input and layout counters for automated tests. Requires the `testing` feature.
+    */
+    pub use low::testing_helper::{click, type_text, send_key};
+    pub use low::layout_helper::get_window_layout_passes;
 }
 
 pub mod constants {
@@ -57,11 +167,22 @@ pub mod constants {
 }
 
 pub use error::{Error, SystemError};
-pub use events::{EventCallback, Event, EventArgs};
-pub use low::other_helper::{message, simple_message, fatal_message, error_message};
+pub use events::{EventCallback, Event, EventArgs, Sender};
+pub use low::other_helper::{message, message_ex, simple_message, fatal_message, error_message, enable_visual_styles,
+  enable_visual_styles_from_resource, keep_awake, keep_awake_state, allow_sleep, KeepAwakeGuard,
+  dpi_awareness_context, thread_dpi_awareness, DpiAwarenessContext, truncate_text};
 pub use controls::{WindowT, Window, MenuT, Menu, MenuItemT, MenuItem, ButtonT, Button, ListBoxT, ListBox, CheckBoxT, CheckBox,
  RadioButtonT, RadioButton, TimerT, Timer, LabelT, Label, ComboBoxT, ComboBox, SeparatorT, Separator, TextInputT, TextInput,
- FileDialogT, FileDialog, CanvasT, Canvas, CanvasRenderer, TextBoxT, TextBox, GroupBoxT, GroupBox, ProgressBarT, ProgressBar,
- DatePickerT, DatePicker};
-pub use resources::{FontT, Font};
-pub use ui::{Ui, dispatch_events, exit};
\ No newline at end of file
+ FileDialogT, FileDialog, CanvasT, Canvas, CanvasRenderer, CanvasState, TextBoxT, TextBox, GroupBoxT, GroupBox, ProgressBarT, ProgressBar,
+ DatePickerT, DatePicker, StaticImageT, StaticImage,
+ MultiColumnListBoxT, MultiColumnListBox, HotkeyInputT, HotkeyInput,
+ IpAddressInputT, IpAddressInput, AnimationT, Animation,
+ PagerT, Pager, RebarT, Rebar, MessageWindowT, MessageWindow, ListViewT, ListView,
+ TabControlT, TabControl, TabItemT, TabItem, RichTextBoxT, RichTextBox,
+ DateTimePickerT, DateTimePicker, MonthCalendarT, MonthCalendar,
+ NumericUpDownT, NumericUpDown, HorizontalScrollBarT, HorizontalScrollBar, VerticalScrollBarT, VerticalScrollBar};
+
+#[cfg(feature = "tree-view")]
+pub use controls::{TreeViewT, TreeView, TreeItem};
+pub use resources::{FontT, Font, SystemFontT, system_font, IconT, Icon, BitmapT, Bitmap, CursorT, Cursor};
+pub use ui::{Ui, dispatch_events, pump_until, exit};
\ No newline at end of file
@@ -20,4 +20,9 @@ define_guid!(CLSID_FileOpenDialog, 3692845724, 59530, 19934, [165, 161, 96, 248,
 define_guid!(CLSID_FileSaveDialog, 3233080051, 47649, 18291, [141, 186, 51, 94, 201, 70, 235, 139]);
 define_guid!(UUIDOF_IFileDialog, 1123569974, 56190, 17308, [133, 241, 228, 7, 93, 19, 95, 200]);
 define_guid!(UUIDOF_IFileOpenDialog, 3581702792, 54445, 18280, [190, 2, 157, 150, 149, 50, 217, 96]);
-define_guid!(IID_IShellItem, 1132621086, 59160, 17134, [188, 85, 161, 226, 97, 195, 123, 254]);
\ No newline at end of file
+define_guid!(IID_IShellItem, 1132621086, 59160, 17134, [188, 85, 161, 226, 97, 195, 123, 254]);
+define_guid!(CLSID_TaskbarList, 1459483460, 64877, 4560, [149, 138, 0, 96, 151, 201, 160, 144]);
+define_guid!(IID_ITaskbarList3, 3927636881, 40488, 19334, [144, 233, 158, 159, 138, 94, 239, 175]);
+define_guid!(IID_IDataObject, 270, 0, 0, [192, 0, 0, 0, 0, 0, 0, 70]);
+define_guid!(CLSID_DragDropHelper, 1180116874, 16667, 4562, [131, 154, 0, 192, 79, 217, 24, 208]);
+define_guid!(IID_IDragSourceHelper, 3730569094, 18298, 4562, [131, 157, 0, 192, 79, 217, 24, 208]);
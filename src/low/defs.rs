@@ -24,7 +24,9 @@ use std::hash::Hash;
 use std::any::{Any, TypeId};
 
 use winapi::{UINT, LRESULT, DWORD, HBRUSH, ULONG_PTR, HMENU, BOOL, c_int, MENUITEMINFOW, IShellItem, HRESULT, IUnknownVtbl,
- IUnknown, PCWSTR, IBindCtx, REFIID, D2D1_FACTORY_TYPE, D2D1_FACTORY_OPTIONS, ID2D1Factory,c_void};
+ IUnknown, PCWSTR, IBindCtx, REFIID, D2D1_FACTORY_TYPE, D2D1_FACTORY_OPTIONS, ID2D1Factory,c_void, HWND, HICON, LPCWSTR,
+ HDC, HGDIOBJ, LPVOID, ULONG, PVOID, TRIVERTEX,
+ HBITMAP, BLENDFUNCTION, BITMAPINFO, LOGFONTW, WORD, GUID, HANDLE, COLORREF, HPEN, DWORD_PTR, LONG, BYTE, WCHAR, HGLOBAL, POINT, HTREEITEM};
 use std::ops::{Deref, DerefMut};
 
 
@@ -44,6 +46,9 @@ pub const NWG_PACK_RESOURCE:     UINT = 0x405;  /// Message sent when packing a
 pub const NWG_TRIGGER:           UINT = 0x406;  /// Message sent when triggering an event
 pub const NWG_CUSTOM_MAX:        UINT = 0x407;  /// Maximum custom event value
 
+// Outside of the custom range, so `commit` never executes it. It is dispatched by the event loop once the current message returns.
+pub const NWG_DEFERRED_UNPACK:   UINT = 0x408;  /// Message sent when a control removal must wait for the current events to end
+
 // Value returned by a window proc if the message execution failed/succeeded
 
 pub const COMMIT_SUCCESS: LRESULT = 0;
@@ -65,8 +70,8 @@ pub const MF_BYPOSITION: UINT = 0x00000400;
 pub const MF_SEPARATOR: UINT = 0x00000800;
 
 pub const ACTCTX_FLAG_RESOURCE_NAME_VALID: u32 = 0x008;
-pub const ACTCTX_FLAG_SET_PROCESS_DEFAULT: u32 = 0x010;
 pub const ACTCTX_FLAG_ASSEMBLY_DIRECTORY_VALID: u32 = 0x004;
+pub const ACTCTX_FLAG_HMODULE_VALID: u32 = 0x080;
 
 pub const LB_ADDSTRING: UINT = 384;
 pub const LB_INSERTSTRING: UINT = 385;
@@ -83,11 +88,15 @@ pub const LB_FINDSTRING: UINT = 399;
 pub const LB_GETSELCOUNT: UINT = 400;
 pub const LB_GETSELITEMS: UINT = 401;
 pub const LB_FINDSTRINGEXACT: UINT = 418;
+pub const LB_SETTABSTOPS: UINT = 402;
+pub const LB_GETITEMRECT: UINT = 408;
+pub const LB_ITEMFROMPOINT: UINT = 425;
 
 pub const LBS_NOTIFY: UINT = 1;
 pub const LBS_NOSEL: UINT = 0x4000;
 pub const LBS_HASSTRINGS: UINT = 64;
 pub const LBS_MULTIPLESEL: UINT = 8;
+pub const LBS_USETABSTOPS: UINT = 128;
 
 pub const LBN_SELCHANGE: UINT = 1;
 pub const LBN_DBLCLK: UINT = 2;
@@ -111,6 +120,14 @@ pub const SS_RIGHT: UINT = 2;
 pub const SS_LEFT: UINT = 0;
 pub const SS_CENTER: UINT = 1;   
 pub const SS_NOPREFIX: UINT = 128;
+pub const SS_ENDELLIPSIS: UINT = 16384;
+pub const SS_ICON: UINT = 3;
+pub const SS_BITMAP: UINT = 14;
+pub const SS_TYPEMASK: UINT = 31;
+pub const SS_CENTERIMAGE: UINT = 512;
+
+pub const STM_SETIMAGE: UINT = 370;
+pub const STM_GETIMAGE: UINT = 371;
 
 pub const CBS_DROPDOWNLIST: UINT = 3;
 pub const CBS_HASSTRINGS: UINT = 512;
@@ -128,6 +145,10 @@ pub const CB_GETLBTEXTLEN: UINT = 329;
 pub const CB_SETCURSEL: UINT = 334;
 pub const CB_SHOWDROPDOWN: UINT = 335;
 pub const CB_SETCUEBANNER: UINT = 5891;
+pub const CB_SETMINVISIBLE: UINT = 5889;
+pub const CB_GETMINVISIBLE: UINT = 5890;
+pub const CB_SETDROPPEDWIDTH: UINT = 352;
+pub const CB_GETDROPPEDWIDTH: UINT = 351;
 
 pub const ES_AUTOHSCROLL: UINT = 128;
 pub const ES_AUTOVSCROLL: UINT = 64;
@@ -141,8 +162,46 @@ pub const EM_GETLIMITTEXT: UINT = 213;
 pub const EN_SETFOCUS: UINT = 256;
 pub const EN_KILLFOCUS: UINT = 512;
 pub const EN_UPDATE: UINT = 1024;
+pub const EN_CHANGE: UINT = 768;
 pub const EN_MAXTEXT: UINT = 1281;
 
+pub const WPF_RESTORETOMAXIMIZED: UINT = 2;
+
+pub const GA_ROOT: UINT = 2;
+
+pub const WA_INACTIVE: WORD = 0;
+pub const WA_CLICKACTIVE: WORD = 2;
+
+pub const PBT_APMSUSPEND: usize = 4;
+pub const PBT_APMRESUMEAUTOMATIC: usize = 18;
+pub const WTS_SESSION_LOCK: usize = 7;
+pub const WTS_SESSION_UNLOCK: usize = 8;
+pub const NOTIFY_FOR_THIS_SESSION: DWORD = 0;
+
+pub const ES_SYSTEM_REQUIRED: DWORD = 0x00000001;
+pub const ES_DISPLAY_REQUIRED: DWORD = 0x00000002;
+pub const ES_CONTINUOUS: DWORD = 0x80000000;
+
+// The thread DPI awareness functions only exist since Windows 10 1607 and are loaded at runtime
+pub type DPI_AWARENESS_CONTEXT = HANDLE;
+pub type SetThreadDpiAwarenessContextFn = unsafe extern "system" fn(DPI_AWARENESS_CONTEXT) -> DPI_AWARENESS_CONTEXT;
+pub type GetThreadDpiAwarenessContextFn = unsafe extern "system" fn() -> DPI_AWARENESS_CONTEXT;
+pub type AreDpiAwarenessContextsEqualFn = unsafe extern "system" fn(DPI_AWARENESS_CONTEXT, DPI_AWARENESS_CONTEXT) -> BOOL;
+pub type GetDpiForWindowFn = unsafe extern "system" fn(HWND) -> UINT;
+
+/// The DPI of a display at 100% scaling
+pub const USER_DEFAULT_SCREEN_DPI: u32 = 96;
+
+pub const DBT_DEVNODES_CHANGED: usize = 0x0007;
+pub const DBT_DEVICEARRIVAL: usize = 0x8000;
+pub const DBT_DEVICEREMOVECOMPLETE: usize = 0x8004;
+pub const DBT_DEVTYP_DEVICEINTERFACE: DWORD = 5;
+pub const DEVICE_NOTIFY_WINDOW_HANDLE: DWORD = 0;
+pub const DEVICE_NOTIFY_ALL_INTERFACE_CLASSES: DWORD = 4;
+
+pub const PW_CLIENTONLY: UINT = 1;
+pub const PW_RENDERFULLCONTENT: UINT = 2;
+
 pub const STN_CLICKED: UINT = 0;
 pub const STN_DBLCLK: UINT = 1;
 
@@ -158,9 +217,54 @@ pub const IDYES: i32 = 6;
 
 pub const SFGAO_FOLDER: u32 = 0x20000000;
 
+pub const HALFTONE: c_int = 4;
+
+pub const AC_SRC_OVER: u8 = 0x00;
+pub const AC_SRC_ALPHA: u8 = 0x01;
+
+pub const ULW_ALPHA: DWORD = 0x00000002;
+
 pub const STATE_SYSTEM_CHECKED: u32 = 0x10;
 pub const STATE_SYSTEM_INVISIBLE: u32 = 0x8000;
 
+pub const DLGC_WANTARROWS: u32 = 0x0001;
+pub const DLGC_WANTTAB: u32 = 0x0002;
+pub const DLGC_WANTALLKEYS: u32 = 0x0004;
+pub const DLGC_WANTCHARS: u32 = 0x0080;
+
+pub const SBS_HORZ: UINT = 0x0000;
+pub const SBS_VERT: UINT = 0x0001;
+
+pub const ES_NOHIDESEL: UINT = 0x100;
+pub const ES_WANTRETURN: UINT = 0x1000;
+
+pub const EM_EXGETSEL: UINT = 0x434;
+pub const EM_EXSETSEL: UINT = 0x437;
+pub const EM_GETCHARFORMAT: UINT = 0x43A;
+pub const EM_SETCHARFORMAT: UINT = 0x444;
+pub const EM_SETEVENTMASK: UINT = 0x445;
+pub const EM_STREAMIN: UINT = 0x449;
+pub const EM_STREAMOUT: UINT = 0x44A;
+
+pub const EN_SELCHANGE: u32 = 0x0702;
+pub const ENM_CHANGE: DWORD = 0x00000001;
+pub const ENM_SELCHANGE: DWORD = 0x00080000;
+
+pub const SF_TEXT: usize = 0x0001;
+pub const SF_RTF: usize = 0x0002;
+pub const SF_UNICODE: usize = 0x0010;
+
+pub const SCF_SELECTION: usize = 0x0001;
+
+pub const CFM_BOLD: DWORD = 0x00000001;
+pub const CFM_ITALIC: DWORD = 0x00000002;
+pub const CFM_UNDERLINE: DWORD = 0x00000004;
+pub const CFM_COLOR: DWORD = 0x40000000;
+pub const CFE_BOLD: DWORD = 0x00000001;
+pub const CFE_ITALIC: DWORD = 0x00000002;
+pub const CFE_UNDERLINE: DWORD = 0x00000004;
+pub const CFE_AUTOCOLOR: DWORD = 0x40000000;
+
 // System structs
 #[repr(C)]
 #[allow(non_snake_case)]
@@ -174,6 +278,170 @@ pub struct MENUINFO {
     pub dwMenuData: ULONG_PTR
 }
 
+#[repr(C)]
+#[allow(non_snake_case)]
+pub struct NONCLIENTMETRICSW {
+    pub cbSize: UINT,
+    pub iBorderWidth: c_int,
+    pub iScrollWidth: c_int,
+    pub iScrollHeight: c_int,
+    pub iCaptionWidth: c_int,
+    pub iCaptionHeight: c_int,
+    pub lfCaptionFont: LOGFONTW,
+    pub iSmCaptionWidth: c_int,
+    pub iSmCaptionHeight: c_int,
+    pub lfSmCaptionFont: LOGFONTW,
+    pub iMenuWidth: c_int,
+    pub iMenuHeight: c_int,
+    pub lfMenuFont: LOGFONTW,
+    pub lfStatusFont: LOGFONTW,
+    pub lfMessageFont: LOGFONTW,
+    pub iPaddedBorderWidth: c_int
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct UNSIGNED_RATIO {
+    pub uiNumerator: u32,
+    pub uiDenominator: u32
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct DWM_TIMING_INFO {
+    pub cbSize: u32,
+    pub rateRefresh: UNSIGNED_RATIO,
+    pub qpcRefreshPeriod: u64,
+    pub rateCompose: UNSIGNED_RATIO,
+    pub qpcVBlank: u64,
+    pub cRefresh: u64,
+    pub cDXRefresh: u32,
+    pub qpcCompose: u64,
+    pub cFrame: u64,
+    pub cDXPresent: u32,
+    pub cRefreshFrame: u64,
+    pub cFrameSubmitted: u64,
+    pub cDXPresentSubmitted: u32,
+    pub cFrameConfirmed: u64,
+    pub cDXPresentConfirmed: u32,
+    pub cRefreshConfirmed: u64,
+    pub cDXRefreshConfirmed: u32,
+    pub cFramesLate: u64,
+    pub cFramesOutstanding: u32,
+    pub cFrameDisplayed: u64,
+    pub qpcFrameDisplayed: u64,
+    pub cRefreshFrameDisplayed: u64,
+    pub cFrameComplete: u64,
+    pub qpcFrameComplete: u64,
+    pub cFramePending: u64,
+    pub qpcFramePending: u64,
+    pub cFramesDisplayed: u64,
+    pub cFramesComplete: u64,
+    pub cFramesPending: u64,
+    pub cFramesAvailable: u64,
+    pub cFramesDropped: u64,
+    pub cFramesMissed: u64,
+    pub cRefreshNextDisplayed: u64,
+    pub cRefreshNextPresented: u64,
+    pub cRefreshesDisplayed: u64,
+    pub cRefreshesPresented: u64,
+    pub cRefreshStarted: u64,
+    pub cPixelsReceived: u64,
+    pub cPixelsDrawn: u64,
+    pub cBuffersEmpty: u64
+}
+
+// The rich edit structures are declared with a 4 bytes packing (see `richedit.h`)
+pub type EDITSTREAMCALLBACK = unsafe extern "system" fn(DWORD_PTR, *mut BYTE, LONG, *mut LONG) -> DWORD;
+
+#[repr(C, packed(4))]
+pub struct EDITSTREAM {
+    pub dwCookie: DWORD_PTR,
+    pub dwError: DWORD,
+    pub pfnCallback: EDITSTREAMCALLBACK
+}
+
+#[repr(C, packed(4))]
+pub struct CHARFORMATW {
+    pub cbSize: UINT,
+    pub dwMask: DWORD,
+    pub dwEffects: DWORD,
+    pub yHeight: LONG,
+    pub yOffset: LONG,
+    pub crTextColor: COLORREF,
+    pub bCharSet: BYTE,
+    pub bPitchAndFamily: BYTE,
+    pub szFaceName: [WCHAR; 32]
+}
+
+#[repr(C)]
+#[allow(non_snake_case)]
+pub struct LVHITTESTINFO {
+    pub pt: POINT,
+    pub flags: UINT,
+    pub iItem: c_int,
+    pub iSubItem: c_int,
+    pub iGroup: c_int
+}
+
+#[repr(C)]
+#[allow(non_snake_case)]
+pub struct TVHITTESTINFO {
+    pub pt: POINT,
+    pub flags: UINT,
+    pub hItem: HTREEITEM
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CHARRANGE {
+    pub cpMin: LONG,
+    pub cpMax: LONG
+}
+
+#[repr(C)]
+pub struct DEV_BROADCAST_HDR {
+    pub dbch_size: DWORD,
+    pub dbch_devicetype: DWORD,
+    pub dbch_reserved: DWORD
+}
+
+#[repr(C)]
+pub struct DEV_BROADCAST_DEVICEINTERFACE_W {
+    pub dbcc_size: DWORD,
+    pub dbcc_devicetype: DWORD,
+    pub dbcc_reserved: DWORD,
+    pub dbcc_classguid: GUID,
+    pub dbcc_name: [u16; 1]
+}
+
+#[repr(C)]
+pub struct FORMATETC {
+    pub cfFormat: WORD,
+    pub ptd: *mut c_void,
+    pub dwAspect: DWORD,
+    pub lindex: LONG,
+    pub tymed: DWORD
+}
+
+// The union of the storage medium is reduced to the global memory handle, which has the size of the union
+#[repr(C)]
+pub struct STGMEDIUM {
+    pub tymed: DWORD,
+    pub hGlobal: HGLOBAL,
+    pub pUnkForRelease: *mut IUnknown
+}
+
+pub const DVASPECT_CONTENT: DWORD = 1;
+pub const TYMED_HGLOBAL: DWORD = 1;
+
+pub const DROPEFFECT_COPY: DWORD = 1;
+pub const DROPEFFECT_MOVE: DWORD = 2;
+pub const DRAGDROP_S_DROP: HRESULT = 0x00040100;
+pub const DRAGDROP_S_CANCEL: HRESULT = 0x00040101;
+
+pub const GMEM_MOVEABLE: UINT = 0x0002;
+
 // COM interfaces
 // Unused functions have an empty signature
 
@@ -273,6 +541,60 @@ interface IShellItemArray(IShellItemArrayVtbl): IUnknown(IUnknownVtbl) {
 }
 );
 
+RIDL!(
+interface ITaskbarList(ITaskbarListVtbl): IUnknown(IUnknownVtbl) {
+    fn HrInit(&mut self) -> HRESULT,
+    fn AddTab(&mut self) -> (),
+    fn DeleteTab(&mut self) -> (),
+    fn ActivateTab(&mut self) -> (),
+    fn SetActiveAlt(&mut self) -> ()
+}
+);
+
+RIDL!(
+interface ITaskbarList2(ITaskbarList2Vtbl): ITaskbarList(ITaskbarListVtbl) {
+    fn MarkFullscreenWindow(&mut self) -> ()
+}
+);
+
+RIDL!(
+interface ITaskbarList3(ITaskbarList3Vtbl): ITaskbarList2(ITaskbarList2Vtbl) {
+    fn SetProgressValue(&mut self) -> (),
+    fn SetProgressState(&mut self) -> (),
+    fn RegisterTab(&mut self) -> (),
+    fn UnregisterTab(&mut self) -> (),
+    fn SetTabOrder(&mut self) -> (),
+    fn SetTabActive(&mut self) -> (),
+    fn ThumbBarAddButtons(&mut self) -> (),
+    fn ThumbBarUpdateButtons(&mut self) -> (),
+    fn ThumbBarSetImageList(&mut self) -> (),
+    fn SetOverlayIcon(&mut self, hwnd: HWND, hIcon: HICON, pszDescription: LPCWSTR) -> HRESULT,
+    fn SetThumbnailTooltip(&mut self) -> (),
+    fn SetThumbnailClip(&mut self) -> ()
+}
+);
+
+RIDL!(
+interface IDataObject(IDataObjectVtbl): IUnknown(IUnknownVtbl) {
+    fn GetData(&mut self) -> (),
+    fn GetDataHere(&mut self) -> (),
+    fn QueryGetData(&mut self) -> (),
+    fn GetCanonicalFormatEtc(&mut self) -> (),
+    fn SetData(&mut self, pformatetc: *mut FORMATETC, pmedium: *mut STGMEDIUM, fRelease: BOOL) -> HRESULT,
+    fn EnumFormatEtc(&mut self) -> (),
+    fn DAdvise(&mut self) -> (),
+    fn DUnadvise(&mut self) -> (),
+    fn EnumDAdvise(&mut self) -> ()
+}
+);
+
+RIDL!(
+interface IDragSourceHelper(IDragSourceHelperVtbl): IUnknown(IUnknownVtbl) {
+    fn InitializeFromBitmap(&mut self) -> (),
+    fn InitializeFromWindow(&mut self, hwnd: HWND, ppt: *mut POINT, pDataObject: *mut IDataObject) -> HRESULT
+}
+);
+
 // System extern
 extern "system" {
     pub fn GetMenuItemCount(menu: HMENU) -> c_int;
@@ -283,7 +605,25 @@ extern "system" {
     pub fn SetMenuItemInfoW(hMenu: HMENU, uItem: UINT, gByPosition: BOOL, lpmii: &mut MENUITEMINFOW) -> BOOL;
     pub fn GetMenuItemInfoW(hMenu: HMENU, uItem: UINT, gByPosition: BOOL, lpmii: &mut MENUITEMINFOW) -> BOOL;
 
+    pub fn StretchBlt(hdcDest: HDC, xDest: c_int, yDest: c_int, wDest: c_int, hDest: c_int, hdcSrc: HDC, xSrc: c_int, ySrc: c_int, wSrc: c_int, hSrc: c_int, rop: DWORD) -> BOOL;
+    pub fn SetStretchBltMode(hdc: HDC, mode: c_int) -> c_int;
+    pub fn GetObjectW(h: HGDIOBJ, c: c_int, pv: LPVOID) -> c_int;
+    pub fn AlphaBlend(hdcDest: HDC, xDest: c_int, yDest: c_int, wDest: c_int, hDest: c_int, hdcSrc: HDC, xSrc: c_int, ySrc: c_int, wSrc: c_int, hSrc: c_int, ftn: BLENDFUNCTION) -> BOOL;
+    pub fn SetDIBits(hdc: HDC, hbm: HBITMAP, start: UINT, cLines: UINT, lpBits: *const c_void, lpbmi: *const BITMAPINFO, ColorUse: UINT) -> c_int;
+    pub fn GradientFill(hdc: HDC, pVertex: *const TRIVERTEX, nVertex: ULONG, pMesh: PVOID, nMesh: ULONG, ulMode: ULONG) -> BOOL;
+    pub fn CreatePen(iStyle: c_int, cWidth: c_int, color: COLORREF) -> HPEN;
+
+    pub fn DwmGetCompositionTimingInfo(hwnd: HWND, pTimingInfo: *mut DWM_TIMING_INFO) -> HRESULT;
+
+    pub fn WTSRegisterSessionNotification(hWnd: HWND, dwFlags: DWORD) -> BOOL;
+    pub fn WTSUnRegisterSessionNotification(hWnd: HWND) -> BOOL;
+
     pub fn SHCreateItemFromParsingName(pszPath: PCWSTR, pbc: *mut IBindCtx, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT;
+    pub fn SHCreateDataObject(pidlFolder: *const c_void, cidl: UINT, apidl: *const c_void, pdtInner: *mut IDataObject, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT;
+    pub fn SHDoDragDrop(hwnd: HWND, pdata: *mut IDataObject, pdsrc: *mut c_void, dwEffect: DWORD, pdwEffect: *mut DWORD) -> HRESULT;
+
+    pub fn OleInitialize(pvReserved: LPVOID) -> HRESULT;
+    pub fn OleUninitialize();
 
     pub fn D2D1CreateFactory(
         factoryType: D2D1_FACTORY_TYPE,
@@ -0,0 +1,157 @@
+/*!
+    Helper functions to query the display refresh rate and the compositor timing
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::mem;
+use std::ptr;
+
+use winapi::HWND;
+
+use defs::CompositionTiming;
+use low::defs::DWM_TIMING_INFO;
+use error::{Error, SystemError};
+
+/// Refresh rate returned when neither the display driver nor the compositor can tell
+const DEFAULT_REFRESH_RATE: f64 = 60.0;
+
+/**
+    Return the refresh rate in Hz of the monitor that displays the largest part of the window.
+
+    The rate is read from the current display mode (`EnumDisplaySettingsW`). If the driver reports the hardware default,
+    the rate of the desktop compositor is returned instead, and if that fails too, 60.0 is returned.
+
+    On a variable refresh rate display, this is the maximum rate of the current mode, not the instant rate.
+*/
+pub unsafe fn refresh_rate(handle: HWND) -> f64 {
+    use user32::{MonitorFromWindow, GetMonitorInfoW, EnumDisplaySettingsW};
+    use winapi::{MONITOR_DEFAULTTONEAREST, MONITORINFOEXW, LPMONITORINFO, DEVMODEW, ENUM_CURRENT_SETTINGS, DWORD, WORD};
+
+    let monitor = MonitorFromWindow(handle, MONITOR_DEFAULTTONEAREST);
+
+    let mut info: MONITORINFOEXW = mem::zeroed();
+    info.cbSize = mem::size_of::<MONITORINFOEXW>() as DWORD;
+
+    if GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as LPMONITORINFO) != 0 {
+        let mut mode: DEVMODEW = mem::zeroed();
+        mode.dmSize = mem::size_of::<DEVMODEW>() as WORD;
+
+        // A frequency of 0 or 1 means "hardware default"
+        if EnumDisplaySettingsW(info.szDevice.as_ptr(), ENUM_CURRENT_SETTINGS, &mut mode) != 0 && mode.dmDisplayFrequency > 1 {
+            return mode.dmDisplayFrequency as f64;
+        }
+    }
+
+    match timing_info() {
+        Ok(ref t) if t.rateRefresh.uiDenominator != 0 => (t.rateRefresh.uiNumerator as f64) / (t.rateRefresh.uiDenominator as f64),
+        _ => DEFAULT_REFRESH_RATE
+    }
+}
+
+/**
+    Return the work area (the monitor area minus the taskbars and the docked toolbars) of the monitor that displays
+    the largest part of the window. The area is in screen coordinates: (x, y, width, height).
+*/
+pub unsafe fn work_area(handle: HWND) -> (i32, i32, u32, u32) {
+    use user32::{MonitorFromWindow, GetMonitorInfoW};
+    use winapi::{MONITOR_DEFAULTTONEAREST, MONITORINFOEXW, LPMONITORINFO, DWORD};
+
+    let monitor = MonitorFromWindow(handle, MONITOR_DEFAULTTONEAREST);
+
+    let mut info: MONITORINFOEXW = mem::zeroed();
+    info.cbSize = mem::size_of::<MONITORINFOEXW>() as DWORD;
+    GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as LPMONITORINFO);
+
+    let r = info.rcWork;
+    (r.left, r.top, (r.right-r.left) as u32, (r.bottom-r.top) as u32)
+}
+
+/**
+    Resize a maximized top level window so that it covers the work area of its monitor again. Called when the work area
+    changes (ex: the taskbar is moved or starts to auto hide).  
+
+    Windows that are not maximized, child windows and windows without a caption (ex: a fullscreen window that covers the whole monitor)
+    are not modified. Returns `true` if the window was resized.
+*/
+pub unsafe fn fit_maximized_window(handle: HWND) -> bool {
+    use user32::{IsZoomed, AdjustWindowRectEx, SetWindowPos};
+    use winapi::{RECT, DWORD, GWL_STYLE, GWL_EXSTYLE, WS_CHILD, WS_CAPTION, SWP_NOZORDER, SWP_NOACTIVATE};
+    use low::window_helper::get_window_long;
+
+    let style = get_window_long(handle, GWL_STYLE) as DWORD;
+    if IsZoomed(handle) == 0 || style & WS_CHILD == WS_CHILD || style & WS_CAPTION != WS_CAPTION {
+        return false;
+    }
+
+    // The resize frame of a maximized window is outside of the work area
+    let ex_style = get_window_long(handle, GWL_EXSTYLE) as DWORD;
+    let mut frame = RECT{left: 0, top: 0, right: 0, bottom: 0};
+    AdjustWindowRectEx(&mut frame, style & !WS_CAPTION, 0, ex_style);
+
+    let (x, y, w, h) = work_area(handle);
+    SetWindowPos(handle, ptr::null_mut(), x + frame.left, y + frame.top, 
+      (w as i32) - frame.left + frame.right, (h as i32) - frame.top + frame.bottom, SWP_NOZORDER|SWP_NOACTIVATE);
+
+    true
+}
+
+/**
+    Return the presentation timing of the desktop compositor.  
+    The compositor runs at the refresh rate of the primary monitor, so the timing may not match a window on another monitor.
+
+    Errors:  
+    • `Error::System` if the compositor is disabled or if the timing could not be read  
+*/
+pub fn composition_timing() -> Result<CompositionTiming, Error> {
+    use kernel32::QueryPerformanceFrequency;
+    use winapi::LARGE_INTEGER;
+
+    let info = match timing_info() {
+        Ok(info) => info,
+        Err(e) => { return Err(e); }
+    };
+
+    let mut frequency: LARGE_INTEGER = 0;
+    unsafe{ QueryPerformanceFrequency(&mut frequency); }
+
+    Ok(CompositionTiming {
+        qpc_frequency: frequency as u64,
+        refresh_period: info.qpcRefreshPeriod,
+        last_vblank: info.qpcVBlank,
+        next_vblank: info.qpcVBlank + info.qpcRefreshPeriod,
+        last_compose: info.qpcCompose,
+    })
+}
+
+fn timing_info() -> Result<DWM_TIMING_INFO, Error> {
+    use low::defs::DwmGetCompositionTimingInfo;
+    use winapi::S_OK;
+    use std::ptr;
+
+    unsafe {
+        let mut info: DWM_TIMING_INFO = mem::zeroed();
+        info.cbSize = mem::size_of::<DWM_TIMING_INFO>() as u32;
+
+        let result = DwmGetCompositionTimingInfo(ptr::null_mut(), &mut info);
+        if result == S_OK {
+            Ok(info)
+        } else {
+            let msg = format!("Could not read the composition timing (0x{:X})", result);
+            Err(Error::System(SystemError::ComError(msg)))
+        }
+    }
+}
@@ -0,0 +1,145 @@
+/*!
+    Low level drag and drop helping functions. Requires the `drag-drop` feature.
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::ptr;
+use std::mem;
+
+use winapi::{HWND, S_OK, S_FALSE};
+
+use low::defs::{IDataObject, IDragSourceHelper};
+use low::other_helper::to_utf16;
+use error::{Error, SystemError};
+
+/**
+    Create a shell data object holding `text` in the `CF_UNICODETEXT` format. The caller must release the data object.
+*/
+unsafe fn create_text_data<'a>(text: &'a str) -> Result<*mut IDataObject, Error> {
+    use kernel32::{GlobalAlloc, GlobalLock, GlobalUnlock, GlobalFree};
+    use winapi::{CF_UNICODETEXT, WORD, TRUE, SIZE_T};
+    use low::defs::{SHCreateDataObject, FORMATETC, STGMEDIUM, DVASPECT_CONTENT, TYMED_HGLOBAL, GMEM_MOVEABLE};
+    use low::clsid::IID_IDataObject;
+
+    let mut data: *mut IDataObject = ptr::null_mut();
+    let r = SHCreateDataObject(ptr::null(), 0, ptr::null(), ptr::null_mut(), &IID_IDataObject(), mem::transmute(&mut data));
+    if r != S_OK {
+        return Err(Error::System(SystemError::ComInstanceCreation("DataObject".to_string())));
+    }
+
+    let text = to_utf16(text);
+    let size = text.len() * mem::size_of::<u16>();
+    let memory = GlobalAlloc(GMEM_MOVEABLE, size as SIZE_T);
+    if memory.is_null() {
+        (&mut *data).Release();
+        return Err(Error::from_last_error("GlobalAlloc"));
+    }
+
+    let dest = GlobalLock(memory);
+    ptr::copy_nonoverlapping(text.as_ptr() as *const u8, dest as *mut u8, size);
+    GlobalUnlock(memory);
+
+    let mut format = FORMATETC { cfFormat: CF_UNICODETEXT as WORD, ptd: ptr::null_mut(), dwAspect: DVASPECT_CONTENT, lindex: -1, tymed: TYMED_HGLOBAL };
+    let mut medium = STGMEDIUM { tymed: TYMED_HGLOBAL, hGlobal: memory, pUnkForRelease: ptr::null_mut() };
+
+    // With `fRelease`, the data object owns the memory if the call succeeds
+    if (&mut *data).SetData(&mut format, &mut medium, TRUE) != S_OK {
+        GlobalFree(memory);
+        (&mut *data).Release();
+        return Err(Error::System(SystemError::ComError("Failed to set the text of the data object".to_string())));
+    }
+
+    Ok(data)
+}
+
+/**
+    Ask the window `handle` for its drag image (the system `DI_GETDRAGIMAGE` message) and attach it to `data`.
+    The list view and the tree view controls answer with a ghost of their selected items. Windows that do not
+    answer get a generic drag image.
+*/
+unsafe fn attach_drag_image(handle: HWND, data: *mut IDataObject) -> Result<(), Error> {
+    use ole32::CoCreateInstance;
+    use user32::{GetCursorPos, ScreenToClient};
+    use winapi::{CLSCTX_INPROC_SERVER, POINT};
+    use low::clsid::{CLSID_DragDropHelper, IID_IDragSourceHelper};
+
+    let mut helper: *mut IDragSourceHelper = ptr::null_mut();
+    let r = CoCreateInstance(&CLSID_DragDropHelper(), ptr::null_mut(), CLSCTX_INPROC_SERVER, &IID_IDragSourceHelper(), mem::transmute(&mut helper));
+    if r != S_OK {
+        return Err(Error::System(SystemError::ComInstanceCreation("DragDropHelper".to_string())));
+    }
+
+    let mut point = POINT{ x: 0, y: 0 };
+    GetCursorPos(&mut point);
+    ScreenToClient(handle, &mut point);
+
+    let r = (&mut *helper).InitializeFromWindow(handle, &mut point, data);
+    (&mut *helper).Release();
+
+    if r == S_OK {
+        Ok(())
+    } else {
+        Err(Error::System(SystemError::ComError("Failed to create the drag image".to_string())))
+    }
+}
+
+/**
+    Start an OLE drag and drop operation from the window `handle` with `text` as the dragged data (`CF_UNICODETEXT`).
+    The drag image is taken from the window (see `IDragSourceHelper::InitializeFromWindow`), so a list view shows
+    a ghost of its selected rows. The dragged text can be copied or moved.
+
+    The function only returns once the operation is done. Returns `Ok(true)` if the text was dropped on a target
+    and `Ok(false)` if the operation was canceled.
+
+    Errors:  
+    • `Error::System(SystemError::ComInstanceCreation(..))` if the data object or the drag image helper could not be created  
+    • `Error::System(SystemError::ComError(..))` if OLE could not be initialized or if the drag and drop operation failed  
+*/
+pub unsafe fn drag_text<'a>(handle: HWND, text: &'a str) -> Result<bool, Error> {
+    use winapi::DWORD;
+    use low::defs::{OleInitialize, OleUninitialize, SHDoDragDrop, DROPEFFECT_COPY, DROPEFFECT_MOVE, DRAGDROP_S_DROP, DRAGDROP_S_CANCEL};
+
+    // NWG initializes COM, but the OLE drag and drop also requires OLE
+    let r = OleInitialize(ptr::null_mut());
+    if r != S_OK && r != S_FALSE {
+        return Err(Error::System(SystemError::ComError("Failed to initialize OLE".to_string())));
+    }
+
+    let result = match create_text_data(text) {
+        Ok(data) => {
+            let result = match attach_drag_image(handle, data) {
+                Ok(()) => {
+                    let mut effect: DWORD = 0;
+                    match SHDoDragDrop(handle, data, ptr::null_mut(), DROPEFFECT_COPY | DROPEFFECT_MOVE, &mut effect) {
+                        DRAGDROP_S_DROP => Ok(effect != 0),
+                        DRAGDROP_S_CANCEL => Ok(false),
+                        _ => Err(Error::System(SystemError::ComError("The drag and drop operation failed".to_string())))
+                    }
+                },
+                Err(e) => Err(e)
+            };
+
+            (&mut *data).Release();
+            result
+        },
+        Err(e) => Err(e)
+    };
+
+    OleUninitialize();
+
+    result
+}
@@ -22,8 +22,12 @@ use std::mem;
 use std::ptr;
 use std::hash::Hash;
 use std::any::TypeId;
+use std::time::{Duration, Instant};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
-use winapi::{HWND, HMENU, UINT, WPARAM, LPARAM, UINT_PTR, DWORD_PTR, LRESULT, DWORD};
+use winapi::{HWND, HMENU, UINT, WPARAM, LPARAM, UINT_PTR, DWORD_PTR, LRESULT, DWORD, NMHDR, MSG};
 
 use ui::UiInner;
 use events::{Event, EventArgs};
@@ -32,6 +36,17 @@ use controls::{ControlType, AnyHandle, Timer};
 /// A magic number to identify the NWG subclass that dispatches events
 const EVENTS_DISPATCH_ID: UINT_PTR = 2465;
 
+/// A magic number to identify the NWG subclass that removes the pre-translate hook of a destroyed window
+const PRE_TRANSLATE_ID: UINT_PTR = 2466;
+
+/// A function that receives the messages of a window before they are translated. Returns `true` if the message was handled.
+type PreTranslateHook = Rc<Fn(&MSG) -> bool>;
+
+thread_local! {
+    /// The pre-translate hooks of the windows of this thread (see `set_window_pre_translate`)
+    static PRE_TRANSLATE_HOOKS: RefCell<HashMap<usize, PreTranslateHook>> = RefCell::new(HashMap::new());
+}
+
 // WARNING! This WHOLE section (from parse_listbox_command to parse_command) will be replaced with the events overhaul in NWG BETA2
 
 fn parse_listbox_command(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
@@ -65,6 +80,24 @@ fn parse_edit_command(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
   }
 }
 
+fn parse_richedit_command(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
+  use low::defs::{EN_SETFOCUS, EN_KILLFOCUS, EN_CHANGE, EN_MAXTEXT};
+  match ncode {
+    EN_CHANGE => Some((id, Event::ValueChanged, EventArgs::None)),
+    EN_MAXTEXT => Some((id, Event::LimitReached, EventArgs::None)),
+    EN_SETFOCUS | EN_KILLFOCUS => Some((id, Event::Focus, EventArgs::Focus(ncode==EN_SETFOCUS))),
+    _ => None
+  }
+}
+
+fn parse_hotkey_command(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
+  use low::defs::EN_CHANGE;
+  match ncode {
+    EN_CHANGE => Some((id, Event::ValueChanged, EventArgs::None)),
+    _ => None
+  }
+}
+
 fn parse_static_command(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
   use low::defs::{STN_CLICKED, STN_DBLCLK};
   match ncode {
@@ -84,13 +117,148 @@ fn parse_datepicker_command(id: u64, ncode: u32) -> Option<(u64, Event, EventArg
   }
 }
 
+fn parse_listview_notify(id: u64, nmdr: &NMHDR) -> Option<(u64, Event, EventArgs)> {
+  use winapi::{LVN_ITEMCHANGED, LVN_COLUMNCLICK, LVIF_STATE, LVIS_SELECTED, NMLISTVIEW};
+  match nmdr.code {
+    LVN_ITEMCHANGED => {
+      // Only raise the event when an item gets selected. Otherwise, the event would also be raised for the unselected item.
+      let nmlv: &NMLISTVIEW = unsafe{ mem::transmute(nmdr) };
+      let selected_changed = (nmlv.uChanged & LVIF_STATE) != 0 && ((nmlv.uNewState ^ nmlv.uOldState) & LVIS_SELECTED) != 0;
+      if selected_changed && (nmlv.uNewState & LVIS_SELECTED) != 0 {
+        Some((id, Event::SelectionChanged, EventArgs::None))
+      } else {
+        None
+      }
+    },
+    LVN_COLUMNCLICK => {
+      let nmlv: &NMLISTVIEW = unsafe{ mem::transmute(nmdr) };
+      Some((id, Event::ColumnClick, EventArgs::Column(nmlv.iSubItem as i32)))
+    },
+    _ => None
+  }
+}
+
+fn parse_ipaddress_notify(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
+  use winapi::IPN_FIELDCHANGED;
+  match ncode {
+    IPN_FIELDCHANGED => Some((id, Event::ValueChanged, EventArgs::None)),
+    _ => None
+  }
+}
+
+fn parse_pager_notify(id: u64, nmdr: &NMHDR) -> Option<(u64, Event, EventArgs)> {
+  use winapi::{PGN_SCROLL, PGN_CALCSIZE, NMPGCALCSIZE};
+  use controls::pager::calc_pager_size;
+  match nmdr.code {
+    PGN_CALCSIZE => {
+      // The pager expects its parent to fill the size of the child
+      unsafe{ calc_pager_size(&mut *(nmdr as *const NMHDR as *mut NMPGCALCSIZE)); }
+      None
+    },
+    PGN_SCROLL => Some((id, Event::ValueChanged, EventArgs::None)),
+    _ => None
+  }
+}
+
+fn parse_rebar_notify(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
+  use winapi::RBN_LAYOUTCHANGED;
+  match ncode {
+    RBN_LAYOUTCHANGED => Some((id, Event::ValueChanged, EventArgs::None)),
+    _ => None
+  }
+}
+
+fn parse_treeview_notify(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
+  use winapi::TVN_SELCHANGEDW;
+  match ncode {
+    TVN_SELCHANGEDW => Some((id, Event::TreeItemSelected, EventArgs::None)),
+    _ => None
+  }
+}
+
+fn parse_datetimepicker_notify(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
+  use winapi::DTN_DATETIMECHANGE;
+  match ncode {
+    DTN_DATETIMECHANGE => Some((id, Event::DateTimeChanged, EventArgs::None)),
+    _ => None
+  }
+}
+
+fn parse_monthcalendar_notify(id: u64, ncode: u32) -> Option<(u64, Event, EventArgs)> {
+  use winapi::MCN_SELCHANGE;
+  match ncode {
+    MCN_SELCHANGE => Some((id, Event::SelectionChanged, EventArgs::None)),
+    _ => None
+  }
+}
+
+fn parse_updown_notify(id: u64, nmdr: &NMHDR) -> Option<(u64, Event, EventArgs)> {
+  use winapi::{UDN_DELTAPOS, NMUPDOWN};
+  use controls::numeric_updown::updown_delta;
+  match nmdr.code {
+    UDN_DELTAPOS => {
+      let data: &NMUPDOWN = unsafe{ mem::transmute(nmdr) };
+      let value = unsafe{ updown_delta(nmdr.hwndFrom, data.iPos, data.iDelta) };
+      Some((id, Event::ValueChanged, EventArgs::Delta{value: value, delta: data.iDelta}))
+    },
+    _ => None
+  }
+}
+
+fn parse_tabcontrol_notify(id: u64, nmdr: &NMHDR) -> Option<(u64, Event, EventArgs)> {
+  use winapi::TCN_SELCHANGE;
+  use controls::tab_control::tab_selection_changed;
+  match nmdr.code {
+    TCN_SELCHANGE => {
+      // The page of the new tab must be displayed before the callbacks are called
+      unsafe{ tab_selection_changed(nmdr.hwndFrom); }
+      Some((id, Event::TabChanged, EventArgs::None))
+    },
+    _ => None
+  }
+}
+
+fn parse_richedit_notify(id: u64, nmdr: &NMHDR) -> Option<(u64, Event, EventArgs)> {
+  use low::defs::EN_SELCHANGE;
+  match nmdr.code {
+    EN_SELCHANGE => Some((id, Event::SelectionChanged, EventArgs::None)),
+    _ => None
+  }
+}
+
 /**
-  Parse the common controls notification passed through the `WM_COMMAND` message.
+  Parse the scroll request of a scroll bar control passed through the `WM_HSCROLL` or `WM_VSCROLL` message.
+*/
+fn parse_scroll(id: u64, control_type: ControlType, handle: HWND, w: WPARAM) -> Option<(u64, Event, EventArgs)> {
+  use winapi::{LOWORD, c_int};
+  use controls::scroll_bar::scroll_bar_scrolled;
+
+  match control_type {
+    ControlType::ScrollBar => match unsafe{ scroll_bar_scrolled(handle, LOWORD(w as DWORD) as c_int) } {
+      Some((kind, value)) => Some((id, Event::Scroll, EventArgs::Scroll{kind: kind, value: value})),
+      None => None
+    },
+    _ => None
+  }
+}
+
+/**
+  Parse the common controls notification passed through the `WM_NOTIFY` message.
 */
 #[inline(always)]
-fn parse_notify(id: u64, control_type: ControlType, w: WPARAM) -> Option<(u64, Event, EventArgs)> {
+fn parse_notify(id: u64, control_type: ControlType, nmdr: &NMHDR) -> Option<(u64, Event, EventArgs)> {
   match control_type {
-    ControlType::DatePicker => parse_datepicker_command(id, w as u32),
+    ControlType::DatePicker => parse_datepicker_command(id, nmdr.code as u32),
+    ControlType::MultiColumnListBox | ControlType::ListView => parse_listview_notify(id, nmdr),
+    ControlType::IpAddressInput => parse_ipaddress_notify(id, nmdr.code as u32),
+    ControlType::Pager => parse_pager_notify(id, nmdr),
+    ControlType::Rebar => parse_rebar_notify(id, nmdr.code as u32),
+    ControlType::TreeView => parse_treeview_notify(id, nmdr.code as u32),
+    ControlType::TabControl => parse_tabcontrol_notify(id, nmdr),
+    ControlType::RichTextBox => parse_richedit_notify(id, nmdr),
+    ControlType::DateTimePicker => parse_datetimepicker_notify(id, nmdr.code as u32),
+    ControlType::MonthCalendar => parse_monthcalendar_notify(id, nmdr.code as u32),
+    ControlType::NumericUpDown => parse_updown_notify(id, nmdr),
     _ => None
   }
 }
@@ -107,7 +275,9 @@ fn parse_command(id: u64, control_type: ControlType, w: WPARAM) -> Option<(u64,
     ControlType::ListBox => parse_listbox_command(id, ncode),
     ControlType::Button => parse_button_command(id, ncode),
     ControlType::TextInput | ControlType::TextBox => parse_edit_command(id, ncode),
-    ControlType::Label => parse_static_command(id, ncode),
+    ControlType::RichTextBox => parse_richedit_command(id, ncode),
+    ControlType::HotkeyInput => parse_hotkey_command(id, ncode),
+    ControlType::Label | ControlType::StaticImage => parse_static_command(id, ncode),
     ControlType::DatePicker => parse_datepicker_command(id, ncode),
     _ => None
   }
@@ -118,17 +288,84 @@ fn parse_command(id: u64, control_type: ControlType, w: WPARAM) -> Option<(u64,
 */
 #[allow(unused_variables)]
 unsafe extern "system" fn process_events<ID: Hash+Clone+'static>(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, id: UINT_PTR, data: DWORD_PTR) -> LRESULT {
-  use comctl32::DefSubclassProc;
+  use comctl32::{DefSubclassProc, RemoveWindowSubclass};
   use user32::GetClientRect;
   use winapi::{WM_KEYDOWN, WM_KEYUP, WM_UNICHAR, WM_CHAR, UNICODE_NOCHAR, WM_MENUCOMMAND, WM_CLOSE, WM_LBUTTONUP, WM_LBUTTONDOWN, 
-    WM_RBUTTONUP, WM_RBUTTONDOWN, WM_MBUTTONUP, WM_MBUTTONDOWN, WM_COMMAND, WM_TIMER, WM_MOVE, WM_SIZING, WM_EXITSIZEMOVE, WM_SIZE,
-    WM_PAINT, WM_NOTIFY, c_int, LOWORD, HIWORD, RECT, NMHDR};
+    WM_RBUTTONUP, WM_RBUTTONDOWN, WM_MBUTTONUP, WM_MBUTTONDOWN, WM_COMMAND, WM_HSCROLL, WM_VSCROLL, WM_TIMER, WM_MOVE, WM_SIZING, WM_EXITSIZEMOVE, WM_SIZE,
+    WM_PAINT, WM_NOTIFY, WM_SETCURSOR, WM_DESTROY, WM_MOUSEMOVE, WM_MOUSELEAVE, WM_MOUSEHOVER, WM_PASTE, WM_ACTIVATE, WM_ACTIVATEAPP, WM_POWERBROADCAST, WM_WTSSESSION_CHANGE, WM_DEVICECHANGE, WM_DISPLAYCHANGE, WM_SETTINGCHANGE, SPI_SETWORKAREA, WM_DPICHANGED, WM_THEMECHANGED, WM_SYSCOLORCHANGE, WM_NCDESTROY, WM_NCHITTEST, HTCLIENT, HTTRANSPARENT, c_int, LOWORD, HIWORD, RECT, NMHDR,
+    GET_X_LPARAM, GET_Y_LPARAM};
+  use user32::SetCursor;
   use low::menu_helper::get_menu_id;
-  use low::defs::{NWG_CUSTOM_MIN, NWG_CUSTOM_MAX};
+  use low::window_helper::{get_window_cursor, set_window_cursor, get_window_cursor_hidden, set_window_cursor_hidden, clear_window_user_data, start_mouse_tracking, stop_mouse_tracking, 
+    set_window_hover_time, set_window_paste_filter, filtered_paste, set_window_session_notifications, unregister_window_device_notifications};
+  use low::defs::{NWG_CUSTOM_MIN, NWG_CUSTOM_MAX, WA_INACTIVE, WA_CLICKACTIVE, PBT_APMSUSPEND, PBT_APMRESUMEAUTOMATIC,
+    WTS_SESSION_LOCK, WTS_SESSION_UNLOCK, DBT_DEVICEARRIVAL, DBT_DEVICEREMOVECOMPLETE, DBT_DEVNODES_CHANGED, DEV_BROADCAST_HDR};
+  use low::layout_helper::{clear_window_layout, invalidate_window_layout, flush_window_layouts};
+  use low::layered_helper::{layered_hit_transparent, free_layered_window};
+  use controls::canvas::update_canvas_activation;
 
   let inner: &mut UiInner<ID> = mem::transmute(data);
   let inner_id: u64;
 
+  #[cfg(feature = "debug-messages")]
+  ::low::message_log::log_message(hwnd, msg, w, l);
+
+  // The window is being destroyed without being unpacked (ex: by the system). The subclass is removed to free its record.
+  if msg == WM_NCDESTROY {
+    RemoveWindowSubclass(hwnd, Some(process_events::<ID>), EVENTS_DISPATCH_ID);
+    return DefSubclassProc(hwnd, msg, w, l);
+  }
+
+  // The control was removed from the ui while one of its messages was being processed (ex: a callback removed the parent window).
+  // The ui data of the control is gone, so only the default processing is done.
+  // The ui message window is never packed, so it is skipped.
+  if hwnd != inner.messages.hwnd && inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).is_none() {
+    return DefSubclassProc(hwnd, msg, w, l);
+  }
+
+  // Per-pixel hit test of the layered windows. The transparent pixels let the clicks through.
+  if msg == WM_NCHITTEST && layered_hit_transparent(hwnd, l) {
+    return HTTRANSPARENT as LRESULT;
+  }
+
+  // Custom cursors. The cursor is only applied if the mouse is over the client area of this exact window, not one of its children.
+  if msg == WM_SETCURSOR && (w as HWND) == hwnd && LOWORD(l as u32) as c_int == HTCLIENT {
+    if get_window_cursor_hidden(hwnd) {
+      SetCursor(ptr::null_mut());
+      return 1;
+    } else if let Some(cursor) = get_window_cursor(hwnd) {
+      SetCursor(cursor);
+      return 1;
+    }
+  }
+
+  // The cached pens, brushes and fonts might not match the new theme
+  if msg == WM_THEMECHANGED || msg == WM_SYSCOLORCHANGE {
+    ::low::gdi_helper::clear_gdi_cache();
+    inner.clear_brushes();
+  }
+
+  // Paste filters. The default paste is skipped if the control has a filter.
+  if msg == WM_PASTE && filtered_paste(hwnd) {
+    return 0;
+  }
+
+  if msg == WM_DESTROY {
+    set_window_paste_filter(hwnd, None);
+    set_window_cursor(hwnd, None);
+    set_window_cursor_hidden(hwnd, false);
+    clear_window_user_data(hwnd);
+    stop_mouse_tracking(hwnd);
+    set_window_hover_time(hwnd, None);
+    set_window_session_notifications(hwnd, false);
+    unregister_window_device_notifications(hwnd);
+    clear_window_layout(hwnd);
+    free_layered_window(hwnd);
+
+    #[cfg(feature = "debug-messages")]
+    ::low::message_log::set_message_logger(hwnd, None);
+  }
+
   let callback_data = match msg {
     WM_PAINT => {
       inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_PAINT)");;
@@ -153,11 +390,25 @@ unsafe extern "system" fn process_events<ID: Hash+Clone+'static>(hwnd: HWND, msg
       let nmdr: &NMHDR = mem::transmute(l);
       if let Some(id) = inner.inner_id_from_handle( &AnyHandle::HWND(nmdr.hwndFrom) ) {
         let control_type = (&mut *inner.controls.get(&id).expect("Could not find a control with with the specified type ID").as_ptr()).control_type();
-        parse_notify(id, control_type, nmdr.code as WPARAM)
+        parse_notify(id, control_type, nmdr)
       } else {
         None
       }
     },
+    WM_HSCROLL | WM_VSCROLL => {
+      // `l` is 0 when the message comes from the standard scroll bars of the window
+      if l == 0 {
+        None
+      } else {
+        let nhandle: HWND = mem::transmute(l);
+        if let Some(id) = inner.inner_id_from_handle( &AnyHandle::HWND(nhandle) ) {
+          let control_type = (&mut *inner.controls.get(&id).expect("Could not find a control with with the specified type ID").as_ptr()).control_type();
+          parse_scroll(id, control_type, nhandle, w)
+        } else {
+          None
+        }
+      }
+    },
     WM_LBUTTONUP | WM_RBUTTONUP  | WM_MBUTTONUP => {
       inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_LBUTTONUP | WM_RBUTTONUP  | WM_MBUTTONUP)");;
       Some( (inner_id, Event::MouseUp, parse_mouse_click(msg, l)) )
@@ -166,6 +417,25 @@ unsafe extern "system" fn process_events<ID: Hash+Clone+'static>(hwnd: HWND, msg
       inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN)");;
       Some( (inner_id, Event::MouseDown, parse_mouse_click(msg, l)) )
     },
+    WM_MOUSEMOVE => {
+      // The system only sends WM_MOUSELEAVE and WM_MOUSEHOVER if the window asked for it
+      if start_mouse_tracking(hwnd) {
+        inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_MOUSEMOVE)");
+        Some( (inner_id, Event::MouseEnter, EventArgs::None) )
+      } else {
+        None
+      }
+    },
+    WM_MOUSELEAVE => {
+      stop_mouse_tracking(hwnd);
+      inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_MOUSELEAVE)");
+      Some( (inner_id, Event::MouseLeave, EventArgs::None) )
+    },
+    WM_MOUSEHOVER => {
+      inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_MOUSEHOVER)");
+      let (x, y) = (GET_X_LPARAM(l), GET_Y_LPARAM(l));
+      Some( (inner_id, Event::MouseHover, EventArgs::Position(x as i32, y as i32)) )
+    },
     WM_KEYDOWN | WM_KEYUP => {
       inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_KEYDOWN | WM_KEYUP)");;
       let evt = if msg == WM_KEYDOWN { Event::KeyDown } else { Event::KeyUp };
@@ -210,6 +480,10 @@ unsafe extern "system" fn process_events<ID: Hash+Clone+'static>(hwnd: HWND, msg
     },
     WM_SIZING | WM_SIZE => {
       inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_SIZING)");
+      if msg == WM_SIZE {
+        invalidate_window_layout(hwnd);
+      }
+
       let mut r: RECT = mem::uninitialized();
       GetClientRect(hwnd, &mut r);
       let w: u32 = (r.right-r.left) as u32;
@@ -228,9 +502,110 @@ unsafe extern "system" fn process_events<ID: Hash+Clone+'static>(hwnd: HWND, msg
       inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_CLOSE)");
       Some( (inner_id, Event::Closed, EventArgs::None) )
     },
+    WM_ACTIVATE => {
+      // A window that gets minimized is also deactivated. The high word of wparam tells if the window is minimized.
+      let state = LOWORD(w as u32);
+      let minimized = HIWORD(w as u32) != 0;
+      update_canvas_activation(hwnd, state != WA_INACTIVE && !minimized);
+
+      inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_ACTIVATE)");
+      let evt = if state == WA_INACTIVE { Event::Deactivated } else { Event::Activated };
+      Some( (inner_id, evt, EventArgs::Activation{click: state == WA_CLICKACTIVE, minimized: minimized}) )
+    },
+    WM_ACTIVATEAPP => {
+      inner_id = inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ).expect("Could not match system handle to ui control (msg: WM_ACTIVATEAPP)");
+      Some( (inner_id, Event::AppActivation, EventArgs::Focus(w != 0)) )
+    },
+    WM_POWERBROADCAST => {
+      // The system always sends PBT_APMRESUMEAUTOMATIC when it resumes. PBT_APMRESUMESUSPEND follows only if a user is present, so it is ignored.
+      let evt = match w {
+        PBT_APMSUSPEND => Some(Event::Suspended),
+        PBT_APMRESUMEAUTOMATIC => Some(Event::Resumed),
+        _ => None
+      };
+
+      match (evt, inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) )) {
+        (Some(evt), Some(inner_id)) => Some( (inner_id, evt, EventArgs::None) ),
+        _ => None
+      }
+    },
+    WM_WTSSESSION_CHANGE => {
+      let evt = match w {
+        WTS_SESSION_LOCK => Some(Event::SessionLocked),
+        WTS_SESSION_UNLOCK => Some(Event::SessionUnlocked),
+        _ => None
+      };
+
+      match (evt, inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) )) {
+        (Some(evt), Some(inner_id)) => Some( (inner_id, evt, EventArgs::None) ),
+        _ => None
+      }
+    },
+    WM_DEVICECHANGE => {
+      let evt = match w {
+        DBT_DEVICEARRIVAL => Some(Event::DeviceArrival),
+        DBT_DEVICEREMOVECOMPLETE => Some(Event::DeviceRemoval),
+        DBT_DEVNODES_CHANGED => Some(Event::DevicesChanged),
+        _ => None
+      };
+
+      // Arrival and removal notifications point to a DEV_BROADCAST_HDR. DBT_DEVNODES_CHANGED has no data.
+      let args = if l != 0 {
+        let header: &DEV_BROADCAST_HDR = mem::transmute(l);
+        EventArgs::Device(header.dbch_devicetype as u32)
+      } else {
+        EventArgs::Device(0)
+      };
+
+      match (evt, inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) )) {
+        (Some(evt), Some(inner_id)) => Some( (inner_id, evt, args) ),
+        _ => None
+      }
+    },
+    WM_DISPLAYCHANGE => {
+      let args = EventArgs::DisplayMode{size: (LOWORD(l as u32) as u32, HIWORD(l as u32) as u32), bits_per_pixel: w as u32};
+      match inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ) {
+        Some(inner_id) => Some( (inner_id, Event::DisplayChanged, args) ),
+        None => None
+      }
+    },
+    WM_SETTINGCHANGE if w as u32 == SPI_SETWORKAREA => {
+      use low::display_helper::{work_area, fit_maximized_window};
+
+      // The system does not resize the maximized windows when the work area changes
+      fit_maximized_window(hwnd);
+
+      let (x, y, width, height) = work_area(hwnd);
+      let args = EventArgs::WorkArea{position: (x, y), size: (width, height)};
+      match inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ) {
+        Some(inner_id) => Some( (inner_id, Event::WorkAreaChanged, args) ),
+        None => None
+      }
+    },
+    WM_DPICHANGED => {
+      use low::defs::USER_DEFAULT_SCREEN_DPI;
+
+      // The x and y DPI are always the same. `l` points to the suggested window rectangle.
+      let dpi = LOWORD(w as u32) as u32;
+      let r: &RECT = mem::transmute(l);
+      let args = EventArgs::Dpi{
+        dpi: dpi,
+        scale: (dpi as f32) / (USER_DEFAULT_SCREEN_DPI as f32),
+        position: (r.left as i32, r.top as i32),
+        size: ((r.right-r.left) as u32, (r.bottom-r.top) as u32)
+      };
+
+      match inner.inner_id_from_handle( &AnyHandle::HWND(hwnd) ) {
+        Some(inner_id) => Some( (inner_id, Event::DpiChanged, args) ),
+        None => None
+      }
+    },
     _ => { None }
   };
 
+  // The children are repositioned before the callbacks are called
+  flush_window_layouts();
+
   if let Some((inner_id, evt, params)) = callback_data {
     inner.trigger(inner_id, evt, params);
   }
@@ -264,13 +639,34 @@ pub fn hook_window_events<ID: Hash+Clone+'static>(uiinner: &mut UiInner<ID>, han
 pub fn unhook_window_events<ID: Hash+Clone+'static>(handle: HWND) { unsafe {
   use comctl32::{RemoveWindowSubclass, GetWindowSubclass};
   use winapi::{TRUE};
+  use low::window_helper::{clear_window_user_data, set_window_session_notifications, unregister_window_device_notifications};
+  use low::layout_helper::clear_window_layout;
+  use low::layered_helper::free_layered_window;
 
   let mut data: DWORD_PTR = 0;
   if GetWindowSubclass(handle, Some(process_events::<ID>), EVENTS_DISPATCH_ID, &mut data) == TRUE {
     RemoveWindowSubclass(handle, Some(process_events::<ID>), EVENTS_DISPATCH_ID);
+
+    // The window will not receive WM_DESTROY through the NWG subclass, so the user data must be freed here
+    clear_window_user_data(handle);
+    set_window_session_notifications(handle, false);
+    unregister_window_device_notifications(handle);
+    clear_window_layout(handle);
+    free_layered_window(handle);
   }
 }}
 
+/**
+  Return true if the events dispatcher of a Ui with the identifier type `ID` is hooked to a window
+*/
+pub fn is_window_hooked<ID: Hash+Clone+'static>(handle: HWND) -> bool { unsafe {
+  use comctl32::GetWindowSubclass;
+  use winapi::{TRUE};
+
+  let mut data: DWORD_PTR = 0;
+  GetWindowSubclass(handle, Some(process_events::<ID>), EVENTS_DISPATCH_ID, &mut data) == TRUE
+}}
+
 /**
   Check if a window is hooked by nwg. If it is, return its ID, if not return None
 */
@@ -298,17 +694,138 @@ pub unsafe fn window_id<ID: Clone+Hash>(handle: HWND, inner_ref: *mut UiInner<ID
 */
 #[inline(always)]
 pub unsafe fn dispatch_events() {
-  use winapi::MSG;
   use user32::{GetMessageW, TranslateMessage, DispatchMessageW};
 
   let mut msg: MSG = mem::uninitialized();
   while GetMessageW(&mut msg, ptr::null_mut(), 0, 0) != 0 {
+      if pre_translate_message(&msg) {
+        continue;
+      }
+
       TranslateMessage(&msg); 
       DispatchMessageW(&msg); 
       // TODO dispatch events sent from other thread / other processes ( after first stable release )
   }
 }
 
+/**
+    Install or remove (if `hook` is None) a function that receives the messages of the window and of its children
+    before they are translated and dispatched. If the function returns `true`, the message is not dispatched.  
+
+    This is the `PreTranslateMessage` mechanism: a hosted control (ex: a rich edit or a text editor) can handle its own
+    keyboard shortcuts before they are used by the accelerators or by the dialog navigation. The hooks are called before
+    every other step of the message loop, starting with the window that received the message and then its parents.  
+
+    The hook is removed when the window is destroyed.
+*/
+pub unsafe fn set_window_pre_translate<F: Fn(&MSG) -> bool + 'static>(handle: HWND, hook: Option<F>) -> Result<(), ::error::Error> {
+  use comctl32::{SetWindowSubclass, RemoveWindowSubclass};
+  use error::Error;
+
+  match hook {
+    Some(hook) => {
+      if SetWindowSubclass(handle, Some(pre_translate_proc), PRE_TRANSLATE_ID, 0) == 0 {
+        return Err(Error::from_last_error("SetWindowSubclass"));
+      }
+
+      let hook: PreTranslateHook = Rc::new(hook);
+      PRE_TRANSLATE_HOOKS.with(|hooks| { hooks.borrow_mut().insert(handle as usize, hook); });
+    },
+    None => {
+      RemoveWindowSubclass(handle, Some(pre_translate_proc), PRE_TRANSLATE_ID);
+      PRE_TRANSLATE_HOOKS.with(|hooks| { hooks.borrow_mut().remove(&(handle as usize)); });
+    }
+  }
+
+  Ok(())
+}
+
+/// Return `true` if a pre-translate hook is installed on the window
+pub fn has_window_pre_translate(handle: HWND) -> bool {
+  PRE_TRANSLATE_HOOKS.with(|hooks| hooks.borrow().contains_key(&(handle as usize)))
+}
+
+/**
+    Call the pre-translate hooks of the window that received the message and of its parents. Stop at the first hook
+    that handles the message. Return `true` if the message was handled.
+*/
+pub unsafe fn pre_translate_message(msg: &MSG) -> bool {
+  use low::window_helper::get_window_parent;
+
+  let mut handle = msg.hwnd;
+  while !handle.is_null() {
+    // The hook is cloned out of the map, so it can install or remove hooks itself
+    let hook = PRE_TRANSLATE_HOOKS.with(|hooks| hooks.borrow().get(&(handle as usize)).cloned());
+    if let Some(hook) = hook {
+      if hook(msg) {
+        return true;
+      }
+    }
+
+    handle = match get_window_parent(handle) {
+      Some(parent) => parent,
+      None => ptr::null_mut()
+    };
+  }
+
+  false
+}
+
+/// Remove the pre-translate hook of a window when it is destroyed
+unsafe extern "system" fn pre_translate_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, _id: UINT_PTR, _data: DWORD_PTR) -> LRESULT {
+  use comctl32::{DefSubclassProc, RemoveWindowSubclass};
+  use winapi::WM_NCDESTROY;
+
+  if msg == WM_NCDESTROY {
+    RemoveWindowSubclass(hwnd, Some(pre_translate_proc), PRE_TRANSLATE_ID);
+    PRE_TRANSLATE_HOOKS.with(|hooks| { hooks.borrow_mut().remove(&(hwnd as usize)); });
+  }
+
+  DefSubclassProc(hwnd, msg, w, l)
+}
+
+/**
+    Dispatch the messages until `predicate` returns true or until `timeout` elapses. Return the last value of `predicate`.
+    If a WM_QUIT message is received, it is posted again so that the next `dispatch_events` also returns.
+*/
+pub unsafe fn pump_until<F: FnMut() -> bool>(mut predicate: F, timeout: Duration) -> bool {
+  use winapi::{WM_QUIT, QS_ALLINPUT, PM_REMOVE, FALSE, DWORD, c_int};
+  use user32::{PeekMessageW, TranslateMessage, DispatchMessageW, MsgWaitForMultipleObjects, PostQuitMessage};
+
+  let start = Instant::now();
+  let mut msg: MSG = mem::uninitialized();
+
+  loop {
+    if predicate() {
+      return true;
+    }
+
+    let elapsed = start.elapsed();
+    if elapsed >= timeout {
+      return false;
+    }
+
+    // Sleep until a message (including WM_TIMER) arrives or the timeout elapses
+    let remaining = timeout - elapsed;
+    let remaining_ms = remaining.as_secs() * 1000 + (remaining.subsec_nanos() / 1_000_000) as u64;
+    MsgWaitForMultipleObjects(0, ptr::null(), FALSE, ::std::cmp::max(remaining_ms, 1) as DWORD, QS_ALLINPUT);
+
+    while PeekMessageW(&mut msg, ptr::null_mut(), 0, 0, PM_REMOVE) != 0 {
+      if msg.message == WM_QUIT {
+        PostQuitMessage(msg.wParam as c_int);
+        return predicate();
+      }
+
+      if pre_translate_message(&msg) {
+        continue;
+      }
+
+      TranslateMessage(&msg); 
+      DispatchMessageW(&msg); 
+    }
+  }
+}
+
 /**
     Send a WM_QUIT to the system queue. Breaks the dispatch_events loop.
 */
@@ -0,0 +1,119 @@
+/*!
+    Helper functions that read and write the value of the input controls. Used to save and restore the state of a form.
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::mem;
+
+use winapi::{HWND, WPARAM, LPARAM};
+use user32::SendMessageW;
+
+use controls::ControlType;
+use defs::{CheckState, FormValue};
+
+/**
+    Read the value of an input control. Return `None` if the control type has no value
+    (only the text inputs, the check boxes, the radio buttons, the combo boxes and the list boxes have one).
+*/
+pub unsafe fn read_control_value(handle: HWND, control_type: &ControlType) -> Option<FormValue> {
+    use low::defs::{BM_GETCHECK, BST_CHECKED, BST_UNCHECKED, CB_GETCURSEL, LB_GETCURSEL, LB_GETSELCOUNT, LB_GETSELITEMS};
+    use low::window_helper::get_window_text;
+
+    match control_type {
+        &ControlType::TextInput | &ControlType::TextBox => Some(FormValue::Text(get_window_text(handle))),
+        &ControlType::CheckBox | &ControlType::RadioButton => {
+            let state = match SendMessageW(handle, BM_GETCHECK, 0, 0) as u32 {
+                BST_CHECKED => CheckState::Checked,
+                BST_UNCHECKED => CheckState::Unchecked,
+                _ => CheckState::Indeterminate
+            };
+            Some(FormValue::Check(state))
+        },
+        &ControlType::ComboBox => {
+            let index = SendMessageW(handle, CB_GETCURSEL, 0, 0);
+            Some(FormValue::Selection(if index == -1 { Vec::new() } else { vec![index as usize] }))
+        },
+        &ControlType::ListBox => {
+            if listbox_multi_select(handle) {
+                let count = SendMessageW(handle, LB_GETSELCOUNT, 0, 0);
+                if count <= 0 {
+                    return Some(FormValue::Selection(Vec::new()));
+                }
+
+                let mut buffer: Vec<u32> = Vec::with_capacity(count as usize);
+                buffer.set_len(count as usize);
+                SendMessageW(handle, LB_GETSELITEMS, count as WPARAM, mem::transmute(buffer.as_mut_ptr()));
+                Some(FormValue::Selection(buffer.into_iter().map(|i| i as usize).collect()))
+            } else {
+                let index = SendMessageW(handle, LB_GETCURSEL, 0, 0);
+                Some(FormValue::Selection(if index == -1 { Vec::new() } else { vec![index as usize] }))
+            }
+        },
+        _ => None
+    }
+}
+
+/**
+    Write the value of an input control. Return `false` if the value does not match the control type.
+*/
+pub unsafe fn write_control_value(handle: HWND, control_type: &ControlType, value: &FormValue) -> bool {
+    use low::defs::{BM_SETCHECK, BST_CHECKED, BST_INDETERMINATE, BST_UNCHECKED, CB_SETCURSEL, LB_SETCURSEL, LB_SETSEL};
+    use low::window_helper::set_window_text;
+
+    match (control_type, value) {
+        (&ControlType::TextInput, &FormValue::Text(ref text)) | (&ControlType::TextBox, &FormValue::Text(ref text)) => {
+            set_window_text(handle, text);
+        },
+        (&ControlType::CheckBox, &FormValue::Check(ref state)) | (&ControlType::RadioButton, &FormValue::Check(ref state)) => {
+            let state = match state {
+                &CheckState::Checked => BST_CHECKED,
+                &CheckState::Indeterminate => BST_INDETERMINATE,
+                &CheckState::Unchecked => BST_UNCHECKED
+            };
+            SendMessageW(handle, BM_SETCHECK, state as WPARAM, 0);
+        },
+        (&ControlType::ComboBox, &FormValue::Selection(ref indexes)) => {
+            let index = indexes.first().map(|&i| i as WPARAM).unwrap_or(usize::max_value() as WPARAM);
+            SendMessageW(handle, CB_SETCURSEL, index, 0);
+        },
+        (&ControlType::ListBox, &FormValue::Selection(ref indexes)) => {
+            if listbox_multi_select(handle) {
+                SendMessageW(handle, LB_SETSEL, 0, -1);
+                for &i in indexes.iter() {
+                    SendMessageW(handle, LB_SETSEL, 1, i as LPARAM);
+                }
+            } else {
+                let index = indexes.first().map(|&i| i as WPARAM).unwrap_or(usize::max_value() as WPARAM);
+                SendMessageW(handle, LB_SETCURSEL, index, 0);
+            }
+        },
+        _ => { return false; }
+    }
+
+    true
+}
+
+/// Return true if a list box accepts more than one selected item
+unsafe fn listbox_multi_select(handle: HWND) -> bool {
+    use low::defs::LBS_MULTIPLESEL;
+    use low::window_helper::get_window_long;
+    use winapi::GWL_STYLE;
+
+    let style = get_window_long(handle, GWL_STYLE) as u32;
+    (style & LBS_MULTIPLESEL) == LBS_MULTIPLESEL
+}
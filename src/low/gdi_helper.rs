@@ -0,0 +1,457 @@
+/*!
+    Low level GDI drawing functions. Used by owner drawn and custom controls.
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::mem;
+use std::ptr;
+use std::cell::RefCell;
+
+use winapi::{HDC, RECT, COLOR16, HBITMAP, HICON, BITMAP, BITMAPINFO, BITMAPINFOHEADER, c_int, HPEN, HBRUSH, HFONT, HGDIOBJ, COLORREF};
+
+use controls::AnyHandle;
+use resources::{Bitmap, Font, FontT, Resource};
+use defs::{GradientDirection, FontQuality};
+use error::Error;
+
+/// Convert a 8 bits color channel to the 16 bits format used by `TRIVERTEX`
+#[inline(always)]
+fn color16(c: u8) -> COLOR16 {
+    (c as COLOR16) << 8
+}
+
+/**
+    Fill `rect` with a linear gradient going from the color `from` to the color `to`.  
+    Colors are in the (red, green, blue) format. If both colors are the same, the rect is filled with a brush from the GDI cache of the thread.
+
+    Returns `false` if the system could not draw the gradient.
+*/
+pub unsafe fn gradient_fill(hdc: HDC, rect: &RECT, from: (u8, u8, u8), to: (u8, u8, u8), direction: GradientDirection) -> bool {
+    use user32::FillRect;
+    use winapi::{TRIVERTEX, GRADIENT_RECT, GRADIENT_FILL_RECT_H, GRADIENT_FILL_RECT_V};
+    use low::defs::GradientFill;
+
+    if from == to {
+        let brush = cached_brush(from);
+        return !brush.is_null() && FillRect(hdc, rect, brush) != 0;
+    }
+
+    let vertex = [
+        TRIVERTEX { x: rect.left, y: rect.top, Red: color16(from.0), Green: color16(from.1), Blue: color16(from.2), Alpha: 0 },
+        TRIVERTEX { x: rect.right, y: rect.bottom, Red: color16(to.0), Green: color16(to.1), Blue: color16(to.2), Alpha: 0 },
+    ];
+
+    let mut mesh = GRADIENT_RECT { UpperLeft: 0, LowerRight: 1 };
+
+    let mode = match direction {
+        GradientDirection::Horizontal => GRADIENT_FILL_RECT_H,
+        GradientDirection::Vertical => GRADIENT_FILL_RECT_V
+    };
+
+    GradientFill(hdc, vertex.as_ptr(), 2, mem::transmute(&mut mesh), 1, mode) != 0
+}
+
+/// Return the handle of a bitmap resource
+#[inline(always)]
+fn bitmap_handle(bitmap: &Bitmap) -> HBITMAP {
+    match bitmap.handle() {
+        AnyHandle::HBITMAP(h) => h,
+        _ => unreachable!()
+    }
+}
+
+/// Return the description of a bitmap
+#[inline(always)]
+unsafe fn bitmap_info(bitmap: HBITMAP) -> BITMAP {
+    use low::defs::GetObjectW;
+
+    let mut info: BITMAP = mem::zeroed();
+    GetObjectW(mem::transmute(bitmap), mem::size_of::<BITMAP>() as c_int, mem::transmute(&mut info));
+    info
+}
+
+/// Return the header describing the pixels of a 32 bits top-down bitmap of size `width` x `height`
+fn pixels_header(width: c_int, height: c_int) -> BITMAPINFO {
+    use winapi::{BI_RGB, DWORD};
+
+    let mut header: BITMAPINFO = unsafe{ mem::zeroed() };
+    header.bmiHeader = BITMAPINFOHEADER {
+        biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD,
+        biWidth: width,
+        biHeight: -height, // Top-down
+        biPlanes: 1,
+        biBitCount: 32,
+        biCompression: BI_RGB,
+        biSizeImage: 0,
+        biXPelsPerMeter: 0, biYPelsPerMeter: 0,
+        biClrUsed: 0, biClrImportant: 0
+    };
+
+    header
+}
+
+/**
+    Read the pixels of a 32 bits bitmap in the BGRA format, from the top row to the bottom row.  
+    Returns the size of the bitmap and its pixels, or `None` if the bitmap is not a 32 bits bitmap or if the system
+    could not access the bitmap pixels.
+*/
+pub unsafe fn bitmap_pixels(bitmap: HBITMAP) -> Option<((c_int, c_int), Vec<u8>)> {
+    use user32::{GetDC, ReleaseDC};
+    use gdi32::GetDIBits;
+    use winapi::{DIB_RGB_COLORS, UINT};
+
+    let info = bitmap_info(bitmap);
+    if info.bmBitsPixel != 32 {
+        return None;
+    }
+
+    let (width, height) = (info.bmWidth, info.bmHeight.abs());
+    let mut header = pixels_header(width, height);
+    let mut pixels: Vec<u8> = vec![0; (width * height * 4) as usize];
+
+    let screen = GetDC(ptr::null_mut());
+    let read = GetDIBits(screen, bitmap, 0, height as UINT, mem::transmute(pixels.as_mut_ptr()), &mut header, DIB_RGB_COLORS);
+    ReleaseDC(ptr::null_mut(), screen);
+
+    if read == 0 {
+        None
+    } else {
+        Some(((width, height), pixels))
+    }
+}
+
+/**
+    Draw the `src` area of `bitmap` into the `dest` area of `hdc`. `alpha` is the opacity applied to the whole bitmap (255 is opaque).  
+    If `per_pixel_alpha` is true and the bitmap has 32 bits per pixel, the alpha channel of the bitmap is also used. In that case,
+    the color channels of the bitmap must be premultiplied by the alpha channel (see `premultiply_alpha`), otherwise the transparent
+    edges will look dark. Bitmaps with an empty alpha channel (ex: a bitmap created from a screen device context) must be drawn
+    with `per_pixel_alpha` set to false, otherwise they are fully transparent.
+
+    Returns `false` if the system could not draw the bitmap.
+*/
+pub unsafe fn draw_bitmap_alpha(hdc: HDC, dest: &RECT, bitmap: &Bitmap, src: &RECT, alpha: u8, per_pixel_alpha: bool) -> bool {
+    use gdi32::{CreateCompatibleDC, SelectObject, DeleteDC};
+    use winapi::BLENDFUNCTION;
+    use low::defs::{AlphaBlend, AC_SRC_OVER, AC_SRC_ALPHA};
+
+    // The system only reads the alpha channel of the 32 bits bitmaps
+    let handle = bitmap_handle(bitmap);
+    let has_alpha = per_pixel_alpha && bitmap_info(handle).bmBitsPixel == 32;
+
+    let blend = BLENDFUNCTION {
+        BlendOp: AC_SRC_OVER,
+        BlendFlags: 0,
+        SourceConstantAlpha: alpha,
+        AlphaFormat: if has_alpha { AC_SRC_ALPHA } else { 0 }
+    };
+
+    let src_dc = CreateCompatibleDC(hdc);
+    let old_bitmap = SelectObject(src_dc, mem::transmute(handle));
+
+    let result = AlphaBlend(
+        hdc, dest.left, dest.top, dest.right - dest.left, dest.bottom - dest.top,
+        src_dc, src.left, src.top, src.right - src.left, src.bottom - src.top,
+        blend
+    );
+
+    SelectObject(src_dc, old_bitmap);
+    DeleteDC(src_dc);
+
+    result != 0
+}
+
+/**
+    Multiply the color channels of a 32 bits bitmap by its alpha channel. This is required before drawing
+    a non-premultiplied bitmap (ex: a bitmap converted from a png) with `draw_bitmap_alpha`.  
+    The bitmap must only be premultiplied once.
+
+    Returns `false` if the bitmap is not a 32 bits bitmap or if the system could not access the bitmap pixels.
+*/
+pub unsafe fn premultiply_alpha(bitmap: &Bitmap) -> bool {
+    use user32::{GetDC, ReleaseDC};
+    use winapi::{DIB_RGB_COLORS, UINT};
+    use low::defs::SetDIBits;
+
+    let handle = bitmap_handle(bitmap);
+    let ((width, height), mut pixels) = match bitmap_pixels(handle) {
+        Some(p) => p,
+        None => { return false; }
+    };
+
+    // Pixels are stored in the BGRA format
+    for px in pixels.chunks_mut(4) {
+        let a = px[3] as u32;
+        px[0] = ((px[0] as u32 * a) / 255) as u8;
+        px[1] = ((px[1] as u32 * a) / 255) as u8;
+        px[2] = ((px[2] as u32 * a) / 255) as u8;
+    }
+
+    let header = pixels_header(width, height);
+    let screen = GetDC(ptr::null_mut());
+    let written = SetDIBits(screen, handle, 0, height as UINT, mem::transmute(pixels.as_ptr()), &header, DIB_RGB_COLORS);
+    ReleaseDC(ptr::null_mut(), screen);
+
+    written != 0
+}
+
+/**
+    Create an icon (if `hotspot` is `None`) or a cursor from a color bitmap and a mask. The bitmaps are copied, so they can be freed
+    once the icon is created. The returned handle must be destroyed with `DestroyIcon`.
+
+    The mask is a monochrome bitmap where the white pixels are transparent. A 32 bits color bitmap uses its alpha channel
+    instead, so it does not need a mask. The other color bitmaps require one.
+
+    Errors:  
+    • `Error::BadResource` if the mask is missing or if the hotspot is outside the bitmap  
+    • `Error::System(SystemError::SystemCall{..})` if the icon could not be created  
+*/
+pub unsafe fn create_icon(color: &Bitmap, mask: Option<&Bitmap>, hotspot: Option<(u32, u32)>) -> Result<HICON, Error> {
+    use user32::CreateIconIndirect;
+    use gdi32::{CreateBitmap, DeleteObject};
+    use winapi::{ICONINFO, DWORD};
+
+    let color = bitmap_handle(color);
+    let info = bitmap_info(color);
+    let (width, height) = (info.bmWidth, info.bmHeight.abs());
+
+    if let Some((x, y)) = hotspot {
+        if x as c_int >= width || y as c_int >= height {
+            return Err(Error::BadResource("The cursor hotspot must be inside the bitmap".to_string()));
+        }
+    }
+
+    // The system still requires a mask for icons with an alpha channel, but it is not used
+    let (mask, temp_mask) = match mask {
+        Some(m) => (bitmap_handle(m), false),
+        None if info.bmBitsPixel == 32 => (CreateBitmap(width, height, 1, 1, ptr::null()), true),
+        None => { return Err(Error::BadResource("A mask is required to create an icon from a bitmap without alpha channel".to_string())); }
+    };
+
+    let (x, y) = hotspot.unwrap_or((0, 0));
+    let mut icon_info = ICONINFO {
+        fIcon: hotspot.is_none() as i32,
+        xHotspot: x as DWORD,
+        yHotspot: y as DWORD,
+        hbmMask: mask,
+        hbmColor: color
+    };
+
+    let icon = CreateIconIndirect(&mut icon_info);
+    let result = if icon.is_null() { Err(Error::from_last_error("CreateIconIndirect")) } else { Ok(icon) };
+
+    if temp_mask {
+        DeleteObject(mem::transmute(mask));
+    }
+
+    result
+}
+
+/// Maximum number of GDI objects kept by the cache of a thread
+const GDI_CACHE_CAPACITY: usize = 64;
+
+/// The description of a cached GDI object
+#[derive(Clone, PartialEq)]
+enum GdiKey {
+    Pen((u8, u8, u8), u32),
+    Brush((u8, u8, u8)),
+    Font(String, c_int, c_int, u32, FontQuality)
+}
+
+thread_local! {
+    /// The cached GDI objects, from the least recently used to the most recently used
+    static GDI_CACHE: RefCell<Vec<(GdiKey, HGDIOBJ)>> = RefCell::new(Vec::new());
+}
+
+/**
+    Delete the least recently used objects of the cache until there is room for a new object.
+    The objects that cannot be deleted (ex: a bitmap still selected into a device context) are skipped
+    and kept in the cache, which can grow over its capacity until they are deselected.
+*/
+unsafe fn evict_gdi_objects(cache: &mut Vec<(GdiKey, HGDIOBJ)>) {
+    use gdi32::DeleteObject;
+
+    let mut index = 0;
+    while cache.len() >= GDI_CACHE_CAPACITY && index < cache.len() {
+        if DeleteObject(cache[index].1) != 0 {
+            cache.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+}
+
+/// Convert a (red, green, blue) color to a `COLORREF`
+#[inline(always)]
+pub fn colorref(color: (u8, u8, u8)) -> COLORREF {
+    (color.0 as COLORREF) | ((color.1 as COLORREF) << 8) | ((color.2 as COLORREF) << 16)
+}
+
+/**
+    Return the cached object matching `key` or create it with `create`. When the cache is full,
+    the least recently used object that is not selected into a device context is deleted. Null objects are not cached.
+*/
+unsafe fn cached_object<F: FnOnce() -> HGDIOBJ>(key: GdiKey, create: F) -> HGDIOBJ {
+    GDI_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+
+        if let Some(index) = cache.iter().position(|&(ref k, _)| *k == key) {
+            let entry = cache.remove(index);
+            let obj = entry.1;
+            cache.push(entry);
+            return obj;
+        }
+
+        let obj = create();
+        if obj.is_null() {
+            return obj;
+        }
+
+        evict_gdi_objects(&mut cache);
+        cache.push((key, obj));
+        obj
+    })
+}
+
+/**
+    Return a solid pen of the color `color` (red, green, blue) and of width `width` from the GDI cache of the thread.  
+    Returns a null handle if the system could not create the pen.
+
+    The pen is owned by the cache: it must not be deleted and it should not be kept after the drawing is done, because
+    the cache deletes its least recently used objects when it is full (the cache keeps 64 objects).
+    An object that is still selected into a device context is never deleted, but the previous object of the
+    device context should be restored as soon as the drawing is done so that the cache can stay under its capacity.
+*/
+pub unsafe fn cached_pen(color: (u8, u8, u8), width: u32) -> HPEN {
+    use low::defs::CreatePen;
+    use winapi::PS_SOLID;
+
+    cached_object(GdiKey::Pen(color, width), || CreatePen(PS_SOLID as c_int, width as c_int, colorref(color)) as HGDIOBJ) as HPEN
+}
+
+/**
+    Return a solid brush of the color `color` (red, green, blue) from the GDI cache of the thread.  
+    Returns a null handle if the system could not create the brush. The same rules as `cached_pen` apply.
+*/
+pub unsafe fn cached_brush(color: (u8, u8, u8)) -> HBRUSH {
+    use gdi32::CreateSolidBrush;
+
+    cached_object(GdiKey::Brush(color), || CreateSolidBrush(colorref(color)) as HGDIOBJ) as HBRUSH
+}
+
+/**
+    Draw `text` inside `rect` with `font`, using the text color of `hdc`. The background behind the text is not filled.  
+    `flags` is a combination of the `TEXT_*` constants that sets the alignment and the wrapping of the text (ex: `TEXT_CENTER | TEXT_WORD_BREAK`).  
+    The smoothing of the characters is set when the font is created (see `FontT::quality`).
+
+    The font and the background mode of `hdc` are restored before the function returns.  
+    Returns the height of the drawn text or 0 if the system could not draw the text.
+*/
+pub unsafe fn draw_text<'a>(hdc: HDC, font: &Font, text: &'a str, rect: &RECT, flags: u32) -> c_int {
+    let font_handle = match font.handle() {
+        AnyHandle::HFONT(h) => h,
+        _ => unreachable!()
+    };
+
+    draw_text_with(hdc, font_handle, text, rect, flags)
+}
+
+/**
+    Same as `draw_text`, but the font described by `font` is taken from the GDI cache of the thread (see `cached_font`)
+    instead of being built as a resource.  
+    Returns 0 if the font could not be created or if the system could not draw the text.
+*/
+pub unsafe fn draw_text_cached<'a, S: Clone+Into<String>>(hdc: HDC, font: &FontT<S>, text: &'a str, rect: &RECT, flags: u32) -> c_int {
+    let family: String = font.family.clone().into();
+    let font_handle = cached_font(&family, font.size, font.weight, font.decoration, font.quality);
+    if font_handle.is_null() {
+        return 0;
+    }
+
+    draw_text_with(hdc, font_handle, text, rect, flags)
+}
+
+/// Draw `text` with the font `font_handle`. See `draw_text`.
+unsafe fn draw_text_with<'a>(hdc: HDC, font_handle: HFONT, text: &'a str, rect: &RECT, flags: u32) -> c_int {
+    use user32::DrawTextW;
+    use gdi32::{SelectObject, SetBkMode};
+    use winapi::{TRANSPARENT, LPRECT};
+    use low::other_helper::to_utf16;
+
+    let text = to_utf16(text);
+    let mut rect = *rect;
+
+    let old_font = SelectObject(hdc, font_handle as HGDIOBJ);
+    let old_mode = SetBkMode(hdc, TRANSPARENT);
+
+    // `to_utf16` adds a null terminator, so the length can be -1
+    let height = DrawTextW(hdc, text.as_ptr(), -1, &mut rect as LPRECT, flags);
+
+    SetBkMode(hdc, old_mode);
+    SelectObject(hdc, old_font);
+
+    height
+}
+
+/**
+    Return a font from the GDI cache of the thread. The parameters are the same as the members of `FontT`.  
+    Returns a null handle if the system could not create the font. The same rules as `cached_pen` apply.
+*/
+pub unsafe fn cached_font<'a>(family: &'a str, size: c_int, weight: c_int, decoration: u32, quality: FontQuality) -> HFONT {
+    use gdi32::CreateFontW;
+    use winapi::{DEFAULT_CHARSET, DEFAULT_QUALITY, CLEARTYPE_QUALITY, ANTIALIASED_QUALITY, NONANTIALIASED_QUALITY,
+      OUT_DEFAULT_PRECIS, CLIP_DEFAULT_PRECIS, VARIABLE_PITCH, DWORD};
+    use defs::{FONT_DECO_ITALIC, FONT_DECO_UNDERLINE, FONT_DECO_STRIKEOUT};
+    use low::other_helper::to_utf16;
+
+    let key = GdiKey::Font(family.to_string(), size, weight, decoration, quality);
+    cached_object(key, || {
+        let family_name = to_utf16(family);
+        let quality = match quality {
+            FontQuality::Default => DEFAULT_QUALITY,
+            FontQuality::ClearType => CLEARTYPE_QUALITY,
+            FontQuality::Grayscale => ANTIALIASED_QUALITY,
+            FontQuality::None => NONANTIALIASED_QUALITY
+        };
+
+        CreateFontW(
+            size, 0, 0, 0, weight,
+            ((decoration & FONT_DECO_ITALIC) != 0) as DWORD,
+            ((decoration & FONT_DECO_UNDERLINE) != 0) as DWORD,
+            ((decoration & FONT_DECO_STRIKEOUT) != 0) as DWORD,
+            DEFAULT_CHARSET, OUT_DEFAULT_PRECIS, CLIP_DEFAULT_PRECIS, quality, VARIABLE_PITCH,
+            family_name.as_ptr()
+        ) as HGDIOBJ
+    }) as HFONT
+}
+
+/**
+    Delete every object of the GDI cache of the thread. The events dispatcher calls it when the system theme
+    or the system colors change (`WM_THEMECHANGED`, `WM_SYSCOLORCHANGE`).  
+    The objects that are still selected into a device context cannot be deleted and stay in the cache.
+*/
+pub unsafe fn clear_gdi_cache() {
+    use gdi32::DeleteObject;
+
+    GDI_CACHE.with(|cache| {
+        cache.borrow_mut().retain(|&(_, obj)| DeleteObject(obj) == 0);
+    });
+}
+
+/// Return the number of objects in the GDI cache of the thread
+pub fn gdi_cache_len() -> usize {
+    GDI_CACHE.with(|cache| cache.borrow().len())
+}
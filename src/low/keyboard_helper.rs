@@ -0,0 +1,80 @@
+/*!
+    Helper functions to read the state of the keyboard
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use winapi::c_int;
+
+/**
+    The state of the modifier keys
+
+    Members:  
+    • `ctrl`: If a control key is held  
+    • `shift`: If a shift key is held  
+    • `alt`: If an alt key is held  
+    • `win`: If a windows key is held  
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct KeyModifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub win: bool,
+}
+
+/**
+    Return true if the key with the virtual key code `vk` is currently held down.  
+    The state is read when the function is called, not when the current message was sent.
+*/
+pub fn is_key_down(vk: u16) -> bool {
+    use user32::GetAsyncKeyState;
+    unsafe{ (GetAsyncKeyState(vk as c_int) as u16 & 0x8000) != 0 }
+}
+
+/// Return true if a control key is held down
+pub fn ctrl_down() -> bool {
+    use winapi::VK_CONTROL;
+    is_key_down(VK_CONTROL as u16)
+}
+
+/// Return true if a shift key is held down
+pub fn shift_down() -> bool {
+    use winapi::VK_SHIFT;
+    is_key_down(VK_SHIFT as u16)
+}
+
+/// Return true if an alt key is held down
+pub fn alt_down() -> bool {
+    use winapi::VK_MENU;
+    is_key_down(VK_MENU as u16)
+}
+
+/// Return true if one of the windows keys is held down
+pub fn win_key_down() -> bool {
+    use winapi::{VK_LWIN, VK_RWIN};
+    is_key_down(VK_LWIN as u16) || is_key_down(VK_RWIN as u16)
+}
+
+/// Return the state of all the modifier keys
+pub fn current_key_modifiers() -> KeyModifiers {
+    KeyModifiers {
+        ctrl: ctrl_down(),
+        shift: shift_down(),
+        alt: alt_down(),
+        win: win_key_down()
+    }
+}
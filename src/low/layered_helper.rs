@@ -0,0 +1,195 @@
+/*!
+    Low level helping functions for the layered windows drawn with `UpdateLayeredWindow`
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::ptr;
+use std::mem;
+
+use winapi::{HWND, HBITMAP, LPARAM, c_int};
+
+use low::other_helper::to_utf16;
+use error::Error;
+
+/// Name of the window property that holds the alpha channel of the last bitmap pushed to a layered window
+const ALPHA_MASK_PROP_NAME: &'static str = "NWG_LAYERED_ALPHA";
+
+/// Name of the window property that holds the hit test threshold of a layered window
+const HIT_THRESHOLD_PROP_NAME: &'static str = "NWG_LAYERED_HIT_THRESHOLD";
+
+/**
+    The alpha channel of the last bitmap pushed to a layered window, one byte per pixel, from the top row to the bottom row.
+*/
+struct AlphaMask {
+    width: c_int,
+    height: c_int,
+    alpha: Vec<u8>
+}
+
+/**
+    Read the alpha channel of a 32 bits bitmap. Returns `None` if the bitmap is not a 32 bits bitmap or
+    if the system could not access the bitmap pixels.
+*/
+unsafe fn read_alpha_mask(bitmap: HBITMAP) -> Option<AlphaMask> {
+    use low::gdi_helper::bitmap_pixels;
+
+    match bitmap_pixels(bitmap) {
+        // Pixels are stored in the BGRA format
+        Some(((width, height), pixels)) => Some(AlphaMask{ width: width, height: height, alpha: pixels.chunks(4).map(|px| px[3]).collect() }),
+        None => None
+    }
+}
+
+/**
+    Draw a layered window with a 32 bits bitmap using `UpdateLayeredWindow`. The window is resized to the size of the bitmap
+    and the alpha channel of the bitmap is used for the transparency. The color channels must be premultiplied by the
+    alpha channel (see `premultiply_alpha`). `alpha` is the opacity applied to the whole window (255 is opaque).
+
+    `WS_EX_LAYERED` is added to the window if it is missing. A window made transparent with `SetLayeredWindowAttributes`
+    cannot be updated this way. The alpha channel of the bitmap is kept on the window for the hit test (see `set_layered_hit_threshold`),
+    so it always matches the last successful update.
+
+    Errors:  
+    • `Error::BadResource` if the bitmap is not a 32 bits bitmap  
+    • `Error::System(SystemError::SystemCall{..})` if the window could not be updated  
+*/
+pub unsafe fn update_layered_window(handle: HWND, bitmap: HBITMAP, alpha: u8) -> Result<(), Error> {
+    use user32::{GetDC, ReleaseDC, UpdateLayeredWindow, SetPropW, RemovePropW};
+    use gdi32::{CreateCompatibleDC, SelectObject, DeleteDC};
+    use winapi::{BLENDFUNCTION, POINT, SIZE, GWL_EXSTYLE, WS_EX_LAYERED};
+    use low::defs::{AC_SRC_OVER, AC_SRC_ALPHA, ULW_ALPHA};
+    use low::window_helper::{get_window_long, set_window_long};
+
+    let mask = match read_alpha_mask(bitmap) {
+        Some(m) => m,
+        None => { return Err(Error::BadResource("Layered windows must be drawn with a 32 bits bitmap".to_string())); }
+    };
+
+    let ex_style = get_window_long(handle, GWL_EXSTYLE) as u32;
+    if ex_style & WS_EX_LAYERED == 0 {
+        set_window_long(handle, GWL_EXSTYLE, (ex_style | WS_EX_LAYERED) as usize);
+    }
+
+    let mut blend = BLENDFUNCTION {
+        BlendOp: AC_SRC_OVER,
+        BlendFlags: 0,
+        SourceConstantAlpha: alpha,
+        AlphaFormat: AC_SRC_ALPHA
+    };
+
+    let mut size = SIZE{ cx: mask.width, cy: mask.height };
+    let mut src_pos = POINT{ x: 0, y: 0 };
+
+    let screen = GetDC(ptr::null_mut());
+    let src_dc = CreateCompatibleDC(screen);
+    let old_bitmap = SelectObject(src_dc, mem::transmute(bitmap));
+
+    let result = UpdateLayeredWindow(handle, screen, ptr::null_mut(), &mut size, src_dc, &mut src_pos, 0, &mut blend, ULW_ALPHA);
+    let result = if result == 0 { Err(Error::from_last_error("UpdateLayeredWindow")) } else { Ok(()) };
+
+    SelectObject(src_dc, old_bitmap);
+    DeleteDC(src_dc);
+    ReleaseDC(ptr::null_mut(), screen);
+
+    if result.is_ok() {
+        let prop_name = to_utf16(ALPHA_MASK_PROP_NAME);
+        let old_mask: *mut AlphaMask = mem::transmute(RemovePropW(handle, prop_name.as_ptr()));
+        if !old_mask.is_null() {
+            drop(Box::from_raw(old_mask));
+        }
+
+        let mask_ptr: *mut AlphaMask = Box::into_raw(Box::new(mask));
+        SetPropW(handle, prop_name.as_ptr(), mem::transmute(mask_ptr));
+    }
+
+    result
+}
+
+/**
+    Enable the per-pixel hit test of a layered window drawn with `update_layered_window`. When the mouse is over a pixel
+    with an alpha lower than `threshold`, the window answers `WM_NCHITTEST` with `HTTRANSPARENT` and the click goes to the window below.
+    Use `None` to disable the hit test.
+
+    This only works on windows created by a Ui, because the hit test is done by the NWG events subclass.
+    Like any `HTTRANSPARENT` answer, the click only goes through to the windows of the same thread.
+*/
+pub unsafe fn set_layered_hit_threshold(handle: HWND, threshold: Option<u8>) {
+    use user32::{SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(HIT_THRESHOLD_PROP_NAME);
+    match threshold {
+        // A value of 0 would be the same as no property, so the threshold is offset by one
+        Some(t) => { SetPropW(handle, prop_name.as_ptr(), mem::transmute((t as usize) + 1)); },
+        None => { RemovePropW(handle, prop_name.as_ptr()); }
+    }
+}
+
+/// Return the hit test threshold set with `set_layered_hit_threshold`, if any
+pub unsafe fn get_layered_hit_threshold(handle: HWND) -> Option<u8> {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(HIT_THRESHOLD_PROP_NAME);
+    let threshold: usize = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if threshold == 0 { None } else { Some((threshold - 1) as u8) }
+}
+
+/**
+    Return `true` if the point `l` (the screen coordinates of a `WM_NCHITTEST` message) is over a pixel of the layered window
+    that is more transparent than its hit test threshold. Returns `false` if the window has no threshold or was never updated.
+*/
+pub unsafe fn layered_hit_transparent(handle: HWND, l: LPARAM) -> bool {
+    use user32::{GetPropW, GetWindowRect};
+    use winapi::{RECT, GET_X_LPARAM, GET_Y_LPARAM};
+
+    let threshold = match get_layered_hit_threshold(handle) {
+        Some(t) => t,
+        None => { return false; }
+    };
+
+    let prop_name = to_utf16(ALPHA_MASK_PROP_NAME);
+    let mask: *mut AlphaMask = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if mask.is_null() {
+        return false;
+    }
+
+    let mask = &*mask;
+
+    let mut rect: RECT = mem::zeroed();
+    GetWindowRect(handle, &mut rect);
+
+    let (x, y) = (GET_X_LPARAM(l) - rect.left, GET_Y_LPARAM(l) - rect.top);
+    if x < 0 || y < 0 || x >= mask.width || y >= mask.height {
+        return false;
+    }
+
+    mask.alpha[(y * mask.width + x) as usize] < threshold
+}
+
+/**
+    Free the alpha channel and remove the hit test threshold saved on a layered window. Called when the window is destroyed.
+*/
+pub unsafe fn free_layered_window(handle: HWND) {
+    use user32::RemovePropW;
+
+    let prop_name = to_utf16(ALPHA_MASK_PROP_NAME);
+    let mask: *mut AlphaMask = mem::transmute(RemovePropW(handle, prop_name.as_ptr()));
+    if !mask.is_null() {
+        drop(Box::from_raw(mask));
+    }
+
+    set_layered_hit_threshold(handle, None);
+}
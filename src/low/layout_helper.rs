@@ -0,0 +1,468 @@
+/*!
+    Simple layout helpers that reposition the children of a window when it is resized
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use std::ptr;
+use std::mem;
+use std::cell::{Cell, RefCell};
+
+use winapi::{HWND, LPARAM, BOOL};
+
+use defs::{Anchor, Dock, Length, RelativePlacement};
+use low::other_helper::to_utf16;
+
+/// Name of the window property that holds the anchor data of a control
+const ANCHOR_PROP_NAME: &'static str = "NWG_ANCHOR";
+
+/// Name of the window property that counts the layout passes of a window. Only used with the `testing` feature.
+#[cfg(feature = "testing")]
+const LAYOUT_PASSES_PROP_NAME: &'static str = "NWG_LAYOUT_PASSES";
+
+thread_local! {
+    /// The windows whose children must be repositioned by the next `flush_window_layouts`
+    static DIRTY_LAYOUTS: RefCell<Vec<HWND>> = RefCell::new(Vec::new());
+
+    /// True while `flush_window_layouts` is running on this thread
+    static FLUSHING_LAYOUTS: Cell<bool> = Cell::new(false);
+}
+
+/// Name of the window property that holds the relative placement of a control
+const RELATIVE_PLACEMENT_PROP_NAME: &'static str = "NWG_RELATIVE_PLACEMENT";
+
+/// Name of the window property that holds the dock of a control
+const DOCK_PROP_NAME: &'static str = "NWG_DOCK";
+
+/// The anchor of a control and its distance to the edges of the parent client area when it was anchored
+struct AnchorData {
+    anchor: Anchor,
+    margins: (i32, i32, i32, i32)
+}
+
+/// Return the rectangle (left, top, right, bottom) of a window in the client coordinates of its parent
+unsafe fn parent_client_rect(handle: HWND, parent: HWND) -> (i32, i32, i32, i32) {
+    use user32::{GetWindowRect, MapWindowPoints};
+    use winapi::{RECT, POINT};
+
+    let mut r: RECT = mem::uninitialized();
+    GetWindowRect(handle, &mut r);
+    MapWindowPoints(ptr::null_mut(), parent, mem::transmute::<&mut RECT, *mut POINT>(&mut r), 2);
+
+    (r.left as i32, r.top as i32, r.right as i32, r.bottom as i32)
+}
+
+/// Return the direct children of a window, from the top of the Z order to the bottom
+unsafe fn direct_children(parent: HWND) -> Vec<HWND> {
+    use user32::{EnumChildWindows, GetParent};
+
+    unsafe extern "system" fn collect_children(handle: HWND, l: LPARAM) -> BOOL {
+        let children: &mut (HWND, Vec<HWND>) = mem::transmute(l);
+        if GetParent(handle) == children.0 {
+            children.1.push(handle);
+        }
+        1
+    }
+
+    // EnumChildWindows also enumerates the descendants
+    let mut children: (HWND, Vec<HWND>) = (parent, Vec::new());
+    EnumChildWindows(parent, Some(collect_children), mem::transmute(&mut children));
+
+    children.1
+}
+
+/// Return the size of the client area of a window
+unsafe fn client_size(handle: HWND) -> (i32, i32) {
+    use user32::GetClientRect;
+    use winapi::RECT;
+
+    let mut r: RECT = mem::uninitialized();
+    GetClientRect(handle, &mut r);
+
+    (r.right as i32, r.bottom as i32)
+}
+
+/**
+    Anchor a child window to the edges of its parent client area. Use `None` to remove the anchor.
+
+    The distances to the parent edges are captured when the function is called, so the function must be called
+    again if the control is moved manually. The children are repositioned by `update_anchored_children`, which the
+    events dispatcher calls when a window of a Ui is resized. The anchor of a docked control is ignored.
+
+    Return `false` if the window has no parent.
+*/
+pub unsafe fn set_window_anchor(handle: HWND, anchor: Option<Anchor>) -> bool {
+    use user32::{GetParent, SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(ANCHOR_PROP_NAME);
+    let old: *mut AnchorData = mem::transmute(RemovePropW(handle, prop_name.as_ptr()));
+    if !old.is_null() {
+        drop(Box::from_raw(old));
+    }
+
+    let anchor = match anchor {
+        Some(a) => a,
+        None => { return true; }
+    };
+
+    let parent = GetParent(handle);
+    if parent.is_null() {
+        return false;
+    }
+
+    let (left, top, right, bottom) = parent_client_rect(handle, parent);
+    let (width, height) = client_size(parent);
+
+    let data = Box::new(AnchorData{ anchor: anchor, margins: (left, top, width - right, height - bottom) });
+    SetPropW(handle, prop_name.as_ptr(), mem::transmute(Box::into_raw(data)));
+
+    true
+}
+
+/// Return the anchor of a child window set with `set_window_anchor`
+pub unsafe fn get_window_anchor(handle: HWND) -> Option<Anchor> {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(ANCHOR_PROP_NAME);
+    let data: *mut AnchorData = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if data.is_null() {
+        None
+    } else {
+        Some((&*data).anchor)
+    }
+}
+
+/// Compute the position of one axis of an anchored control
+fn anchor_axis(start: i32, end: i32, margin_start: i32, margin_end: i32, parent_size: i32, anchor_start: bool, anchor_end: bool) -> (i32, i32) {
+    let size = end - start;
+    match (anchor_start, anchor_end) {
+        (true, true) => (margin_start, (parent_size - margin_start - margin_end).max(0)),
+        (false, true) => (parent_size - margin_end - size, size),
+        _ => (start, size)
+    }
+}
+
+/**
+    Reposition the direct children of `parent` that were anchored with `set_window_anchor`.
+*/
+pub unsafe fn update_anchored_children(parent: HWND) {
+    use user32::{GetPropW, SetWindowPos};
+    use winapi::{SWP_NOZORDER, SWP_NOACTIVATE};
+
+    let (width, height) = client_size(parent);
+    let prop_name = to_utf16(ANCHOR_PROP_NAME);
+
+    for handle in direct_children(parent) {
+        let data: *mut AnchorData = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+        if data.is_null() || get_window_dock(handle).is_some() {
+            continue;
+        }
+
+        let data = &*data;
+        let (left, top, right, bottom) = parent_client_rect(handle, parent);
+        let (ml, mt, mr, mb) = data.margins;
+        let (x, w) = anchor_axis(left, right, ml, mr, width, data.anchor.left, data.anchor.right);
+        let (y, h) = anchor_axis(top, bottom, mt, mb, height, data.anchor.top, data.anchor.bottom);
+
+        SetWindowPos(handle, ptr::null_mut(), x, y, w, h, SWP_NOZORDER|SWP_NOACTIVATE);
+    }
+}
+
+/**
+    Dock a child window to an edge of its parent client area, or make it fill the remaining space with `Dock::Fill`.
+    Use `None` to undock the window. The docked children of the parent are repositioned immediately.
+
+    The docked children are processed from the bottom of the Z order to the top: each child takes its place against
+    an edge of the space left by the previous children. Children docked to the same edge are stacked.
+    The children are repositioned by `update_docked_children`, which the events dispatcher calls when a window of a Ui is resized.
+
+    Return `false` if the window has no parent.
+*/
+pub unsafe fn set_window_dock(handle: HWND, dock: Option<Dock>) -> bool {
+    use user32::{GetParent, SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(DOCK_PROP_NAME);
+    let parent = GetParent(handle);
+
+    match dock {
+        Some(dock) => {
+            if parent.is_null() {
+                return false;
+            }
+
+            // The value is offset by one so that a null property means "not docked"
+            let value = dock as usize + 1;
+            SetPropW(handle, prop_name.as_ptr(), mem::transmute(value));
+        },
+        None => {
+            RemovePropW(handle, prop_name.as_ptr());
+        }
+    }
+
+    if !parent.is_null() {
+        invalidate_window_layout(parent);
+        flush_window_layouts();
+    }
+
+    true
+}
+
+/// Return the dock of a child window set with `set_window_dock`
+pub unsafe fn get_window_dock(handle: HWND) -> Option<Dock> {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(DOCK_PROP_NAME);
+    let value: usize = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    match value {
+        1 => Some(Dock::Top),
+        2 => Some(Dock::Bottom),
+        3 => Some(Dock::Left),
+        4 => Some(Dock::Right),
+        5 => Some(Dock::Fill),
+        _ => None
+    }
+}
+
+/**
+    Reposition the direct children of `parent` that were docked with `set_window_dock`.
+*/
+pub unsafe fn update_docked_children(parent: HWND) {
+    use user32::SetWindowPos;
+    use winapi::{SWP_NOZORDER, SWP_NOACTIVATE};
+
+    let (width, height) = client_size(parent);
+    let (mut left, mut top, mut right, mut bottom) = (0, 0, width, height);
+
+    for handle in direct_children(parent).into_iter().rev() {
+        let dock = match get_window_dock(handle) {
+            Some(d) => d,
+            None => { continue; }
+        };
+
+        let (l, t, r, b) = parent_client_rect(handle, parent);
+        let (w, h) = (r - l, b - t);
+        let (x, y, w, h) = match dock {
+            Dock::Top => { top += h; (left, top - h, right - left, h) },
+            Dock::Bottom => { bottom -= h; (left, bottom, right - left, h) },
+            Dock::Left => { left += w; (left - w, top, w, bottom - top) },
+            Dock::Right => { right -= w; (right, top, w, bottom - top) },
+            Dock::Fill => (left, top, right - left, bottom - top)
+        };
+
+        SetWindowPos(handle, ptr::null_mut(), x, y, w.max(0), h.max(0), SWP_NOZORDER|SWP_NOACTIVATE);
+    }
+}
+
+/// Resolve a length against the size of the parent client area
+fn resolve_length(length: Length, parent_size: i32) -> i32 {
+    match length {
+        Length::Px(px) => px,
+        Length::Percent(p) => ((parent_size as f32) * p / 100.0).round() as i32
+    }
+}
+
+/// Move a child window to its relative placement
+unsafe fn apply_relative_placement(handle: HWND, placement: &RelativePlacement, parent_size: (i32, i32)) {
+    use user32::SetWindowPos;
+    use winapi::{SWP_NOZORDER, SWP_NOACTIVATE};
+
+    let (width, height) = parent_size;
+    let x = resolve_length(placement.x, width);
+    let y = resolve_length(placement.y, height);
+    let w = resolve_length(placement.width, width);
+    let h = resolve_length(placement.height, height);
+
+    SetWindowPos(handle, ptr::null_mut(), x, y, w.max(0), h.max(0), SWP_NOZORDER|SWP_NOACTIVATE);
+}
+
+/**
+    Place a child window relatively to the client area of its parent. Use `None` to remove the relative placement;
+    the window then keeps its current position and size.
+
+    The window is moved immediately and every time its parent is resized by `update_relative_children`, which the
+    events dispatcher calls when a window of a Ui is resized. The relative placement of a docked control is ignored.
+
+    Return `false` if the window has no parent.
+*/
+pub unsafe fn set_window_relative_placement(handle: HWND, placement: Option<RelativePlacement>) -> bool {
+    use user32::{GetParent, SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(RELATIVE_PLACEMENT_PROP_NAME);
+    let old: *mut RelativePlacement = mem::transmute(RemovePropW(handle, prop_name.as_ptr()));
+    if !old.is_null() {
+        drop(Box::from_raw(old));
+    }
+
+    let placement = match placement {
+        Some(p) => p,
+        None => { return true; }
+    };
+
+    let parent = GetParent(handle);
+    if parent.is_null() {
+        return false;
+    }
+
+    apply_relative_placement(handle, &placement, client_size(parent));
+    SetPropW(handle, prop_name.as_ptr(), mem::transmute(Box::into_raw(Box::new(placement))));
+
+    true
+}
+
+/// Return the relative placement of a child window set with `set_window_relative_placement`
+pub unsafe fn get_window_relative_placement(handle: HWND) -> Option<RelativePlacement> {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(RELATIVE_PLACEMENT_PROP_NAME);
+    let placement: *mut RelativePlacement = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if placement.is_null() {
+        None
+    } else {
+        Some(*placement)
+    }
+}
+
+/**
+    Reposition the direct children of `parent` that were placed with `set_window_relative_placement`.
+*/
+pub unsafe fn update_relative_children(parent: HWND) {
+    let parent_size = client_size(parent);
+
+    for handle in direct_children(parent) {
+        if get_window_dock(handle).is_some() {
+            continue;
+        }
+
+        if let Some(placement) = get_window_relative_placement(handle) {
+            apply_relative_placement(handle, &placement, parent_size);
+        }
+    }
+}
+
+/**
+    Reposition the docked, the relatively placed and the anchored children of `parent`, in that order.
+*/
+pub unsafe fn update_window_layout(parent: HWND) {
+    update_docked_children(parent);
+    update_relative_children(parent);
+    update_anchored_children(parent);
+
+    #[cfg(feature = "testing")]
+    count_layout_pass(parent);
+}
+
+/// Increment the number of layout passes of a window. See `get_window_layout_passes`.
+#[cfg(feature = "testing")]
+unsafe fn count_layout_pass(handle: HWND) {
+    use user32::{GetPropW, SetPropW};
+
+    let prop_name = to_utf16(LAYOUT_PASSES_PROP_NAME);
+    let passes: usize = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    SetPropW(handle, prop_name.as_ptr(), mem::transmute(passes + 1));
+}
+
+/**
+    Mark the layout of `parent` as dirty. The children of the window are repositioned by the next call to `flush_window_layouts`.  
+    The events dispatcher invalidates the layout of a window of a Ui when it is resized and flushes the layouts before
+    the callbacks of the message are called.
+*/
+pub unsafe fn invalidate_window_layout(parent: HWND) {
+    DIRTY_LAYOUTS.with(|dirty| {
+        let mut dirty = dirty.borrow_mut();
+        if !dirty.contains(&parent) {
+            dirty.push(parent);
+        }
+    });
+}
+
+/// Return the number of ancestors of a window
+unsafe fn window_depth(handle: HWND) -> usize {
+    use user32::GetParent;
+
+    let mut depth = 0;
+    let mut parent = GetParent(handle);
+    while !parent.is_null() {
+        depth += 1;
+        parent = GetParent(parent);
+    }
+
+    depth
+}
+
+/**
+    Update the layout of the windows invalidated with `invalidate_window_layout`, from the top level windows to their descendants.
+
+    Repositioning the children of a window resizes them, which invalidates their own layout. Those layouts are updated by the
+    same flush, so every dirty layout is only computed once even if the resize of a top level window goes through many nested layouts.  
+    Calls made while a flush is running on the thread do nothing.
+*/
+pub unsafe fn flush_window_layouts() {
+    use user32::IsWindow;
+
+    if FLUSHING_LAYOUTS.with(|f| f.get()) {
+        return;
+    }
+
+    FLUSHING_LAYOUTS.with(|f| f.set(true));
+
+    loop {
+        let mut dirty: Vec<HWND> = DIRTY_LAYOUTS.with(|dirty| mem::replace(&mut *dirty.borrow_mut(), Vec::new()));
+        if dirty.is_empty() {
+            break;
+        }
+
+        dirty.sort_by_key(|&h| window_depth(h));
+        for handle in dirty {
+            if IsWindow(handle) != 0 {
+                update_window_layout(handle);
+            }
+        }
+    }
+
+    FLUSHING_LAYOUTS.with(|f| f.set(false));
+}
+
+/**
+    Return the number of times the layout of a window was updated by `update_window_layout`.
+    Useful to check that a resize does not recompute the same layout many times. Requires the `testing` feature.
+*/
+#[cfg(feature = "testing")]
+pub unsafe fn get_window_layout_passes(handle: HWND) -> usize {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(LAYOUT_PASSES_PROP_NAME);
+    mem::transmute(GetPropW(handle, prop_name.as_ptr()))
+}
+
+/**
+    Remove the anchor, the dock, the relative placement and the layout state of a window. Must be called before the window is destroyed.  
+    Unlike the setters, the siblings of the window are not repositioned.
+*/
+pub unsafe fn clear_window_layout(handle: HWND) {
+    use user32::RemovePropW;
+
+    set_window_anchor(handle, None);
+    set_window_relative_placement(handle, None);
+
+    let dock_prop = to_utf16(DOCK_PROP_NAME);
+    RemovePropW(handle, dock_prop.as_ptr());
+
+    #[cfg(feature = "testing")]
+    RemovePropW(handle, to_utf16(LAYOUT_PASSES_PROP_NAME).as_ptr());
+
+    DIRTY_LAYOUTS.with(|dirty| dirty.borrow_mut().retain(|&h| h != handle));
+}
@@ -142,7 +142,8 @@ impl<ID: Hash+Clone+'static> MessageHandler<ID> {
 #[allow(unused_variables)]
 unsafe extern "system" fn message_window_proc<ID: Hash+Clone+'static>(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
     use user32::{DefWindowProcW};
-    use low::defs::{NWG_PACK_USER_VALUE, NWG_PACK_CONTROL, NWG_UNPACK, NWG_BIND, NWG_UNBIND, NWG_TRIGGER, NWG_PACK_RESOURCE, COMMIT_SUCCESS, COMMIT_FAILED};
+    use low::defs::{NWG_PACK_USER_VALUE, NWG_PACK_CONTROL, NWG_UNPACK, NWG_BIND, NWG_UNBIND, NWG_TRIGGER, NWG_PACK_RESOURCE, NWG_DEFERRED_UNPACK,
+      COMMIT_SUCCESS, COMMIT_FAILED};
     use low::defs::{PackUserValueArgs, PackControlArgs, UnpackArgs, BindArgs, UnbindArgs, PackResourceArgs, TriggerArgs};
 
     let ui: &mut UiInner<ID> = mem::transmute(w);
@@ -182,6 +183,16 @@ unsafe extern "system" fn message_window_proc<ID: Hash+Clone+'static>(hwnd: HWND
                 panic!("Could not downcast command NWG_UNPACK_CONTROL args into a inner id.");
             }
         },
+        NWG_DEFERRED_UNPACK => {
+            // Not executed by a commit, so there is no one to report the error to. If the control was already removed, the unpack fails silently.
+            let args: Box<Any> = Box::from_raw(*Box::from_raw(args));
+            if let Ok(params) = args.downcast::<UnpackArgs>() {
+                ui.unpack(*params);
+                (true, None)
+            } else {
+                panic!("Could not downcast command NWG_DEFERRED_UNPACK args into a inner id.");
+            }
+        },
         NWG_BIND => {
             let args: Box<Any> = Box::from_raw(*Box::from_raw(args));
             if let Ok(params) = args.downcast::<BindArgs<ID>>() {
@@ -238,28 +249,24 @@ unsafe extern "system" fn message_window_proc<ID: Hash+Clone+'static>(hwnd: HWND
     Create the message handler window class.
 
     * If the class creation is successful or the class already exists, returns `Ok`
-    * If there was an error while creating the class, returns a `Err(SystemError::UiCreation)`
+    * If there was an error while creating the class, returns the system error raised by `build_sysclass`
 */
 unsafe fn setup_class<ID: Hash+Clone+'static>() -> Result<(), SystemError> {
-    use low::window_helper::{SysclassParams, build_sysclass};
+    use low::window_helper::{SysclassParams, build_sysclass, NO_BACKGROUND_BRUSH};
     let params = SysclassParams{ 
         class_name: MESSAGE_HANDLE_CLASS_NAME, 
         sysproc: Some(message_window_proc::<ID>),
-        background: Some(ptr::null_mut()), style: None
+        background: Some(NO_BACKGROUND_BRUSH), style: None
     };
     
-    if let Err(_) = build_sysclass(params) {
-        Err(SystemError::UiCreation)
-    } else {
-        Ok(())
-    }
+    build_sysclass(params)
 }
 
 /**
     Create a NWG message-only window.
 
     * If the window creation is successful, returns `Ok(window_handle)`  
-    * If the window creation fails, returns `Err(SystemError::UiCreation)`
+    * If the window creation fails, returns `Err(SystemError::SystemCall{..})` with the system error message
 */
 unsafe fn create_window<ID: Hash+Clone>() -> Result<HWND, SystemError> {
     use kernel32::GetModuleHandleW;
@@ -267,7 +274,7 @@ unsafe fn create_window<ID: Hash+Clone>() -> Result<HWND, SystemError> {
     use winapi::HWND_MESSAGE;
 
     let hmod = GetModuleHandleW(ptr::null_mut());
-    if hmod.is_null() { return Err(SystemError::UiCreation); }
+    if hmod.is_null() { return Err(SystemError::from_last_error("GetModuleHandleW")); }
 
     let class_name = to_utf16(MESSAGE_HANDLE_CLASS_NAME);
     let window_name = to_utf16("");
@@ -283,7 +290,7 @@ unsafe fn create_window<ID: Hash+Clone>() -> Result<HWND, SystemError> {
     );
 
     if handle.is_null() {
-        Err(SystemError::UiCreation)
+        Err(SystemError::from_last_error(&format!("CreateWindowExW({})", MESSAGE_HANDLE_CLASS_NAME)))
     } else {
         Ok(handle)
     }
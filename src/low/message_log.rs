@@ -0,0 +1,106 @@
+/*!
+    Debug helpers that log the system messages received by the controls.
+    Only compiled with the `debug-messages` feature.
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::mem;
+
+use winapi::{HWND, UINT, WPARAM, LPARAM};
+
+use low::other_helper::to_utf16;
+
+/**
+    A function that receives the messages of a control. The arguments are the window handle, the symbolic name
+    of the message (or its hexadecimal value if the message is unknown), the message id, the WPARAM and the LPARAM.
+*/
+pub type MessageLogger = fn(HWND, &str, u32, usize, usize);
+
+/// Name of the window property that holds the message logger of a window
+const LOGGER_PROP_NAME: &'static str = "NWG_MESSAGE_LOGGER";
+
+/// Symbolic names of the most common window messages
+const MESSAGE_NAMES: &'static [(UINT, &'static str)] = &[
+    (0x0000, "WM_NULL"), (0x0001, "WM_CREATE"), (0x0002, "WM_DESTROY"), (0x0003, "WM_MOVE"), (0x0005, "WM_SIZE"),
+    (0x0006, "WM_ACTIVATE"), (0x0007, "WM_SETFOCUS"), (0x0008, "WM_KILLFOCUS"), (0x000A, "WM_ENABLE"), (0x000B, "WM_SETREDRAW"),
+    (0x000C, "WM_SETTEXT"), (0x000D, "WM_GETTEXT"), (0x000E, "WM_GETTEXTLENGTH"), (0x000F, "WM_PAINT"), (0x0010, "WM_CLOSE"),
+    (0x0012, "WM_QUIT"), (0x0014, "WM_ERASEBKGND"), (0x0015, "WM_SYSCOLORCHANGE"), (0x0018, "WM_SHOWWINDOW"),
+    (0x001A, "WM_SETTINGCHANGE"), (0x001C, "WM_ACTIVATEAPP"), (0x001F, "WM_CANCELMODE"), (0x0020, "WM_SETCURSOR"),
+    (0x0021, "WM_MOUSEACTIVATE"), (0x0024, "WM_GETMINMAXINFO"), (0x002B, "WM_DRAWITEM"), (0x002C, "WM_MEASUREITEM"),
+    (0x0030, "WM_SETFONT"), (0x0031, "WM_GETFONT"), (0x0046, "WM_WINDOWPOSCHANGING"), (0x0047, "WM_WINDOWPOSCHANGED"),
+    (0x004E, "WM_NOTIFY"), (0x007B, "WM_CONTEXTMENU"), (0x007C, "WM_STYLECHANGING"), (0x007D, "WM_STYLECHANGED"),
+    (0x007E, "WM_DISPLAYCHANGE"), (0x007F, "WM_GETICON"), (0x0080, "WM_SETICON"), (0x0081, "WM_NCCREATE"), (0x0082, "WM_NCDESTROY"),
+    (0x0083, "WM_NCCALCSIZE"), (0x0084, "WM_NCHITTEST"), (0x0085, "WM_NCPAINT"), (0x0086, "WM_NCACTIVATE"), (0x0087, "WM_GETDLGCODE"),
+    (0x00A0, "WM_NCMOUSEMOVE"), (0x00A1, "WM_NCLBUTTONDOWN"), (0x00A2, "WM_NCLBUTTONUP"), (0x00A3, "WM_NCLBUTTONDBLCLK"),
+    (0x0100, "WM_KEYDOWN"), (0x0101, "WM_KEYUP"), (0x0102, "WM_CHAR"), (0x0104, "WM_SYSKEYDOWN"), (0x0105, "WM_SYSKEYUP"),
+    (0x0106, "WM_SYSCHAR"), (0x0109, "WM_UNICHAR"), (0x0110, "WM_INITDIALOG"), (0x0111, "WM_COMMAND"), (0x0112, "WM_SYSCOMMAND"),
+    (0x0113, "WM_TIMER"), (0x0114, "WM_HSCROLL"), (0x0115, "WM_VSCROLL"), (0x0116, "WM_INITMENU"), (0x0117, "WM_INITMENUPOPUP"),
+    (0x011F, "WM_MENUSELECT"), (0x0126, "WM_MENUCOMMAND"), (0x0127, "WM_CHANGEUISTATE"), (0x0128, "WM_UPDATEUISTATE"),
+    (0x0129, "WM_QUERYUISTATE"), (0x0132, "WM_CTLCOLORMSGBOX"), (0x0133, "WM_CTLCOLOREDIT"), (0x0134, "WM_CTLCOLORLISTBOX"),
+    (0x0135, "WM_CTLCOLORBTN"), (0x0136, "WM_CTLCOLORDLG"), (0x0137, "WM_CTLCOLORSCROLLBAR"), (0x0138, "WM_CTLCOLORSTATIC"),
+    (0x0200, "WM_MOUSEMOVE"), (0x0201, "WM_LBUTTONDOWN"), (0x0202, "WM_LBUTTONUP"), (0x0203, "WM_LBUTTONDBLCLK"),
+    (0x0204, "WM_RBUTTONDOWN"), (0x0205, "WM_RBUTTONUP"), (0x0206, "WM_RBUTTONDBLCLK"), (0x0207, "WM_MBUTTONDOWN"),
+    (0x0208, "WM_MBUTTONUP"), (0x0209, "WM_MBUTTONDBLCLK"), (0x020A, "WM_MOUSEWHEEL"), (0x020E, "WM_MOUSEHWHEEL"),
+    (0x0210, "WM_PARENTNOTIFY"), (0x0214, "WM_SIZING"), (0x0215, "WM_CAPTURECHANGED"), (0x0216, "WM_MOVING"),
+    (0x0218, "WM_POWERBROADCAST"), (0x0219, "WM_DEVICECHANGE"), (0x0231, "WM_ENTERSIZEMOVE"), (0x0232, "WM_EXITSIZEMOVE"),
+    (0x0233, "WM_DROPFILES"), (0x02A1, "WM_MOUSEHOVER"), (0x02A3, "WM_MOUSELEAVE"), (0x02B1, "WM_WTSSESSION_CHANGE"),
+    (0x02E0, "WM_DPICHANGED"), (0x0300, "WM_CUT"), (0x0301, "WM_COPY"), (0x0302, "WM_PASTE"), (0x0303, "WM_CLEAR"),
+    (0x0304, "WM_UNDO"), (0x0318, "WM_PRINTCLIENT"), (0x031A, "WM_THEMECHANGED"), (0x031E, "WM_DWMCOMPOSITIONCHANGED"),
+];
+
+/**
+    Return the symbolic name of a window message. Return `None` if the message is not in the table.  
+    Messages in the `WM_USER` and `WM_APP` ranges are never named because their meaning depends on the window class.
+*/
+pub fn message_name(msg: u32) -> Option<&'static str> {
+    MESSAGE_NAMES.iter()
+      .find(|&&(id, _)| id == msg)
+      .map(|&(_, name)| name)
+}
+
+/**
+    Start logging the messages received by a window. Use `None` to stop logging.  
+    This only works on windows created by a Ui, because the messages are logged by the NWG events subclass.
+*/
+pub unsafe fn set_message_logger(handle: HWND, logger: Option<MessageLogger>) {
+    use user32::{SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(LOGGER_PROP_NAME);
+    match logger {
+        Some(l) => { SetPropW(handle, prop_name.as_ptr(), mem::transmute(l)); },
+        None => { RemovePropW(handle, prop_name.as_ptr()); }
+    }
+}
+
+/**
+    Send a message to the logger of the window, if any. Called by the NWG events subclass.
+*/
+pub unsafe fn log_message(handle: HWND, msg: UINT, w: WPARAM, l: LPARAM) {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(LOGGER_PROP_NAME);
+    let logger = GetPropW(handle, prop_name.as_ptr());
+    if logger.is_null() {
+        return;
+    }
+
+    let logger: MessageLogger = mem::transmute(logger);
+    match message_name(msg) {
+        Some(name) => logger(handle, name, msg, w as usize, l as usize),
+        None => logger(handle, &format!("0x{:04X}", msg), msg, w as usize, l as usize)
+    }
+}
@@ -25,4 +25,22 @@ pub mod events;
 pub mod message_handler;
 pub mod menu_helper;
 pub mod window_helper;
-pub mod other_helper;
\ No newline at end of file
+pub mod other_helper;
+pub mod taskbar_helper;
+pub mod layered_helper;
+pub mod tooltip_helper;
+pub mod gdi_helper;
+pub mod keyboard_helper;
+pub mod mouse_helper;
+pub mod display_helper;
+pub mod layout_helper;
+pub mod form_helper;
+
+#[cfg(feature = "debug-messages")]
+pub mod message_log;
+
+#[cfg(feature = "testing")]
+pub mod testing_helper;
+
+#[cfg(feature = "drag-drop")]
+pub mod drag_helper;
\ No newline at end of file
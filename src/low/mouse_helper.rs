@@ -0,0 +1,91 @@
+/*!
+    Helper functions to read the position of the mouse cursor
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use winapi::{HWND, POINT, RECT};
+
+/**
+    Return the position of the mouse cursor in screen coordinates.  
+    The position is read when the function is called, not when the current message was sent.
+*/
+pub fn cursor_position() -> (i32, i32) {
+    use user32::GetCursorPos;
+
+    let mut pt = POINT{x: 0, y: 0};
+    unsafe{ GetCursorPos(&mut pt); }
+    (pt.x as i32, pt.y as i32)
+}
+
+/**
+    Return the position of the mouse cursor relative to the client area of the window `handle`.  
+    Coordinates can be negative or bigger than the window size if the cursor is outside the window.
+*/
+pub unsafe fn cursor_client_position(handle: HWND) -> (i32, i32) {
+    use user32::{GetCursorPos, ScreenToClient};
+
+    let mut pt = POINT{x: 0, y: 0};
+    GetCursorPos(&mut pt);
+    ScreenToClient(handle, &mut pt);
+    (pt.x as i32, pt.y as i32)
+}
+
+/**
+    Return true if the mouse cursor is inside the client area of the window `handle`.  
+    This do not check if the window is covered by another window.
+*/
+pub unsafe fn is_cursor_inside(handle: HWND) -> bool {
+    is_cursor_within_margin(handle, 0)
+}
+
+/**
+    Return true if the mouse cursor is inside the client area of the window `handle` expanded by `margin` pixels on each side.  
+    A negative margin shrinks the area.
+*/
+pub unsafe fn is_cursor_within_margin(handle: HWND, margin: i32) -> bool {
+    use user32::{GetCursorPos, ScreenToClient, GetClientRect, PtInRect};
+
+    let mut pt = POINT{x: 0, y: 0};
+    let mut r: RECT = RECT{left: 0, top: 0, right: 0, bottom: 0};
+    GetCursorPos(&mut pt);
+    ScreenToClient(handle, &mut pt);
+    GetClientRect(handle, &mut r);
+
+    r.left -= margin; r.top -= margin;
+    r.right += margin; r.bottom += margin;
+
+    PtInRect(&r, pt) != 0
+}
+
+/**
+    Return the maximum time in milliseconds between the two clicks of a double click, as set by the user.  
+    Use it instead of a fixed delay when a custom control detects double clicks.
+*/
+pub fn system_double_click_time() -> u32 {
+    use user32::GetDoubleClickTime;
+    unsafe{ GetDoubleClickTime() as u32 }
+}
+
+/**
+    Return the distance in pixels (horizontal, vertical) the mouse must move with a button down before a drag starts, as set by the user.  
+    A move smaller than the threshold should still be handled as a click.
+*/
+pub fn system_drag_threshold() -> (u32, u32) {
+    use user32::GetSystemMetrics;
+    use winapi::{SM_CXDRAG, SM_CYDRAG};
+    unsafe{ (GetSystemMetrics(SM_CXDRAG) as u32, GetSystemMetrics(SM_CYDRAG) as u32) }
+}
@@ -21,10 +21,15 @@
 
 use std::ptr;
 use std::mem;
+use std::cell::Cell;
+use std::marker::PhantomData;
 
-use winapi::DWORD;
+use winapi::{DWORD, FARPROC, HWND};
 
-use defs::{MessageParams, MessageButtons, MessageIcons, MessageChoice};
+use defs::{MessageParams, MessageOptions, MessageButtons, MessageIcons, MessageChoice, KeepAwake, DpiAwareness};
+use error::{Error, SystemError};
+use low::defs::{DPI_AWARENESS_CONTEXT, SetThreadDpiAwarenessContextFn, GetThreadDpiAwarenessContextFn, AreDpiAwarenessContextsEqualFn,
+  GetDpiForWindowFn, USER_DEFAULT_SCREEN_DPI};
 
 /**
     Encode a string value into a utf16 string. Adds a null char at the end of the string.
@@ -52,6 +57,38 @@ pub fn from_utf16(s: &[u16]) -> String {
     os_string.into_string().unwrap_or("Decoding error".to_string())
 }
 
+/**
+    Truncate `text` so that its UTF-16 encoding (the length counted by the system) holds at most `max_length` units.
+    The text is always cut between two characters, so a surrogate pair (ex: an emoji) is never split.  
+    If `ellipsis` is true and the text is truncated, the last character is replaced by `…`.
+*/
+pub fn truncate_text<'a>(text: &'a str, max_length: usize, ellipsis: bool) -> String {
+    let length: usize = text.chars().map(|c| c.len_utf16()).sum();
+    if length <= max_length {
+        return text.to_string();
+    }
+
+    // The ellipsis takes one UTF-16 unit
+    let (max_length, suffix) = match (ellipsis, max_length) {
+        (true, 0) => (0, ""),
+        (true, m) => (m - 1, "\u{2026}"),
+        (false, m) => (m, "")
+    };
+
+    let mut truncated = String::with_capacity(text.len());
+    let mut used = 0;
+    for c in text.chars() {
+        used += c.len_utf16();
+        if used > max_length {
+            break;
+        }
+        truncated.push(c);
+    }
+
+    truncated.push_str(suffix);
+    truncated
+}
+
 /**
     Read a string from a wide char pointer. Undefined behaviour if [ptr] is not null terminated.
 */
@@ -92,14 +129,109 @@ pub unsafe fn get_system_error() -> (DWORD, String) {
   (code, error_message)
 }
 
+thread_local! {
+    /// Set to true once the visual styles were successfully enabled. An activation context is only active
+    /// on the thread that activated it, so every thread that creates a Ui must enable the visual styles.
+    static VISUAL_STYLES_ENABLED: Cell<bool> = Cell::new(false);
+}
+
 /**
-  Enable the Windows visual style in the application without having to use a manifest
+  Enable the Windows visual style in the application without having to use a manifest.
+
+  If the application already has an active activation context (ex: from a manifest), it is used as is.
+  Otherwise, NWG activates the manifest embedded in shell32.dll. Calling the function more than once on the same thread has no effect.
+  The activation context is only active on the calling thread.
+  If the application embeds its own manifest, use `enable_visual_styles_from_resource` instead.
+
+  This function is called when a Ui is created. A failure is not fatal: the controls will use the classic style.
+
+  Errors:  
+  • `Error::System(SystemError::VisualStyles)` if no activation context could be activated  
 */
-pub unsafe fn enable_visual_styles() {
-    use kernel32::{ActivateActCtx, CreateActCtxW, GetSystemDirectoryW};
-    use winapi::{MAX_PATH, ULONG, ACTCTXW, ULONG_PTR, ICC_STANDARD_CLASSES, ICC_DATE_CLASSES, ICC_PROGRESS_CLASS, ICC_WIN95_CLASSES, INITCOMMONCONTROLSEX};
+pub fn enable_visual_styles() -> Result<(), Error> {
+    if VISUAL_STYLES_ENABLED.with(|e| e.get()) {
+        return Ok(());
+    }
+
+    let activated = unsafe{ has_activation_context() || activate_shell32_context() };
+
+    // The common controls classes must be registered even if the visual styles could not be enabled
+    register_common_controls();
+
+    if !activated {
+        return Err(Error::System(SystemError::VisualStyles));
+    }
+
+    VISUAL_STYLES_ENABLED.with(|e| e.set(true));
+
+    Ok(())
+}
+
+/**
+  Enable the Windows visual style using a manifest embedded in the application executable. `resource_id` is the ID
+  of the manifest resource (`RT_MANIFEST`). This is the recommended way if the application ships its own manifest
+  under an ID that Windows does not load automatically.
+
+  The visual styles must be enabled before the first Ui is created, because `Ui::new` calls `enable_visual_styles`.
+  Once the visual styles are enabled, calling any of the two functions has no effect.
+
+  Errors:  
+  • `Error::System(SystemError::SystemCall)` if the manifest could not be found or activated  
+*/
+pub fn enable_visual_styles_from_resource(resource_id: u16) -> Result<(), Error> {
+    if VISUAL_STYLES_ENABLED.with(|e| e.get()) {
+        return Ok(());
+    }
+
+    let result = unsafe{ activate_module_context(resource_id) };
+    register_common_controls();
+
+    if result.is_ok() {
+        VISUAL_STYLES_ENABLED.with(|e| e.set(true));
+    }
+
+    result
+}
+
+/**
+  Register the common controls classes used by NWG
+*/
+fn register_common_controls() {
+    use winapi::{ICC_STANDARD_CLASSES, ICC_DATE_CLASSES, ICC_PROGRESS_CLASS, ICC_WIN95_CLASSES, ICC_INTERNET_CLASSES,
+      ICC_PAGESCROLLER_CLASS, ICC_COOL_CLASSES, INITCOMMONCONTROLSEX};
     use comctl32::InitCommonControlsEx;
-    use low::defs::{ACTCTX_FLAG_RESOURCE_NAME_VALID, ACTCTX_FLAG_SET_PROCESS_DEFAULT, ACTCTX_FLAG_ASSEMBLY_DIRECTORY_VALID};
+
+    let controls_classes = INITCOMMONCONTROLSEX {
+        dwSize: mem::size_of::<INITCOMMONCONTROLSEX>() as DWORD,
+        dwICC: ICC_DATE_CLASSES|ICC_STANDARD_CLASSES|ICC_PROGRESS_CLASS|ICC_WIN95_CLASSES|ICC_INTERNET_CLASSES|ICC_PAGESCROLLER_CLASS|ICC_COOL_CLASSES
+    };
+
+    unsafe{ InitCommonControlsEx(&controls_classes); }
+}
+
+/**
+  Check if an activation context is active on the current thread (ex: the application has a manifest)
+*/
+unsafe fn has_activation_context() -> bool {
+    use kernel32::{GetCurrentActCtx, ReleaseActCtx};
+    use winapi::HANDLE;
+
+    let mut handle: HANDLE = ptr::null_mut();
+    if GetCurrentActCtx(&mut handle) == 0 || handle.is_null() {
+        return false;
+    }
+
+    ReleaseActCtx(handle);
+    true
+}
+
+/**
+  Activate the manifest embedded in shell32.dll that enables the visual styles. Return `false` if the activation failed.
+*/
+unsafe fn activate_shell32_context() -> bool {
+    use kernel32::{ActivateActCtx, CreateActCtxW, GetSystemDirectoryW};
+    use winapi::{MAX_PATH, ULONG, ACTCTXW, ULONG_PTR, HANDLE};
+    use low::defs::{ACTCTX_FLAG_RESOURCE_NAME_VALID, ACTCTX_FLAG_ASSEMBLY_DIRECTORY_VALID};
 
     let mut sys_dir: Vec<u16> = Vec::with_capacity(MAX_PATH);
     sys_dir.set_len(MAX_PATH);
@@ -109,8 +241,10 @@ pub unsafe fn enable_visual_styles() {
 
     let mut activation_cookie: ULONG_PTR = 0;
     let mut act_ctx = ACTCTXW {
-        cbSize: mem::size_of::<ACTCTXW> as ULONG,
-        dwFlags: ACTCTX_FLAG_RESOURCE_NAME_VALID | ACTCTX_FLAG_SET_PROCESS_DEFAULT | ACTCTX_FLAG_ASSEMBLY_DIRECTORY_VALID,
+        cbSize: mem::size_of::<ACTCTXW>() as ULONG,
+        // The context is activated on the current thread. It is not set as the process default, so that
+        // a Ui created on another thread can activate it again.
+        dwFlags: ACTCTX_FLAG_RESOURCE_NAME_VALID | ACTCTX_FLAG_ASSEMBLY_DIRECTORY_VALID,
         lpSource: source.as_mut_ptr(),
         wProcessorArchitecture: 0,
         wLangId: 0,
@@ -121,14 +255,57 @@ pub unsafe fn enable_visual_styles() {
     };
 
     let handle = CreateActCtxW(&mut act_ctx);
-    ActivateActCtx(handle, &mut activation_cookie);
+    if handle == (-1isize as HANDLE) { // INVALID_HANDLE_VALUE
+        return false;
+    }
 
-    let controls_classes = INITCOMMONCONTROLSEX {
-        dwSize: mem::size_of::<INITCOMMONCONTROLSEX> as DWORD,
-        dwICC: ICC_DATE_CLASSES|ICC_STANDARD_CLASSES|ICC_PROGRESS_CLASS|ICC_WIN95_CLASSES
+    ActivateActCtx(handle, &mut activation_cookie) != 0
+}
+
+/**
+  Activate the manifest resource `resource_id` embedded in the application executable.
+*/
+unsafe fn activate_module_context(resource_id: u16) -> Result<(), Error> {
+    use kernel32::{ActivateActCtx, CreateActCtxW, GetModuleHandleW, GetModuleFileNameW};
+    use winapi::{MAX_PATH, ULONG, ACTCTXW, ULONG_PTR, HANDLE};
+    use low::defs::{ACTCTX_FLAG_RESOURCE_NAME_VALID, ACTCTX_FLAG_HMODULE_VALID};
+
+    let module = GetModuleHandleW(ptr::null());
+    if module.is_null() {
+        return Err(Error::from_last_error("GetModuleHandleW"));
+    }
+
+    let mut source: Vec<u16> = Vec::with_capacity(MAX_PATH);
+    source.set_len(MAX_PATH);
+    if GetModuleFileNameW(module, source.as_mut_ptr(), MAX_PATH as u32) == 0 {
+        return Err(Error::from_last_error("GetModuleFileNameW"));
+    }
+
+    let mut activation_cookie: ULONG_PTR = 0;
+    let mut act_ctx = ACTCTXW {
+        cbSize: mem::size_of::<ACTCTXW>() as ULONG,
+        // Like the shell32 context, the context is only activated on the current thread. The process default
+        // can only be set once, so a Ui created on another thread could not enable the visual styles.
+        dwFlags: ACTCTX_FLAG_RESOURCE_NAME_VALID | ACTCTX_FLAG_HMODULE_VALID,
+        lpSource: source.as_mut_ptr(),
+        wProcessorArchitecture: 0,
+        wLangId: 0,
+        lpAssemblyDirectory: ptr::null_mut(),
+        lpResourceName: mem::transmute(resource_id as usize), // MAKEINTRESOURCE
+        lpApplicationName: ptr::null_mut(),
+        hModule: module
     };
 
-    InitCommonControlsEx(&controls_classes);
+    let handle = CreateActCtxW(&mut act_ctx);
+    if handle == (-1isize as HANDLE) { // INVALID_HANDLE_VALUE
+        return Err(Error::from_last_error("CreateActCtxW"));
+    }
+
+    if ActivateActCtx(handle, &mut activation_cookie) == 0 {
+        return Err(Error::from_last_error("ActivateActCtx"));
+    }
+
+    Ok(())
 }
 
 /**
@@ -147,10 +324,22 @@ pub unsafe fn enable_com() {
     * params: A `MessageParams` structure that defines how the message box should look
 */
 pub fn message<'a>(params: &MessageParams) -> MessageChoice {
+    message_ex(params, &MessageOptions::default())
+}
+
+/**
+    Create an application wide message box using `MessageBoxExW`
+
+    Parameters:  
+    * params: A `MessageParams` structure that defines how the message box should look
+    * options: A `MessageOptions` structure that defines the default button, the z-order, the text alignment and the language of the message box
+*/
+pub fn message_ex<'a>(params: &MessageParams, options: &MessageOptions) -> MessageChoice {
     use winapi::{MB_ABORTRETRYIGNORE, MB_CANCELTRYCONTINUE, MB_OK, MB_OKCANCEL, MB_RETRYCANCEL, MB_YESNO,
-     MB_YESNOCANCEL, MB_ICONSTOP, MB_ICONINFORMATION, MB_ICONQUESTION, MB_ICONEXCLAMATION};
+     MB_YESNOCANCEL, MB_ICONSTOP, MB_ICONINFORMATION, MB_ICONQUESTION, MB_ICONEXCLAMATION, MB_DEFBUTTON1,
+     MB_DEFBUTTON2, MB_DEFBUTTON3, MB_TOPMOST, MB_RIGHT};
     use low::defs::{IDABORT, IDCANCEL, IDCONTINUE, IDIGNORE, IDNO, IDOK, IDRETRY, IDTRYAGAIN, IDYES};
-    use user32::MessageBoxW;
+    use user32::MessageBoxExW;
 
     let text = to_utf16(params.content);
     let title = to_utf16(params.title);
@@ -173,7 +362,17 @@ pub fn message<'a>(params: &MessageParams) -> MessageChoice {
         MessageIcons::Warning => MB_ICONEXCLAMATION
     };
 
-    let answer = unsafe{ MessageBoxW(ptr::null_mut(), text.as_ptr(), title.as_ptr(), buttons | icons) };
+    let default_button = match options.default_button {
+        2 => MB_DEFBUTTON2,
+        3 => MB_DEFBUTTON3,
+        _ => MB_DEFBUTTON1
+    };
+
+    let flags = buttons | icons | default_button |
+      if options.topmost     { MB_TOPMOST } else { 0 } |
+      if options.right_align { MB_RIGHT }   else { 0 };
+
+    let answer = unsafe{ MessageBoxExW(ptr::null_mut(), text.as_ptr(), title.as_ptr(), flags, options.lang) };
     match answer {
         IDABORT => MessageChoice::Abort,
         IDCANCEL => MessageChoice::Cancel,
@@ -234,4 +433,188 @@ pub fn simple_message<'a>(title: &'a str, content: &'a str) -> MessageChoice {
     };
 
     message(&params)
-}
\ No newline at end of file
+}
+
+/**
+    Prevent the system from sleeping (and the display from turning off with `KeepAwake::Display`) until the 
+    returned guard is dropped or until `allow_sleep` is called. 
+
+    The request is bound to the calling thread, so the guard cannot be sent to another thread.
+    When the guard is dropped, the thread goes back to the state it had before the call, so the guards can be nested.
+*/
+pub fn keep_awake(mode: KeepAwake) -> KeepAwakeGuard {
+    use kernel32::SetThreadExecutionState;
+    use low::defs::{ES_CONTINUOUS, ES_SYSTEM_REQUIRED, ES_DISPLAY_REQUIRED};
+
+    let flags = match mode {
+        KeepAwake::System => ES_CONTINUOUS | ES_SYSTEM_REQUIRED,
+        KeepAwake::Display => ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED
+    };
+
+    // Returns the previous state or 0 if the call failed
+    let previous = unsafe{ SetThreadExecutionState(flags) };
+
+    KeepAwakeGuard{ previous: previous, p: PhantomData }
+}
+
+/**
+    Return what the calling thread currently prevents the system from doing (see `keep_awake`), or `None` if the system can sleep.
+*/
+pub fn keep_awake_state() -> Option<KeepAwake> {
+    use kernel32::SetThreadExecutionState;
+    use low::defs::{ES_SYSTEM_REQUIRED, ES_DISPLAY_REQUIRED};
+
+    // Without `ES_CONTINUOUS`, the state of the thread is not changed
+    let state = unsafe{ SetThreadExecutionState(0) };
+    if state & ES_DISPLAY_REQUIRED != 0 {
+        Some(KeepAwake::Display)
+    } else if state & ES_SYSTEM_REQUIRED != 0 {
+        Some(KeepAwake::System)
+    } else {
+        None
+    }
+}
+
+/**
+    Let the system sleep and turn off the display normally. Cancels the previous `keep_awake` calls of the thread.
+*/
+pub fn allow_sleep() {
+    use kernel32::SetThreadExecutionState;
+    use low::defs::ES_CONTINUOUS;
+
+    unsafe{ SetThreadExecutionState(ES_CONTINUOUS); }
+}
+
+/**
+    A guard returned by `keep_awake`. Restores the previous state of the thread when dropped.
+*/
+#[must_use = "the system can sleep again as soon as the guard is dropped"]
+pub struct KeepAwakeGuard {
+    previous: DWORD,
+    p: PhantomData<*const ()>
+}
+
+impl Drop for KeepAwakeGuard {
+    fn drop(&mut self) {
+        use kernel32::SetThreadExecutionState;
+        use low::defs::ES_CONTINUOUS;
+
+        unsafe{ SetThreadExecutionState(ES_CONTINUOUS | self.previous); }
+    }
+}
+
+/**
+    Return the address of a function exported by user32 or `None` if the function does not exist in the system version.  
+    Used for the functions that are too recent to be linked directly.
+*/
+unsafe fn user32_proc<'a>(name: &'a str) -> Option<FARPROC> {
+    use kernel32::{GetModuleHandleW, GetProcAddress};
+    use std::ffi::CString;
+
+    let module = GetModuleHandleW(to_utf16("user32.dll").as_ptr());
+    let name = CString::new(name).unwrap();
+    match GetProcAddress(module, name.as_ptr()) {
+        p if p.is_null() => None,
+        p => Some(p)
+    }
+}
+
+fn dpi_context_value(awareness: DpiAwareness) -> DPI_AWARENESS_CONTEXT {
+    let value: isize = match awareness {
+        DpiAwareness::Unaware => -1,
+        DpiAwareness::SystemAware => -2,
+        DpiAwareness::PerMonitorAware => -3,
+        DpiAwareness::PerMonitorAwareV2 => -4,
+        DpiAwareness::UnawareGdiScaled => -5
+    };
+
+    value as DPI_AWARENESS_CONTEXT
+}
+
+/**
+    Change the DPI awareness of the calling thread until the returned guard is dropped. The previous awareness is then restored.  
+    Use it around the creation of windows that need a specific awareness, for example when hosting third-party UI.
+
+    The guard is bound to the calling thread, so it cannot be sent to another thread. Nested guards must be dropped in reverse order.  
+    Return an error if the system does not support the awareness (per thread awareness requires Windows 10 1607).
+*/
+pub fn dpi_awareness_context(awareness: DpiAwareness) -> Result<DpiAwarenessContext, Error> {
+    unsafe {
+        let set_context: SetThreadDpiAwarenessContextFn = match user32_proc("SetThreadDpiAwarenessContext") {
+            Some(p) => mem::transmute(p),
+            None => { return Err(Error::from_last_error("SetThreadDpiAwarenessContext")); }
+        };
+
+        let previous = set_context(dpi_context_value(awareness));
+        if previous.is_null() {
+            return Err(Error::from_last_error("SetThreadDpiAwarenessContext"));
+        }
+
+        Ok(DpiAwarenessContext{ previous: previous, set_context: set_context })
+    }
+}
+
+/**
+    Return the DPI awareness of the calling thread or `None` if the system does not support per thread awareness.
+*/
+pub fn thread_dpi_awareness() -> Option<DpiAwareness> {
+    unsafe {
+        let get_context: GetThreadDpiAwarenessContextFn = match user32_proc("GetThreadDpiAwarenessContext") {
+            Some(p) => mem::transmute(p),
+            None => { return None; }
+        };
+
+        let equal_contexts: AreDpiAwarenessContextsEqualFn = match user32_proc("AreDpiAwarenessContextsEqual") {
+            Some(p) => mem::transmute(p),
+            None => { return None; }
+        };
+
+        let current = get_context();
+        let values = [DpiAwareness::Unaware, DpiAwareness::SystemAware, DpiAwareness::PerMonitorAware,
+          DpiAwareness::PerMonitorAwareV2, DpiAwareness::UnawareGdiScaled];
+
+        values.iter().cloned().find(|&v| equal_contexts(current, dpi_context_value(v)) != 0)
+    }
+}
+
+/**
+    Return the DPI of the window. Use `GetDpiForWindow` (Windows 10 1607) and fall back to the DPI of the screen on older systems.  
+    The DPI is 96 at 100% scaling.
+*/
+pub unsafe fn window_dpi(handle: HWND) -> u32 {
+    use user32::{GetDC, ReleaseDC};
+    use gdi32::GetDeviceCaps;
+    use winapi::LOGPIXELSX;
+
+    if let Some(p) = user32_proc("GetDpiForWindow") {
+        let get_dpi: GetDpiForWindowFn = mem::transmute(p);
+        match get_dpi(handle) {
+            0 => {},
+            dpi => { return dpi; }
+        }
+    }
+
+    let dc = GetDC(handle);
+    if dc.is_null() {
+        return USER_DEFAULT_SCREEN_DPI;
+    }
+
+    let dpi = GetDeviceCaps(dc, LOGPIXELSX);
+    ReleaseDC(handle, dc);
+
+    if dpi > 0 { dpi as u32 } else { USER_DEFAULT_SCREEN_DPI }
+}
+
+/**
+    A guard returned by `dpi_awareness_context`. Restores the previous DPI awareness of the thread when dropped.
+*/
+pub struct DpiAwarenessContext {
+    previous: DPI_AWARENESS_CONTEXT,
+    set_context: SetThreadDpiAwarenessContextFn
+}
+
+impl Drop for DpiAwarenessContext {
+    fn drop(&mut self) {
+        unsafe{ (self.set_context)(self.previous); }
+    }
+}
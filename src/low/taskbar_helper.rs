@@ -0,0 +1,153 @@
+/*!
+    Low level taskbar helping functions
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::ptr;
+use std::mem;
+
+use winapi::{HWND, HICON, UINT, S_OK};
+
+use low::defs::ITaskbarList3;
+use low::other_helper::to_utf16;
+use error::{Error, SystemError};
+
+/// Name of the window property that holds the overlay icon of a window
+const OVERLAY_PROP_NAME: &'static str = "NWG_TASKBAR_OVERLAY";
+
+/// Identifier of the `TaskbarCreated` message. Initialized on the first call to `taskbar_created_message`.
+static mut TASKBAR_CREATED: UINT = 0;
+
+/**
+    Data saved on a window in order to reapply the overlay icon after the taskbar is recreated
+*/
+struct OverlayData {
+    icon: HICON,
+    description: Vec<u16>
+}
+
+/**
+    Return the identifier of the `TaskbarCreated` message. This message is broadcasted to every
+    top level window when explorer restarts.
+*/
+pub unsafe fn taskbar_created_message() -> UINT {
+    use user32::RegisterWindowMessageW;
+
+    if TASKBAR_CREATED == 0 {
+        let name = to_utf16("TaskbarCreated");
+        TASKBAR_CREATED = RegisterWindowMessageW(name.as_ptr());
+    }
+
+    TASKBAR_CREATED
+}
+
+/**
+    Create and initialize a new ITaskbarList3 instance. The caller must release the instance.
+*/
+unsafe fn create_taskbar() -> Result<*mut ITaskbarList3, Error> {
+    use ole32::CoCreateInstance;
+    use winapi::CLSCTX_INPROC_SERVER;
+    use low::clsid::{CLSID_TaskbarList, IID_ITaskbarList3};
+
+    let mut taskbar: *mut ITaskbarList3 = ptr::null_mut();
+    let r = CoCreateInstance(&CLSID_TaskbarList(), ptr::null_mut(), CLSCTX_INPROC_SERVER, &IID_ITaskbarList3(), mem::transmute(&mut taskbar));
+    if r != S_OK {
+        return Err(Error::System(SystemError::ComInstanceCreation("TaskbarList".to_string())));
+    }
+
+    if (&mut *taskbar).HrInit() != S_OK {
+        (&mut *taskbar).Release();
+        return Err(Error::System(SystemError::ComError("Failed to initialize the taskbar list".to_string())));
+    }
+
+    Ok(taskbar)
+}
+
+/**
+    Send the overlay icon saved on the window (if any) to the taskbar
+*/
+unsafe fn apply_overlay_icon(handle: HWND, data: Option<&OverlayData>) -> Result<(), Error> {
+    let taskbar = match create_taskbar() {
+        Ok(t) => &mut *t,
+        Err(e) => { return Err(e); }
+    };
+
+    let r = match data {
+        Some(d) => taskbar.SetOverlayIcon(handle, d.icon, d.description.as_ptr()),
+        None => taskbar.SetOverlayIcon(handle, ptr::null_mut(), ptr::null())
+    };
+
+    taskbar.Release();
+
+    if r != S_OK {
+        Err(Error::System(SystemError::ComError("Failed to set the taskbar overlay icon".to_string())))
+    } else {
+        Ok(())
+    }
+}
+
+/**
+    Set or remove (if `icon` is None) the taskbar overlay icon of a window. A copy of the icon is kept on
+    the window so that it can be reapplied if the taskbar is recreated.
+*/
+pub unsafe fn set_overlay_icon<'a>(handle: HWND, icon: Option<HICON>, description: &'a str) -> Result<(), Error> {
+    use user32::{SetPropW, CopyIcon};
+
+    free_overlay_icon(handle);
+
+    let data = match icon {
+        Some(i) => Some(OverlayData{ icon: CopyIcon(i), description: to_utf16(description) }),
+        None => None
+    };
+
+    let result = apply_overlay_icon(handle, data.as_ref());
+
+    if let Some(data) = data {
+        let prop_name = to_utf16(OVERLAY_PROP_NAME);
+        let data_ptr: *mut OverlayData = Box::into_raw(Box::new(data));
+        SetPropW(handle, prop_name.as_ptr(), mem::transmute(data_ptr));
+    }
+
+    result
+}
+
+/**
+    Reapply the overlay icon of a window. Called when the window receives the `TaskbarCreated` message.
+*/
+pub unsafe fn reapply_overlay_icon(handle: HWND) {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(OVERLAY_PROP_NAME);
+    let data_ptr: *mut OverlayData = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if !data_ptr.is_null() {
+        apply_overlay_icon(handle, Some(&*data_ptr)).ok();
+    }
+}
+
+/**
+    Free the overlay data saved on a window. Does not remove the overlay icon from the taskbar.
+*/
+pub unsafe fn free_overlay_icon(handle: HWND) {
+    use user32::{RemovePropW, DestroyIcon};
+
+    let prop_name = to_utf16(OVERLAY_PROP_NAME);
+    let data_ptr: *mut OverlayData = mem::transmute(RemovePropW(handle, prop_name.as_ptr()));
+    if !data_ptr.is_null() {
+        let data = Box::from_raw(data_ptr);
+        DestroyIcon(data.icon);
+    }
+}
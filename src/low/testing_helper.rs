@@ -0,0 +1,117 @@
+/*!
+    Functions that simulate the user input for automated tests.
+    Only compiled with the `testing` feature.
+
+    The messages are sent directly to the control (`SendMessageW`), so the events callbacks are executed
+    before the functions return. The input does not go through the system input queue: the mouse cursor
+    does not move and the keyboard state (`GetKeyState`) is not updated.
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::mem;
+
+use winapi::{HWND, UINT, WPARAM, LPARAM};
+
+use controls::AnyHandle;
+use error::Error;
+
+/// Return the window handle or `Error::ControlRequired` if the handle is not a window handle
+fn window_handle(handle: &AnyHandle) -> Result<HWND, Error> {
+    match handle {
+        &AnyHandle::HWND(h) => Ok(h),
+        _ => Err(Error::ControlRequired)
+    }
+}
+
+/**
+    Simulate a left click in the middle of a control.
+
+    Errors:  
+    • `Error::ControlRequired` if the handle is not a window handle  
+*/
+pub fn click(handle: &AnyHandle) -> Result<(), Error> {
+    use user32::{SendMessageW, GetClientRect};
+    use winapi::{RECT, WM_LBUTTONDOWN, WM_LBUTTONUP, MK_LBUTTON};
+
+    let hwnd = match window_handle(handle) {
+        Ok(h) => h,
+        Err(e) => { return Err(e); }
+    };
+
+    unsafe {
+        let mut r: RECT = mem::zeroed();
+        GetClientRect(hwnd, &mut r);
+
+        let (x, y) = ((r.right / 2) as u32, (r.bottom / 2) as u32);
+        let pos = ((y << 16) | (x & 0xFFFF)) as LPARAM;
+
+        SendMessageW(hwnd, WM_LBUTTONDOWN, MK_LBUTTON as WPARAM, pos);
+        SendMessageW(hwnd, WM_LBUTTONUP, 0, pos);
+    }
+
+    Ok(())
+}
+
+/**
+    Simulate the user typing `text` in a control. Each character is sent as a `WM_CHAR` message.
+
+    Errors:  
+    • `Error::ControlRequired` if the handle is not a window handle  
+*/
+pub fn type_text<'a>(handle: &AnyHandle, text: &'a str) -> Result<(), Error> {
+    use user32::SendMessageW;
+    use winapi::WM_CHAR;
+
+    let hwnd = match window_handle(handle) {
+        Ok(h) => h,
+        Err(e) => { return Err(e); }
+    };
+
+    for c in text.encode_utf16() {
+        unsafe{ SendMessageW(hwnd, WM_CHAR, c as WPARAM, 1); }
+    }
+
+    Ok(())
+}
+
+/**
+    Simulate the user pressing and releasing the key with the virtual key code `vk` over a control.
+
+    Errors:  
+    • `Error::ControlRequired` if the handle is not a window handle  
+*/
+pub fn send_key(handle: &AnyHandle, vk: u32) -> Result<(), Error> {
+    use user32::{SendMessageW, MapVirtualKeyW};
+    use winapi::{WM_KEYDOWN, WM_KEYUP};
+
+    let hwnd = match window_handle(handle) {
+        Ok(h) => h,
+        Err(e) => { return Err(e); }
+    };
+
+    unsafe {
+        // Repeat count of 1 and scan code. The key up message also sets the previous state and transition bits.
+        let scan_code = MapVirtualKeyW(vk as UINT, 0) & 0xFF;
+        let down = (1 | (scan_code << 16)) as LPARAM;
+        let up = (1 | (scan_code << 16) | (1 << 30) | (1 << 31)) as LPARAM;
+
+        SendMessageW(hwnd, WM_KEYDOWN, vk as WPARAM, down);
+        SendMessageW(hwnd, WM_KEYUP, vk as WPARAM, up);
+    }
+
+    Ok(())
+}
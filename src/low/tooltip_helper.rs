@@ -0,0 +1,192 @@
+/*!
+    Low level helping functions for the tooltips that show the full text of a truncated control text
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::mem;
+
+use winapi::{HWND, UINT, WPARAM, LPARAM, LRESULT, UINT_PTR, DWORD_PTR, TOOLINFOW};
+
+use low::other_helper::{to_utf16, from_utf16};
+use error::Error;
+
+/// Name of the window property that holds the auto tooltip of a control
+const AUTO_TOOLTIP_PROP_NAME: &'static str = "NWG_AUTO_TOOLTIP";
+
+const AUTO_TOOLTIP_ID: UINT_PTR = 5675;
+
+/**
+    A function that returns the full text under the point (x, y) of the control (in client coordinates)
+    if the text is truncated. Returns `None` if there is no text under the point or if the text fits.
+*/
+pub type TruncatedText = unsafe fn(HWND, i32, i32) -> Option<String>;
+
+/// The tooltip of a control created with `set_auto_tooltip`
+struct AutoTooltip {
+    tooltip: HWND,
+    text: TruncatedText,
+    current: Vec<u16>
+}
+
+/**
+    Create a tooltip that shows the full text of a control when the mouse is over a truncated text.
+    `text` is called every time the mouse moves over the control to find the text under the mouse.
+
+    The tooltip is owned by `parent` and is destroyed with the control.
+
+    Errors:  
+    • `Error::System(..)` if the tooltip could not be created or attached to the control  
+*/
+pub unsafe fn set_auto_tooltip(handle: HWND, parent: HWND, text: TruncatedText) -> Result<(), Error> {
+    use low::window_helper::{WindowParams, build_window};
+    use winapi::{WS_POPUP, WS_EX_TOPMOST, TTS_NOPREFIX, TTS_ALWAYSTIP, TTM_ADDTOOLW};
+    use user32::{SendMessageW, SetPropW, RemovePropW, DestroyWindow};
+    use comctl32::SetWindowSubclass;
+
+    let params = WindowParams {
+        title: "",
+        class_name: "tooltips_class32",
+        position: (0, 0),
+        size: (0, 0),
+        flags: WS_POPUP | TTS_NOPREFIX | TTS_ALWAYSTIP,
+        ex_flags: Some(WS_EX_TOPMOST),
+        parent: parent
+    };
+
+    let tooltip = match build_window(params) {
+        Ok(h) => h,
+        Err(e) => { return Err(Error::System(e)); }
+    };
+
+    let mut current = vec![0u16];
+    let mut tool = tool_info(handle, &mut current);
+    if SendMessageW(tooltip, TTM_ADDTOOLW, 0, &mut tool as *mut TOOLINFOW as LPARAM) == 0 {
+        DestroyWindow(tooltip);
+        return Err(Error::from_last_error("TTM_ADDTOOLW"));
+    }
+
+    let prop_name = to_utf16(AUTO_TOOLTIP_PROP_NAME);
+    let data: *mut AutoTooltip = Box::into_raw(Box::new(AutoTooltip{ tooltip: tooltip, text: text, current: current }));
+    SetPropW(handle, prop_name.as_ptr(), mem::transmute(data));
+
+    if SetWindowSubclass(handle, Some(auto_tooltip_proc), AUTO_TOOLTIP_ID, 0) == 0 {
+        let error = Error::from_last_error("SetWindowSubclass");
+        RemovePropW(handle, prop_name.as_ptr());
+        drop(Box::from_raw(data));
+        DestroyWindow(tooltip);
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+/**
+    Return the text currently shown by the auto tooltip of a control, or `None` if the control has no auto tooltip.
+    The text is empty if the mouse was not over a truncated text the last time it moved over the control.
+*/
+pub unsafe fn auto_tooltip_text(handle: HWND) -> Option<String> {
+    match auto_tooltip(handle) {
+        Some(data) => Some(from_utf16(&(&*data).current)),
+        None => None
+    }
+}
+
+/**
+    Return the width of `text` drawn with the font of the control, or `None` if the text could not be measured.
+*/
+pub unsafe fn text_width<'a>(handle: HWND, text: &'a str) -> Option<u32> {
+    use user32::{GetDC, ReleaseDC, SendMessageW};
+    use gdi32::{GetTextExtentPoint32W, SelectObject};
+    use winapi::{SIZE, WM_GETFONT, HGDIOBJ, c_int};
+
+    let dc = GetDC(handle);
+    if dc.is_null() { return None; }
+
+    let font = SendMessageW(handle, WM_GETFONT, 0, 0) as HGDIOBJ;
+    let old_font = if font.is_null() { font } else { SelectObject(dc, font) };
+
+    let text = to_utf16(text);
+    let mut size: SIZE = mem::zeroed();
+    let ok = GetTextExtentPoint32W(dc, text.as_ptr(), (text.len() - 1) as c_int, &mut size);
+
+    if !old_font.is_null() { SelectObject(dc, old_font); }
+    ReleaseDC(handle, dc);
+
+    if ok != 0 { Some(size.cx as u32) } else { None }
+}
+
+// Private functions
+
+unsafe fn auto_tooltip(handle: HWND) -> Option<*mut AutoTooltip> {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(AUTO_TOOLTIP_PROP_NAME);
+    let data: *mut AutoTooltip = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if data.is_null() { None } else { Some(data) }
+}
+
+/// The tool of the tooltip covers the whole control
+unsafe fn tool_info(handle: HWND, text: &mut Vec<u16>) -> TOOLINFOW {
+    use user32::GetParent;
+    use winapi::{TTF_IDISHWND, TTF_SUBCLASS};
+
+    let mut tool: TOOLINFOW = mem::zeroed();
+    tool.cbSize = mem::size_of::<TOOLINFOW>() as UINT;
+    tool.uFlags = TTF_IDISHWND | TTF_SUBCLASS;
+    tool.hwnd = GetParent(handle);
+    tool.uId = handle as UINT_PTR;
+    tool.lpszText = text.as_mut_ptr() as *mut _;
+    tool
+}
+
+#[allow(unused_variables)]
+unsafe extern "system" fn auto_tooltip_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, id: UINT_PTR, data: DWORD_PTR) -> LRESULT {
+    use comctl32::{DefSubclassProc, RemoveWindowSubclass};
+    use user32::{SendMessageW, RemovePropW, DestroyWindow};
+    use winapi::{WM_MOUSEMOVE, WM_NCDESTROY, TTM_UPDATETIPTEXTW, TTM_POP, GET_X_LPARAM, GET_Y_LPARAM};
+
+    match msg {
+        WM_MOUSEMOVE => if let Some(data) = auto_tooltip(hwnd) {
+            let data = &mut *data;
+            let text = match (data.text)(hwnd, GET_X_LPARAM(l), GET_Y_LPARAM(l)) {
+                Some(t) => to_utf16(&t),
+                None => vec![0u16]
+            };
+
+            // The tooltip is hidden when the mouse moves to another text, so it does not show the old text
+            if text != data.current {
+                data.current = text;
+                let mut tool = tool_info(hwnd, &mut data.current);
+                SendMessageW(data.tooltip, TTM_POP, 0, 0);
+                SendMessageW(data.tooltip, TTM_UPDATETIPTEXTW, 0, &mut tool as *mut TOOLINFOW as LPARAM);
+            }
+        },
+        WM_NCDESTROY => {
+            let prop_name = to_utf16(AUTO_TOOLTIP_PROP_NAME);
+            let data: *mut AutoTooltip = mem::transmute(RemovePropW(hwnd, prop_name.as_ptr()));
+            if !data.is_null() {
+                DestroyWindow((&*data).tooltip);
+                drop(Box::from_raw(data));
+            }
+
+            RemoveWindowSubclass(hwnd, Some(auto_tooltip_proc), AUTO_TOOLTIP_ID);
+        },
+        _ => {}
+    }
+
+    DefSubclassProc(hwnd, msg, w, l)
+}
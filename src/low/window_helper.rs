@@ -22,21 +22,31 @@ use std::ptr;
 use std::mem;
 use std::hash::Hash;
 
-use winapi::{HWND, HFONT, HBRUSH, WNDPROC, DWORD, LPARAM, BOOL, c_int};
+use winapi::{HWND, HFONT, HICON, HCURSOR, HBRUSH, WNDPROC, DWORD, LPARAM, WPARAM, UINT, BOOL, c_int, UINT_PTR, DWORD_PTR, SUBCLASSPROC, GUID};
 
 use ui::{UiInner, Ui};
 use controls::{AnyHandle};
 use low::other_helper::to_utf16;
 use error::{Error, SystemError};
 
+/**
+    A class background that do not erase anything (a null brush). The system never paints the background of the windows of the class,
+    so the window procedure owns all the painting. Use it for custom drawn, OpenGL or DirectX controls, where any erase
+    is visible as a white or black flash when the window is resized.
+*/
+pub const NO_BACKGROUND_BRUSH: HBRUSH = 0 as HBRUSH;
+
 /**
     Params used to build a system class
 
     Members:  
     • `class_name`: System class name  
     • `sysproc`: The system class procedure  
-    • `background`: If specified, the background color of the created window  
+    • `background`: The brush used to erase the background of the windows of the class. If `None`, use the default window color.
+       Use `Some(NO_BACKGROUND_BRUSH)` to never erase the background.  
     • `style`: System class style  
+
+    The parameters are only used when the class is registered, the first time `build_sysclass` is called with the class name.
 */
 pub struct SysclassParams<S: Into<String>> {
     pub class_name: S,
@@ -70,7 +80,7 @@ pub struct WindowParams<S1: Into<String>, S2: Into<String>> {
     Try to create a system class using the parameters provided in `SysclassParams`. Will not fail if
     the system class already exists.
     
-    Returns `Err(SystemError::SysclassCreationFailed)` if the system class creation failed.
+    Returns `Err(SystemError::SystemCall{..})` if the system class creation failed. The error holds the system error message.
 
     Note that if the system class window proc used is malformed, the program will most likely segfault.
 */
@@ -80,7 +90,7 @@ pub unsafe fn build_sysclass<S: Into<String>>(p: SysclassParams<S>) -> Result<()
     use winapi::{WNDCLASSEXW, CS_HREDRAW, CS_VREDRAW, IDC_ARROW, COLOR_WINDOW, UINT, ERROR_CLASS_ALREADY_EXISTS};
 
     let hmod = GetModuleHandleW(ptr::null_mut());
-    if hmod.is_null() { return Err(SystemError::SystemClassCreation); }
+    if hmod.is_null() { return Err(SystemError::from_last_error("GetModuleHandleW")); }
 
     let class_name = to_utf16(p.class_name.into().as_ref());
 
@@ -112,7 +122,7 @@ pub unsafe fn build_sysclass<S: Into<String>>(p: SysclassParams<S>) -> Result<()
 
     let class_token = RegisterClassExW(&class);
     if class_token == 0 && GetLastError() != ERROR_CLASS_ALREADY_EXISTS { 
-        Err(SystemError::SystemClassCreation)
+        Err(SystemError::from_last_error("RegisterClassExW"))
     } else {
         Ok(())
     }
@@ -122,19 +132,28 @@ pub unsafe fn build_sysclass<S: Into<String>>(p: SysclassParams<S>) -> Result<()
     Try to create a system class using the parameters provided in `WindowParams`.
     
     Returns `Ok(HWND)` where HWND is the newly created window handle
-    Returns `Err(SystemError::WindowCreationFail)` if the system window creation failed.
+    Returns `Err(SystemError::ParentNotFound)` if the parent is not null, not `HWND_MESSAGE` and is not an existing window.
+    Returns `Err(SystemError::SystemCall{..})` if the system window creation failed. The error holds the system error message
+    and the name of the class (ex: `CreateWindowExW(MyClass)`), so an unregistered class is easy to find.
 
     Note that if the system class window proc used is malformed, the program will most likely segfault.
 */
 pub unsafe fn build_window<S1: Into<String>, S2: Into<String>>(p: WindowParams<S1, S2>) -> Result<HWND, SystemError>{
     use kernel32::GetModuleHandleW;
-    use user32::{CreateWindowExW, GetDesktopWindow, GetWindowRect};
-    use winapi::{WS_EX_COMPOSITED, RECT};
+    use user32::{CreateWindowExW, GetDesktopWindow, GetWindowRect, IsWindow};
+    use winapi::{WS_EX_COMPOSITED, RECT, HWND_MESSAGE};
 
     let hmod = GetModuleHandleW(ptr::null_mut());
-    if hmod.is_null() { return Err(SystemError::WindowCreationFail); }
+    if hmod.is_null() { return Err(SystemError::from_last_error("GetModuleHandleW")); }
 
-    let class_name = to_utf16(p.class_name.into().as_ref());
+    // CreateWindowExW only returns a generic "invalid window handle" error for a bad parent.
+    // HWND_MESSAGE is not a real window, it creates a message-only window.
+    if !p.parent.is_null() && p.parent != HWND_MESSAGE && IsWindow(p.parent) == 0 {
+        return Err(SystemError::ParentNotFound);
+    }
+
+    let class_name_str: String = p.class_name.into();
+    let class_name = to_utf16(&class_name_str);
     let window_name = to_utf16(p.title.into().as_ref());
 
     let px = match p.position.0 { 
@@ -175,7 +194,7 @@ pub unsafe fn build_window<S1: Into<String>, S2: Into<String>>(p: WindowParams<S
     );
 
     if handle.is_null() {
-        Err(SystemError::WindowCreationFail)
+        Err(SystemError::from_last_error(&format!("CreateWindowExW({})", class_name_str)))
     } else {
         fix_overlapped_window_size(handle, p.size);
         Ok(handle)
@@ -274,6 +293,12 @@ pub unsafe fn set_window_text<'a>(handle: HWND, text: &'a str) {
     SetWindowTextW(handle, text.as_ptr());
 }
 
+/// Set the window text, truncated to `max_length` UTF-16 units. See `truncate_text`.
+pub unsafe fn set_window_text_truncated<'a>(handle: HWND, text: &'a str, max_length: usize, ellipsis: bool) {
+    use low::other_helper::truncate_text;
+    set_window_text(handle, &truncate_text(text, max_length, ellipsis));
+}
+
 
 /// Set window position
 #[inline(always)]
@@ -326,6 +351,30 @@ pub unsafe fn get_window_size(handle: HWND) -> (u32, u32) {
     (r.right as u32, r.bottom as u32)
 }
 
+/// Get the window rectangle (left, top, right, bottom) in screen coordinates
+#[inline(always)]
+pub unsafe fn get_window_screen_rect(handle: HWND) -> (i32, i32, i32, i32) {
+    use user32::GetWindowRect;
+    use winapi::RECT;
+    
+    let mut r: RECT = mem::uninitialized();
+    GetWindowRect(handle, &mut r);
+
+    (r.left as i32, r.top as i32, r.right as i32, r.bottom as i32)
+}
+
+/// Get the window client area (x, y, width, height) in client coordinates
+#[inline(always)]
+pub unsafe fn get_window_client_rect(handle: HWND) -> (i32, i32, u32, u32) {
+    use user32::GetClientRect;
+    use winapi::RECT;
+    
+    let mut r: RECT = mem::uninitialized();
+    GetClientRect(handle, &mut r);
+
+    (r.left as i32, r.top as i32, (r.right - r.left) as u32, (r.bottom - r.top) as u32)
+}
+
 /// Get the window enabled state
 #[inline(always)]
 pub unsafe fn get_window_enabled(handle: HWND) -> bool {
@@ -372,6 +421,97 @@ pub unsafe fn get_window_visibility(handle: HWND) -> bool {
     IsWindowVisible(handle) != 0
 }
 
+/**
+    Set the range, the position and the page size of a scroll bar in a single call, so that the scroll bar is only redrawn once.  
+    `bar` is `SB_HORZ` or `SB_VERT` for the standard scroll bars of a window, or `SB_CTL` for a scroll bar control.
+*/
+pub unsafe fn set_window_scroll_info(handle: HWND, bar: c_int, min: i32, max: i32, pos: i32, page: u32) {
+    use user32::SetScrollInfo;
+    use winapi::{SCROLLINFO, SIF_RANGE, SIF_PAGE, SIF_POS, TRUE};
+
+    let info = SCROLLINFO {
+        cbSize: mem::size_of::<SCROLLINFO>() as UINT,
+        fMask: SIF_RANGE | SIF_PAGE | SIF_POS,
+        nMin: min as c_int,
+        nMax: max as c_int,
+        nPage: page as UINT,
+        nPos: pos as c_int,
+        nTrackPos: 0
+    };
+
+    SetScrollInfo(handle, bar, &info, TRUE);
+}
+
+/**
+    Return the range, the position and the page size of a scroll bar as `(min, max, pos, page)`.  
+    See `set_window_scroll_info` for the values of `bar`.
+*/
+pub unsafe fn get_window_scroll_info(handle: HWND, bar: c_int) -> (i32, i32, i32, u32) {
+    use user32::GetScrollInfo;
+    use winapi::{SCROLLINFO, SIF_RANGE, SIF_PAGE, SIF_POS};
+
+    let mut info = SCROLLINFO {
+        cbSize: mem::size_of::<SCROLLINFO>() as UINT,
+        fMask: SIF_RANGE | SIF_PAGE | SIF_POS,
+        nMin: 0, nMax: 0, nPage: 0, nPos: 0, nTrackPos: 0
+    };
+
+    GetScrollInfo(handle, bar, &mut info);
+
+    (info.nMin as i32, info.nMax as i32, info.nPos as i32, info.nPage as u32)
+}
+
+/**
+    Convert a horizontal length in dialog units to pixels for the window `handle`. The conversion uses `MapDialogRect`
+    if the window is a dialog and the system dialog base units otherwise.  
+    Dialog units are used by the listbox tab stops.
+*/
+pub unsafe fn dialog_units_to_pixels(du: u32, handle: HWND) -> u32 {
+    use user32::{MapDialogRect, GetDialogBaseUnits};
+    use winapi::{RECT, LOWORD};
+
+    let mut r = RECT{left: 0, top: 0, right: du as i32, bottom: 0};
+    if MapDialogRect(handle, &mut r) != 0 {
+        (r.right - r.left) as u32
+    } else {
+        let base_x = LOWORD(GetDialogBaseUnits() as u32) as u32;
+        (du * base_x) / 4
+    }
+}
+
+/**
+    Return the parent of a child window. Return `None` if the window is a top level window.  
+    The owner of a top level window is not considered to be its parent.
+*/
+pub unsafe fn get_window_parent(handle: HWND) -> Option<HWND> {
+    use user32::GetParent;
+    use winapi::{GWL_STYLE, WS_CHILD};
+
+    if (get_window_long(handle, GWL_STYLE) as u32) & WS_CHILD != WS_CHILD {
+        return None;
+    }
+
+    let parent = GetParent(handle);
+    if parent.is_null() { None } else { Some(parent) }
+}
+
+/**
+    Return the top level window that contains the window. If the window is a top level window, return the window itself.
+*/
+pub unsafe fn get_root_window(handle: HWND) -> HWND {
+    use user32::GetAncestor;
+    use low::defs::GA_ROOT;
+
+    let root = GetAncestor(handle, GA_ROOT);
+    if root.is_null() { handle } else { root }
+}
+
+/// Return true if the window `handle` is a child or a descendant of the window `ancestor`
+pub unsafe fn is_window_child_of(handle: HWND, ancestor: HWND) -> bool {
+    use user32::IsChild;
+    IsChild(ancestor, handle) != 0
+}
+
 
 #[inline(always)]
 pub fn handle_of_window<ID: Clone+Hash>(ui: &Ui<ID>, id: &ID, err: &'static str) -> Result<HWND, Error> {
@@ -391,33 +531,497 @@ pub fn handle_of_font<ID: Clone+Hash>(ui: &Ui<ID>, id: &ID, err: &'static str) -
     }
 } 
 
-#[cfg(target_arch = "x86")] use winapi::LONG;
-#[cfg(target_arch = "x86_64")] use winapi::LONG_PTR;
+#[inline(always)]
+pub fn handle_of_icon<ID: Clone+Hash>(ui: &Ui<ID>, id: &ID, err: &'static str) -> Result<HICON, Error> {
+    match ui.handle_of(id) {
+        Ok(AnyHandle::HICON(h)) => Ok(h),
+        Ok(_) => Err(Error::BadResource(err.to_string())),
+        Err(e) => Err(e)
+    }
+}
+
+/**
+    Set the big (`ICON_BIG`) or the small (`ICON_SMALL`) icon of a window. Use `None` to remove the icon.
+
+    Returns `Err(SystemError::SystemCall{..})` if the window or the icon handle is not valid.
+*/
+pub unsafe fn set_window_icon(handle: HWND, size: usize, icon: Option<HICON>) -> Result<(), SystemError> {
+    use user32::{SendMessageW, GetIconInfo};
+    use kernel32::{SetLastError, GetLastError};
+    use gdi32::DeleteObject;
+    use winapi::{WM_SETICON, LPARAM, ICONINFO};
+
+    let icon: LPARAM = match icon {
+        Some(h) => {
+            // Validate the icon before it is sent to the window
+            let mut info: ICONINFO = mem::uninitialized();
+            if GetIconInfo(h, &mut info) == 0 {
+                return Err(SystemError::from_last_error("GetIconInfo"));
+            }
+            if !info.hbmMask.is_null() { DeleteObject(mem::transmute(info.hbmMask)); }
+            if !info.hbmColor.is_null() { DeleteObject(mem::transmute(info.hbmColor)); }
+
+            mem::transmute(h)
+        },
+        None => 0
+    };
+
+    // WM_SETICON returns the previous icon, so the failures can only be detected with the last error
+    SetLastError(0);
+    SendMessageW(handle, WM_SETICON, size, icon);
+    if GetLastError() != 0 {
+        Err(SystemError::from_last_error("SendMessageW"))
+    } else {
+        Ok(())
+    }
+}
+
+/**
+    Get the big (`ICON_BIG`) or the small (`ICON_SMALL`) icon of a window.
+
+    Returns `Err(SystemError::SystemCall{..})` if the window handle is not valid.
+*/
+pub unsafe fn get_window_icon(handle: HWND, size: usize) -> Result<Option<HICON>, SystemError> {
+    use user32::SendMessageW;
+    use kernel32::{SetLastError, GetLastError};
+    use winapi::WM_GETICON;
+
+    SetLastError(0);
+    let icon: HICON = mem::transmute(SendMessageW(handle, WM_GETICON, size, 0));
+    if GetLastError() != 0 {
+        Err(SystemError::from_last_error("SendMessageW"))
+    } else if icon.is_null() {
+        Ok(None)
+    } else {
+        Ok(Some(icon))
+    }
+}
+
+/// Name of the window property that holds the cursor of a window
+const CURSOR_PROP_NAME: &'static str = "NWG_CURSOR";
+
+/**
+    Set the cursor displayed when the mouse is over the client area of the window. If `cursor` is `None`,
+    the window uses the cursor of its class (or the cursor of its parent) again.
+
+    The window does not own the cursor, so the cursor must outlive the window or be removed before it is destroyed.
+    This only works on windows created by a Ui, because the cursor is applied by the NWG events subclass.
+*/
+pub unsafe fn set_window_cursor(handle: HWND, cursor: Option<HCURSOR>) {
+    use user32::{SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(CURSOR_PROP_NAME);
+    match cursor {
+        Some(c) => { SetPropW(handle, prop_name.as_ptr(), mem::transmute(c)); },
+        None => { RemovePropW(handle, prop_name.as_ptr()); }
+    }
+}
+
+/// Return the cursor set with `set_window_cursor`, if any
+pub unsafe fn get_window_cursor(handle: HWND) -> Option<HCURSOR> {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(CURSOR_PROP_NAME);
+    let cursor: HCURSOR = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if cursor.is_null() { None } else { Some(cursor) }
+}
+
+/// Name of the window property set when the cursor is hidden over a window
+const HIDE_CURSOR_PROP_NAME: &'static str = "NWG_HIDE_CURSOR";
+
+/**
+    Hide or show the cursor when the mouse is over the client area of the window. A hidden cursor takes
+    precedence over the cursor set with `set_window_cursor`.
+
+    This only works on windows created by a Ui, because the cursor is applied by the NWG events subclass.
+*/
+pub unsafe fn set_window_cursor_hidden(handle: HWND, hidden: bool) {
+    use user32::{SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(HIDE_CURSOR_PROP_NAME);
+    if hidden {
+        SetPropW(handle, prop_name.as_ptr(), mem::transmute(1usize));
+    } else {
+        RemovePropW(handle, prop_name.as_ptr());
+    }
+}
+
+/// Return `true` if the cursor is hidden over the window
+pub unsafe fn get_window_cursor_hidden(handle: HWND) -> bool {
+    use user32::GetPropW;
+
+    let prop_name = to_utf16(HIDE_CURSOR_PROP_NAME);
+    !GetPropW(handle, prop_name.as_ptr()).is_null()
+}
+
+/// Name of the window property that holds the hover time of a window
+const HOVER_TIME_PROP_NAME: &'static str = "NWG_HOVER_TIME";
+
+/// Name of the window property set while the mouse is tracked over a window
+const MOUSE_TRACKING_PROP_NAME: &'static str = "NWG_MOUSE_TRACKING";
+
+/**
+    Set the time in milliseconds the mouse must stay still over the window before `Event::MouseHover` is raised.  
+    If `time` is `None` or `HOVER_DEFAULT`, the system default hover time is used. The new time is used the next time the mouse enters the window.
+*/
+pub unsafe fn set_window_hover_time(handle: HWND, time: Option<u32>) {
+    use user32::{SetPropW, RemovePropW};
+    use winapi::HOVER_DEFAULT;
+
+    let prop_name = to_utf16(HOVER_TIME_PROP_NAME);
+    match time {
+        // A value of 0 would be the same as no property, so the time is offset by one
+        Some(t) if t != HOVER_DEFAULT => { SetPropW(handle, prop_name.as_ptr(), mem::transmute((t as usize).saturating_add(1))); },
+        _ => { RemovePropW(handle, prop_name.as_ptr()); }
+    }
+}
+
+/// Return the hover time of the window set with `set_window_hover_time` or `HOVER_DEFAULT` if it was not set
+pub unsafe fn get_window_hover_time(handle: HWND) -> u32 {
+    use user32::GetPropW;
+    use winapi::HOVER_DEFAULT;
+
+    let prop_name = to_utf16(HOVER_TIME_PROP_NAME);
+    let time: usize = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if time == 0 { HOVER_DEFAULT } else { (time - 1) as u32 }
+}
+
+/**
+    Start tracking the mouse over the window if it is not already tracked. Return `true` if the mouse just entered the window.  
+    The tracking ends when the system sends `WM_MOUSELEAVE`, and `stop_mouse_tracking` must then be called.
+*/
+pub unsafe fn start_mouse_tracking(handle: HWND) -> bool {
+    use user32::{TrackMouseEvent, GetPropW, SetPropW};
+    use winapi::{TRACKMOUSEEVENT, TME_LEAVE, TME_HOVER, DWORD};
+
+    let prop_name = to_utf16(MOUSE_TRACKING_PROP_NAME);
+    if !GetPropW(handle, prop_name.as_ptr()).is_null() {
+        return false;
+    }
+
+    let mut tme = TRACKMOUSEEVENT {
+        cbSize: mem::size_of::<TRACKMOUSEEVENT>() as DWORD,
+        dwFlags: TME_LEAVE | TME_HOVER,
+        hwndTrack: handle,
+        dwHoverTime: get_window_hover_time(handle) as DWORD
+    };
+
+    TrackMouseEvent(&mut tme);
+    SetPropW(handle, prop_name.as_ptr(), mem::transmute(1usize));
+
+    true
+}
+
+/// Clear the mouse tracking state of the window. Called when the system sends `WM_MOUSELEAVE` or when the window is destroyed.
+pub unsafe fn stop_mouse_tracking(handle: HWND) {
+    use user32::RemovePropW;
+
+    let prop_name = to_utf16(MOUSE_TRACKING_PROP_NAME);
+    RemovePropW(handle, prop_name.as_ptr());
+}
+
+/// Name of the window property that marks a window registered for session notifications
+const SESSION_NOTIFY_PROP_NAME: &'static str = "NWG_SESSION_NOTIFY";
+
+/**
+    Register or unregister the window for the session notifications (`WM_WTSSESSION_CHANGE`).  
+    A registered window must be unregistered before it is destroyed. Windows created by a Ui are unregistered automatically.
+
+    Return `false` if the system could not register the window.
+*/
+pub unsafe fn set_window_session_notifications(handle: HWND, enabled: bool) -> bool {
+    use user32::{SetPropW, GetPropW, RemovePropW};
+    use low::defs::{WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION};
+
+    let prop_name = to_utf16(SESSION_NOTIFY_PROP_NAME);
+    let registered = !GetPropW(handle, prop_name.as_ptr()).is_null();
+
+    if enabled && !registered {
+        if WTSRegisterSessionNotification(handle, NOTIFY_FOR_THIS_SESSION) == 0 {
+            return false;
+        }
+        SetPropW(handle, prop_name.as_ptr(), mem::transmute(1usize));
+    } else if !enabled && registered {
+        WTSUnRegisterSessionNotification(handle);
+        RemovePropW(handle, prop_name.as_ptr());
+    }
+
+    true
+}
+
+/// Name of the window property that holds the device notification handle of a window
+const DEVICE_NOTIFY_PROP_NAME: &'static str = "NWG_DEVICE_NOTIFY";
+
+/**
+    Register the window for the device interface notifications (`WM_DEVICECHANGE` with `DBT_DEVTYP_DEVICEINTERFACE`).  
+    If `class` is `None`, the window receives the notifications of every device interface class. 
+    A previous registration of the window is replaced.
+
+    The window is unregistered by `unregister_window_device_notifications`. Windows created by a Ui are unregistered automatically.  
+    Return `false` if the system could not register the window.
+*/
+pub unsafe fn register_window_device_notifications(handle: HWND, class: Option<&GUID>) -> bool {
+    use user32::{RegisterDeviceNotificationW, SetPropW};
+    use low::defs::{DEV_BROADCAST_DEVICEINTERFACE_W, DBT_DEVTYP_DEVICEINTERFACE, DEVICE_NOTIFY_WINDOW_HANDLE, 
+      DEVICE_NOTIFY_ALL_INTERFACE_CLASSES};
+
+    unregister_window_device_notifications(handle);
+
+    let mut filter: DEV_BROADCAST_DEVICEINTERFACE_W = mem::zeroed();
+    filter.dbcc_size = mem::size_of::<DEV_BROADCAST_DEVICEINTERFACE_W>() as DWORD;
+    filter.dbcc_devicetype = DBT_DEVTYP_DEVICEINTERFACE;
+
+    let flags = match class {
+        Some(guid) => { filter.dbcc_classguid = *guid; DEVICE_NOTIFY_WINDOW_HANDLE },
+        None => DEVICE_NOTIFY_WINDOW_HANDLE | DEVICE_NOTIFY_ALL_INTERFACE_CLASSES
+    };
+
+    let notify = RegisterDeviceNotificationW(mem::transmute(handle), mem::transmute(&mut filter), flags);
+    if notify.is_null() {
+        return false;
+    }
+
+    let prop_name = to_utf16(DEVICE_NOTIFY_PROP_NAME);
+    SetPropW(handle, prop_name.as_ptr(), mem::transmute(notify));
+
+    true
+}
+
+/// Unregister the device notifications of a window registered with `register_window_device_notifications`, if any
+pub unsafe fn unregister_window_device_notifications(handle: HWND) {
+    use user32::{UnregisterDeviceNotification, RemovePropW};
+    use winapi::HDEVNOTIFY;
+
+    let prop_name = to_utf16(DEVICE_NOTIFY_PROP_NAME);
+    let notify: HDEVNOTIFY = mem::transmute(RemovePropW(handle, prop_name.as_ptr()));
+    if !notify.is_null() {
+        UnregisterDeviceNotification(notify);
+    }
+}
+
+/// Name of the window property that holds the paste filter of an edit control
+const PASTE_FILTER_PROP_NAME: &'static str = "NWG_PASTE_FILTER";
+
+/**
+    Set a function that transforms the clipboard text before it is pasted in an edit control. Use `None` to remove the filter.  
+    This only works on controls created by a Ui, because the filter is applied by the NWG events subclass.
+*/
+pub unsafe fn set_window_paste_filter(handle: HWND, filter: Option<fn(&str) -> String>) {
+    use user32::{SetPropW, RemovePropW};
+
+    let prop_name = to_utf16(PASTE_FILTER_PROP_NAME);
+    match filter {
+        Some(f) => { SetPropW(handle, prop_name.as_ptr(), mem::transmute(f)); },
+        None => { RemovePropW(handle, prop_name.as_ptr()); }
+    }
+}
+
+/**
+    Paste the clipboard text in an edit control after passing it through the paste filter of the control.  
+    Return `false` if the control has no paste filter, in which case the default paste must be executed.
+*/
+pub unsafe fn filtered_paste(handle: HWND) -> bool {
+    use user32::{GetPropW, SendMessageW, OpenClipboard, CloseClipboard, GetClipboardData};
+    use kernel32::{GlobalLock, GlobalUnlock};
+    use winapi::{CF_UNICODETEXT, EM_REPLACESEL, GWL_STYLE, ES_READONLY, TRUE};
+    use low::other_helper::from_wide_ptr;
+
+    let prop_name = to_utf16(PASTE_FILTER_PROP_NAME);
+    let filter = GetPropW(handle, prop_name.as_ptr());
+    if filter.is_null() {
+        return false;
+    }
+
+    // Read-only controls ignore the paste
+    if (get_window_long(handle, GWL_STYLE) as u32) & ES_READONLY == ES_READONLY {
+        return true;
+    }
+
+    let filter: fn(&str) -> String = mem::transmute(filter);
+
+    if OpenClipboard(handle) == 0 {
+        return true;
+    }
+
+    let data = GetClipboardData(CF_UNICODETEXT);
+    let text = if data.is_null() {
+        None
+    } else {
+        let text_ptr = GlobalLock(data);
+        let text = if text_ptr.is_null() { None } else { Some(from_wide_ptr(mem::transmute(text_ptr))) };
+        GlobalUnlock(data);
+        text
+    };
+
+    CloseClipboard();
+
+    if let Some(text) = text {
+        let filtered = to_utf16(&filter(&text));
+        SendMessageW(handle, EM_REPLACESEL as UINT, TRUE as WPARAM, mem::transmute(filtered.as_ptr()));
+    }
+
+    true
+}
+
+/// Name of the window property that holds the user data of a window
+const USER_DATA_PROP_NAME: &'static str = "NWG_USER_DATA";
+
+/**
+    Attach a value to a window. If the window already had a value, the old value is dropped.  
+    The data is stored in a window property and not in `GWLP_USERDATA`, so it can be used on any window.
+
+    The value is dropped when the window is removed from its Ui or when `clear_window_user_data` is called.
+    Windows that were not created by a Ui must call `clear_window_user_data` before being destroyed or the value will leak.
+*/
+pub unsafe fn set_window_user_data<T: 'static>(handle: HWND, data: T) {
+    use user32::SetPropW;
+    use std::any::Any;
+
+    clear_window_user_data(handle);
+
+    let prop_name = to_utf16(USER_DATA_PROP_NAME);
+    let data: Box<Box<Any>> = Box::new(Box::new(data));
+    SetPropW(handle, prop_name.as_ptr(), mem::transmute(Box::into_raw(data)));
+}
+
+/**
+    Return a pointer to the value attached to the window with `set_window_user_data`.  
+    Return `None` if the window has no value or if the value is not a `T`.
+
+    The pointer is only valid until the value is replaced or cleared (this includes the destruction of the window),
+    so it must not be kept.
+*/
+pub unsafe fn window_user_data<T: 'static>(handle: HWND) -> Option<*mut T> {
+    use user32::GetPropW;
+    use std::any::Any;
+
+    let prop_name = to_utf16(USER_DATA_PROP_NAME);
+    let data: *mut Box<Any> = mem::transmute(GetPropW(handle, prop_name.as_ptr()));
+    if data.is_null() {
+        None
+    } else {
+        (&mut *data).downcast_mut::<T>().map(|value| value as *mut T)
+    }
+}
+
+/// Remove and drop the value attached to the window with `set_window_user_data`, if any
+pub unsafe fn clear_window_user_data(handle: HWND) {
+    use user32::RemovePropW;
+    use std::any::Any;
+
+    let prop_name = to_utf16(USER_DATA_PROP_NAME);
+    let data: *mut Box<Any> = mem::transmute(RemovePropW(handle, prop_name.as_ptr()));
+    if !data.is_null() {
+        drop(Box::from_raw(data));
+    }
+}
+
+#[cfg(target_pointer_width = "32")] use winapi::LONG;
+#[cfg(target_pointer_width = "64")] use winapi::LONG_PTR;
 
 #[inline(always)]
-#[cfg(target_arch = "x86_64")]
+#[cfg(target_pointer_width = "64")]
 pub fn get_window_long(handle: HWND, index: c_int) -> LONG_PTR {
     use user32::GetWindowLongPtrW;
     unsafe{ GetWindowLongPtrW(handle, index) }
 }
 
 #[inline(always)]
-#[cfg(target_arch = "x86")]
+#[cfg(target_pointer_width = "32")]
 pub fn get_window_long(handle: HWND, index: c_int) -> LONG {
     use user32::GetWindowLongW;
     unsafe { GetWindowLongW(handle, index) }
 }
 
 #[inline(always)]
-#[cfg(target_arch = "x86_64")]
+#[cfg(target_pointer_width = "64")]
 pub fn set_window_long(handle: HWND, index: c_int, v: usize) {
     use user32::SetWindowLongPtrW;
     unsafe{ SetWindowLongPtrW(handle, index, v as LONG_PTR); }
 }
 
 #[inline(always)]
-#[cfg(target_arch = "x86")]
+#[cfg(target_pointer_width = "32")]
 pub fn set_window_long(handle: HWND, index: c_int, v: usize) {
     use user32::SetWindowLongW;
     unsafe { SetWindowLongW(handle, index, v as LONG); }
-}
\ No newline at end of file
+}
+
+/**
+    A subclass installed on a system window. The subclass is removed when the guard is dropped.
+
+    Because window handles are bound to the thread that created them, a guard cannot be sent to another thread.
+
+    Members:  
+    • `handle`: The subclassed window  
+    • `id`: The subclass id passed to the system  
+    • `proc`: The subclass procedure  
+*/
+pub struct SubclassGuard {
+    handle: HWND,
+    id: UINT_PTR,
+    proc: SUBCLASSPROC
+}
+
+impl SubclassGuard {
+
+    /**
+        Install the subclass procedure `proc` on the window `handle`. `data` is passed to the
+        procedure as its `dwRefData` parameter. The pair (`proc`, `id`) must be unique on the window.
+
+        Errors:  
+        • `Error::UserError` if `proc` is None  
+        • `Error::System(SystemError::SystemCall{..})` if the window is not valid or if the system could not install the subclass  
+    */
+    pub fn install(handle: HWND, id: usize, proc: SUBCLASSPROC, data: usize) -> Result<SubclassGuard, Error> {
+        use comctl32::SetWindowSubclass;
+        use user32::IsWindow;
+        use kernel32::SetLastError;
+        use winapi::ERROR_INVALID_WINDOW_HANDLE;
+
+        if proc.is_none() {
+            return Err(Error::UserError("A subclass procedure is required to install a subclass".to_string()));
+        }
+
+        if unsafe{ IsWindow(handle) } == 0 {
+            unsafe{ SetLastError(ERROR_INVALID_WINDOW_HANDLE); }
+            return Err(Error::from_last_error("SetWindowSubclass"));
+        }
+
+        let ok = unsafe{ SetWindowSubclass(handle, proc, id as UINT_PTR, data as DWORD_PTR) };
+        match ok {
+            0 => Err(Error::from_last_error("SetWindowSubclass")),
+            _ => Ok(SubclassGuard{ handle: handle, id: id as UINT_PTR, proc: proc })
+        }
+    }
+
+    /**
+        Replace the reference data passed to the subclass procedure.  
+        The system has no dedicated function for this, calling `SetWindowSubclass` with the same procedure and id updates it.
+    */
+    pub fn set_data(&self, data: usize) {
+        use comctl32::SetWindowSubclass;
+        unsafe{ SetWindowSubclass(self.handle, self.proc, self.id, data as DWORD_PTR); }
+    }
+
+    /// Return the handle of the subclassed window
+    pub fn handle(&self) -> HWND { self.handle }
+
+    /// Return the subclass id
+    pub fn id(&self) -> usize { self.id as usize }
+
+}
+
+impl Drop for SubclassGuard {
+    fn drop(&mut self) {
+        use comctl32::RemoveWindowSubclass;
+        use user32::IsWindow;
+
+        // If the window was already destroyed, the subclass was removed by the system.
+        unsafe{ 
+            if IsWindow(self.handle) != 0 {
+                RemoveWindowSubclass(self.handle, self.proc, self.id);
+            }
+        }
+    }
+}
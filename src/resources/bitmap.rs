@@ -0,0 +1,227 @@
+/*!
+    Bitmap resources creation
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::any::TypeId;
+use std::hash::Hash;
+use std::ptr;
+
+use winapi::{HBITMAP, c_int};
+
+use ui::Ui;
+use controls::AnyHandle;
+use resources::{ResourceT, Resource};
+use error::Error;
+
+/**
+    A template that can create a bitmap resource from a `.bmp` file
+
+    Params:  
+    • `source`: The path to the bitmap file  
+    • `size`: The size of the loaded bitmap. If `(0, 0)`, use the size of the image in the file  
+*/
+#[derive(Clone)]
+pub struct BitmapT<S: Clone+Into<String>> {
+    pub source: S,
+    pub size: (u32, u32)
+}
+
+impl<ID: Clone+Hash, S: Clone+Into<String>> ResourceT<ID> for BitmapT<S> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<Bitmap>() }
+
+    #[allow(unused_variables)]
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Resource>, Error> {
+        use user32::LoadImageW;
+        use winapi::{IMAGE_BITMAP, LR_LOADFROMFILE};
+        use low::other_helper::to_utf16;
+
+        let source = to_utf16(self.source.clone().into().as_ref());
+        let (w, h) = self.size;
+
+        let handle = unsafe{ LoadImageW(
+            ptr::null_mut(),
+            source.as_ptr(),
+            IMAGE_BITMAP,
+            w as c_int, h as c_int,
+            LR_LOADFROMFILE
+        ) };
+
+        if handle.is_null() {
+            Err(Error::from_last_error("LoadImageW"))
+        } else {
+            Ok( Box::new( Bitmap{ handle: handle as HBITMAP, owned: true } ) )
+        }
+    }
+}
+
+/**
+    A bitmap resource
+*/
+pub struct Bitmap {
+    handle: HBITMAP,
+    owned: bool
+}
+
+impl Bitmap {
+
+    /**
+        Wrap a bitmap handle obtained from the system (ex: with `CreateDIBSection` or from a WIC conversion).  
+        If `owned` is true, the bitmap is deleted when the resource is freed.
+    */
+    pub fn from_hbitmap(handle: HBITMAP, owned: bool) -> Bitmap {
+        Bitmap{ handle: handle, owned: owned }
+    }
+
+    /**
+        Create a copy of the bitmap `handle`. The copy is owned by the returned resource, so the original
+        bitmap can be deleted at any time.
+
+        Errors:  
+        • `Error::System(SystemError::SystemCall{..})` if the bitmap could not be copied  
+    */
+    pub fn clone_hbitmap(handle: HBITMAP) -> Result<Bitmap, Error> {
+        use user32::{GetDC, ReleaseDC};
+        use gdi32::{GetDIBits, CreateDIBitmap};
+        use winapi::{BITMAP, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, CBM_INIT, DWORD, UINT};
+        use low::defs::GetObjectW;
+        use std::mem;
+
+        unsafe {
+            let mut info: BITMAP = mem::zeroed();
+            let read = GetObjectW(mem::transmute(handle), mem::size_of::<BITMAP>() as c_int, mem::transmute(&mut info));
+            if read == 0 {
+                return Err(Error::from_last_error("GetObjectW"));
+            }
+
+            let (width, height) = (info.bmWidth, info.bmHeight.abs());
+            let mut header: BITMAPINFO = mem::zeroed();
+            header.bmiHeader = BITMAPINFOHEADER {
+                biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD,
+                biWidth: width,
+                biHeight: -height, // Top-down
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB,
+                biSizeImage: 0,
+                biXPelsPerMeter: 0, biYPelsPerMeter: 0,
+                biClrUsed: 0, biClrImportant: 0
+            };
+
+            let mut pixels: Vec<u8> = vec![0; (width * height * 4) as usize];
+
+            let screen = GetDC(ptr::null_mut());
+            let copy = match GetDIBits(screen, handle, 0, height as UINT, mem::transmute(pixels.as_mut_ptr()), &mut header, DIB_RGB_COLORS) {
+                0 => ptr::null_mut(),
+                _ => CreateDIBitmap(screen, &header.bmiHeader, CBM_INIT, mem::transmute(pixels.as_ptr()), &header, DIB_RGB_COLORS)
+            };
+            ReleaseDC(ptr::null_mut(), screen);
+
+            if copy.is_null() {
+                Err(Error::from_last_error("CreateDIBitmap"))
+            } else {
+                Ok( Bitmap{ handle: copy, owned: true } )
+            }
+        }
+    }
+
+    /**
+        Render the client area of a window into a new bitmap. Useful to compare the appearance of a Ui in automated tests.  
+        The window is asked to paint itself (`PrintWindow`), so it does not need to be visible on the screen.
+
+        On systems that do not support `PW_RENDERFULLCONTENT` (before Windows 8.1), the content drawn by the desktop
+        compositor (ex: layered windows) may be missing from the bitmap.
+
+        Errors:  
+        • `Error::ControlRequired` if the handle is not a window handle  
+        • `Error::System(SystemError::SystemCall{..})` if the bitmap could not be created  
+    */
+    pub fn capture(handle: &AnyHandle) -> Result<Bitmap, Error> {
+        use user32::{GetDC, ReleaseDC, PrintWindow, GetClientRect};
+        use gdi32::{CreateCompatibleDC, CreateCompatibleBitmap, SelectObject, DeleteDC, BitBlt};
+        use winapi::{RECT, SRCCOPY};
+        use low::defs::{PW_CLIENTONLY, PW_RENDERFULLCONTENT};
+        use std::mem;
+
+        let hwnd = match handle {
+            &AnyHandle::HWND(h) => h,
+            _ => { return Err(Error::ControlRequired); }
+        };
+
+        unsafe {
+            let mut rect: RECT = mem::zeroed();
+            GetClientRect(hwnd, &mut rect);
+            let (w, h) = ((rect.right - rect.left) as c_int, (rect.bottom - rect.top) as c_int);
+
+            let window_dc = GetDC(hwnd);
+            let bitmap = CreateCompatibleBitmap(window_dc, w, h);
+            if bitmap.is_null() {
+                let err = Error::from_last_error("CreateCompatibleBitmap");
+                ReleaseDC(hwnd, window_dc);
+                return Err(err);
+            }
+
+            let memory_dc = CreateCompatibleDC(window_dc);
+            let old_bitmap = SelectObject(memory_dc, mem::transmute(bitmap));
+
+            // Older systems reject the unknown PW_RENDERFULLCONTENT flag. In last resort, copy what is on the screen.
+            if PrintWindow(hwnd, memory_dc, PW_CLIENTONLY | PW_RENDERFULLCONTENT) == 0 
+              && PrintWindow(hwnd, memory_dc, PW_CLIENTONLY) == 0 {
+                BitBlt(memory_dc, 0, 0, w, h, window_dc, 0, 0, SRCCOPY);
+            }
+
+            SelectObject(memory_dc, old_bitmap);
+            DeleteDC(memory_dc);
+            ReleaseDC(hwnd, window_dc);
+
+            Ok( Bitmap{ handle: bitmap, owned: true } )
+        }
+    }
+
+    /// Return true if the bitmap handle is deleted with the resource
+    pub fn owned(&self) -> bool { self.owned }
+
+    /**
+        Delete the bitmap handle if it is owned by the resource. After the call, the resource holds a null handle.
+    */
+    pub fn destroy(&mut self) {
+        use gdi32::DeleteObject;
+
+        if self.owned && !self.handle.is_null() {
+            unsafe{ DeleteObject(::std::mem::transmute(self.handle)); }
+        }
+
+        self.handle = ptr::null_mut();
+        self.owned = false;
+    }
+
+}
+
+impl Resource for Bitmap {
+    fn handle(&self) -> AnyHandle { AnyHandle::HBITMAP(self.handle) }
+
+    fn free(&mut self) {
+        self.destroy();
+    }
+}
+
+/// An owned handle is deleted when the resource is dropped. Does nothing if the resource was already freed.
+impl Drop for Bitmap {
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
@@ -0,0 +1,180 @@
+/*!
+    Cursor resources creation
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::any::TypeId;
+use std::hash::Hash;
+use std::ptr;
+
+use winapi::HCURSOR;
+
+use ui::Ui;
+use controls::AnyHandle;
+use resources::{ResourceT, Resource, Bitmap};
+use error::Error;
+use defs::SystemCursor;
+use low::other_helper::to_utf16;
+
+/**
+    A template that can create a cursor resource from a `.cur` or `.ani` file
+
+    Params:  
+    • `source`: The path to the cursor file  
+*/
+#[derive(Clone)]
+pub struct CursorT<S: Clone+Into<String>> {
+    pub source: S
+}
+
+impl<ID: Clone+Hash, S: Clone+Into<String>> ResourceT<ID> for CursorT<S> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<Cursor>() }
+
+    #[allow(unused_variables)]
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Resource>, Error> {
+        let source: String = self.source.clone().into();
+        match Cursor::from_file(&source) {
+            Ok(c) => Ok( Box::new(c) ),
+            Err(e) => Err(e)
+        }
+    }
+}
+
+/**
+    A cursor resource
+*/
+pub struct Cursor {
+    handle: HCURSOR,
+    owned: bool
+}
+
+impl Cursor {
+
+    /**
+        Load the cursor stored in the resources of the executable under the identifier `id`.
+
+        Errors:  
+        • `Error::System(SystemError::SystemCall{..})` if the cursor could not be loaded  
+    */
+    pub fn from_resource(id: u16) -> Result<Cursor, Error> {
+        use user32::LoadCursorW;
+        use kernel32::GetModuleHandleW;
+        use winapi::LPCWSTR;
+
+        // Cursors loaded from the resources are shared, they must not be destroyed
+        let handle = unsafe{ LoadCursorW(GetModuleHandleW(ptr::null()), id as usize as LPCWSTR) };
+        if handle.is_null() {
+            Err(Error::from_last_error("LoadCursorW"))
+        } else {
+            Ok( Cursor{ handle: handle, owned: false } )
+        }
+    }
+
+    /**
+        Load a cursor from a `.cur` or `.ani` file.
+
+        Errors:  
+        • `Error::System(SystemError::SystemCall{..})` if the cursor could not be loaded  
+    */
+    pub fn from_file<'a>(path: &'a str) -> Result<Cursor, Error> {
+        use user32::LoadCursorFromFileW;
+
+        let path = to_utf16(path);
+        let handle = unsafe{ LoadCursorFromFileW(path.as_ptr()) };
+        if handle.is_null() {
+            Err(Error::from_last_error("LoadCursorFromFileW"))
+        } else {
+            Ok( Cursor{ handle: handle, owned: true } )
+        }
+    }
+
+    /// Return one of the standard system cursors. System cursors are shared and are never destroyed.
+    pub fn system(cursor: SystemCursor) -> Cursor {
+        use user32::LoadCursorW;
+        use winapi::{IDC_ARROW, IDC_IBEAM, IDC_WAIT, IDC_CROSS, IDC_UPARROW, IDC_SIZENWSE, IDC_SIZENESW, IDC_SIZEWE, IDC_SIZENS,
+          IDC_SIZEALL, IDC_NO, IDC_HAND, IDC_APPSTARTING, IDC_HELP};
+
+        let id = match cursor {
+            SystemCursor::Arrow => IDC_ARROW,
+            SystemCursor::IBeam => IDC_IBEAM,
+            SystemCursor::Wait => IDC_WAIT,
+            SystemCursor::Cross => IDC_CROSS,
+            SystemCursor::UpArrow => IDC_UPARROW,
+            SystemCursor::SizeNWSE => IDC_SIZENWSE,
+            SystemCursor::SizeNESW => IDC_SIZENESW,
+            SystemCursor::SizeWE => IDC_SIZEWE,
+            SystemCursor::SizeNS => IDC_SIZENS,
+            SystemCursor::SizeAll => IDC_SIZEALL,
+            SystemCursor::No => IDC_NO,
+            SystemCursor::Hand => IDC_HAND,
+            SystemCursor::AppStarting => IDC_APPSTARTING,
+            SystemCursor::Help => IDC_HELP,
+        };
+
+        let handle = unsafe{ LoadCursorW(ptr::null_mut(), id) };
+        Cursor{ handle: handle, owned: false }
+    }
+
+    /**
+        Create a cursor from a color bitmap and a monochrome mask (white pixels are transparent). `hotspot` is the position
+        of the click point in the bitmap.  
+        A 32 bits bitmap with a premultiplied alpha channel does not need a mask. The bitmaps are copied, so they can be freed after the call.
+
+        Errors:  
+        • `Error::BadResource` if the mask is missing or if the hotspot is outside the bitmap  
+        • `Error::System(SystemError::SystemCall{..})` if the cursor could not be created  
+    */
+    pub fn from_bitmap(color: &Bitmap, mask: Option<&Bitmap>, hotspot: (u32, u32)) -> Result<Cursor, Error> {
+        match unsafe{ ::low::gdi_helper::create_icon(color, mask, Some(hotspot)) } {
+            Ok(h) => Ok( Cursor{ handle: h, owned: true } ),
+            Err(e) => Err(e)
+        }
+    }
+
+    /// Return true if the cursor handle is destroyed with the resource
+    pub fn owned(&self) -> bool { self.owned }
+
+    /**
+        Destroy the cursor handle if it is owned by the resource. After the call, the resource holds a null handle.
+    */
+    pub fn destroy(&mut self) {
+        use user32::DestroyCursor;
+
+        if self.owned && !self.handle.is_null() {
+            unsafe{ DestroyCursor(self.handle); }
+        }
+
+        self.handle = ptr::null_mut();
+        self.owned = false;
+    }
+
+}
+
+impl Resource for Cursor {
+    fn handle(&self) -> AnyHandle { AnyHandle::HCURSOR(self.handle) }
+
+    fn free(&mut self) {
+        self.destroy();
+    }
+}
+
+/// An owned handle is destroyed when the resource is dropped. Does nothing if the resource was already freed.
+impl Drop for Cursor {
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
@@ -25,8 +25,8 @@ use winapi::{HFONT, DWORD, c_int};
 use ui::Ui;
 use controls::AnyHandle;
 use resources::{ResourceT, Resource};
-use error::{Error, SystemError};
-use defs::{FONT_DECO_ITALIC, FONT_DECO_UNDERLINE, FONT_DECO_STRIKEOUT};
+use error::Error;
+use defs::{FONT_DECO_ITALIC, FONT_DECO_UNDERLINE, FONT_DECO_STRIKEOUT, SystemFont, FontQuality};
 
 /**
     A template that can create a font resource
@@ -35,6 +35,7 @@ use defs::{FONT_DECO_ITALIC, FONT_DECO_UNDERLINE, FONT_DECO_STRIKEOUT};
     • `size`: The height, in logical units, of the font's character cell or character. 0 means default height.  
     • `weight`: The weight of the font in the range 0 through 1000. For example, 400 is normal and 700 is bold. See the FONT_WEIGHT_* constants for convenience  
     • `decoration`: Extra style for the font. A bitwise combination of the FONT_DECO_* constants. Ex: FONT_DECO_ITALIC | FONT_DECO_UNDERLINE | FONT_DECO_STRIKEOUT  
+    • `quality`: How the characters are smoothed. Use `FontQuality::Grayscale` to draw on a transparent or layered surface  
 */
 #[derive(Clone)]
 pub struct FontT<S: Clone+Into<String>> {
@@ -42,6 +43,7 @@ pub struct FontT<S: Clone+Into<String>> {
     pub size: c_int,
     pub weight: c_int,
     pub decoration: u32,
+    pub quality: FontQuality,
 }
 
 impl<ID: Clone+Hash, S: Clone+Into<String>> ResourceT<ID> for FontT<S> {
@@ -50,13 +52,21 @@ impl<ID: Clone+Hash, S: Clone+Into<String>> ResourceT<ID> for FontT<S> {
     #[allow(unused_variables)]
     fn build(&self, ui: &Ui<ID>) -> Result<Box<Resource>, Error> {
         use gdi32::CreateFontW;
-        use winapi::{DEFAULT_CHARSET, CLEARTYPE_QUALITY, OUT_DEFAULT_PRECIS, CLIP_DEFAULT_PRECIS, VARIABLE_PITCH};
+        use winapi::{DEFAULT_CHARSET, DEFAULT_QUALITY, CLEARTYPE_QUALITY, ANTIALIASED_QUALITY, NONANTIALIASED_QUALITY,
+          OUT_DEFAULT_PRECIS, CLIP_DEFAULT_PRECIS, VARIABLE_PITCH};
         use low::other_helper::to_utf16;
 
         let use_italic = ((self.decoration & FONT_DECO_ITALIC) != 0) as DWORD;
         let use_underline = ((self.decoration & FONT_DECO_UNDERLINE) != 0) as DWORD;
         let use_strikeout = ((self.decoration & FONT_DECO_STRIKEOUT) != 0) as DWORD;
 
+        let quality = match self.quality {
+            FontQuality::Default => DEFAULT_QUALITY,
+            FontQuality::ClearType => CLEARTYPE_QUALITY,
+            FontQuality::Grayscale => ANTIALIASED_QUALITY,
+            FontQuality::None => NONANTIALIASED_QUALITY
+        };
+
         let family_name = to_utf16(self.family.clone().into().as_ref());
 
         let handle = unsafe{ CreateFontW(
@@ -69,19 +79,81 @@ impl<ID: Clone+Hash, S: Clone+Into<String>> ResourceT<ID> for FontT<S> {
             DEFAULT_CHARSET,          // fdwCharSet
             OUT_DEFAULT_PRECIS,       // fdwOutputPrecision
             CLIP_DEFAULT_PRECIS,      // fdwClipPrecision
-            CLEARTYPE_QUALITY,        // fdwQuality
+            quality,                  // fdwQuality
             VARIABLE_PITCH,           // fdwPitchAndFamily
             family_name.as_ptr(),     // lpszFace
         ) };
 
         if handle.is_null() {
-            Err(Error::System(SystemError::FontCreation))
+            Err(Error::from_last_error("CreateFontW"))
         } else {
             Ok( Box::new( Font{ handle: handle } ) )
         }
     }
 }
 
+/**
+    A template that creates a copy of one of the fonts used by the system (see `SystemFont`).
+
+    Params:  
+    • `font`: The system font to copy  
+*/
+#[derive(Clone)]
+pub struct SystemFontT {
+    pub font: SystemFont
+}
+
+impl<ID: Clone+Hash> ResourceT<ID> for SystemFontT {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<Font>() }
+
+    #[allow(unused_variables)]
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Resource>, Error> {
+        match system_font(self.font) {
+            Ok(f) => Ok( Box::new(f) ),
+            Err(e) => Err(e)
+        }
+    }
+}
+
+/**
+    Create a copy of a font used by the system. The returned font is owned by the caller and
+    must be freed with `Resource::free` once it is no longer used. To let a Ui manage the font, pack a `SystemFontT` instead.
+
+    Errors:  
+    • `Error::System(SystemError::SystemCall{..})` if the system metrics could not be read or if the font could not be created  
+*/
+pub fn system_font(sf: SystemFont) -> Result<Font, Error> {
+    use user32::SystemParametersInfoW;
+    use gdi32::CreateFontIndirectW;
+    use winapi::{UINT, SPI_GETNONCLIENTMETRICS};
+    use low::defs::NONCLIENTMETRICSW;
+    use std::mem;
+
+    let mut metrics: NONCLIENTMETRICSW = unsafe{ mem::zeroed() };
+    let size = mem::size_of::<NONCLIENTMETRICSW>() as UINT;
+    metrics.cbSize = size;
+
+    let ok = unsafe{ SystemParametersInfoW(SPI_GETNONCLIENTMETRICS, size, mem::transmute(&mut metrics), 0) };
+    if ok == 0 {
+        return Err(Error::from_last_error("SystemParametersInfoW"));
+    }
+
+    let logfont = match sf {
+        SystemFont::Caption => &metrics.lfCaptionFont,
+        SystemFont::SmallCaption => &metrics.lfSmCaptionFont,
+        SystemFont::Menu => &metrics.lfMenuFont,
+        SystemFont::Status => &metrics.lfStatusFont,
+        SystemFont::Message => &metrics.lfMessageFont,
+    };
+
+    let handle = unsafe{ CreateFontIndirectW(logfont) };
+    if handle.is_null() {
+        Err(Error::from_last_error("CreateFontIndirectW"))
+    } else {
+        Ok( Font{ handle: handle } )
+    }
+}
+
 /**
     A font resource
 */
@@ -89,6 +161,15 @@ pub struct Font {
     handle: HFONT
 }
 
+impl Font {
+
+    /// Create a copy of the font used by the message boxes and most native controls. See `system_font`.
+    pub fn system_message() -> Result<Font, Error> {
+        system_font(SystemFont::Message)
+    }
+
+}
+
 impl Resource for Font {
     fn handle(&self) -> AnyHandle { AnyHandle::HFONT(self.handle) }
 
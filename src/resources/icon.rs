@@ -0,0 +1,172 @@
+/*!
+    Icon resources creation
+*/
+/*
+    Copyright (C) 2016  Gabriel Dubé
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program.  If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::any::TypeId;
+use std::hash::Hash;
+use std::ptr;
+
+use winapi::{HICON, c_int};
+
+use ui::Ui;
+use controls::AnyHandle;
+use resources::{ResourceT, Resource, Bitmap};
+use error::Error;
+
+/**
+    A template that can create an icon resource from a `.ico` file
+
+    Params:  
+    • `source`: The path to the icon file  
+    • `size`: The size of the icon to load. If `(0, 0)`, use the system default icon size  
+*/
+#[derive(Clone)]
+pub struct IconT<S: Clone+Into<String>> {
+    pub source: S,
+    pub size: (u32, u32)
+}
+
+impl<ID: Clone+Hash, S: Clone+Into<String>> ResourceT<ID> for IconT<S> {
+    fn resource_type_id(&self) -> TypeId { TypeId::of::<Icon>() }
+
+    #[allow(unused_variables)]
+    fn build(&self, ui: &Ui<ID>) -> Result<Box<Resource>, Error> {
+        let source: String = self.source.clone().into();
+        match Icon::from_file(&source, self.size) {
+            Ok(icon) => Ok( Box::new(icon) ),
+            Err(e) => Err(e)
+        }
+    }
+}
+
+/**
+    An icon resource
+*/
+pub struct Icon {
+    handle: HICON,
+    owned: bool
+}
+
+impl Icon {
+
+    /**
+        Wrap an icon handle obtained from the system (ex: with `SHGetFileInfo`).  
+        If `owned` is true, the icon is destroyed when the resource is freed. Shared icons (ex: from `LoadIcon`)
+        must never be owned.
+    */
+    pub fn from_hicon(handle: HICON, owned: bool) -> Icon {
+        Icon{ handle: handle, owned: owned }
+    }
+
+    /**
+        Load an icon from a `.ico` file. The icon is owned by the returned resource.  
+        If `size` is `(0, 0)`, use the system default icon size. Use `IconT` to let a Ui manage the icon.
+
+        Errors:  
+        • `Error::System(SystemError::SystemCall{..})` if the file does not exist or is not a valid icon  
+    */
+    pub fn from_file<'a>(path: &'a str, size: (u32, u32)) -> Result<Icon, Error> {
+        use user32::LoadImageW;
+        use winapi::{IMAGE_ICON, LR_LOADFROMFILE, LR_DEFAULTSIZE};
+        use low::other_helper::to_utf16;
+
+        let source = to_utf16(path);
+        let (w, h) = size;
+        let default_size = if w == 0 && h == 0 { LR_DEFAULTSIZE } else { 0 };
+
+        let handle = unsafe{ LoadImageW(
+            ptr::null_mut(),
+            source.as_ptr(),
+            IMAGE_ICON,
+            w as c_int, h as c_int,
+            LR_LOADFROMFILE | default_size
+        ) };
+
+        if handle.is_null() {
+            Err(Error::from_last_error("LoadImageW"))
+        } else {
+            Ok( Icon{ handle: handle as HICON, owned: true } )
+        }
+    }
+
+    /**
+        Create a copy of the icon `handle`. The copy is owned by the returned resource, so the original
+        icon can be destroyed at any time.
+
+        Errors:  
+        • `Error::System(SystemError::SystemCall{..})` if the icon could not be copied  
+    */
+    pub fn clone_hicon(handle: HICON) -> Result<Icon, Error> {
+        use user32::CopyIcon;
+
+        let copy = unsafe{ CopyIcon(handle) };
+        if copy.is_null() {
+            Err(Error::from_last_error("CopyIcon"))
+        } else {
+            Ok( Icon{ handle: copy, owned: true } )
+        }
+    }
+
+    /**
+        Create an icon from a color bitmap and a monochrome mask (white pixels are transparent).  
+        A 32 bits bitmap with a premultiplied alpha channel does not need a mask. The bitmaps are copied, so they can be freed after the call.
+
+        Errors:  
+        • `Error::BadResource` if the mask is missing  
+        • `Error::System(SystemError::SystemCall{..})` if the icon could not be created  
+    */
+    pub fn from_bitmap(color: &Bitmap, mask: Option<&Bitmap>) -> Result<Icon, Error> {
+        match unsafe{ ::low::gdi_helper::create_icon(color, mask, None) } {
+            Ok(h) => Ok( Icon{ handle: h, owned: true } ),
+            Err(e) => Err(e)
+        }
+    }
+
+    /// Return true if the icon handle is destroyed with the resource
+    pub fn owned(&self) -> bool { self.owned }
+
+    /**
+        Destroy the icon handle if it is owned by the resource. After the call, the resource holds a null handle.
+    */
+    pub fn destroy(&mut self) {
+        use user32::DestroyIcon;
+
+        if self.owned && !self.handle.is_null() {
+            unsafe{ DestroyIcon(self.handle); }
+        }
+
+        self.handle = ptr::null_mut();
+        self.owned = false;
+    }
+
+}
+
+impl Resource for Icon {
+    fn handle(&self) -> AnyHandle { AnyHandle::HICON(self.handle) }
+
+    fn free(&mut self) {
+        self.destroy();
+    }
+}
+
+/// An owned handle is destroyed when the resource is dropped. Does nothing if the resource was already freed.
+impl Drop for Icon {
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
@@ -19,6 +19,9 @@
 */
 
 pub mod font;
+pub mod icon;
+pub mod bitmap;
+pub mod cursor;
 
 use std::any::TypeId;
 use std::hash::Hash;
@@ -27,7 +30,10 @@ use ui::Ui;
 use controls::AnyHandle;
 use error::Error;
 
-pub use resources::font::{FontT, Font};
+pub use resources::font::{FontT, Font, SystemFontT, system_font};
+pub use resources::icon::{IconT, Icon};
+pub use resources::bitmap::{BitmapT, Bitmap};
+pub use resources::cursor::{CursorT, Cursor};
 
 /**
     Structures implementing this trait can be used by a Ui to build a Resource
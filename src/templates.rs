@@ -140,7 +140,9 @@ pub fn $n(ui: &$crate::Ui<$t>) -> Result<(), $crate::Error> {
     • resizable: `false`  
     • visible: `true`  
     • disabled: `false`  
-    • exit_on_close: `true`
+    • exit_on_close: `true`  
+    • icon_big: `None`  
+    • icon_small: `None`
 
     Usage:  
     `nwg_window!()`  
@@ -155,7 +157,7 @@ macro_rules! nwg_window {
             title: "Native Windows GUI", 
             position: (100, 100), size: (800, 600), 
             resizable: false, visible: true, disabled: false, 
-            exit_on_close: true
+            exit_on_close: true, icon_big: None, icon_small: None
         };
         
         $( t.$i = $v; );*
@@ -173,7 +175,11 @@ macro_rules! nwg_window {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
-    • font: `None`
+    • tab_stop: `true`  
+    • group: `false`  
+    • font: `None`  
+    • command_link: `false`  
+    • note: `None`
 
     Usage:  
     `nwg_button!(parent="MyParent";)`  
@@ -187,8 +193,9 @@ macro_rules! nwg_button {
         $crate::ButtonT{ 
             text: "", 
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, 
-            parent: $p, font: None
+            visible: true, disabled: false, tab_stop: true, group: false, 
+            parent: $p, font: None,
+            command_link: false, note: None
         };
         
         $( t.$i = $v; );*
@@ -206,6 +213,8 @@ macro_rules! nwg_button {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
     • checkstate: `CheckState::Unchecked`  
     • tristate: `false`  
     • font: `None`
@@ -222,7 +231,7 @@ macro_rules! nwg_checkbox {
         $crate::CheckBoxT{ 
             text: "", 
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, 
+            visible: true, disabled: false, tab_stop: true, group: false, 
             checkstate: $crate::constants::CheckState::Unchecked,
             tristate: false,
             parent: $p, font: None
@@ -245,7 +254,11 @@ macro_rules! nwg_checkbox {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
     • placeholder: `None`  
+    • visible_items: `None`  
+    • dropdown_width: `None`  
     • font: `None`
 
     Usage:  
@@ -260,8 +273,8 @@ macro_rules! nwg_combobox {
         $crate::ComboBoxT::<$t>{ 
             collection: [],
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, 
-            placeholder: None,
+            visible: true, disabled: false, tab_stop: true, group: false, 
+            placeholder: None, visible_items: None, dropdown_width: None,
             parent: $p, font: None
         };
         
@@ -274,8 +287,8 @@ macro_rules! nwg_combobox {
         $crate::ComboBoxT{ 
             collection: vec![],
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, 
-            placeholder: None,
+            visible: true, disabled: false, tab_stop: true, group: false, 
+            placeholder: None, visible_items: None, dropdown_width: None,
             parent: $p, font: None
         };
         
@@ -295,7 +308,8 @@ macro_rules! nwg_combobox {
     • visible: `true`  
     • disabled: `false`  
     • align: `HTextAlign::Left`  
-    • font: `None`
+    • font: `None`  
+    • auto_tooltip: `false`
 
     Usage:  
     `nwg_label!(parent="MyParent";)`  
@@ -311,7 +325,7 @@ macro_rules! nwg_label {
             position: (0, 0), size: (100, 30), 
             visible: true, disabled: false, 
             align: $crate::constants::HTextAlign::Left,
-            parent: $p, font: None
+            parent: $p, font: None, auto_tooltip: false
         };
         
         $( t.$i = $v; );*
@@ -331,9 +345,13 @@ macro_rules! nwg_label {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
     • readonly: `false`  
     • multi_select: `false`  
-    • font: `None`
+    • tab_stops: `false`  
+    • font: `None`  
+    • auto_tooltip: `false`
 
     Usage:  
     `nwg_listbox!(parent="MyParent";)`  
@@ -347,8 +365,8 @@ macro_rules! nwg_listbox {
         $crate::ListBoxT::<$t, _>{ 
             collection: vec![],
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, readonly: false, multi_select: false,
-            parent: $p, font: None
+            visible: true, disabled: false, tab_stop: true, group: false, readonly: false, multi_select: false, tab_stops: false,
+            parent: $p, font: None, auto_tooltip: false
         };
         
         $( t.$i = $v; );*
@@ -361,8 +379,8 @@ macro_rules! nwg_listbox {
         $crate::ListBoxT::<_, _>{ 
             collection: vec![],
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, readonly: false, multi_select: false,
-            parent: $p, font: None
+            visible: true, disabled: false, tab_stop: true, group: false, readonly: false, multi_select: false, tab_stops: false,
+            parent: $p, font: None, auto_tooltip: false
         };
         
         $( t.$i = $v; );*
@@ -439,6 +457,8 @@ macro_rules! nwg_separator {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
     • checkstate: `CheckState::Unchecked`  
     • font: `None`
 
@@ -454,7 +474,7 @@ macro_rules! nwg_radiobutton {
         $crate::RadioButtonT{
             text: "",
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, 
+            visible: true, disabled: false, tab_stop: true, group: false, 
             parent: $p,
             checkstate: $crate::constants::CheckState::Unchecked,
             font: None
@@ -495,6 +515,8 @@ macro_rules! nwg_timer {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
     • readonly: `false`  
     • password: `false`  
     • limit: `32_767`  
@@ -513,7 +535,7 @@ macro_rules! nwg_textinput {
         $crate::TextInputT::<_, &'static str, _> {
             text: "",
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, readonly: false, password: false,
+            visible: true, disabled: false, tab_stop: true, group: false, readonly: false, password: false,
             limit: 32_767,
             placeholder: None,
             parent: $p,
@@ -533,6 +555,8 @@ macro_rules! nwg_textinput {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
     • readonly: `false`  
     • limit: `32_767`  
     • scrollbars: `(false, false)`  
@@ -550,7 +574,7 @@ macro_rules! nwg_textbox {
         $crate::TextBoxT::<_, _> {
             text: "",
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, readonly: false,
+            visible: true, disabled: false, tab_stop: true, group: false, readonly: false,
             limit: 32_767,
             scrollbars: (false, false),
             parent: $p,
@@ -634,6 +658,84 @@ macro_rules! nwg_progressbar {
     }} 
 }
 
+/**
+    Sane defaults for the DateTimePicker control. Requires a parent.
+
+    Defaults:  
+    • value: Now  
+    • position: `(0, 0)`  
+    • size: `(150, 30)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • font: `None`  
+    • flags: `DateTimePickerFlags::DateTime`  
+    • format: `None`  
+    • optional: `false`  
+
+    Usage:  
+    `nwg_date_time_picker!(parent="MyParent";)`  
+    `nwg_date_time_picker!(parent="MyParent"; visible=false; size=(10, 10))`  
+    `nwg_date_time_picker!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_date_time_picker {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::DateTimePickerT{ 
+            value: None,
+            position: (0, 0), size: (150, 30), 
+            visible: true, disabled: false, tab_stop: true,
+            flags: $crate::constants::DateTimePickerFlags::DateTime,
+            parent: $p, font: None,
+            format: None::<&'static str>, optional: false
+        };
+        
+        $( t.$i = $v; );*
+
+        t
+    }}
+}
+
+/**
+    Sane defaults for the MonthCalendar control. Requires a parent.
+
+    Defaults:  
+    • value: Todays date  
+    • position: `(0, 0)`  
+    • size: `(250, 200)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • font: `None`  
+    • multi_select: `false`  
+    • week_numbers: `false`  
+    • range: `(None, None)`  
+
+    Usage:  
+    `nwg_month_calendar!(parent="MyParent";)`  
+    `nwg_month_calendar!(parent="MyParent"; visible=false; size=(10, 10))`  
+    `nwg_month_calendar!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_month_calendar {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::MonthCalendarT{ 
+            value: None,
+            position: (0, 0), size: (250, 200), 
+            visible: true, disabled: false, tab_stop: true,
+            parent: $p, font: None,
+            multi_select: false, week_numbers: false,
+            range: (None, None)
+        };
+        
+        $( t.$i = $v; );*
+
+        t
+    }}
+}
+
 /**
     Sane defaults for the DatePicker control. Requires a parent.
 
@@ -643,6 +745,8 @@ macro_rules! nwg_progressbar {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
     • font: `None`  
     • align: `HTextAlign::Left`  
     • format: The system locale format in a short format (ex: 2017-01-01)  
@@ -660,7 +764,7 @@ macro_rules! nwg_datepicker {
         $crate::DatePickerT{ 
             value: None,
             position: (0, 0), size: (100, 30), 
-            visible: true, disabled: false, 
+            visible: true, disabled: false, tab_stop: true, group: false, 
             align: $crate::constants::HTextAlign::Left,
             parent: $p, font: None,
             format: "", optional: false,
@@ -716,6 +820,14 @@ macro_rules! nwg_filedialog {
     • size: `(100, 30)`  
     • visible: `true`  
     • disabled: `false`  
+    • min_size: `None`  
+    • max_size: `None`  
+    • topmost: `false`  
+    • alpha: `None`  
+    • center: `false`  
+    • icon_file: `None`  
+    • tool_window: `false`  
+    • owned: `false`  
 
     Usage:  
     `nwg_canvas!(parent="MyParent";)`  
@@ -729,6 +841,8 @@ macro_rules! nwg_canvas {
         $crate::CanvasT {
             position: (0, 0), size: (100, 100), 
             visible: true, disabled: false,
+            min_size: None, max_size: None,
+            topmost: false, alpha: None, center: false, icon_file: None, tool_window: false, owned: false,
             parent: $p,
         };
         $( t.$i = $v; );*
@@ -737,6 +851,519 @@ macro_rules! nwg_canvas {
 }
 
 
+/**
+    Sane defaults for the StaticImage control. Requires a parent.
+
+    Defaults:  
+    • image: `None`  
+    • position: `(0, 0)`  
+    • size: `(100, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • center: `true`  
+
+    Usage:  
+    `nwg_image!(parent="MyParent";)`  
+    `nwg_image!(parent="MyParent"; image=Some("MyBitmap"); size=(10, 10))`  
+    `nwg_image!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_image {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::StaticImageT {
+            image: None,
+            position: (0, 0), size: (100, 100), 
+            visible: true, disabled: false, center: true,
+            parent: $p,
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the MultiColumnListBox control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(100, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
+    • multi_select: `false`  
+    • font: `None`  
+    • auto_tooltip: `false`
+
+    Usage:  
+    `nwg_multi_column_listbox!(parent="MyParent";)`  
+    `nwg_multi_column_listbox!(parent="MyParent"; multi_select=true; size=(300, 200))`  
+    `nwg_multi_column_listbox!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_multi_column_listbox {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::MultiColumnListBoxT {
+            position: (0, 0), size: (100, 100), 
+            visible: true, disabled: false, tab_stop: true, group: false, multi_select: false,
+            parent: $p, font: None, auto_tooltip: false
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the TreeView control. Requires a parent and the `tree-view` feature.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(100, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
+    • lines: `true`  
+    • buttons: `true`  
+    • font: `None`  
+    • auto_tooltip: `false`
+
+    Usage:  
+    `nwg_tree_view!(parent="MyParent";)`  
+    `nwg_tree_view!(parent="MyParent"; lines=false; size=(300, 200))`  
+    `nwg_tree_view!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[cfg(feature = "tree-view")]
+#[macro_export]
+macro_rules! nwg_tree_view {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::TreeViewT {
+            position: (0, 0), size: (100, 100), 
+            visible: true, disabled: false, tab_stop: true, group: false, lines: true, buttons: true,
+            parent: $p, font: None, auto_tooltip: false
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the ListView control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(100, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
+    • style: `ListViewStyle::Report`  
+    • font: `None`  
+    • auto_tooltip: `false`
+
+    Usage:  
+    `nwg_list_view!(parent="MyParent";)`  
+    `nwg_list_view!(parent="MyParent"; style=nwg::constants::ListViewStyle::List; size=(300, 200))`  
+    `nwg_list_view!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_list_view {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::ListViewT {
+            position: (0, 0), size: (100, 100), 
+            visible: true, disabled: false, tab_stop: true, group: false, style: $crate::constants::ListViewStyle::Report,
+            parent: $p, font: None, auto_tooltip: false
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the TabControl control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(100, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
+    • multi_line: `false`  
+    • buttons: `false`  
+    • font: `None`
+
+    Usage:  
+    `nwg_tab_control!(parent="MyParent";)`  
+    `nwg_tab_control!(parent="MyParent"; multi_line=true; size=(300, 200))`  
+    `nwg_tab_control!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_tab_control {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::TabControlT {
+            position: (0, 0), size: (100, 100), 
+            visible: true, disabled: false, tab_stop: true, group: false, multi_line: false, buttons: false,
+            parent: $p, font: None
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+/**
+    Sane defaults for the TabItem control. Requires a tab control parent.
+
+    Defaults:  
+    • label: `"Tab"`  
+
+    Usage:  
+    `nwg_tab_item!(parent="MyTabControl";)`  
+    `nwg_tab_item!(parent="MyTabControl"; label="General")`  
+*/
+#[macro_export]
+macro_rules! nwg_tab_item {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t =  $crate::TabItemT{ label: "Tab", parent: $p };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the RichTextBox control. Requires a parent.
+
+    Defaults:  
+    • text: `""`  
+    • position: `(0, 0)`  
+    • size: `(200, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • readonly: `false`  
+    • scrollbars: `(false, true)`  
+    • font: `None`
+
+    Usage:  
+    `nwg_rich_text_box!(parent="MyParent";)`  
+    `nwg_rich_text_box!(parent="MyParent"; visible=false; size=(10, 10))`  
+    `nwg_rich_text_box!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_rich_text_box {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::RichTextBoxT::<_, _> {
+            text: "",
+            position: (0, 0), size: (200, 100), 
+            visible: true, disabled: false, tab_stop: true, readonly: false,
+            scrollbars: (false, true),
+            parent: $p,
+            font: None
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+/**
+    Sane defaults for the NumericUpDown control. Requires a parent.
+
+    Defaults:  
+    • value: `0`  
+    • min: `0`  
+    • max: `100`  
+    • step: `1`  
+    • wrap: `false`  
+    • position: `(0, 0)`  
+    • size: `(100, 25)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • font: `None`
+
+    Usage:  
+    `nwg_numeric_updown!(parent="MyParent";)`  
+    `nwg_numeric_updown!(parent="MyParent"; visible=false; size=(10, 10))`  
+    `nwg_numeric_updown!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_numeric_updown {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::NumericUpDownT::<_> {
+            value: 0, min: 0, max: 100, step: 1, wrap: false,
+            position: (0, 0), size: (100, 25), 
+            visible: true, disabled: false, tab_stop: true,
+            parent: $p,
+            font: None
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+/**
+    Sane defaults for the HorizontalScrollBar control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(100, 17)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • range: `(0, 100)`  
+    • value: `0`  
+    • page_size: `10`  
+
+    Usage:  
+    `nwg_horizontal_scroll_bar!(parent="MyParent";)`  
+    `nwg_horizontal_scroll_bar!(parent="MyParent"; visible=false; size=(10, 10))`  
+    `nwg_horizontal_scroll_bar!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_horizontal_scroll_bar {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::HorizontalScrollBarT::<_> {
+            position: (0, 0), size: (100, 17), 
+            visible: true, disabled: false,
+            range: (0, 100), value: 0, page_size: 10,
+            parent: $p
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+/**
+    Sane defaults for the VerticalScrollBar control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(17, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • range: `(0, 100)`  
+    • value: `0`  
+    • page_size: `10`  
+
+    Usage:  
+    `nwg_vertical_scroll_bar!(parent="MyParent";)`  
+    `nwg_vertical_scroll_bar!(parent="MyParent"; visible=false; size=(10, 10))`  
+    `nwg_vertical_scroll_bar!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_vertical_scroll_bar {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::VerticalScrollBarT::<_> {
+            position: (0, 0), size: (17, 100), 
+            visible: true, disabled: false,
+            range: (0, 100), value: 0, page_size: 10,
+            parent: $p
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+/**
+    Sane defaults for the HotkeyInput control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(100, 25)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
+    • hotkey: `None`  
+    • font: `None`
+
+    Usage:  
+    `nwg_hotkey_input!(parent="MyParent";)`  
+    `nwg_hotkey_input!(parent="MyParent"; hotkey=Some(my_shortcut); size=(150, 25))`  
+    `nwg_hotkey_input!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_hotkey_input {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::HotkeyInputT {
+            position: (0, 0), size: (100, 25), 
+            visible: true, disabled: false, tab_stop: true, group: false, hotkey: None,
+            parent: $p, font: None
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the IpAddressInput control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(150, 25)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • tab_stop: `true`  
+    • group: `false`  
+    • address: `None`  
+    • font: `None`
+
+    Usage:  
+    `nwg_ip_address_input!(parent="MyParent";)`  
+    `nwg_ip_address_input!(parent="MyParent"; address=Some([127, 0, 0, 1]))`  
+    `nwg_ip_address_input!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_ip_address_input {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::IpAddressInputT {
+            position: (0, 0), size: (150, 25), 
+            visible: true, disabled: false, tab_stop: true, group: false, address: None,
+            parent: $p, font: None
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the Animation control. Requires a parent.
+
+    Defaults:  
+    • source: `""`  
+    • position: `(0, 0)`  
+    • size: `(100, 100)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • autoplay: `false`  
+    • center: `true`  
+    • transparent: `true`  
+
+    Usage:  
+    `nwg_animation!(parent="MyParent";)`  
+    `nwg_animation!(parent="MyParent"; source="copy.avi"; autoplay=true)`  
+    `nwg_animation!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_animation {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::AnimationT {
+            source: "",
+            position: (0, 0), size: (100, 100), 
+            visible: true, disabled: false, 
+            autoplay: false, center: true, transparent: true,
+            parent: $p
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the Pager control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(100, 30)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • vertical: `false`  
+    • autoscroll: `false`  
+
+    Usage:  
+    `nwg_pager!(parent="MyParent";)`  
+    `nwg_pager!(parent="MyParent"; vertical=true; size=(30, 100))`  
+    `nwg_pager!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_pager {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::PagerT {
+            position: (0, 0), size: (100, 30), 
+            visible: true, disabled: false, 
+            vertical: false, autoscroll: false,
+            parent: $p
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
+/**
+    Sane defaults for the Rebar control. Requires a parent.
+
+    Defaults:  
+    • position: `(0, 0)`  
+    • size: `(300, 30)`  
+    • visible: `true`  
+    • disabled: `false`  
+    • fixed_order: `false`  
+    • band_borders: `true`  
+
+    Usage:  
+    `nwg_rebar!(parent="MyParent";)`  
+    `nwg_rebar!(parent="MyParent"; fixed_order=true; size=(500, 30))`  
+    `nwg_rebar!(parent="MyParent"; \* Any combinations of the template properties*\)`    
+*/
+#[macro_export]
+macro_rules! nwg_rebar {
+    (parent=$p:expr; $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::RebarT {
+            position: (0, 0), size: (300, 30), 
+            visible: true, disabled: false, 
+            fixed_order: false, band_borders: true,
+            parent: $p
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+/**
+    Sane defaults for the MessageWindow control.
+
+    Defaults:  
+    • title: `""`
+
+    Usage:  
+    `nwg_message_window!()`  
+    `nwg_message_window!(title="MyMessageWindow")`  
+*/
+#[macro_export]
+macro_rules! nwg_message_window {
+    ($( $i:ident=$v:expr );*) => { {
+        let mut t = 
+        $crate::MessageWindowT{
+            title: ""
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+
 //---- Resources ----//
 
 /**
@@ -747,6 +1374,7 @@ macro_rules! nwg_canvas {
     • size: `12`  
     • weight: `FONT_WEIGHT_NORMAL`  
     • decoration: `FONT_DECO_NORMAL`  
+    • quality: `FontQuality::ClearType`  
 
     Usage:  
     `nwg_font!()`  
@@ -761,10 +1389,94 @@ macro_rules! nwg_font {
             family: "Arial", size: 12,
             weight: $crate::constants::FONT_WEIGHT_NORMAL,
             decoration: $crate::constants::FONT_DECO_NORMAL,
+            quality: $crate::constants::FontQuality::ClearType,
         };
         
         $( t.$i = $v; );*
 
         t
     }}
-}
\ No newline at end of file
+}
+
+/**
+    Sane defaults for the SystemFont resource.
+
+    Defaults:  
+    • font: `SystemFont::Message`  
+
+    Usage:  
+    `nwg_system_font!()`  
+    `nwg_system_font!(font=SystemFont::Caption)`  
+*/
+#[macro_export]
+macro_rules! nwg_system_font {
+    ( $( $i:ident=$v:expr );* ) => { {
+        let mut t = 
+        $crate::SystemFontT {
+            font: $crate::constants::SystemFont::Message
+        };
+        $( t.$i = $v; );*
+        t
+    }}
+}
+
+/**
+    Sane defaults for the Icon resource. Requires a source.
+
+    Defaults:  
+    • size: `(0, 0)` (system default icon size)  
+
+    Usage:  
+    `nwg_icon!(source="app.ico")`  
+    `nwg_icon!(source="app.ico"; size=(16, 16))`  
+*/
+#[macro_export]
+macro_rules! nwg_icon {
+    (source=$s:expr $(; $i:ident=$v:expr )* ) => { {
+        let mut t = 
+        $crate::IconT{ 
+            source: $s, size: (0, 0)
+        };
+        
+        $( t.$i = $v; );*
+
+        t
+    }}
+}
+
+/**
+    Sane defaults for the Bitmap resource. Requires a source.
+
+    Defaults:  
+    • size: `(0, 0)` (size of the image in the file)  
+
+    Usage:  
+    `nwg_bitmap!(source="image.bmp")`  
+    `nwg_bitmap!(source="image.bmp"; size=(32, 32))`  
+*/
+#[macro_export]
+macro_rules! nwg_bitmap {
+    (source=$s:expr $(; $i:ident=$v:expr )* ) => { {
+        let mut t = 
+        $crate::BitmapT{ 
+            source: $s, size: (0, 0)
+        };
+        
+        $( t.$i = $v; );*
+
+        t
+    }}
+}
+
+/**
+    Sane defaults for the Cursor resource. Requires a source.
+
+    Usage:  
+    `nwg_cursor!(source="pointer.cur")`  
+*/
+#[macro_export]
+macro_rules! nwg_cursor {
+    (source=$s:expr) => { {
+        $crate::CursorT{ source: $s }
+    }}
+}
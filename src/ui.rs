@@ -25,12 +25,16 @@ use std::collections::HashMap;
 use std::any::{Any, TypeId};
 use std::cell::{RefCell, Ref, RefMut};
 use std::rc::Rc;
+use std::time::Duration;
+
+use winapi::{HBRUSH, COLORREF};
 
 use low::message_handler::MessageHandler;
 use low::defs::{PackUserValueArgs, PackControlArgs, PackResourceArgs, UnpackArgs, BindArgs, UnbindArgs, TriggerArgs};
 use controls::{ControlT, Control, AnyHandle};
 use resources::{ResourceT, Resource};
-use events::{Event, EventCallback, EventArgs};
+use events::{Event, EventCallback, EventArgs, Sender};
+use defs::{FormValue, ControlSpec, ControlKind};
 use error::Error;
 
 type InnerId = u64;
@@ -60,7 +64,10 @@ pub struct UiInner<ID: Hash+Clone+'static> {
     pub inner_public_map: HashMap<InnerId, (ID, TypeId)>,
 
     // Map the handle of the controls to its ui inner id. Used when matching controls from within the events proc
-    pub handle_inner_map: HashMap<HandleHash, InnerId>
+    pub handle_inner_map: HashMap<HandleHash, InnerId>,
+
+    // Solid brushes shared by the controls of the ui, keyed by color. Used when handling the WM_CTLCOLOR* messages
+    pub brushes: HashMap<COLORREF, HBRUSH>
 }
 
 impl<ID: Hash+Clone> UiInner<ID> {
@@ -72,8 +79,10 @@ impl<ID: Hash+Clone> UiInner<ID> {
             Err(e) => { return Err(e); }
         };
 
+        // Visual styles are not required by NWG. If they can't be enabled, the controls use the classic style
+        enable_visual_styles().ok();
+
         unsafe{ 
-            enable_visual_styles(); 
             enable_com();
         }
 
@@ -84,7 +93,18 @@ impl<ID: Hash+Clone> UiInner<ID> {
             control_events: HashMap::with_capacity(32),
             resources: HashMap::with_capacity(16),
             inner_public_map: HashMap::with_capacity(64),
-            handle_inner_map: HashMap::with_capacity(32) })
+            handle_inner_map: HashMap::with_capacity(32),
+            brushes: HashMap::new() })
+    }
+
+    /// Delete the solid brushes of the ui. Called when the ui is dropped or when the system theme changes.
+    pub fn clear_brushes(&mut self) {
+        use gdi32::DeleteObject;
+        use winapi::HGDIOBJ;
+
+        for (_, brush) in self.brushes.drain() {
+            unsafe{ DeleteObject(brush as HGDIOBJ); }
+        }
     }
 
     pub fn pack_user_value(&mut self, params: PackUserValueArgs<ID>) -> Option<Error> {
@@ -122,6 +142,9 @@ impl<ID: Hash+Clone> UiInner<ID> {
                         event_collection.insert(e, Rc::new(Vec::new()));
                     }
 
+                    // Every control supports the destroy notice
+                    event_collection.insert(Event::DestroyNotice, Rc::new(Vec::new()));
+
                     self.inner_public_map.insert(inner_id, (params.id, params.value.resource_type_id()));
                     self.controls.insert(inner_id, RefCell::new(control) );
                     self.control_events.insert(inner_id, event_collection);
@@ -159,7 +182,7 @@ impl<ID: Hash+Clone> UiInner<ID> {
     }
 
     fn unpack_control(&mut self, id: InnerId) -> Option<Error> {
-        use low::events::unhook_window_events;
+        use low::events::{unhook_window_events, is_window_hooked};
         use low::menu_helper::{list_menu_children};
         use low::window_helper::list_window_children;
        
@@ -170,13 +193,8 @@ impl<ID: Hash+Clone> UiInner<ID> {
         }
 
         // Check if one of the control events is currently being executed
-        {
-            let events_collection = self.control_events.get_mut(&id).unwrap();
-            for mut ec in events_collection.values_mut() {
-                if Rc::get_mut(&mut ec).is_none() {
-                    return Some(Error::ControlInUse);
-                }
-            }
+        if self.events_in_use(id) {
+            return Some(Error::ControlInUse);
         }
 
         // Unpack the children
@@ -194,8 +212,22 @@ impl<ID: Hash+Clone> UiInner<ID> {
                 children.append( &mut list_window_children(h, self as *mut UiInner<ID>) );
                 children
             },
-            AnyHandle::HMENU_ITEM(_, _) | AnyHandle::HFONT(_) | AnyHandle::Custom(_, _) => vec![id], // These handle can't have children
+            AnyHandle::HMENU_ITEM(_, _) | AnyHandle::HFONT(_) | AnyHandle::HICON(_) | AnyHandle::HBITMAP(_) | AnyHandle::HCURSOR(_) | AnyHandle::Custom(_, _) => vec![id], // These handle can't have children
         };
+
+        // If the events of a child are being executed (ex: a button that destroys its parent window in its click callback),
+        // the children cannot be freed now. The removal is executed once the current system message returns.
+        if children_ids.iter().skip(1).any(|&child| self.events_in_use(child)) {
+            use low::defs::NWG_DEFERRED_UNPACK;
+            let data = UnpackArgs{ id: id };
+            self.messages.post(self as *mut UiInner<ID>, NWG_DEFERRED_UNPACK, Box::new(data) as Box<Any>);
+            return None;
+        }
+
+        // First pass: notify the control and its children while all of them are still alive
+        for id in children_ids.iter() {
+            self.trigger(*id, Event::DestroyNotice, EventArgs::None);
+        }
        
         for id in children_ids.iter().rev() {
 
@@ -211,9 +243,12 @@ impl<ID: Hash+Clone> UiInner<ID> {
             let handle_hash = *self.handle_inner_map.iter().find(|&(_, ref v)| *v == id).expect("Could not match handle while unpacking control").0;
             self.handle_inner_map.remove(&handle_hash);
 
-            // Unhook the events dispatcher if its a window
+            // Unhook the events dispatcher if its a window. The subclass must be removed before the window is destroyed.
             match control.handle() {
-                AnyHandle::HWND(h) => unhook_window_events::<ID>(h),
+                AnyHandle::HWND(h) => {
+                    unhook_window_events::<ID>(h);
+                    debug_assert!(!is_window_hooked::<ID>(h), "The events dispatcher is still hooked to an unpacked window");
+                },
                 _ => {/* Nothing to free here */}
             };
             
@@ -225,6 +260,14 @@ impl<ID: Hash+Clone> UiInner<ID> {
         None
     }
 
+    /// Return true if one of the callbacks of the control `id` is currently being executed
+    fn events_in_use(&mut self, id: InnerId) -> bool {
+        match self.control_events.get_mut(&id) {
+            Some(events_collection) => events_collection.values_mut().any(|ec| Rc::get_mut(ec).is_none()),
+            None => false
+        }
+    }
+
     fn unpack_resource(&mut self, id: InnerId) -> Option<Error> {
         // Check if the resource is currently borrowed by the user
         if let Err(_) = self.resources.get(&id).unwrap().try_borrow_mut() { 
@@ -440,6 +483,7 @@ impl<ID: Hash+Clone> Drop for UiInner<ID> {
         unhook_window_events::<ID>(self.messages.hwnd);
 
         self.messages.free();
+        self.clear_brushes();
     }
 
 }
@@ -447,6 +491,15 @@ impl<ID: Hash+Clone> Drop for UiInner<ID> {
 
 /**
     Object that manage the GUI elements
+
+    Every element packed in a Ui is identified by a value of type `ID` chosen by the application. Only the hash 
+    of the identifier is kept by the Ui, so `ID` only has to implement `Hash` and `Clone`. Two identifiers with the same hash 
+    refer to the same element. An enum deriving `Hash` and `Clone` is the recommended identifier type; see the crate documentation 
+    for an example.
+
+    A Ui and its controls belong to the thread that created it. A Ui can be created on any thread, including 
+    a worker thread that runs its own `dispatch_events` loop (ex: a background `MessageWindow` that receives `WM_COPYDATA`). 
+    `exit` only stops the loop of the calling thread.
 */
 pub struct Ui<ID: Hash+Clone+'static> {
     inner: *mut UiInner<ID>
@@ -497,7 +550,7 @@ impl<ID:Hash+Clone> Ui<ID> {
         Either call `ui.commit` to execute it now or wait for the command to be executed in the main event loop.  
 
         Commit returns  
-        • `Error::KeyExist` if the key already exists in the ui  
+        • `Error::KeyExists` if the key already exists in the ui  
     */
     pub fn pack_value<T: Into<Box<T>>+'static >(&self, id: &ID, value: T) {
         use low::defs::{NWG_PACK_USER_VALUE};
@@ -513,7 +566,7 @@ impl<ID:Hash+Clone> Ui<ID> {
         Either call `ui.commit` to execute it now or wait for the command to be executed in the main event loop.
 
         Commit returns  
-          • `Error::KeyExist` if the key already exists in the ui  
+          • `Error::KeyExists` if the key already exists in the ui  
           • `Error::{Any}` if the template creation fails  
     */
     pub fn pack_control<T: ControlT<ID>+'static>(&self, id: &ID, value: T) {
@@ -530,7 +583,7 @@ impl<ID:Hash+Clone> Ui<ID> {
         Either call `ui.commit` to execute it now or wait for the command to be executed in the main event loop.
 
         Commit returns  
-          • `Error::KeyExist` if the key already exists in the ui  
+          • `Error::KeyExists` if the key already exists in the ui  
           • `Error::{Any}` if the template creation fails  
     */
     pub fn pack_resource<T: ResourceT<ID>+'static>(&self, id: &ID, value: T) {
@@ -541,11 +594,93 @@ impl<ID:Hash+Clone> Ui<ID> {
         inner.messages.post(self.inner, NWG_PACK_RESOURCE, Box::new(data) as Box<Any> );
     }
 
+    /**
+        Create the controls described by `specs` in the control identified by `parent`, in order.  
+        Each spec is packed with the template macro of its control (ex: `nwg_button!`), so the members that are not in
+        `ControlSpec` keep their default values. The controls with a `placement` are placed relatively to `parent` once they are created.
+
+        Unlike `pack_control`, the controls are created immediately and the commands waiting in the Ui command queue are not executed,
+        so `parent` must already be created. If a control cannot be created, the controls created by this call are removed and
+        the ui is left unchanged.
+
+        Return the id and the handle of the created controls, in the order of `specs`.
+
+        Returns:  
+          • `Error::KeyExists` if an id is not unique  
+          • `Error::KeyNotFound` if `parent` is not in the ui  
+          • `Error::{Any}` if the creation of a control fails  
+    */
+    pub fn build_ui(&self, parent: &ID, specs: &[ControlSpec<ID>]) -> Result<Vec<(ID, AnyHandle)>, Error> {
+        use controls::ComboBoxT;
+
+        let inner = unsafe{ &mut *self.inner };
+        let mut handles: Vec<(ID, AnyHandle)> = Vec::with_capacity(specs.len());
+
+        for spec in specs.iter() {
+            let (text, position, size, visible, disabled) = (spec.text, spec.position, spec.size, spec.visible, spec.disabled);
+            let (tab_stop, group) = (spec.tab_stop, spec.group);
+            let items: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+            let p = parent.clone();
+
+            let value: Box<ControlT<ID>> = match spec.kind {
+                ControlKind::Button => Box::new(nwg_button!(parent=p; text=text; position=position; size=size;
+                  visible=visible; disabled=disabled; tab_stop=tab_stop; group=group)),
+                ControlKind::Label => Box::new(nwg_label!(parent=p; text=text; position=position; size=size;
+                  visible=visible; disabled=disabled)),
+                ControlKind::TextInput => Box::new(nwg_textinput!(parent=p; text=text; position=position; size=size;
+                  visible=visible; disabled=disabled; tab_stop=tab_stop; group=group)),
+                ControlKind::TextBox => Box::new(nwg_textbox!(parent=p; text=text; position=position; size=size;
+                  visible=visible; disabled=disabled; tab_stop=tab_stop; group=group)),
+                ControlKind::CheckBox => Box::new(nwg_checkbox!(parent=p; text=text; position=position; size=size;
+                  visible=visible; disabled=disabled; tab_stop=tab_stop; group=group)),
+                ControlKind::RadioButton => Box::new(nwg_radiobutton!(parent=p; text=text; position=position; size=size;
+                  visible=visible; disabled=disabled; tab_stop=tab_stop; group=group)),
+                ControlKind::GroupBox => Box::new(nwg_groupbox!(parent=p; text=text; position=position; size=size;
+                  visible=visible; disabled=disabled)),
+                ControlKind::ComboBox => {
+                    let t: ComboBoxT<String, ID, &'static str> = nwg_combobox!(parent=p; collection=items; position=position; size=size;
+                      visible=visible; disabled=disabled; tab_stop=tab_stop; group=group);
+                    Box::new(t)
+                },
+                ControlKind::ListBox => Box::new(nwg_listbox!(parent=p; collection=items; position=position; size=size;
+                  visible=visible; disabled=disabled; tab_stop=tab_stop; group=group)),
+            };
+
+            let handle = match inner.pack_control(PackControlArgs{ id: spec.id.clone(), value: value }) {
+                None => inner.handle_of(UiInner::hash_id(&spec.id)),
+                Some(e) => Err(e)
+            };
+
+            match handle {
+                Ok(h) => handles.push((spec.id.clone(), h)),
+                Err(e) => {
+                    // All or nothing: remove the controls created by this call
+                    for &(ref id, _) in handles.iter().rev() {
+                        inner.unpack(UnpackArgs{ id: UiInner::hash_id(id) });
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        for (spec, &(_, ref handle)) in specs.iter().zip(handles.iter()) {
+            if spec.placement.is_some() {
+                handle.set_relative_placement(spec.placement);
+            }
+        }
+
+        Ok(handles)
+    }
+
      /**
         Remove a element from the ui using its ID. The ID can identify a control, a resource or a user value.  
         Delayed, this only registers the command in the ui message queue.   
         Either call `ui.commit` to execute it now or wait for the command to be executed in the main event loop.
 
+        If the callbacks of one of the control children are being executed (ex: a button callback that removes its parent window),
+        the removal is delayed until the current system message returns. In that case, `commit` succeeds and the control is removed
+        by the event loop.
+
         Commit may returns:  
           • `Error::ControlInUse` if the control callbacks are being executed  
           • `Error::ControlInUse` if the object is currently borrowed (using ui.get or ui.get_mut)  
@@ -664,6 +799,26 @@ impl<ID:Hash+Clone> Ui<ID> {
         unreachable!()
     }
 
+    /**
+        Call `f` with an immutable reference to the element identified by `id` in the Ui and return its result.  
+        The element is borrowed for the duration of the call. Ex: `ui.with_control::<Button, _, _>(&100, |b| b.set_text("Hello"))`
+
+        Params:  
+          • id: The id that identify the element in the ui  
+          • f: The function to call with the element  
+
+        Returns:  
+          • `Error::KeyNotFound` will be returned if the key was not found in the Ui  
+          • `Error::BadType` will be returned if the key exists, but the type do not match  
+          • `Error::BorrowError` will be returned if the element was already borrowed mutably  
+    */
+    pub fn with_control<T: 'static, R, F: FnOnce(&T) -> R>(&self, id: &ID, f: F) -> Result<R, Error> {
+        match self.get::<T>(id) {
+            Ok(v) => Ok( f(&**v) ),
+            Err(e) => Err(e)
+        }
+    }
+
     /**
         Bind/Add a callback to a control event.  
         Delayed, this only registers the command in the ui message queue. 
@@ -693,6 +848,29 @@ impl<ID:Hash+Clone> Ui<ID> {
         inner.messages.post(self.inner, NWG_BIND, Box::new(data) as Box<Any> );
     }
 
+    /**
+        Bind/Add a callback that receives the control that raised the event instead of its ID.  
+        Ex: `ui.bind_typed(&100, &200, Event::Click, |_, btn: Sender<Button>, _, _| { btn.control().map(|b| b.set_text("Clicked")); })`
+
+        The control is looked up with `get` when the event is raised and it stays borrowed while the callback runs, so the callback
+        cannot borrow it mutably. If the control does not have the type `T` or cannot be borrowed, its handle is passed instead.  
+        The callback is not called if the control is no longer in the ui.
+
+        Params and commit errors are the same as `bind`.
+    */
+    pub fn bind_typed<T, F>(&self, id: &ID, cb_id: &ID, event: Event, cb: F) where
+      T: 'static,
+      F: Fn(&Ui<ID>, Sender<T>, &Event, &EventArgs) -> ()+'static {
+        self.bind(id, cb_id, event, move |ui, id, event, args| {
+            match ui.get::<T>(id) {
+                Ok(control) => { cb(ui, Sender::Control(&**control), event, args); },
+                Err(_) => if let Ok(handle) = ui.handle_of(id) {
+                    cb(ui, Sender::Handle(handle), event, args);
+                }
+            }
+        });
+    }
+
     /**
         Unbind/Remove a callback to a control event.  
         Delayed, this only registers the command in the ui message queue. 
@@ -743,6 +921,32 @@ impl<ID:Hash+Clone> Ui<ID> {
         inner.messages.post(self.inner, NWG_TRIGGER, Box::new(data) as Box<Any> );
     }
 
+    /**
+        Return a solid brush of the color `color` (red, green, blue) shared by the controls of the ui.
+        Custom controls return it from their `WM_CTLCOLOR*` handlers, which are called each time a control is painted.
+
+        Unlike the GDI cache of the thread, the brushes of the ui are never evicted. The brush is owned by the ui and
+        it must not be deleted. It stays valid until the system theme or colors change or until the ui is dropped.
+        Returns a null handle if the system could not create the brush.
+    */
+    pub fn brush(&self, color: (u8, u8, u8)) -> HBRUSH {
+        use gdi32::CreateSolidBrush;
+        use low::gdi_helper::colorref;
+
+        let inner = unsafe{ &mut *self.inner };
+        let color = colorref(color);
+        if let Some(&brush) = inner.brushes.get(&color) {
+            return brush;
+        }
+
+        let brush = unsafe{ CreateSolidBrush(color) };
+        if !brush.is_null() {
+            inner.brushes.insert(color, brush);
+        }
+
+        brush
+    }
+
     /**
         Return the underlying handle of a control or a resource.
         While this method is safe, anything done with the returned handle definitely won't be.
@@ -769,6 +973,107 @@ impl<ID:Hash+Clone> Ui<ID> {
         inner.inner_public_map.contains_key(&UiInner::hash_id(id))
     }
 
+    /**
+        Return an iterator over the id and the underlying handle of every control in the ui. Resources and user values are not included.  
+        The order of the controls is unspecified. Controls that are currently borrowed mutably when the iterator reaches them are skipped.  
+        The controls are read lazily, so the iterator must not be kept alive across a call to `commit`.
+    */
+    pub fn enumerate_controls<'a>(&'a self) -> Box<Iterator<Item=(ID, AnyHandle)> + 'a> {
+        let inner: &'a UiInner<ID> = unsafe{ &*self.inner };
+        let controls = inner.controls.iter()
+          .filter_map(move |(inner_id, control)| {
+              match (inner.inner_public_map.get(inner_id), control.try_borrow()) {
+                  (Some(&(ref id, _)), Ok(c)) => Some((id.clone(), c.handle())),
+                  _ => None
+              }
+          });
+
+        Box::new(controls)
+    }
+
+    /**
+        Return the number of controls in the ui. Resources and user values are not counted.  
+        Like `enumerate_controls`, the controls that are currently borrowed mutably are skipped, so the count always matches the number of controls returned by `enumerate_controls`.
+    */
+    pub fn control_count(&self) -> usize {
+        let inner = unsafe{ &mut (&*self.inner) };
+        inner.controls.iter()
+          .filter(|&(inner_id, control)| inner.inner_public_map.contains_key(inner_id) && control.try_borrow().is_ok())
+          .count()
+    }
+
+    /**
+        Return the value of every input control inside the window identified by `window` (text inputs, text boxes, check boxes,
+        radio buttons, combo boxes and list boxes). The order of the values is unspecified. Controls that are currently borrowed mutably are skipped.  
+        Use `restore_form_state` to apply the values again.
+
+        Returns:  
+          • `Error::KeyNotFound` if the id is not in the Ui.  
+          • `Error::ControlOrResourceRequired` if the id indentify a user value  
+          • `Error::BorrowError` if the window was already borrowed mutably  
+    */
+    pub fn form_state(&self, window: &ID) -> Result<Vec<(ID, FormValue)>, Error> {
+        use low::form_helper::read_control_value;
+
+        let root = match self.handle_of(window) {
+            Ok(h) => h,
+            Err(e) => { return Err(e); }
+        };
+
+        let inner = unsafe{ &mut (&*self.inner) };
+        let state = inner.controls.iter()
+          .filter_map(|(inner_id, control)| {
+              let control = match control.try_borrow() {
+                  Ok(c) => c,
+                  Err(_) => { return None; }
+              };
+
+              let value = match control.handle() {
+                  AnyHandle::HWND(h) if AnyHandle::HWND(h).is_child_of(&root) => unsafe{ read_control_value(h, &control.control_type()) },
+                  _ => None
+              };
+
+              match (inner.inner_public_map.get(inner_id), value) {
+                  (Some(&(ref id, _)), Some(value)) => Some((id.clone(), value)),
+                  _ => None
+              }
+          })
+          .collect();
+
+        Ok(state)
+    }
+
+    /**
+        Apply the values returned by `form_state`. The values of the controls that are no longer in the ui,
+        that are borrowed mutably or that do not match the value type are skipped.  
+        The controls raise their usual change events. Return the number of values applied.
+    */
+    pub fn restore_form_state(&self, state: &[(ID, FormValue)]) -> usize {
+        use low::form_helper::write_control_value;
+
+        let inner = unsafe{ &mut (&*self.inner) };
+        let mut restored = 0;
+
+        for &(ref id, ref value) in state.iter() {
+            // The borrow is released before the value is written, because the change events might need the control
+            let target = match inner.controls.get(&UiInner::hash_id(id)).map(|c| c.try_borrow()) {
+                Some(Ok(control)) => match control.handle() {
+                    AnyHandle::HWND(h) => Some((h, control.control_type())),
+                    _ => None
+                },
+                _ => None
+            };
+
+            if let Some((handle, control_type)) = target {
+                if unsafe{ write_control_value(handle, &control_type, value) } {
+                    restored += 1;
+                }
+            }
+        }
+
+        restored
+    }
+
     /**
         Return the message window handle of the ui. Useful for controls or functions that requires a window (such as timers)
     */
@@ -797,6 +1102,19 @@ pub fn dispatch_events() {
     unsafe{ ::low::events::dispatch_events(); }
 }
 
+/**
+    Dispatch the messages waiting in the system message queue until `predicate` returns true or until `timeout` elapses.
+    Useful in tests to wait for the result of an action without blocking in `dispatch_events`.
+
+    Timers and the NWG commands keep being processed while waiting. If a quit event is received, the function
+    returns immediately and the quit event is kept for the next `dispatch_events`.
+
+    Return `true` if `predicate` returned true before the timeout.
+*/
+pub fn pump_until<F: FnMut() -> bool>(predicate: F, timeout: Duration) -> bool {
+    unsafe{ ::low::events::pump_until(predicate, timeout) }
+}
+
 /**
     Send a WM_QUIT to the system queue. Breaks the `dispatch_events` loop.
 */
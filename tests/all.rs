@@ -2,13 +2,18 @@
 #![allow(unused_variables)]
 
 extern crate native_windows_gui as nwg;
+extern crate winapi;
+extern crate user32;
+extern crate gdi32;
+extern crate kernel32;
 
 use nwg::*;
 use nwg::constants::*;
 
 fn setup_ui() -> Ui<u64> { Ui::new().unwrap() }
-fn window() -> WindowT<&'static str> {  WindowT{title: "", position:(-600,-600), size:(100, 100), resizable:true, visible:true, disabled:false, exit_on_close:true} }
-fn default_font() -> FontT<&'static str> { FontT{ family: "Arial", size: 10, weight: FONT_WEIGHT_BOLD, decoration: FONT_DECO_ITALIC|FONT_DECO_STRIKEOUT } }
+fn window() -> WindowT<&'static str, u64> {  WindowT{title: "", position:(-600,-600), size:(100, 100), resizable:true, visible:true, disabled:false, exit_on_close:true, icon_big: None, icon_small: None} }
+fn canvas() -> CanvasT<u64> { CanvasT{parent: 1000, position: (0, 0), size: (50, 50), visible: true, disabled: false, min_size: None, max_size: None, topmost: false, alpha: None, center: false, icon_file: None, tool_window: false, owned: false} }
+fn default_font() -> FontT<&'static str> { FontT{ family: "Arial", size: 10, weight: FONT_WEIGHT_BOLD, decoration: FONT_DECO_ITALIC|FONT_DECO_STRIKEOUT, quality: FontQuality::ClearType } }
 
 macro_rules! test_visibility {
     ($ui:expr, $id:expr, $t:ty) => (
@@ -159,6 +164,24 @@ fn test_ui_pack_control() {
     let r = ui.commit();
     assert!(r.is_err() && r.err().unwrap() == Error::KeyExists, "Commit was successful");
 
+    // Enumerate the controls
+    ui.pack_resource(&1002, default_font());
+    ui.commit().expect("Commit was not successful");
+    assert!(ui.control_count() == 2, "Control count do not match");
+
+    let mut ids: Vec<u64> = ui.enumerate_controls().map(|(id, _)| id).collect();
+    ids.sort();
+    assert!(ids == vec![1000, 1001], "Enumerated controls do not match");
+
+    // Controls borrowed mutably are skipped by both functions
+    {
+        let _w = ui.get_mut::<Window>(&1000).expect("Failed to get control");
+        let ids: Vec<u64> = ui.enumerate_controls().map(|(id, _)| id).collect();
+        assert!(ids == vec![1001], "Enumerated controls do not match");
+        assert!(ui.control_count() == ids.len(), "Control count should match the enumerated controls");
+    }
+
+    assert!(ui.control_count() == ui.enumerate_controls().count(), "Control count should match the enumerated controls");
 }
 
 #[test]
@@ -176,6 +199,27 @@ fn test_ui_pack_resource() {
     ui.pack_resource(&1000, default_font());
     let r = ui.commit();
     assert!(r.is_err() && r.err().unwrap() == Error::KeyExists, "Commit was successful");
+
+    // System fonts
+    ui.pack_resource(&1001, SystemFontT{font: SystemFont::Caption});
+    ui.commit().expect("Commit was not successful");
+    { let f = ui.get::<Font>(&1001); f.expect("Failed to get control"); }
+
+    // Font quality
+    let qualities = [FontQuality::Default, FontQuality::ClearType, FontQuality::Grayscale, FontQuality::None];
+    for (i, quality) in qualities.iter().enumerate() {
+        let mut font = default_font();
+        font.quality = *quality;
+        ui.pack_resource(&(1010+i as u64), font);
+    }
+    ui.commit().expect("Commit was not successful");
+    { let f = ui.get::<Font>(&1012); f.expect("Failed to get control"); }
+
+    {
+        use nwg::custom::Resource;
+        let mut f = Font::system_message().expect("Failed to create the system font");
+        f.free();
+    }
 }
 
 #[test]
@@ -189,7 +233,7 @@ fn test_ui_unpack() {
     ui.pack_value(&1002, true);
     ui.pack_resource(&1003, default_font());
     ui.pack_resource(&1004, default_font());
-    ui.pack_control(&1005, ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, parent: 1001, font: None});
+    ui.pack_control(&1005, ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1001, font: None, command_link: false, note: None});
     
     ui.bind(&1001, &5000, Event::Destroyed, move |_, _, _, _|{ unsafe{ *(&mut *x) += 1; } } );
     ui.bind(&1005, &5000, Event::Destroyed, move |_, _, _, _|{ unsafe{ *(&mut *x) += 1; } } );
@@ -284,518 +328,3645 @@ fn test_ui_bind() {
 }
 
 #[test]
-fn test_ui_unbind() {
-    let ui = setup_ui();
-    
-    ui.pack_control(&1000, window());
-    ui.pack_control(&1002, MenuItemT{text: "", parent: 1000, disabled: false});
-    ui.pack_value(&1001, 5u32);
+fn test_pre_translate() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use nwg::custom::{AnyHandle, MSG, set_window_pre_translate, has_window_pre_translate};
+    use winapi::WM_APP;
+    use user32::PostMessageW;
+
+    // WM_APP+1 is handled by the window hook, WM_APP+2 by the button hook and WM_APP+3 by none of them
+    const HANDLED_BY_WINDOW: u32 = WM_APP + 1;
+    const HANDLED_BY_BUTTON: u32 = WM_APP + 2;
+    const NOT_HANDLED: u32 = WM_APP + 3;
 
-    ui.bind(&1000, &5000, Event::Destroyed, |_, _, _, _|{});
-    ui.commit().expect("Commit was not successful");
+    let ui = setup_ui();
+    let btn_t = ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
 
-    ui.unbind(&1000, &5000, Event::Destroyed);
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, btn_t);
     ui.commit().expect("Commit was not successful");
 
-    // Should be able to rebind destroyed callbacks
-    ui.bind(&1000, &5000, Event::Destroyed, |_, _, _, _|{});
-    ui.commit().expect("Commit was not successful");
+    let (window, button) = match (ui.handle_of(&1000), ui.handle_of(&1001)) {
+        (Ok(AnyHandle::HWND(w)), Ok(AnyHandle::HWND(b))) => (w, b),
+        _ => panic!("Bad handles")
+    };
 
-    // Cannot unbind events to user values
-    ui.unbind(&1001, &5000, Event::Destroyed);
-    let r = ui.commit();
-    assert!(r.is_err() && r.err().unwrap() == Error::ControlRequired, "Commit was successful");
+    let hooks_called: Rc<RefCell<Vec<(&'static str, u32)>>> = Rc::new(RefCell::new(Vec::new()));
+    let dispatched: Rc<RefCell<Vec<u32>>> = Rc::new(RefCell::new(Vec::new()));
 
-    // Key not in Ui
-    ui.unbind(&1005, &5000, Event::Destroyed);
-    let r = ui.commit();
-    assert!(r.is_err() && r.err().unwrap() == Error::KeyNotFound, "Commit was successful");
+    let (window_log, button_log, dispatch_log) = (hooks_called.clone(), hooks_called.clone(), dispatched.clone());
+    ui.bind(&1001, &1002, Event::Raw, move |_, _, _, args| {
+        if let &EventArgs::Raw(msg, _, _) = args {
+            if msg >= HANDLED_BY_WINDOW && msg <= NOT_HANDLED { dispatch_log.borrow_mut().push(msg); }
+        }
+    });
 
-    // Event not supported
-    ui.unbind(&1002, &5000, Event::MouseUp);
-    let r = ui.commit();
-    assert!(r.is_err() && r.err().unwrap() == Error::EventNotSupported(Event::MouseUp), "Commit was successful");
+    unsafe {
+        set_window_pre_translate(window, Some(move |msg: &MSG| {
+            if msg.message < HANDLED_BY_WINDOW || msg.message > NOT_HANDLED { return false; }
+            window_log.borrow_mut().push(("window", msg.message));
+            msg.message == HANDLED_BY_WINDOW
+        })).expect("Failed to install the hook");
+
+        set_window_pre_translate(button, Some(move |msg: &MSG| {
+            if msg.message < HANDLED_BY_WINDOW || msg.message > NOT_HANDLED { return false; }
+            button_log.borrow_mut().push(("button", msg.message));
+            msg.message == HANDLED_BY_BUTTON
+        })).expect("Failed to install the hook");
+    }
+    assert!(has_window_pre_translate(window) && has_window_pre_translate(button), "Hooks should be installed");
+
+    // The hook of the window that received the message is called first, then the hooks of its parents
+    unsafe{ PostMessageW(button, HANDLED_BY_WINDOW, 0, 0); }
+    assert!(pump_until(|| hooks_called.borrow().len() == 2, Duration::from_secs(5)), "Hooks were not called");
+    assert_eq!(*hooks_called.borrow(), vec![("button", HANDLED_BY_WINDOW), ("window", HANDLED_BY_WINDOW)]);
+
+    // A hook that handles the message stops the parents hooks and the dispatch
+    hooks_called.borrow_mut().clear();
+    unsafe{
+        PostMessageW(button, HANDLED_BY_BUTTON, 0, 0);
+        PostMessageW(button, NOT_HANDLED, 0, 0);
+    }
+    assert!(pump_until(|| !dispatched.borrow().is_empty(), Duration::from_secs(5)), "Message was not dispatched");
+    assert_eq!(*hooks_called.borrow(), vec![("button", HANDLED_BY_BUTTON), ("button", NOT_HANDLED), ("window", NOT_HANDLED)]);
+    assert_eq!(*dispatched.borrow(), vec![NOT_HANDLED], "Only the message not handled by the hooks should be dispatched");
 
-    // Callback do not exists
-    ui.unbind(&1000, &5001, Event::Destroyed);
-    let r = ui.commit();
-    assert!(r.is_err() && r.err().unwrap() == Error::KeyNotFound, "Commit was successful");
+    unsafe{ set_window_pre_translate(window, None::<fn(&MSG) -> bool>).expect("Failed to remove the hook"); }
+    assert!(!has_window_pre_translate(window), "Hook should be removed");
 
-    ui.bind(&1000, &5001, Event::Destroyed, |ui, id, _, _|{
-        // When event callbacks are being dipatched, it is impossible to unbind a callbacks on the same control with the same event
-        ui.unbind(&1000, &5000, Event::Destroyed);
-        let r = ui.commit();
-        assert!(r.is_err() && r.err().unwrap() == Error::ControlInUse, "Commit was successful");
-    });
+    // The hook is removed with the window
+    ui.unpack(&1001);
     ui.commit().expect("Commit was not successful");
-
-    { ui.get::<Window>(&1000).unwrap().close(); }
-    dispatch_events();
+    assert!(!has_window_pre_translate(button), "Hook should be removed when the window is destroyed");
 }
 
-
 #[test]
-fn test_user_trigger() {
+fn test_unpack_unhook() {
+    use nwg::custom::{AnyHandle, is_window_hooked};
+
     let ui = setup_ui();
-    let mut flag_set: bool = false;
-    let x = &mut flag_set as *mut bool;
-    
-    ui.pack_value(&1001, 5u32);
+    let btn_t = ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
+
     ui.pack_control(&1000, window());
-    ui.bind(&1000, &5000, Event::MouseDown, move |_, _, _, _|{ unsafe{ *(&mut *x) = true; } });
-    ui.bind(&1000, &5000, Event::MouseUp, move |ui, _, _, _|{ 
-        ui.trigger(&1000, Event::MouseDown, EventArgs::None);
-        let r = ui.commit().expect("Commit was not successful");;
-    });
-   
-    ui.trigger(&1000, Event::MouseDown, EventArgs::None);
+    ui.pack_control(&1001, btn_t);
     ui.commit().expect("Commit was not successful");
 
-    ui.trigger(&1030, Event::MouseDown, EventArgs::None);
-    let r = ui.commit();
-    assert!(r.is_err() && r.err().unwrap() == Error::KeyNotFound, "Commit was successful");
-
-    ui.trigger(&1001, Event::MouseDown, EventArgs::None);
-    let r = ui.commit();
-    assert!(r.is_err() && r.err().unwrap() == Error::ControlRequired, "Commit was successful");
-
-    ui.trigger(&1000, Event::SelectionChanged, EventArgs::None);
-    let r = ui.commit();
-    assert!(r.is_err() && r.err().unwrap() == Error::EventNotSupported(Event::SelectionChanged), "Commit was successful");
+    let (window, button) = match (ui.handle_of(&1000), ui.handle_of(&1001)) {
+        (Ok(AnyHandle::HWND(w)), Ok(AnyHandle::HWND(b))) => (w, b),
+        _ => panic!("Bad handles")
+    };
 
-    ui.trigger(&1000, Event::MouseUp, EventArgs::None);
-    let r = ui.commit();
+    assert!(is_window_hooked::<u64>(window) && is_window_hooked::<u64>(button), "Controls should be hooked by the ui");
+    assert!(!is_window_hooked::<u32>(button), "Button should not be hooked by a ui with another identifier type");
 
-    assert!(flag_set, "Flag was not set");
+    ui.unpack(&1001);
+    ui.commit().expect("Commit was not successful");
+    assert!(!is_window_hooked::<u64>(button), "Unpacked control should not be hooked");
+    assert!(is_window_hooked::<u64>(window), "Window should still be hooked");
 }
 
-
 #[test]
-fn test_window_control_user_close() {
+fn test_destroy_notice() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
     let ui = setup_ui();
-    let mut callback_executed: bool = false;
-    let x = &mut callback_executed as *mut bool;
+    let btn_t = ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
 
     ui.pack_control(&1000, window());
-    ui.bind(&1000, &5000, Event::Destroyed, move |_, _, _, _|{ unsafe{ *(&mut *x) = true; } });
+    ui.pack_control(&1001, btn_t);
+    ui.pack_control(&1002, MenuT{ text: "Test", parent: 1000, disabled: false  });
     ui.commit().expect("Commit was not successful");
 
-    assert!(ui.has_id(&1000), "ID 1000 was not found in id after commit");
-    
-    // Try to close the window
-    { ui.get::<Window>(&1000).unwrap().close(); }
+    let log: Rc<RefCell<Vec<(u64, Event)>>> = Rc::new(RefCell::new(Vec::new()));
+    for &id in [1000u64, 1001].iter() {
+        for &evt in [Event::DestroyNotice, Event::Destroyed].iter() {
+            let log = log.clone();
+            ui.bind(&id, &5000, evt, move |_, id, evt, _|{ log.borrow_mut().push((*id, *evt)); } );
+        }
+    }
 
-    // Dispatch the waiting close event
-    dispatch_events();
+    // Every control supports the destroy notice
+    ui.bind(&1002, &5000, Event::DestroyNotice, |_, _, _, _|{} );
+    ui.commit().expect("Commit was not successful");
+
+    ui.unpack(&1000);
+    ui.commit().expect("Commit was not successful");
+
+    let log = log.borrow();
+    assert!(log.len() == 4, "Expected 4 events, got {:?}", *log);
+    assert!(log[0] == (1000, Event::DestroyNotice) && log[1] == (1001, Event::DestroyNotice), "Destroy notices should be raised parent first: {:?}", *log);
+    assert!(log[2] == (1001, Event::Destroyed) && log[3] == (1000, Event::Destroyed), "Destroyed should be raised children first: {:?}", *log);
 }
 
 #[test]
-fn test_drop_callback() {
-    let mut callback_executed: bool = false;
-    
-    {
-        let x = &mut callback_executed as *mut bool;
-        let ui = setup_ui();
-        ui.pack_control(&1000, window());
-        ui.bind(&1000, &5000, Event::Destroyed, move |_, _, _, _|{ unsafe{ *(&mut *x) = true; } });
-        ui.commit().expect("Commit was not successful");
-    }
+fn test_canvas_child_style() {
+    use nwg::custom::{AnyHandle, get_window_long};
+    const GWL_STYLE: i32 = -16;
+    const GWL_EXSTYLE: i32 = -20;
+    const WS_CHILD: u32 = 0x40000000;
+    const WS_FRAME: u32 = 0x00C00000 | 0x00080000 | 0x00040000; // caption, sysmenu, thickframe
+    const WS_EX_FRAME: u32 = 0x00000100 | 0x00000200 | 0x00000001; // windowedge, clientedge, dlgmodalframe
 
-    assert!(callback_executed, "Destroy callback was not executed.")
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, canvas());
+    ui.commit().expect("Commit was not successful");
+
+    let canvas = match ui.handle_of(&1001) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let style = get_window_long(canvas, GWL_STYLE) as u32;
+    let ex_style = get_window_long(canvas, GWL_EXSTYLE) as u32;
+
+    assert!(style & WS_CHILD == WS_CHILD, "A child canvas should have the WS_CHILD style");
+    assert!(style & WS_FRAME == 0, "A child canvas should not have frame styles: {:x}", style & WS_FRAME);
+    assert!(ex_style & WS_EX_FRAME == 0, "A child canvas should not have frame extended styles: {:x}", ex_style & WS_EX_FRAME);
 }
 
 #[test]
-fn test_menus() {
+fn test_canvas_activation() {
+    use nwg::custom::AnyHandle;
+    use winapi::{WM_ACTIVATE, WPARAM};
+    use user32::SendMessageW;
+    const WA_INACTIVE: WPARAM = 0;
+    const WA_ACTIVE: WPARAM = 1;
+
     let ui = setup_ui();
-    
-    let mut free_count: u8 = 0;
-    let x = &mut free_count as *mut u8;
 
     ui.pack_control(&1000, window());
-    ui.bind(&1000, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    
-    ui.pack_control(&1001, MenuT{ text: "Test1", parent: 1000, disabled: false  });
-    ui.bind(&1001, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.pack_control(&1001, canvas());
+    ui.pack_control(&1002, CanvasT{position: (-600, -600), tool_window: true, owned: true, ..canvas()});
+    ui.pack_control(&1003, CanvasT{position: (-600, -600), ..canvas()});
+    ui.commit().expect("Commit was not successful");
 
-    ui.pack_control(&2003, MenuItemT{ text: "TestItem4", parent: 1000, disabled: false  });
-    ui.bind(&2003, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    
-    ui.pack_control(&1002, MenuT{ text: "Test2", parent: 1000, disabled: false  });
-    ui.pack_control(&1003, MenuT{ text: "Test3", parent: 1002, disabled: false  });
-    ui.pack_control(&1004, MenuT{ text: "Test4", parent: 1002, disabled: false  });
-    ui.pack_control(&2000, MenuItemT{ text: "TestItem1", parent: 1002, disabled: false  });
-    ui.bind(&1002, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&1003, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&1004, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&2000, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    let states: Vec<_> = [1001u64, 1002, 1003].iter().map(|id| ui.get::<Canvas<u64>>(id).expect("Failed to get canvas").state()).collect();
+    ui.get::<Canvas<u64>>(&1003).unwrap().set_parent(None).expect("Failed to detach the canvas");
 
-    ui.pack_control(&1005, MenuT{ text: "Test5", parent: 1000, disabled: false });
-    ui.pack_control(&1006, MenuT{ text: "Test6", parent: 1005, disabled: false });
-    ui.pack_control(&1007, MenuT{ text: "Test7", parent: 1006, disabled: false });
-    ui.pack_control(&2001, MenuItemT{ text: "TestItem2", parent: 1007, disabled: false  });
-    ui.bind(&1005, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&1006, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&1007, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&2001, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    let (window, top_canvas) = match (ui.handle_of(&1000), ui.handle_of(&1003)) {
+        (Ok(AnyHandle::HWND(w)), Ok(AnyHandle::HWND(c))) => (w, c),
+        _ => panic!("Bad handles")
+    };
 
-    ui.pack_control(&1008, MenuT{ text: "Test8", parent: 1000, disabled: false });
-    ui.pack_control(&1009, MenuT{ text: "Test9", parent: 1008, disabled: false });
-    ui.pack_control(&2002, MenuItemT{ text: "TestItem3", parent: 1000, disabled: false  });
-    ui.bind(&1008, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&1009, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
-    ui.bind(&2002, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    // The child and the owned canvases follow the activation of the window
+    unsafe{ SendMessageW(window, WM_ACTIVATE, WA_INACTIVE, 0); }
+    assert!(!states[0].is_active(), "The child canvas should be inactive");
+    assert!(!states[1].is_active(), "The owned canvas should be inactive");
+    assert!(states[2].is_active(), "A canvas that is not owned by the window should stay active");
 
-    ui.commit().expect("Commit was not successful");
+    unsafe{ SendMessageW(window, WM_ACTIVATE, WA_ACTIVE, 0); }
+    assert!(states[0].is_active() && states[1].is_active(), "The canvases should be active");
 
-    {
-        // Menu should be enabled
-        let (menu, menuitem) = (ui.get::<Menu>(&1001).unwrap(), ui.get::<MenuItem>(&2002).unwrap());
-        assert!(menu.get_enabled() == true, "Menu is not enabled");
-        assert!(menuitem.get_enabled() == true, "Menu is not enabled");
+    // A top level canvas follows its own activation
+    unsafe{ SendMessageW(top_canvas, WM_ACTIVATE, WA_INACTIVE, 0); }
+    assert!(!states[2].is_active(), "The top level canvas should be inactive");
+    assert!(states[0].is_active() && states[1].is_active(), "The other canvases should stay active");
 
-        menu.set_enabled(false);
-        menuitem.set_enabled(false);
+    unsafe{ SendMessageW(top_canvas, WM_ACTIVATE, WA_ACTIVE, 0); }
+    assert!(states[2].is_active(), "The top level canvas should be active");
+}
 
-        assert!(menu.get_enabled() == false, "Menu is enabled");
-        assert!(menuitem.get_enabled() == false, "Menu is enabled");
-    }
+#[test]
+fn test_canvas_reparent() {
+    use nwg::custom::{AnyHandle, get_window_long, get_window_parent};
+    const GWL_STYLE: i32 = -16;
+    const WS_CHILD: u32 = 0x40000000;
+    const WS_CAPTION: u32 = 0x00C00000;
 
-    // Removing a menu without items
-    ui.unpack(&1001);
-    ui.commit().expect("Commit was not successful");
-    assert!(ui.has_id(&1001) == false, "Destroyed menu key '1001' was found in ui");
-    assert!(free_count == 1, "Freecount was not increased!");
+    let ui = setup_ui();
 
-    // Removing a menu item
-    ui.unpack(&2003);
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, window());
+    ui.pack_control(&1002, CanvasT{size: (200, 200), ..canvas()});
     ui.commit().expect("Commit was not successful");
-    assert!(ui.has_id(&2003) == false, "Destroyed menu key '1001' was found in ui");
-    assert!(free_count == 2, "Freecount was not increased!");
 
-    // Removing a menu with subitems
-    ui.unpack(&1002);
-    ui.commit().expect("Commit was not successful");
-    assert!(ui.has_id(&1002) == false, "Destroyed menu key '1002' was found in ui");
-    assert!(ui.has_id(&1003) == false, "Destroyed menu key '1003' was found in ui");
-    assert!(ui.has_id(&1004) == false, "Destroyed menu key '1004' was found in ui");
-    assert!(ui.has_id(&2000) == false, "Destroyed menu item key '2000' was found in ui");
-    assert!(free_count == 6, "Freecount was not increased by 4!");
+    let (w1, w2, handle) = match (ui.handle_of(&1000), ui.handle_of(&1001), ui.handle_of(&1002)) {
+        (Ok(AnyHandle::HWND(w1)), Ok(AnyHandle::HWND(w2)), Ok(AnyHandle::HWND(c))) => (w1, w2, c),
+        _ => panic!("Bad handles")
+    };
 
-    // Removing a menu with subitems that have subitems
-    ui.unpack(&1005);
-    ui.commit().expect("Commit was not successful");
-    assert!(ui.has_id(&1005) == false, "Destroyed menu key '1005' was found in ui");
-    assert!(ui.has_id(&1006) == false, "Destroyed menu key '1006' was found in ui");
-    assert!(ui.has_id(&1007) == false, "Destroyed menu key '1007' was found in ui");
-    assert!(ui.has_id(&2001) == false, "Destroyed menu item key '2001' was found in ui");
-    assert!(free_count == 10, "Freecount was not increased by 4!");
+    let canvas = ui.get::<Canvas<u64>>(&1002).expect("Failed to get canvas");
+    let state = canvas.state();
+
+    assert!(canvas.is_child(), "Canvas should be a child");
+    assert!(match canvas.parent() { Some(AnyHandle::HWND(h)) => h == w1, _ => false }, "Canvas parent should be the first window");
+
+    canvas.set_parent(Some(&AnyHandle::HWND(w2))).expect("Failed to move the canvas");
+    assert!(unsafe{ get_window_parent(handle) } == Some(w2), "Canvas parent should be the second window");
+
+    canvas.set_parent(None).expect("Failed to detach the canvas");
+    let style = get_window_long(handle, GWL_STYLE) as u32;
+    assert!(unsafe{ get_window_parent(handle) }.is_none(), "Canvas should be a top level window");
+    assert!(!canvas.is_child() && canvas.parent().is_none(), "Canvas should not have a parent");
+    assert!(style & WS_CHILD == 0 && style & WS_CAPTION == WS_CAPTION, "A top level canvas should have a caption");
+    assert!(canvas.get_size() == (200, 200), "Canvas should keep its client size");
+    assert!(canvas.physical_size() == (200, 200), "Physical size should not include the caption");
+    assert!(canvas.scale_factor() >= 1.0, "Scale factor should be at least 1.0");
+
+    canvas.set_parent(Some(&AnyHandle::HWND(w1))).expect("Failed to move the canvas back");
+    let style = get_window_long(handle, GWL_STYLE) as u32;
+    assert!(unsafe{ get_window_parent(handle) } == Some(w1), "Canvas parent should be the first window");
+    assert!(style & WS_CHILD == WS_CHILD && style & WS_CAPTION == 0, "A child canvas should not have a caption");
+    assert!(canvas.get_size() == (200, 200), "Canvas should keep its client size");
+    assert!(canvas.get_visibility(), "Canvas should stay visible");
+
+    let r = canvas.set_parent(Some(&AnyHandle::HMENU(::std::ptr::null_mut())));
+    assert!(r.is_err(), "A menu cannot be the parent of a canvas");
+    assert!(match ui.handle_of(&1002) { Ok(AnyHandle::HWND(h)) => h == handle, _ => false }, "Canvas should keep its handle");
+    assert!(!state.is_closing(), "Canvas state should be preserved");
+}
 
-    // Removing a window should also free its menus
-    ui.unpack(&1000);
+#[test]
+fn test_canvas_tool_window() {
+    use nwg::custom::{AnyHandle, get_window_long};
+    const GWL_EXSTYLE: i32 = -20;
+    const GWL_HWNDPARENT: i32 = -8;
+    const WS_EX_TOOLWINDOW: u32 = 0x00000080;
+
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, CanvasT{size: (200, 200), tool_window: true, ..canvas()});
     ui.commit().expect("Commit was not successful");
-    assert!(ui.has_id(&1000) == false, "Destroyed menu key '1000' was found in ui");
-    assert!(ui.has_id(&1008) == false, "Destroyed menu key '1008' was found in ui");
-    assert!(ui.has_id(&1009) == false, "Destroyed menu key '1009' was found in ui");
-    assert!(ui.has_id(&2002) == false, "Destroyed menu item key '2002' was found in ui");
-    assert!(free_count == 14, "Freecount was not increased by 3!");
+
+    let (window, handle) = match (ui.handle_of(&1000), ui.handle_of(&1001)) {
+        (Ok(AnyHandle::HWND(w)), Ok(AnyHandle::HWND(c))) => (w, c),
+        _ => panic!("Bad handles")
+    };
+
+    let canvas = ui.get::<Canvas<u64>>(&1001).expect("Failed to get canvas");
+    assert!((get_window_long(handle, GWL_EXSTYLE) as u32) & WS_EX_TOOLWINDOW == WS_EX_TOOLWINDOW, "Canvas should be a tool window");
+
+    // A detached tool window is owned by its old top level window
+    canvas.set_parent(None).expect("Failed to detach the canvas");
+    assert!(canvas.parent().is_none(), "Canvas should not have a parent");
+    assert!(get_window_long(handle, GWL_HWNDPARENT) as usize == window as usize, "Canvas should be owned by the window");
+    assert!((get_window_long(handle, GWL_EXSTYLE) as u32) & WS_EX_TOOLWINDOW == WS_EX_TOOLWINDOW, "Canvas should stay a tool window");
 }
 
 #[test]
-fn test_window() {
+fn test_canvas_owned() {
+    use nwg::custom::{AnyHandle, get_window_long};
+    const GWL_STYLE: i32 = -16;
+    const GWL_HWNDPARENT: i32 = -8;
+    const WS_CHILD: u32 = 0x40000000;
+    const WS_CAPTION: u32 = 0x00C00000;
+
     let ui = setup_ui();
 
-    // pack test
     ui.pack_control(&1000, window());
+    ui.pack_control(&1001, CanvasT{size: (200, 150), center: true, owned: true, ..canvas()});
     ui.commit().expect("Commit was not successful");
 
-    // methods test
-    test_visibility!(ui, &1000, Window);
-    test_position!(ui, &1000, Window);
-    test_size!(ui, &1000, Window);
-    test_enabled!(ui, &1000, Window);
+    let (window, handle) = match (ui.handle_of(&1000), ui.handle_of(&1001)) {
+        (Ok(AnyHandle::HWND(w)), Ok(AnyHandle::HWND(c))) => (w, c),
+        _ => panic!("Bad handles")
+    };
+
+    let canvas = ui.get::<Canvas<u64>>(&1001).expect("Failed to get canvas");
+    let style = get_window_long(handle, GWL_STYLE) as u32;
+
+    assert!(style & WS_CHILD == 0 && style & WS_CAPTION == WS_CAPTION, "An owned canvas should be a top level window");
+    assert!(!canvas.is_child() && canvas.parent().is_none(), "An owned canvas should not have a parent");
+    assert!(get_window_long(handle, GWL_HWNDPARENT) as usize == window as usize, "Canvas should be owned by the window");
+    assert!(canvas.physical_size() == (200, 150), "The template size should be the client size");
 }
 
 #[test]
-fn test_buttons() {
-    let ui = setup_ui();
+fn test_canvas_options() {
+    use nwg::custom::{AnyHandle, get_window_long, get_window_client_rect};
+    use winapi::WM_ERASEBKGND;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
+    const GWL_EXSTYLE: i32 = -20;
+    const WS_EX_LAYERED: u32 = 0x00080000;
 
-    let mut btn_t = ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, parent: 1000, font: None};
-    let btn_t2 = CheckBoxT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, checkstate: CheckState::Checked, tristate: false, parent: 1000, font: None};
+    let ui = setup_ui();
 
-    ui.pack_resource(&10_000, default_font());
     ui.pack_control(&1000, window());
-    ui.pack_control(&1001, MenuItemT{text: "", parent: 1000, disabled: false});
+    ui.pack_control(&1001, CanvasT{size: (20, 20), min_size: Some((10, 10)), topmost: true, alpha: Some(128), center: true, ..canvas()});
+    ui.commit().expect("Commit was not successful");
 
-    // pack test
-    ui.pack_control(&1002, btn_t.clone());
-    ui.pack_control(&1010, btn_t2.clone());
+    // A missing icon file fails the canvas creation
+    ui.pack_control(&1002, CanvasT{size: (20, 20), icon_file: Some("does_not_exist.ico".to_string()), ..canvas()});
+    assert!(ui.commit().is_err(), "The canvas should not be created without its icon");
+    assert!(!ui.has_id(&1002), "The canvas should not be packed");
 
-    btn_t.font = Some(10_000);
-    ui.pack_control(&1003, btn_t.clone() );
-    ui.commit().expect("Commit was not successful");
+    let (window, handle) = match (ui.handle_of(&1000), ui.handle_of(&1001)) {
+        (Ok(AnyHandle::HWND(w)), Ok(AnyHandle::HWND(c))) => (w, c),
+        _ => panic!("Bad handles")
+    };
 
-    btn_t.parent = 9999;
-    ui.pack_control(&1004, btn_t.clone() );
-    match ui.commit() { Err(Error::KeyNotFound) => {}, r => panic!("Should have returned Error::KeyNotFound, got {:?}", r) }
+    let canvas = ui.get::<Canvas<u64>>(&1001).expect("Failed to get canvas");
+    let (_, _, w, h) = unsafe{ get_window_client_rect(window) };
+    let ex_style = get_window_long(handle, GWL_EXSTYLE) as u32;
 
-    btn_t.parent = 1001;
-    ui.pack_control(&1004, btn_t.clone() );
-    match ui.commit() { Err(Error::BadParent(_)) => {}, r => panic!("Should have returned Error::BadParent, got {:?}", r) }
+    assert!(canvas.get_position() == (((w as i32)-20)/2, ((h as i32)-20)/2), "Canvas should be centered in its parent");
+    assert!(ex_style & WS_EX_LAYERED == WS_EX_LAYERED, "Canvas with an alpha value should be layered");
+    assert!(canvas.get_visibility(), "Canvas should be shown after it is configured");
 
-    btn_t.parent = 1000;
-    btn_t.font = Some(1000);
-    ui.pack_control(&1004, btn_t.clone() );
+    let cursor = Cursor::system(SystemCursor::Hand);
+    canvas.set_cursor(Some(&cursor));
+    canvas.set_cursor(None);
+
+    assert!(canvas.get_cursor_visible(), "Cursor should be visible by default");
+    canvas.set_cursor_visible(false);
+    assert!(!canvas.get_cursor_visible(), "Cursor should be hidden");
+    canvas.set_cursor_visible(true);
+    assert!(canvas.get_cursor_visible(), "Cursor should be visible");
+
+    // The dialog keys are the answer of the canvas to `WM_GETDLGCODE`
+    let dialog_code = || unsafe{ user32::SendMessageW(handle, winapi::WM_GETDLGCODE, 0, 0) as u32 };
+    let all_keys = DIALOG_KEYS_TAB | DIALOG_KEYS_ARROWS | DIALOG_KEYS_CHARS | DIALOG_KEYS_ALL;
+
+    assert!(canvas.dialog_keys() == DIALOG_KEYS_NONE, "Canvas should not handle the dialog keys by default");
+    assert!(dialog_code() & all_keys == 0, "Canvas should not want the dialog keys by default");
+
+    canvas.set_dialog_keys(DIALOG_KEYS_TAB | DIALOG_KEYS_ARROWS);
+    assert!(canvas.dialog_keys() == DIALOG_KEYS_TAB | DIALOG_KEYS_ARROWS, "Dialog keys do not match");
+    assert!(dialog_code() & all_keys == DIALOG_KEYS_TAB | DIALOG_KEYS_ARROWS, "WM_GETDLGCODE should return the dialog keys");
+
+    canvas.set_dialog_keys(DIALOG_KEYS_ALL);
+    assert!(dialog_code() & all_keys == DIALOG_KEYS_ALL, "WM_GETDLGCODE should return the dialog keys");
+
+    canvas.set_dialog_keys(DIALOG_KEYS_NONE);
+    assert!(canvas.dialog_keys() == DIALOG_KEYS_NONE, "Dialog keys should be cleared");
+    assert!(dialog_code() & all_keys == 0, "WM_GETDLGCODE should not return the cleared dialog keys");
+
+    let bad_icon = Icon::from_hicon(unsafe{ ::std::mem::transmute(0xDEADusize) }, false);
+    assert!(canvas.icon().expect("Failed to read the icon").is_none(), "Canvas should not have an icon");
+    assert!(canvas.set_icon(Some(&bad_icon)).is_err(), "Setting an invalid icon should fail");
+    assert!(canvas.set_icon(None).is_ok(), "Removing the icon should succeed");
+
+    let (paint_count, erase_count) = (Rc::new(Cell::new(0u32)), Rc::new(Cell::new(0u32)));
+    let (paints, erases) = (paint_count.clone(), erase_count.clone());
+    ui.bind(&1001, &5000, Event::Paint, move |_, _, _, _|{ paints.set(paints.get()+1); } );
+    ui.bind(&1001, &5001, Event::Raw, move |_, _, _, args|{
+        if let &EventArgs::Raw(WM_ERASEBKGND, _, _) = args { erases.set(erases.get()+1); }
+    });
+    ui.commit().expect("Commit was not successful");
+
+    // Drop the paint queued when the canvas was shown
+    unsafe{ user32::ValidateRect(handle, ::std::ptr::null()); }
+
+    // Invalidate erases the background before the paint, invalidate_no_erase does not
+    canvas.invalidate();
+    assert!(pump_until(|| paint_count.get() == 1, Duration::from_secs(5)), "Invalidate should paint the canvas");
+    assert!(erase_count.get() == 1, "Invalidate should erase the background");
+
+    canvas.invalidate_no_erase();
+    assert!(pump_until(|| paint_count.get() == 2, Duration::from_secs(5)), "invalidate_no_erase should paint the canvas");
+    assert!(erase_count.get() == 1, "invalidate_no_erase should not erase the background");
+
+    // Redraw paints the canvas before it returns
+    canvas.redraw();
+    assert!(paint_count.get() == 3, "Redraw should paint the canvas immediately");
+
+    canvas.set_visibility(false);
+    canvas.redraw();
+    assert!(paint_count.get() == 3, "Redraw should do nothing on a hidden canvas");
+}
+
+#[test]
+fn test_canvas_state() {
+    use nwg::custom::{Control, AnyHandle};
+    use winapi::RECT;
+    use std::{mem, thread};
+
+    fn client_size(canvas: &Canvas<u64>) -> (u32, u32) {
+        let handle = match canvas.handle() { AnyHandle::HWND(h) => h, _ => panic!("Bad handle") };
+        let mut rect: RECT = unsafe{ mem::zeroed() };
+        unsafe{ user32::GetClientRect(handle, &mut rect); }
+        ((rect.right - rect.left) as u32, (rect.bottom - rect.top) as u32)
+    }
+
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, CanvasT{size: (50, 40), ..canvas()});
+    ui.commit().expect("Commit was not successful");
+
+    let state = {
+        let canvas = ui.get::<Canvas<u64>>(&1001).expect("Failed to get canvas");
+        let state = canvas.state();
+        assert!(state.size() == client_size(&canvas), "The state should hold the canvas size");
+        assert!(state.is_active() && !state.is_closing());
+
+        // Each resize bumps the generation
+        let generation = state.generation();
+        canvas.set_size(80, 60);
+        assert!(state.size() == client_size(&canvas), "The state should follow the canvas size");
+        assert!(state.generation() > generation, "A resize should increase the generation");
+
+        state
+    };
+
+    // The state can be read from a render thread
+    let (size, generation) = (state.size(), state.generation());
+    let thread_state = state.clone();
+    let read = thread::spawn(move || (thread_state.size(), thread_state.generation(), thread_state.is_closing())).join().expect("Render thread failed");
+    assert!(read == (size, generation, false), "The render thread should read the same state");
+
+    // The closing flag is set when the canvas is destroyed and the state outlives the canvas
+    ui.unpack(&1001);
+    ui.commit().expect("Commit was not successful");
+    assert!(state.is_closing(), "The state should be closing once the canvas is destroyed");
+    assert!(thread::spawn(move || state.is_closing()).join().unwrap(), "The render thread should see the closing flag");
+}
+
+#[test]
+fn test_canvas_refresh_rate() {
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, CanvasT{size: (50, 40), ..canvas()});
+    ui.commit().expect("Commit was not successful");
+
+    let canvas = ui.get::<Canvas<u64>>(&1001).expect("Failed to get canvas");
+
+    // The rate falls back to the compositor rate or to 60 Hz, so it is never 0
+    let rate = canvas.refresh_rate();
+    assert!(rate > 0.0 && rate.is_finite(), "The refresh rate should be a positive number, got {}", rate);
+
+    // The compositor can be disabled (ex: Windows 7 basic theme), but if the timing is read it must be coherent
+    match canvas.composition_timing() {
+        Ok(timing) => {
+            assert!(timing.qpc_frequency > 0, "The performance counter frequency should not be 0");
+            assert!(timing.next_vblank == timing.last_vblank + timing.refresh_period, "The next vblank should be one period after the last one");
+        },
+        Err(Error::System(_)) => {},
+        Err(e) => panic!("Unexpected composition timing error: {:?}", e)
+    }
+}
+
+#[test]
+#[cfg(feature = "raw-window-handle")]
+fn test_canvas_raw_window_handle() {
+    extern crate raw_window_handle;
+    use raw_window_handle::{HasRawWindowHandle, HasRawDisplayHandle, RawWindowHandle, RawDisplayHandle};
+    use nwg::custom::AnyHandle;
+
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, CanvasT{size: (50, 40), ..canvas()});
+    ui.commit().expect("Commit was not successful");
+
+    let handle = match ui.handle_of(&1001) { Ok(AnyHandle::HWND(h)) => h, _ => panic!("Bad handle") };
+    let canvas = ui.get::<Canvas<u64>>(&1001).expect("Failed to get canvas");
+
+    match canvas.raw_window_handle() {
+        RawWindowHandle::Win32(h) => {
+            assert!(h.hwnd == handle as *mut _, "The raw handle should be the canvas window");
+            assert!(!h.hinstance.is_null(), "The raw handle should have the module instance");
+        },
+        _ => panic!("The raw handle of a canvas should be a Win32 handle")
+    }
+
+    match canvas.raw_display_handle() {
+        RawDisplayHandle::Windows(_) => {},
+        _ => panic!("The display handle of a canvas should be a Windows handle")
+    }
+}
+
+#[test]
+fn test_subclass_guard() {
+    use nwg::custom::{AnyHandle, SubclassGuard};
+    use winapi::{HWND, UINT, WPARAM, LPARAM, LRESULT, UINT_PTR, DWORD_PTR, WM_APP};
+
+    extern "system" { fn DefSubclassProc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT; }
+
+    const GET_DATA: UINT = WM_APP + 10;
+
+    // Answer `GET_DATA` with the reference data of the subclass
+    unsafe extern "system" fn proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, id: UINT_PTR, data: DWORD_PTR) -> LRESULT {
+        if msg == GET_DATA { data as LRESULT } else { DefSubclassProc(hwnd, msg, w, l) }
+    }
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    let handle = match ui.handle_of(&1000) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let get_data = || unsafe{ user32::SendMessageW(handle, GET_DATA, 0, 0) };
+
+    {
+        let guard = SubclassGuard::install(handle, 42, Some(proc), 10).expect("Failed to install the subclass");
+        assert!(guard.handle() == handle && guard.id() == 42);
+        assert!(get_data() == 10, "The subclass should receive the messages");
+
+        guard.set_data(20);
+        assert!(get_data() == 20, "The reference data should be updated");
+    }
+
+    assert!(get_data() == 0, "The subclass should be removed when the guard is dropped");
+
+    match SubclassGuard::install(handle, 42, None, 0) { Err(Error::UserError(_)) => {}, _ => panic!("A subclass procedure should be required") }
+    assert!(SubclassGuard::install(::std::ptr::null_mut(), 42, Some(proc), 0).is_err(), "A subclass should require a valid window");
+}
+
+#[test]
+fn test_sysclass_background() {
+    use nwg::custom::{AnyHandle, SysclassParams, build_sysclass, NO_BACKGROUND_BRUSH};
+    use user32::{GetClassInfoExW, GetClassLongPtrW, DefWindowProcW};
+    use kernel32::GetModuleHandleW;
+    use winapi::{WNDCLASSEXW, HBRUSH, COLOR_WINDOW, UINT};
+    use std::{mem, ptr};
+    const GCLP_HBRBACKGROUND: i32 = -10;
+
+    let class_background = |name: &str| -> HBRUSH {
+        let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+        unsafe {
+            let mut class: WNDCLASSEXW = mem::zeroed();
+            class.cbSize = mem::size_of::<WNDCLASSEXW>() as UINT;
+            assert!(GetClassInfoExW(GetModuleHandleW(ptr::null()), name.as_ptr(), &mut class) != 0, "The class is not registered");
+            class.hbrBackground
+        }
+    };
+
+    let params = SysclassParams{ class_name: "NWG_TEST_NO_BACKGROUND", sysproc: Some(DefWindowProcW), background: Some(NO_BACKGROUND_BRUSH), style: None };
+    unsafe{ build_sysclass(params) }.expect("Failed to register the class");
+    assert!(class_background("NWG_TEST_NO_BACKGROUND").is_null(), "The class should not have a background brush");
+
+    // The parameters are only used the first time the class is registered
+    let params = SysclassParams{ class_name: "NWG_TEST_NO_BACKGROUND", sysproc: Some(DefWindowProcW), background: None, style: None };
+    unsafe{ build_sysclass(params) }.expect("Registering a class twice should not fail");
+    assert!(class_background("NWG_TEST_NO_BACKGROUND").is_null(), "The class background should not change");
+
+    let params = SysclassParams{ class_name: "NWG_TEST_DEFAULT_BACKGROUND", sysproc: Some(DefWindowProcW), background: None, style: None };
+    unsafe{ build_sysclass(params) }.expect("Failed to register the class");
+    assert!(class_background("NWG_TEST_DEFAULT_BACKGROUND") as usize == COLOR_WINDOW as usize, "The class should have the default window color");
+
+    // The canvas class never erases its background
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, CanvasT{size: (50, 40), ..canvas()});
+    ui.commit().expect("Commit was not successful");
+
+    let handle = match ui.handle_of(&1001) { Ok(AnyHandle::HWND(h)) => h, _ => panic!("Bad handle") };
+    assert!(unsafe{ GetClassLongPtrW(handle, GCLP_HBRBACKGROUND) } == 0, "The canvas class should not have a background brush");
+}
+
+#[test]
+fn test_canvas_erase_background() {
+    use nwg::custom::AnyHandle;
+    use std::cell::Cell;
+    use winapi::{WM_ERASEBKGND, WPARAM};
+
+    thread_local!(static ERASE_CALLS: Cell<u32> = Cell::new(0));
+    fn skip_erase() -> bool { ERASE_CALLS.with(|c| c.set(c.get()+1)); false }
+    fn keep_erase() -> bool { ERASE_CALLS.with(|c| c.set(c.get()+1)); true }
+    fn calls() -> u32 { ERASE_CALLS.with(|c| c.get()) }
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, CanvasT{size: (20, 20), ..canvas()});
+    ui.commit().expect("Commit was not successful");
+
+    let handle = match ui.handle_of(&1001) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let canvas = ui.get::<Canvas<u64>>(&1001).expect("Failed to get canvas");
+
+    unsafe {
+        let hdc = user32::GetDC(handle);
+        let erase = || user32::SendMessageW(handle, WM_ERASEBKGND, hdc as WPARAM, 0);
+
+        // The canvas class has no background brush, so the system does not erase anything
+        assert!(erase() == 0, "The background should not be erased without a brush");
+
+        // A handler returning false marks the background as erased
+        canvas.on_erase_background(Some(skip_erase));
+        assert!(erase() == 1, "The handler should skip the erase");
+        assert!(calls() == 1, "The handler should be called");
+
+        // A handler returning true lets the system erase the background
+        canvas.on_erase_background(Some(keep_erase));
+        assert!(erase() == 0, "The system should erase the background");
+        assert!(calls() == 2, "The handler should be called");
+
+        canvas.on_erase_background(None);
+        erase();
+        assert!(calls() == 2, "The handler should be removed");
+
+        user32::ReleaseDC(handle, hdc);
+    }
+}
+
+#[test]
+fn test_ui_bind_typed() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let ui = setup_ui();
+    let btn_t = ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, btn_t);
+
+    ui.bind_typed(&1001, &5000, Event::Click, |_, sender: Sender<Button>, _, _| {
+        let btn = sender.control().expect("Sender should be a button");
+        btn.set_text("Clicked");
+    });
+
+    // The sender does not have the expected type, so its handle is passed
+    let handle_received = Rc::new(Cell::new(false));
+    let received = handle_received.clone();
+    ui.bind_typed(&1001, &5001, Event::Click, move |_, sender: Sender<CheckBox>, _, _| {
+        if let Sender::Handle(_) = sender { received.set(true); }
+    });
+
+    ui.trigger(&1001, Event::Click, EventArgs::None);
+    ui.commit().expect("Commit was not successful");
+
+    assert!(&ui.get::<Button>(&1001).unwrap().get_text() == "Clicked", "Typed callback was not called");
+    assert!(handle_received.get(), "Untyped sender should be passed as a handle");
+}
+
+#[test]
+fn test_ui_unbind() {
+    let ui = setup_ui();
+    
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1002, MenuItemT{text: "", parent: 1000, disabled: false});
+    ui.pack_value(&1001, 5u32);
+
+    ui.bind(&1000, &5000, Event::Destroyed, |_, _, _, _|{});
+    ui.commit().expect("Commit was not successful");
+
+    ui.unbind(&1000, &5000, Event::Destroyed);
+    ui.commit().expect("Commit was not successful");
+
+    // Should be able to rebind destroyed callbacks
+    ui.bind(&1000, &5000, Event::Destroyed, |_, _, _, _|{});
+    ui.commit().expect("Commit was not successful");
+
+    // Cannot unbind events to user values
+    ui.unbind(&1001, &5000, Event::Destroyed);
+    let r = ui.commit();
+    assert!(r.is_err() && r.err().unwrap() == Error::ControlRequired, "Commit was successful");
+
+    // Key not in Ui
+    ui.unbind(&1005, &5000, Event::Destroyed);
+    let r = ui.commit();
+    assert!(r.is_err() && r.err().unwrap() == Error::KeyNotFound, "Commit was successful");
+
+    // Event not supported
+    ui.unbind(&1002, &5000, Event::MouseUp);
+    let r = ui.commit();
+    assert!(r.is_err() && r.err().unwrap() == Error::EventNotSupported(Event::MouseUp), "Commit was successful");
+
+    // Callback do not exists
+    ui.unbind(&1000, &5001, Event::Destroyed);
+    let r = ui.commit();
+    assert!(r.is_err() && r.err().unwrap() == Error::KeyNotFound, "Commit was successful");
+
+    ui.bind(&1000, &5001, Event::Destroyed, |ui, id, _, _|{
+        // When event callbacks are being dipatched, it is impossible to unbind a callbacks on the same control with the same event
+        ui.unbind(&1000, &5000, Event::Destroyed);
+        let r = ui.commit();
+        assert!(r.is_err() && r.err().unwrap() == Error::ControlInUse, "Commit was successful");
+    });
+    ui.commit().expect("Commit was not successful");
+
+    { ui.get::<Window>(&1000).unwrap().close(); }
+    dispatch_events();
+}
+
+
+#[test]
+fn test_ui_deferred_unpack() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, ButtonT{text: "Close", position: (0, 0), size: (50, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None});
+    ui.commit().expect("Commit was not successful");
+
+    // (commit result, parent still packed) seen by the callback
+    let seen: Rc<RefCell<Option<(bool, bool)>>> = Rc::new(RefCell::new(None));
+    let seen_cb = seen.clone();
+
+    // A button that removes its parent window in its own callback
+    ui.bind(&1001, &5000, Event::Click, move |ui, _, _, _| {
+        ui.unpack(&1000);
+        let r = ui.commit();
+        *seen_cb.borrow_mut() = Some((r.is_ok(), ui.has_id(&1000)));
+    });
+    ui.commit().expect("Commit was not successful");
+
+    ui.trigger(&1001, Event::Click, EventArgs::None);
+    ui.commit().expect("Commit was not successful");
+
+    assert!(*seen.borrow() == Some((true, true)), "The removal should succeed and be delayed while the callback is running");
+    assert!(ui.has_id(&1000) && ui.has_id(&1001), "The removal should wait for the event loop");
+
+    // Dispatch the queued messages until the window is removed
+    assert!(pump_until(|| !ui.has_id(&1000), Duration::from_secs(5)), "The window should be removed by the event loop");
+    assert!(!ui.has_id(&1001), "The children should be removed with the window");
+}
+
+#[test]
+fn test_user_trigger() {
+    let ui = setup_ui();
+    let mut flag_set: bool = false;
+    let x = &mut flag_set as *mut bool;
+    
+    ui.pack_value(&1001, 5u32);
+    ui.pack_control(&1000, window());
+    ui.bind(&1000, &5000, Event::MouseDown, move |_, _, _, _|{ unsafe{ *(&mut *x) = true; } });
+    ui.bind(&1000, &5000, Event::MouseUp, move |ui, _, _, _|{ 
+        ui.trigger(&1000, Event::MouseDown, EventArgs::None);
+        let r = ui.commit().expect("Commit was not successful");;
+    });
+   
+    ui.trigger(&1000, Event::MouseDown, EventArgs::None);
+    ui.commit().expect("Commit was not successful");
+
+    ui.trigger(&1030, Event::MouseDown, EventArgs::None);
+    let r = ui.commit();
+    assert!(r.is_err() && r.err().unwrap() == Error::KeyNotFound, "Commit was successful");
+
+    ui.trigger(&1001, Event::MouseDown, EventArgs::None);
+    let r = ui.commit();
+    assert!(r.is_err() && r.err().unwrap() == Error::ControlRequired, "Commit was successful");
+
+    ui.trigger(&1000, Event::SelectionChanged, EventArgs::None);
+    let r = ui.commit();
+    assert!(r.is_err() && r.err().unwrap() == Error::EventNotSupported(Event::SelectionChanged), "Commit was successful");
+
+    ui.trigger(&1000, Event::MouseUp, EventArgs::None);
+    let r = ui.commit();
+
+    assert!(flag_set, "Flag was not set");
+}
+
+
+#[test]
+fn test_window_control_user_close() {
+    let ui = setup_ui();
+    let mut callback_executed: bool = false;
+    let x = &mut callback_executed as *mut bool;
+
+    ui.pack_control(&1000, window());
+    ui.bind(&1000, &5000, Event::Destroyed, move |_, _, _, _|{ unsafe{ *(&mut *x) = true; } });
+    ui.commit().expect("Commit was not successful");
+
+    assert!(ui.has_id(&1000), "ID 1000 was not found in id after commit");
+    
+    // Try to close the window
+    { ui.get::<Window>(&1000).unwrap().close(); }
+
+    // Dispatch the waiting close event
+    dispatch_events();
+}
+
+#[test]
+fn test_drop_callback() {
+    let mut callback_executed: bool = false;
+    
+    {
+        let x = &mut callback_executed as *mut bool;
+        let ui = setup_ui();
+        ui.pack_control(&1000, window());
+        ui.bind(&1000, &5000, Event::Destroyed, move |_, _, _, _|{ unsafe{ *(&mut *x) = true; } });
+        ui.commit().expect("Commit was not successful");
+    }
+
+    assert!(callback_executed, "Destroy callback was not executed.")
+}
+
+#[test]
+fn test_menus() {
+    let ui = setup_ui();
+    
+    let mut free_count: u8 = 0;
+    let x = &mut free_count as *mut u8;
+
+    ui.pack_control(&1000, window());
+    ui.bind(&1000, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    
+    ui.pack_control(&1001, MenuT{ text: "Test1", parent: 1000, disabled: false  });
+    ui.bind(&1001, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+
+    ui.pack_control(&2003, MenuItemT{ text: "TestItem4", parent: 1000, disabled: false  });
+    ui.bind(&2003, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    
+    ui.pack_control(&1002, MenuT{ text: "Test2", parent: 1000, disabled: false  });
+    ui.pack_control(&1003, MenuT{ text: "Test3", parent: 1002, disabled: false  });
+    ui.pack_control(&1004, MenuT{ text: "Test4", parent: 1002, disabled: false  });
+    ui.pack_control(&2000, MenuItemT{ text: "TestItem1", parent: 1002, disabled: false  });
+    ui.bind(&1002, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&1003, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&1004, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&2000, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+
+    ui.pack_control(&1005, MenuT{ text: "Test5", parent: 1000, disabled: false });
+    ui.pack_control(&1006, MenuT{ text: "Test6", parent: 1005, disabled: false });
+    ui.pack_control(&1007, MenuT{ text: "Test7", parent: 1006, disabled: false });
+    ui.pack_control(&2001, MenuItemT{ text: "TestItem2", parent: 1007, disabled: false  });
+    ui.bind(&1005, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&1006, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&1007, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&2001, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+
+    ui.pack_control(&1008, MenuT{ text: "Test8", parent: 1000, disabled: false });
+    ui.pack_control(&1009, MenuT{ text: "Test9", parent: 1008, disabled: false });
+    ui.pack_control(&2002, MenuItemT{ text: "TestItem3", parent: 1000, disabled: false  });
+    ui.bind(&1008, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&1009, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+    ui.bind(&2002, &10_000, Event::Destroyed, move |_,_,_,_|{ unsafe{  *(&mut *x) += 1 } });
+
+    ui.commit().expect("Commit was not successful");
+
+    {
+        // Menu should be enabled
+        let (menu, menuitem) = (ui.get::<Menu>(&1001).unwrap(), ui.get::<MenuItem>(&2002).unwrap());
+        assert!(menu.get_enabled() == true, "Menu is not enabled");
+        assert!(menuitem.get_enabled() == true, "Menu is not enabled");
+
+        menu.set_enabled(false);
+        menuitem.set_enabled(false);
+
+        assert!(menu.get_enabled() == false, "Menu is enabled");
+        assert!(menuitem.get_enabled() == false, "Menu is enabled");
+    }
+
+    // Removing a menu without items
+    ui.unpack(&1001);
+    ui.commit().expect("Commit was not successful");
+    assert!(ui.has_id(&1001) == false, "Destroyed menu key '1001' was found in ui");
+    assert!(free_count == 1, "Freecount was not increased!");
+
+    // Removing a menu item
+    ui.unpack(&2003);
+    ui.commit().expect("Commit was not successful");
+    assert!(ui.has_id(&2003) == false, "Destroyed menu key '1001' was found in ui");
+    assert!(free_count == 2, "Freecount was not increased!");
+
+    // Removing a menu with subitems
+    ui.unpack(&1002);
+    ui.commit().expect("Commit was not successful");
+    assert!(ui.has_id(&1002) == false, "Destroyed menu key '1002' was found in ui");
+    assert!(ui.has_id(&1003) == false, "Destroyed menu key '1003' was found in ui");
+    assert!(ui.has_id(&1004) == false, "Destroyed menu key '1004' was found in ui");
+    assert!(ui.has_id(&2000) == false, "Destroyed menu item key '2000' was found in ui");
+    assert!(free_count == 6, "Freecount was not increased by 4!");
+
+    // Removing a menu with subitems that have subitems
+    ui.unpack(&1005);
+    ui.commit().expect("Commit was not successful");
+    assert!(ui.has_id(&1005) == false, "Destroyed menu key '1005' was found in ui");
+    assert!(ui.has_id(&1006) == false, "Destroyed menu key '1006' was found in ui");
+    assert!(ui.has_id(&1007) == false, "Destroyed menu key '1007' was found in ui");
+    assert!(ui.has_id(&2001) == false, "Destroyed menu item key '2001' was found in ui");
+    assert!(free_count == 10, "Freecount was not increased by 4!");
+
+    // Removing a window should also free its menus
+    ui.unpack(&1000);
+    ui.commit().expect("Commit was not successful");
+    assert!(ui.has_id(&1000) == false, "Destroyed menu key '1000' was found in ui");
+    assert!(ui.has_id(&1008) == false, "Destroyed menu key '1008' was found in ui");
+    assert!(ui.has_id(&1009) == false, "Destroyed menu key '1009' was found in ui");
+    assert!(ui.has_id(&2002) == false, "Destroyed menu item key '2002' was found in ui");
+    assert!(free_count == 14, "Freecount was not increased by 3!");
+}
+
+#[test]
+fn test_window() {
+    let ui = setup_ui();
+
+    // pack test
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1000, Window);
+    test_position!(ui, &1000, Window);
+    test_size!(ui, &1000, Window);
+    test_enabled!(ui, &1000, Window);
+
+    // activation events
+    ui.bind(&1000, &5000, Event::Activated, |_, _, _, _|{});
+    ui.bind(&1000, &5001, Event::Deactivated, |_, _, _, _|{});
+    ui.bind(&1000, &5002, Event::AppActivation, |_, _, _, _|{});
+    ui.commit().expect("Activation events should be supported by windows");
+
+    {
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        assert!(window.set_session_notifications(true), "Session notifications registration failed");
+        assert!(window.set_session_notifications(true), "Registering twice should be a no-op");
+        assert!(window.set_session_notifications(false), "Session notifications unregistration failed");
+    }
+
+    {
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        assert!(window.register_device_notifications(None), "Device notifications registration failed");
+        assert!(window.register_device_notifications(None), "Registering twice should replace the registration");
+        window.unregister_device_notifications();
+    }
+
+    {
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        let placement = WindowPlacement{state: PlacementState::Normal, position: (10, 10), size: (200, 150), restore_maximized: false};
+
+        window.set_placement(&placement);
+        assert!(window.get_placement() == placement, "Window placement do not match");
+        assert!(window.show_state() == PlacementState::Normal, "Show state do not match");
+
+        window.set_show_state(PlacementState::Maximized);
+        assert!(window.show_state() == PlacementState::Maximized, "Show state do not match");
+        assert!(window.normal_rect() == ((10, 10), (200, 150)), "The restored rect should be kept when the window is maximized");
+
+        window.set_show_state(PlacementState::Minimized);
+        assert!(window.show_state() == PlacementState::Minimized, "Show state do not match");
+
+        window.set_show_state(PlacementState::Normal);
+        assert!(window.show_state() == PlacementState::Normal, "Show state do not match");
+        assert!(window.normal_rect() == ((10, 10), (200, 150)), "Restored rect do not match");
+
+        let cursor = Cursor::system(SystemCursor::Hand);
+        assert!(!cursor.owned(), "System cursors must be shared");
+        window.set_cursor(Some(&cursor));
+        window.set_cursor(None);
+
+        window.set_hover_time(Some(250));
+        assert!(window.get_hover_time() == 250, "Hover time do not match");
+        window.set_hover_time(None);
+        assert!(window.get_hover_time() == 0xFFFFFFFF, "Hover time was not reset");
+        window.set_hover_time(Some(0));
+        assert!(window.get_hover_time() == 0, "Hover time do not match");
+        window.set_hover_time(Some(0xFFFFFFFE));
+        assert!(window.get_hover_time() == 0xFFFFFFFE, "Hover time do not match");
+        window.set_hover_time(Some(0xFFFFFFFF));
+        assert!(window.get_hover_time() == 0xFFFFFFFF, "HOVER_DEFAULT should restore the system default");
+
+        let bad_icon = Icon::from_hicon(unsafe{ ::std::mem::transmute(0xDEADusize) }, false);
+        assert!(window.set_icon_big(Some(&bad_icon)).is_err(), "Setting an invalid icon should fail");
+        assert!(window.set_icon_small(Some(&bad_icon)).is_err(), "Setting an invalid icon should fail");
+        assert!(window.set_icon_big(None).is_ok(), "Removing the icon should succeed");
+        assert!(window.set_icon_small(None).is_ok(), "Removing the icon should succeed");
+        assert!(window.get_icon_big().expect("Failed to read the icon").is_none(), "Window should not have a big icon");
+        assert!(window.get_icon_small().expect("Failed to read the icon").is_none(), "Window should not have a small icon");
+    }
+
+    match ui.handle_of(&1000) {
+        Ok(nwg::custom::AnyHandle::HWND(h)) => unsafe {
+            nwg::custom::set_window_user_data(h, 42u32);
+            match nwg::custom::window_user_data::<u32>(h) {
+                Some(value) => { assert!(*value == 42, "User data do not match"); *value = 43; },
+                None => panic!("User data not found")
+            }
+            assert!(nwg::custom::window_user_data::<u32>(h).map(|v| *v) == Some(43), "User data was not modified");
+            assert!(nwg::custom::window_user_data::<String>(h).is_none(), "User data type must be checked");
+
+            nwg::custom::clear_window_user_data(h);
+            assert!(nwg::custom::window_user_data::<u32>(h).is_none(), "User data was not cleared");
+        },
+        _ => panic!("Window handle not found")
+    }
+
+    {
+        let window = ui.handle_of(&1000).expect("Control not found");
+        let mut snapshot = Bitmap::capture(&window).expect("Window capture failed");
+        assert!(snapshot.owned(), "Captured bitmaps must be owned");
+
+        // The window capture is a 32 bits bitmap on a true color screen, so it does not need a mask
+        let mut icon = Icon::from_bitmap(&snapshot, None).expect("Failed to create an icon from a bitmap");
+        assert!(icon.owned(), "Icons created from a bitmap must be owned");
+        icon.destroy();
+
+        let mut cursor = Cursor::from_bitmap(&snapshot, None, (5, 5)).expect("Failed to create a cursor from a bitmap");
+        assert!(cursor.owned(), "Cursors created from a bitmap must be owned");
+        cursor.destroy();
+
+        match Cursor::from_bitmap(&snapshot, None, (10_000, 10_000)) { Err(Error::BadResource(_)) => {}, _ => panic!("Should have returned Error::BadResource") }
+
+        nwg::custom::Resource::free(&mut snapshot);
+
+        let font = nwg::custom::AnyHandle::HFONT(::std::ptr::null_mut());
+        match Bitmap::capture(&font) { Err(Error::ControlRequired) => {}, _ => panic!("Should have returned Error::ControlRequired") }
+    }
+}
+
+/// Write a 16x16 32 bits icon file filled with `color` (BGRA) in the temp directory and return its path
+fn write_icon_file(name: &str, color: [u8; 4]) -> String {
+    use std::io::Write;
+
+    let mut data: Vec<u8> = Vec::new();
+    let u16le = |v: u16| vec![v as u8, (v >> 8) as u8];
+    let u32le = |v: u32| vec![v as u8, (v >> 8) as u8, (v >> 16) as u8, (v >> 24) as u8];
+
+    // Icon directory with one 16x16 image. The image is a BITMAPINFOHEADER, the pixels and a 1 bit mask.
+    let image_size = 40 + 16 * 16 * 4 + 16 * 4;
+    data.extend(u16le(0)); data.extend(u16le(1)); data.extend(u16le(1));
+    data.extend(&[16, 16, 0, 0]); data.extend(u16le(1)); data.extend(u16le(32)); data.extend(u32le(image_size)); data.extend(u32le(22));
+
+    // The height of the header covers the pixels and the mask
+    data.extend(u32le(40)); data.extend(u32le(16)); data.extend(u32le(32)); data.extend(u16le(1)); data.extend(u16le(32));
+    data.extend(u32le(0)); data.extend(u32le(0)); data.extend(u32le(0)); data.extend(u32le(0)); data.extend(u32le(0)); data.extend(u32le(0));
+    for _ in 0..(16 * 16) { data.extend(&color); }
+    data.extend(vec![0u8; 16 * 4]);
+
+    let path = ::std::env::temp_dir().join(name);
+    let mut file = ::std::fs::File::create(&path).expect("Failed to create the icon file");
+    file.write_all(&data).expect("Failed to write the icon file");
+
+    path.to_string_lossy().into_owned()
+}
+
+/// Write a `size` x `size` 24 bits bitmap file filled with `color` (BGR) in the temp directory and return its path
+fn write_bitmap_file(name: &str, size: u32, color: [u8; 3]) -> String {
+    use std::io::Write;
+
+    let mut data: Vec<u8> = Vec::new();
+    let u16le = |v: u16| vec![v as u8, (v >> 8) as u8];
+    let u32le = |v: u32| vec![v as u8, (v >> 8) as u8, (v >> 16) as u8, (v >> 24) as u8];
+
+    // The rows of the bitmap are aligned on 4 bytes
+    let row_size = (size * 3 + 3) & !3;
+    let pixels_size = row_size * size;
+
+    data.extend(&[b'B', b'M']); data.extend(u32le(54 + pixels_size)); data.extend(u32le(0)); data.extend(u32le(54));
+    data.extend(u32le(40)); data.extend(u32le(size)); data.extend(u32le(size)); data.extend(u16le(1)); data.extend(u16le(24));
+    data.extend(u32le(0)); data.extend(u32le(pixels_size)); data.extend(u32le(0)); data.extend(u32le(0)); data.extend(u32le(0)); data.extend(u32le(0));
+    for _ in 0..size {
+        for _ in 0..size { data.extend(&color); }
+        data.extend(vec![0u8; (row_size - size * 3) as usize]);
+    }
+
+    let path = ::std::env::temp_dir().join(name);
+    let mut file = ::std::fs::File::create(&path).expect("Failed to create the bitmap file");
+    file.write_all(&data).expect("Failed to write the bitmap file");
+
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn test_overlay_icon() {
+    use nwg::custom::AnyHandle;
+
+    // The overlay data is saved in a window property
+    fn has_overlay(handle: winapi::HWND) -> bool {
+        let name: Vec<u16> = "NWG_TASKBAR_OVERLAY".encode_utf16().chain(Some(0)).collect();
+        unsafe{ !user32::GetPropW(handle, name.as_ptr()).is_null() }
+    }
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    // A missing icon file fails the resource creation
+    ui.pack_resource(&1001, IconT{source: "does_not_exist.ico", size: (0, 0)});
+    assert!(ui.commit().is_err(), "The icon should not be created from a missing file");
+    assert!(!ui.has_id(&1001), "The icon should not be packed");
+
+    ui.pack_resource(&1002, IconT{source: write_icon_file("nwg_test_overlay.ico", [0, 0, 255, 255]), size: (0, 0)});
+    ui.commit().expect("Failed to load the icon file");
+
+    let handle = match ui.handle_of(&1000) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let window = ui.get::<Window>(&1000).expect("Control not found");
+
+    // The taskbar may not be available on the test machine, but the overlay is saved in any case
+    {
+        let icon = ui.get::<Icon>(&1002).expect("Icon not found");
+        window.set_overlay_icon(Some(&**icon), "Status");
+    }
+    assert!(has_overlay(handle), "The overlay should be saved on the window");
+
+    // The window keeps a copy of the icon, so the overlay can be reapplied after the icon is freed
+    ui.unpack(&1002);
+    ui.commit().expect("Commit was not successful");
+    unsafe{ user32::SendMessageW(handle, user32::RegisterWindowMessageW("TaskbarCreated\0".encode_utf16().collect::<Vec<u16>>().as_ptr()), 0, 0); }
+    assert!(has_overlay(handle), "The overlay should be kept when the taskbar is recreated");
+
+    window.set_overlay_icon(None, "");
+    assert!(!has_overlay(handle), "The overlay should be removed");
+}
+
+#[test]
+fn test_icon_handles() {
+    use nwg::custom::{Resource, AnyHandle};
+    use winapi::{HICON, IDI_APPLICATION};
+
+    fn icon_handle(icon: &Icon) -> HICON {
+        match icon.handle() { AnyHandle::HICON(h) => h, _ => panic!("Bad handle") }
+    }
+
+    // A shared system icon must not be owned
+    let system = unsafe{ user32::LoadIconW(::std::ptr::null_mut(), IDI_APPLICATION) };
+    assert!(!system.is_null(), "Failed to load the system icon");
+
+    let mut shared = Icon::from_hicon(system, false);
+    assert!(!shared.owned() && icon_handle(&shared) == system);
+    shared.destroy();
+    assert!(icon_handle(&shared).is_null(), "A destroyed icon should hold a null handle");
+    assert!(Icon::clone_hicon(system).is_ok(), "A borrowed icon should not be destroyed");
+
+    // A copy is owned and independent from the original icon
+    let mut copy = Icon::clone_hicon(system).expect("Failed to copy the icon");
+    let copy_handle = icon_handle(&copy);
+    assert!(copy.owned() && copy_handle != system, "A copied icon should be owned");
+
+    copy.free();
+    assert!(!copy.owned() && icon_handle(&copy).is_null(), "A freed icon should hold a null handle");
+    assert!(Icon::clone_hicon(copy_handle).is_err(), "An owned icon should be destroyed when freed");
+    assert!(Icon::clone_hicon(system).is_ok(), "The original icon should not be destroyed with its copy");
+
+    assert!(Icon::clone_hicon(::std::ptr::null_mut()).is_err(), "A null icon cannot be copied");
+}
+
+#[test]
+fn test_layered_window() {
+    use nwg::custom::{AnyHandle, update_layered_window};
+    use gdi32::{CreateDIBSection, DeleteObject};
+    use user32::{SendMessageW, GetWindowRect};
+    use winapi::{BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DWORD, RECT, LPARAM, HBITMAP, WM_NCHITTEST, HTTRANSPARENT, c_void};
+    use std::{mem, ptr};
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    let handle = match ui.handle_of(&1000) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let window = ui.get::<Window>(&1000).expect("Control not found");
+
+    // A 20x10 bitmap. If `transparent` is true, the left half is transparent. The right half is opaque.
+    let make_bitmap = |transparent: bool| -> HBITMAP { unsafe {
+        let mut header: BITMAPINFO = mem::zeroed();
+        header.bmiHeader = BITMAPINFOHEADER {
+            biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD, biWidth: 20, biHeight: -10, biPlanes: 1, biBitCount: 32,
+            biCompression: BI_RGB, biSizeImage: 0, biXPelsPerMeter: 0, biYPelsPerMeter: 0, biClrUsed: 0, biClrImportant: 0
+        };
+
+        let mut bits: *mut c_void = ptr::null_mut();
+        let handle = CreateDIBSection(ptr::null_mut(), &header, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+        assert!(!handle.is_null(), "Failed to create the bitmap");
+
+        let pixels = ::std::slice::from_raw_parts_mut(bits as *mut u8, 20 * 10 * 4);
+        for (i, px) in pixels.chunks_mut(4).enumerate() {
+            let a = if transparent && i % 20 < 10 { 0 } else { 255 };
+            px[0] = a; px[1] = a; px[2] = a; px[3] = a;
+        }
+
+        handle
+    }};
+
+    let window_rect = || -> RECT { unsafe {
+        let mut rect: RECT = mem::zeroed();
+        GetWindowRect(handle, &mut rect);
+        rect
+    }};
+
+    let hit_test = |x: i32, y: i32| -> bool {
+        let rect = window_rect();
+        let (x, y) = ((rect.left + x) as u16 as u32, (rect.top + y) as u16 as u32);
+        unsafe{ SendMessageW(handle, WM_NCHITTEST, 0, ((y << 16) | x) as LPARAM) == HTTRANSPARENT as isize }
+    };
+
+    let (bitmap, opaque) = (make_bitmap(true), make_bitmap(false));
+
+    unsafe {
+        assert!(window.hit_test_threshold().is_none(), "The hit test should be disabled by default");
+        update_layered_window(handle, bitmap, 255).expect("Failed to update the layered window");
+        let rect = window_rect();
+        assert!((rect.right - rect.left, rect.bottom - rect.top) == (20, 10), "The window should be resized to the size of the bitmap");
+        assert!(!hit_test(2, 5), "The hit test should be disabled");
+
+        window.set_hit_test_threshold(Some(128));
+        assert!(window.hit_test_threshold() == Some(128), "Threshold do not match");
+        assert!(hit_test(2, 5), "Transparent pixels should let the clicks through");
+        assert!(!hit_test(15, 5), "Opaque pixels should receive the clicks");
+
+        // The alpha buffer follows the last update
+        update_layered_window(handle, opaque, 255).expect("Failed to update the layered window");
+        assert!(!hit_test(2, 5), "The hit test should use the last bitmap");
+
+        update_layered_window(handle, bitmap, 255).expect("Failed to update the layered window");
+        window.set_hit_test_threshold(None);
+        assert!(!hit_test(2, 5), "The hit test should be disabled");
+
+        match update_layered_window(handle, ptr::null_mut(), 255) { Err(Error::BadResource(_)) => {}, _ => panic!("Should have returned Error::BadResource") }
+
+        DeleteObject(bitmap as _);
+        DeleteObject(opaque as _);
+    }
+}
+
+#[test]
+fn test_window_background_image() {
+    use nwg::custom::{AnyHandle, get_window_long};
+    use gdi32::{CreateDIBSection, CreateCompatibleDC, SelectObject, DeleteDC, DeleteObject};
+    use winapi::{BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DWORD, BOOL, GWL_STYLE, WS_CLIPCHILDREN, WM_ERASEBKGND, WPARAM, c_void};
+    use std::{mem, ptr, slice};
+
+    extern "system" { fn GdiFlush() -> BOOL; }
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.pack_resource(&1001, BitmapT{source: write_bitmap_file("nwg_test_background.bmp", 4, [0, 0, 255]), size: (0, 0)});
+    ui.commit().expect("Commit was not successful");
+
+    let handle = match ui.handle_of(&1000) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let window = ui.get::<Window>(&1000).expect("Control not found");
+
+    unsafe {
+        // The background is erased in a 20x20 32 bits surface
+        let mut header: BITMAPINFO = mem::zeroed();
+        header.bmiHeader = BITMAPINFOHEADER {
+            biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD, biWidth: 20, biHeight: -20, biPlanes: 1, biBitCount: 32,
+            biCompression: BI_RGB, biSizeImage: 0, biXPelsPerMeter: 0, biYPelsPerMeter: 0, biClrUsed: 0, biClrImportant: 0
+        };
+
+        let mut bits: *mut c_void = ptr::null_mut();
+        let surface = CreateDIBSection(ptr::null_mut(), &header, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+        assert!(!surface.is_null(), "Failed to create the bitmap");
+        let pixels = slice::from_raw_parts_mut(bits as *mut u8, 20 * 20 * 4);
+
+        let hdc = CreateCompatibleDC(ptr::null_mut());
+        let old = SelectObject(hdc, surface as _);
+
+        let erase = |pixels: &mut [u8]| {
+            for b in pixels.iter_mut() { *b = 0; }
+            user32::SendMessageW(handle, WM_ERASEBKGND, hdc as WPARAM, 0);
+            GdiFlush();
+        };
+
+        // A 4x4 red image is tiled over the whole surface
+        {
+            let image = ui.get::<Bitmap>(&1001).expect("Bitmap not found");
+            window.set_background_image(Some(&**image), BackgroundMode::Tile);
+        }
+        assert!(get_window_long(handle, GWL_STYLE) as u32 & WS_CLIPCHILDREN == WS_CLIPCHILDREN, "The children should be clipped");
+
+        erase(pixels);
+        assert!(pixels.chunks(4).all(|px| px[0..3] == [0, 0, 255]), "The image should be tiled in the background");
+
+        // The window keeps a copy of the image
+        ui.unpack(&1001);
+        ui.commit().expect("Commit was not successful");
+        erase(pixels);
+        assert!(pixels.chunks(4).all(|px| px[0..3] == [0, 0, 255]), "The image should be kept after it is freed");
+
+        // Without image, the class background is used
+        window.set_background_image(None, BackgroundMode::Tile);
+        erase(pixels);
+        assert!(pixels.chunks(4).all(|px| px[0..3] != [0, 0, 255]), "The image should be removed");
+
+        SelectObject(hdc, old);
+        DeleteDC(hdc);
+        DeleteObject(surface as _);
+    }
+}
+
+#[test]
+fn test_buttons() {
+    let ui = setup_ui();
+
+    let mut btn_t = ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
+    let btn_t2 = CheckBoxT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, checkstate: CheckState::Checked, tristate: false, parent: 1000, font: None};
+
+    ui.pack_resource(&10_000, default_font());
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, MenuItemT{text: "", parent: 1000, disabled: false});
+
+    // pack test
+    ui.pack_control(&1002, btn_t.clone());
+    ui.pack_control(&1010, btn_t2.clone());
+
+    btn_t.font = Some(10_000);
+    ui.pack_control(&1003, btn_t.clone() );
+    ui.commit().expect("Commit was not successful");
+
+    btn_t.parent = 9999;
+    ui.pack_control(&1004, btn_t.clone() );
+    match ui.commit() { Err(Error::KeyNotFound) => {}, r => panic!("Should have returned Error::KeyNotFound, got {:?}", r) }
+
+    btn_t.parent = 1001;
+    ui.pack_control(&1004, btn_t.clone() );
+    match ui.commit() { Err(Error::BadParent(_)) => {}, r => panic!("Should have returned Error::BadParent, got {:?}", r) }
+
+    btn_t.parent = 1000;
+    btn_t.font = Some(1000);
+    ui.pack_control(&1004, btn_t.clone() );
+    match ui.commit() { Err(Error::BadResource(_)) => {}, r => panic!("Should have returned Error::BadResource, got {:?}", r) }
+
+    // methods test
+    test_visibility!(ui, &1002, Button);
+    test_position!(ui, &1002, Button);
+    test_size!(ui, &1002, Button);
+    test_enabled!(ui, &1002, Button);
+
+    btn_t.command_link = true;
+    btn_t.note = Some("NOTE");
+    ui.pack_control(&1005, btn_t.clone() );
+    ui.commit().expect("Commit was not successful");
+
+    {
+        let link = ui.get::<Button>(&1005).expect("Control not found");
+        assert!(link.get_command_link());
+        assert!(&link.get_note() == "NOTE");
+        link.set_note("OTHER NOTE");
+        assert!(&link.get_note() == "OTHER NOTE");
+        link.set_shield(true);
+
+        let btn = ui.get::<Button>(&1002).expect("Control not found");
+        assert!(!btn.get_command_link());
+    }
+
+    assert!(ui.with_control(&1005, |link: &Button| link.get_command_link()) == Ok(true), "Command link do not match");
+    match ui.with_control(&9999, |_: &Button| ()) { Err(Error::KeyNotFound) => {}, r => panic!("Should have returned Error::KeyNotFound, got {:?}", r) }
+    match ui.with_control(&1005, |_: &CheckBox| ()) { Err(Error::BadType) => {}, r => panic!("Should have returned Error::BadType, got {:?}", r) }
+
+    {
+        let window = ui.handle_of(&1000).expect("Control not found");
+        let btn = ui.handle_of(&1002).expect("Control not found");
+
+        assert!(btn.is_child_of(&window), "Button should be a child of the window");
+        assert!(!window.is_child_of(&btn), "Window should not be a child of the button");
+        assert!(window.parent().is_none(), "A top level window should not have a parent");
+
+        let (l, t, r, b) = btn.screen_rect().expect("Button should have a screen rect");
+        assert!(r - l == 100 && b - t == 30, "Screen rect do not match the button size");
+        match btn.client_rect() { Some((0, 0, 100, 30)) => {}, r => panic!("Bad client rect {:?}", r) }
+
+        match (btn.parent(), btn.root_window(), window) {
+            (Some(nwg::custom::AnyHandle::HWND(p)), nwg::custom::AnyHandle::HWND(r), nwg::custom::AnyHandle::HWND(w)) => {
+                assert!(p == w, "Parent do not match");
+                assert!(r == w, "Root window do not match");
+            },
+            _ => panic!("Bad handles")
+        }
+    }
+
+    btn_t.tab_stop = false;
+    btn_t.group = true;
+    ui.pack_control(&1006, btn_t.clone() );
+    ui.commit().expect("Commit was not successful");
+
+    {
+        use nwg::custom::{AnyHandle, get_window_long};
+        const WS_TABSTOP: usize = 0x00010000;
+        const WS_GROUP: usize = 0x00020000;
+
+        match (ui.handle_of(&1002).expect("Control not found"), ui.handle_of(&1006).expect("Control not found")) {
+            (AnyHandle::HWND(b1), AnyHandle::HWND(b2)) => {
+                let (s1, s2) = (get_window_long(b1, -16) as usize, get_window_long(b2, -16) as usize);
+                assert!(s1 & WS_TABSTOP == WS_TABSTOP && s1 & WS_GROUP == 0, "Button should be a tab stop");
+                assert!(s2 & WS_TABSTOP == 0 && s2 & WS_GROUP == WS_GROUP, "Button should start a group");
+            },
+            _ => panic!("Bad handles")
+        }
+    }
+
+    {
+        let stretched = ui.handle_of(&1006).expect("Control not found");
+        let right = ui.handle_of(&1003).expect("Control not found");
+        assert!(stretched.anchor().is_none(), "Controls should not be anchored by default");
+
+        assert!(stretched.set_anchor(Some(Anchor{left: true, top: true, right: true, bottom: false})));
+        assert!(right.set_anchor(Some(Anchor{left: false, top: true, right: true, bottom: false})));
+        assert!(right.anchor() == Some(Anchor{left: false, top: true, right: true, bottom: false}));
+
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        let (w, h) = window.get_size();
+        window.set_size(w + 50, h);
+
+        let btn = ui.get::<Button>(&1006).expect("Control not found");
+        assert!(btn.get_size() == (150, 30), "Button should be stretched with the window");
+        assert!(btn.get_position() == (10, 10), "Stretched button should not move");
+
+        let btn = ui.get::<Button>(&1003).expect("Control not found");
+        assert!(btn.get_position() == (60, 10), "Button should keep its distance to the right edge");
+        assert!(btn.get_size() == (100, 30), "Button should not be resized");
+
+        window.set_size(w, h);
+        assert!(stretched.set_anchor(None));
+        assert!(stretched.anchor().is_none());
+    }
+
+    {
+        let checkbox = ui.get::<CheckBox>(&1010).expect("Control not found");
+
+        assert!(checkbox.get_checkstate() == CheckState::Checked);
+        checkbox.set_checkstate(CheckState::Unchecked);
+        assert!(checkbox.get_checkstate() == CheckState::Unchecked);
+
+        assert!(checkbox.get_text().as_str() == "TEST");
+        checkbox.set_text("Waloo");
+        assert!(checkbox.get_text().as_str() == "Waloo");
+    }
+}
+
+#[test]
+fn test_button_mouse_events() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use nwg::custom::AnyHandle;
+    use user32::SendMessageW;
+    use winapi::{WM_MOUSEMOVE, WM_MOUSELEAVE, WM_MOUSEHOVER, LPARAM};
+
+    let ui = setup_ui();
+    let btn_t = ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, btn_t);
+    ui.commit().expect("Commit was not successful");
+
+    // (event, cursor position)
+    let log: Rc<RefCell<Vec<(Event, Option<(i32, i32)>)>>> = Rc::new(RefCell::new(Vec::new()));
+    for &evt in [Event::MouseEnter, Event::MouseLeave, Event::MouseHover].iter() {
+        let log = log.clone();
+        ui.bind(&1001, &5000, evt, move |_, _, evt, args|{
+            let position = match args { &EventArgs::Position(x, y) => Some((x, y)), _ => None };
+            log.borrow_mut().push((*evt, position));
+        });
+    }
+    ui.commit().expect("Mouse events should be supported by the buttons");
+
+    let handle = match ui.handle_of(&1001) { Ok(AnyHandle::HWND(h)) => h, _ => panic!("Bad handle") };
+    let position = ((6 << 16) | 5) as LPARAM;
+    unsafe {
+        SendMessageW(handle, WM_MOUSEMOVE, 0, position);
+        SendMessageW(handle, WM_MOUSEMOVE, 0, position);
+        SendMessageW(handle, WM_MOUSEHOVER, 0, position);
+        SendMessageW(handle, WM_MOUSELEAVE, 0, 0);
+    }
+
+    let log = log.borrow();
+    assert!(log.len() == 3, "Expected 3 events, got {:?}", *log);
+    assert!(log[0].0 == Event::MouseEnter, "The first move should raise MouseEnter");
+    assert!(log[1] == (Event::MouseHover, Some((5, 6))), "MouseHover should receive the cursor position");
+    assert!(log[2].0 == Event::MouseLeave, "Leaving the button should raise MouseLeave");
+}
+
+#[test]
+fn test_listbox() {
+    let ui = setup_ui();
+
+    let col = vec!["Foo", "FooBar", "Excelsior", "wOOsh"];
+    let mut lb_t = ListBoxT {
+        collection: col.clone(),
+        position:(10, 50), size: (100, 90),
+        visible: true, disabled: false, tab_stop: true, group: false, readonly: false, multi_select: false, tab_stops: true,
+        parent: 1000,
+        font: None,
+        auto_tooltip: false
+    };
+
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1002, lb_t.clone());
+    lb_t.multi_select = true;
+    ui.pack_control(&1003, lb_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1002, ListBox<&'static str>);
+    test_position!(ui, &1002, ListBox<&'static str>);
+    test_size!(ui, &1002, ListBox<&'static str>, (200, 192)); // Listbox height is forced to be rounded to match the items height.
+    test_enabled!(ui, &1002, ListBox<&'static str>);
+
+    {
+        let mut lb = ui.get_mut::<ListBox<&'static str>>(&1002).expect("Control not found!");
+
+        assert!(lb.get_readonly() == false, "Listbox should not be readonly");
+        assert!(lb.get_multi_select() == false, "Listbox should not be multi select");
+
+        lb.set_readonly(true);
+        assert!(lb.get_readonly() == true, "Listbox should be readonly");
+        lb.set_readonly(false);
+
+        lb.set_multi_select(true);
+        assert!(lb.get_multi_select() == true, "Listbox should be multi-select");
+        lb.set_multi_select(false);
+
+        assert!(lb.set_tab_stops(&[20, 60]), "Tab stops were not set");
+        lb.clear_tab_stops();
+
+        assert!(lb.collection() == &col, "Collection do not match");
+        assert!(lb.collection_mut() == &col, "Collection do not match");
+        assert!(lb.len() == 4, "Collection length should be 4");
+
+        lb.push("Foohoy!");
+        assert!(lb.collection() == &["Foo", "FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
+        assert!(lb.get_string(4).unwrap().as_str() == "Foohoy!", "Item text do not match");
+        assert!(lb.len() == 5, "Collection length should be 5");
+
+        lb.remove(0);
+        assert!(lb.collection() == &["FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
+        assert!(lb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match");
+
+        assert!(lb.get_selected_index().is_none(), "No index should be selected");
+        assert!(lb.get_selected_indexes().len() == 0, "Indexes vector length should be 0");
+
+        lb.set_selected_index(1);
+
+        assert!(lb.get_selected_index() == Some(1), "Current index is not 1");
+        assert!(lb.get_selected_indexes().len() == 0, "Indexes vector length should be 0");
+        assert!(lb.index_selected(1), "Index 1 is not selected");
+        assert!(lb.index_selected(2) == false, "Index 2 is selected");
+        assert!(lb.index_selected(665) == false, "Index 665 is selected");
+        assert!(lb.len_selected() == 1, "Selected length is not 1");
+
+        lb.set_selected_index(usize::max_value());
+        assert!(lb.get_selected_index().is_none(), "No index should be selected");
+
+        assert!(lb.find_string("foo", false) == Some(0), "find_string shoud have returned 0");
+        assert!(lb.find_string("foo", true) == None, "find_string shoud have returned None");
+        assert!(lb.find_string("Foohoy!", true) == Some(3), "find_string shoud have returned None");
+
+        assert!(lb.get_string(100) == None, "Item text should be None");
+
+        lb.collection_mut().remove(0);
+        assert!(lb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match"); // Ui and inner collection not synced
+        lb.sync();
+        assert!(lb.get_string(0).unwrap().as_str() == "Excelsior", "Item text do not match"); // Ui and inner collection synced
+
+        lb.clear();
+        assert!(lb.len() == 0, "Length is not 0");
+    }
+
+    {
+        let lb = ui.get::<ListBox<&'static str>>(&1003).expect("Control not found!");
+
+        assert!(lb.get_multi_select() == true, "Listbox should not be multi select");
+
+        assert!(lb.get_selected_indexes().len() == 0, "Indexes vector length should be 0");
+
+        lb.set_index_selected(0, true);
+        lb.set_index_selected(2, true);
+        assert!(lb.len_selected() == 2, "Selected length is not 2");
+
+        assert!(lb.get_selected_indexes() == [0, 2], "Selected indexes do not match");
+
+        lb.set_index_selected(0, false);
+        assert!(lb.len_selected() == 1, "Selected length is not 1");
+        assert!(lb.get_selected_indexes() == [2], "Selected indexes do not match");
+
+        lb.set_index_selected(usize::max_value(), true);
+        assert!(lb.len_selected() == 4, "Selected length is not 4");
+        assert!(lb.get_selected_indexes() == [0,1,2,3], "Selected indexes do not match");
+
+        lb.set_range_selected(0, 2, false);
+        assert!(lb.len_selected() == 1, "Selected length is not 1");
+        
+        lb.set_range_selected(0, 6, true);
+        assert!(lb.len_selected() == 4, "Selected length is not 4");
+        assert!(lb.get_selected_indexes() == [0,1,2,3], "Selected indexes do not match");
+    }
+    
+}
+
+#[test]
+fn test_timer() {
+    // TODO
+}
+
+#[test]
+fn test_combobox() {
+    let ui = setup_ui();
+
+    let col = vec!["Foo", "FooBar", "Excelsior", "wOOsh"];
+    let cb_t = ComboBoxT {
+        collection: col.clone(),
+        position:(10, 50), size: (100, 90),
+        visible: true, disabled: false, tab_stop: true, group: false,
+        placeholder: Some("TEST"),
+        visible_items: Some(5), dropdown_width: None,
+        parent: 1000,
+        font: None 
+    };
+
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1002, cb_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1002, ComboBox<&'static str>);
+    test_position!(ui, &1002, ComboBox<&'static str>);
+    test_enabled!(ui, &1002, ComboBox<&'static str>);
+
+    {
+        let mut cb = ui.get_mut::<ComboBox<&'static str>>(&1002).expect("Control not found!");
+
+        assert!(cb.get_visible_item_count() == 5, "Visible item count do not match");
+        cb.set_dropdown_width(300);
+        assert!(cb.get_dropdown_width() == 300, "Dropdown width do not match");
+
+        assert!(cb.collection() == &col, "Collection do not match");
+        assert!(cb.collection_mut() == &col, "Collection do not match");
+        assert!(cb.len() == 4, "Collection length should be 4");
+
+        cb.push("Foohoy!");
+        assert!(cb.collection() == &["Foo", "FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
+        assert!(cb.get_string(4).unwrap().as_str() == "Foohoy!", "Item text do not match");
+        assert!(cb.len() == 5, "Collection length should be 5");
+
+        cb.remove(0);
+        assert!(cb.collection() == &["FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
+        assert!(cb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match");
+
+        assert!(cb.get_selected_index().is_none(), "No index should be selected");
+        assert!(cb.get_selected_text().as_str() == "");
+
+        cb.set_selected_index(1);
+        assert!(cb.get_selected_text().as_str() == "Excelsior");
+        assert!(cb.get_selected_index() == Some(1), "Current index is not 1");
+
+        cb.set_selected_index(usize::max_value());
+        assert!(cb.get_selected_index().is_none(), "No index should be selected");
+
+        assert!(cb.find_string("foo", false) == Some(0), "find_string shoud have returned 0");
+        assert!(cb.find_string("foo", true) == None, "find_string shoud have returned None");
+        assert!(cb.find_string("Foohoy!", true) == Some(3), "find_string shoud have returned None");
+
+        assert!(cb.get_string(100) == None, "Item text should be None");
+
+        cb.collection_mut().remove(0);
+        assert!(cb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match"); // Ui and inner collection not synced
+        cb.sync();
+        assert!(cb.get_string(0).unwrap().as_str() == "Excelsior", "Item text do not match"); // Ui and inner collection synced
+
+        cb.clear();
+        assert!(cb.len() == 0, "Length is not 0");
+    }
+}
+
+#[test]
+fn text_textinput() {
+    let ui = setup_ui();
+
+    let ti_t = TextInputT::<_, &'static str, _> {
+        text: "TEST",
+        position: (0, 0), size: (100, 30), 
+        visible: true, disabled: false, tab_stop: true, group: false, readonly: false, password: false,
+        limit: 10,
+        placeholder: None,
+        parent: 1000,
+        font: None
+    };
+
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1001, ti_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, TextInput);
+    test_position!(ui, &1001, TextInput);
+    test_size!(ui, &1001, TextInput);
+    test_enabled!(ui, &1001, TextInput);
+
+    {
+        let tinput = ui.get::<TextInput>(&1001).expect("Control not found");
+
+        assert!(tinput.get_text().as_str() == "TEST");
+        tinput.set_text("Waloo");
+        assert!(tinput.get_text().as_str() == "Waloo");
+
+        assert!(tinput.get_readonly() == false);
+        tinput.set_readonly(true);
+        assert!(tinput.get_readonly() == true);
+
+        assert!(tinput.get_password() == false);
+        tinput.set_password(true);
+        assert!(tinput.get_password() == true);
+
+        assert!(tinput.get_limit() == 10);
+        tinput.set_limit(10_000);
+        assert!(tinput.get_limit() == 10_000);
+    }
+}
+
+fn set_clipboard_text(text: &str) {
+    use user32::{OpenClipboard, EmptyClipboard, SetClipboardData, CloseClipboard};
+    use kernel32::{GlobalAlloc, GlobalLock, GlobalUnlock};
+    use winapi::{CF_UNICODETEXT, SIZE_T};
+    use std::ptr;
+    const GMEM_MOVEABLE: u32 = 0x0002;
+
+    let text: Vec<u16> = text.encode_utf16().chain(Some(0)).collect();
+    unsafe {
+        assert!(OpenClipboard(ptr::null_mut()) != 0, "Could not open the clipboard");
+        EmptyClipboard();
+
+        // The clipboard owns the memory once the data is set
+        let memory = GlobalAlloc(GMEM_MOVEABLE, (text.len() * 2) as SIZE_T);
+        let dest = GlobalLock(memory) as *mut u16;
+        ptr::copy_nonoverlapping(text.as_ptr(), dest, text.len());
+        GlobalUnlock(memory);
+
+        assert!(!SetClipboardData(CF_UNICODETEXT, memory).is_null(), "Could not set the clipboard data");
+        CloseClipboard();
+    }
+}
+
+fn paste_upper(text: &str) -> String { text.to_uppercase() }
+fn paste_no_digits(text: &str) -> String { text.chars().filter(|c| !c.is_digit(10)).collect() }
+
+#[test]
+fn test_paste_filter() {
+    use nwg::custom::AnyHandle;
+    use user32::SendMessageW;
+    use winapi::WM_PASTE;
+
+    let ui = setup_ui();
+
+    let ti_t = TextInputT::<_, &'static str, _> {
+        text: "",
+        position: (0, 0), size: (100, 30), 
+        visible: true, disabled: false, tab_stop: true, group: false, readonly: false, password: false,
+        limit: 100,
+        placeholder: None,
+        parent: 1000,
+        font: None
+    };
+
+    let tb_t = TextBoxT {
+        text: "",
+        position: (0, 30), size: (100, 60), 
+        visible: true, disabled: false, tab_stop: true, group: false, readonly: false,
+        limit: 100,
+        scrollbars: (false, false),
+        parent: 1000,
+        font: None
+    };
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, ti_t);
+    ui.pack_control(&1002, tb_t);
+    ui.commit().expect("Commit was not successful");
+
+    let paste = |id: u64| {
+        let handle = match ui.handle_of(&id) { Ok(AnyHandle::HWND(h)) => h, _ => panic!("Bad handle") };
+        unsafe{ SendMessageW(handle, WM_PASTE, 0, 0); }
+    };
+
+    set_clipboard_text("abc 123");
+
+    {
+        let tinput = ui.get::<TextInput>(&1001).expect("Control not found");
+
+        tinput.set_paste_filter(Some(paste_upper));
+        paste(1001);
+        assert!(tinput.get_text().as_str() == "ABC 123", "The pasted text should be filtered");
+
+        // Without a filter, the default paste is executed
+        tinput.set_paste_filter(None);
+        tinput.set_text("");
+        paste(1001);
+        assert!(tinput.get_text().as_str() == "abc 123", "The pasted text should not be filtered");
+
+        // Read-only controls ignore the paste
+        tinput.set_paste_filter(Some(paste_upper));
+        tinput.set_text("");
+        tinput.set_readonly(true);
+        paste(1001);
+        assert!(tinput.get_text().as_str() == "", "A read-only control should ignore the paste");
+    }
+
+    {
+        let tbox = ui.get::<TextBox>(&1002).expect("Control not found");
+        tbox.set_paste_filter(Some(paste_no_digits));
+        paste(1002);
+        assert!(tbox.get_text().as_str() == "abc ", "The pasted text should be filtered");
+    }
+}
+
+#[test]
+#[cfg(feature = "testing")]
+fn test_simulated_input() {
+    let ui = setup_ui();
+
+    let ti_t = TextInputT::<_, &'static str, _> {
+        text: "",
+        position: (0, 0), size: (100, 30), 
+        visible: true, disabled: false, tab_stop: true, group: false, readonly: false, password: false,
+        limit: 100,
+        placeholder: None,
+        parent: 1000,
+        font: None
+    };
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, ti_t);
+    ui.commit().expect("Commit was not successful");
+
+    let handle = ui.handle_of(&1001).expect("Control not found");
+    nwg::testing::type_text(&handle, "Hello").expect("Could not type the text");
+    nwg::testing::click(&handle).expect("Could not click the control");
+
+    {
+        let tinput = ui.get::<TextInput>(&1001).expect("Control not found");
+        assert!(tinput.get_text().as_str() == "Hello", "Text do not match");
+    }
+
+    let font = nwg::custom::AnyHandle::HFONT(::std::ptr::null_mut());
+    match nwg::testing::send_key(&font, 0x41) { Err(Error::ControlRequired) => {}, _ => panic!("Should have returned Error::ControlRequired") }
+}
+
+#[test]
+fn test_static_image() {
+    use nwg::custom::AnyHandle;
+    use user32::SendMessageW;
+    use gdi32::{CreateDIBSection, GetObjectType};
+    use winapi::{BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DWORD, HGDIOBJ, IMAGE_BITMAP, WPARAM, c_void};
+    use std::{mem, ptr, slice};
+
+    const STM_GETIMAGE: u32 = 0x0173;
+
+    fn icon_handle(icon: &Icon) -> winapi::HICON {
+        match icon.handle() { AnyHandle::HICON(h) => h, _ => unreachable!() }
+    }
+
+    let ui = setup_ui();
+
+    let img_t = StaticImageT{image: None, position: (0, 0), size: (100, 100), visible: true, disabled: false, center: true, parent: 1000};
+
+    ui.pack_resource(&10_000, default_font());
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1001, img_t.clone());
+    ui.commit().expect("Commit was not successful");
+
+    let mut bad_img_t = img_t.clone();
+    bad_img_t.image = Some(10_000);
+    ui.pack_control(&1002, bad_img_t);
     match ui.commit() { Err(Error::BadResource(_)) => {}, r => panic!("Should have returned Error::BadResource, got {:?}", r) }
 
     // methods test
-    test_visibility!(ui, &1002, Button);
-    test_position!(ui, &1002, Button);
-    test_size!(ui, &1002, Button);
-    test_enabled!(ui, &1002, Button);
+    test_visibility!(ui, &1001, StaticImage);
+    test_position!(ui, &1001, StaticImage);
+    test_size!(ui, &1001, StaticImage);
+    test_enabled!(ui, &1001, StaticImage);
+
+    {
+        let img = ui.get::<StaticImage>(&1001).expect("Control not found");
+
+        assert!(img.get_image_type() == ImageType::Bitmap);
+        img.set_icon(None);
+        assert!(img.get_image_type() == ImageType::Icon);
+        img.set_bitmap(None);
+        assert!(img.get_image_type() == ImageType::Bitmap);
+        assert!(img.size() == (0, 0), "A control without image should have an empty image size");
+
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        let handle = ui.handle_of(&1000).expect("Control not found");
+        let snapshot = Bitmap::capture(&handle).expect("Window capture failed");
+        img.set_bitmap(Some(&snapshot));
+        assert!(img.size() == window.get_size(), "Bitmap size do not match");
+
+        let icon = Icon::from_bitmap(&snapshot, None).expect("Failed to create the icon");
+        img.set_icon(Some(&icon));
+        assert!(img.size() == window.get_size(), "Icon size do not match");
+
+        img.set_bitmap(None);
+        assert!(Icon::clone_hicon(icon_handle(&icon)).is_ok(), "The icon of the user should not be destroyed");
+
+        // The control displays a copy of the 32 bits bitmaps with an alpha channel. The copy is freed with the next image.
+        let alpha = unsafe {
+            let mut header: BITMAPINFO = mem::zeroed();
+            header.bmiHeader = BITMAPINFOHEADER {
+                biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD, biWidth: 4, biHeight: -4, biPlanes: 1, biBitCount: 32,
+                biCompression: BI_RGB, biSizeImage: 0, biXPelsPerMeter: 0, biYPelsPerMeter: 0, biClrUsed: 0, biClrImportant: 0
+            };
+
+            let mut bits: *mut c_void = ptr::null_mut();
+            let handle = CreateDIBSection(ptr::null_mut(), &header, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+            assert!(!handle.is_null(), "Failed to create the bitmap");
+            for px in slice::from_raw_parts_mut(bits as *mut u8, 64).chunks_mut(4) { px.copy_from_slice(&[64, 64, 64, 128]); }
+            Bitmap::from_hbitmap(handle, true)
+        };
+
+        let alpha_handle = match alpha.handle() { AnyHandle::HBITMAP(h) => h, _ => unreachable!() };
+        let control = match ui.handle_of(&1001) { Ok(AnyHandle::HWND(h)) => h, _ => panic!("Bad handle") };
+        img.set_bitmap(Some(&alpha));
+        let displayed = unsafe{ SendMessageW(control, STM_GETIMAGE, IMAGE_BITMAP as WPARAM, 0) as HGDIOBJ };
+
+        img.set_bitmap(None);
+        assert!(unsafe{ GetObjectType(alpha_handle as HGDIOBJ) } != 0, "The bitmap of the user should not be deleted");
+        if displayed != alpha_handle as HGDIOBJ {
+            assert!(unsafe{ GetObjectType(displayed) } == 0, "The copy made by the control should be deleted");
+        }
+    }
+}
+
+#[test]
+fn test_multi_column_listbox() {
+    let ui = setup_ui();
+
+    let lb_t = MultiColumnListBoxT{position: (0, 0), size: (300, 100), visible: true, disabled: false, tab_stop: true, group: false, multi_select: true, parent: 1000, font: None, auto_tooltip: false};
+
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1001, lb_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, MultiColumnListBox);
+    test_position!(ui, &1001, MultiColumnListBox);
+    test_size!(ui, &1001, MultiColumnListBox);
+    test_enabled!(ui, &1001, MultiColumnListBox);
+
+    {
+        let lb = ui.get::<MultiColumnListBox>(&1001).expect("Control not found");
+
+        lb.add_column(0, 100, "Name");
+        lb.add_column(1, 100, "Value");
+        lb.add_item(vec!["Foo".to_string(), "1".to_string()]);
+        lb.add_item(vec!["Bar".to_string(), "2".to_string()]);
+        assert!(lb.len() == 2, "Length is not 2");
+
+        assert!(lb.get_item_text(1, 0).as_str() == "Bar", "Item text do not match");
+        assert!(lb.get_item_text(1, 1).as_str() == "2", "Item text do not match");
+
+        assert!(lb.get_selected_index() == None, "No row should be selected");
+        lb.set_selected(0, true);
+        lb.set_selected(1, true);
+        assert!(lb.get_selected_index() == Some(0), "Selected index do not match");
+        assert!(lb.get_selected_indexes() == vec![0, 1], "Selected indexes do not match");
+
+        lb.remove_item(0);
+        assert!(lb.get_item_text(0, 0).as_str() == "Bar", "Item text do not match");
+
+        lb.clear();
+        assert!(lb.len() == 0, "Length is not 0");
+    }
+}
+
+#[test]
+#[cfg(feature = "drag-drop")]
+fn test_multi_column_listbox_drag() {
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, MultiColumnListBoxT{position: (0, 0), size: (300, 100), visible: true, disabled: false, tab_stop: true, group: false, multi_select: true, parent: 1000, font: None, auto_tooltip: false});
+    ui.commit().expect("Commit was not successful");
+
+    let lb = ui.get::<MultiColumnListBox>(&1001).expect("Control not found");
+    lb.add_column(0, 100, "Name");
+    lb.add_item(vec!["file.txt".to_string()]);
+
+    // Without a selection, the drag operation is not started
+    assert!(lb.get_selected_indexes().is_empty());
+    match lb.drag_selection() { Ok(false) => {}, _ => panic!("A listbox without selection should not start a drag operation") }
+}
+
+#[test]
+fn test_gradient_fill() {
+    use gdi32::{CreateDIBSection, CreateCompatibleDC, SelectObject, DeleteDC, DeleteObject};
+    use winapi::{BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DWORD, RECT, BOOL, c_void};
+    use nwg::custom::gradient_fill;
+    use std::{mem, ptr, slice};
+
+    extern "system" { fn GdiFlush() -> BOOL; }
+
+    unsafe {
+        // A 16x16 32 bits bitmap
+        let mut header: BITMAPINFO = mem::zeroed();
+        header.bmiHeader = BITMAPINFOHEADER {
+            biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD, biWidth: 16, biHeight: -16, biPlanes: 1, biBitCount: 32,
+            biCompression: BI_RGB, biSizeImage: 0, biXPelsPerMeter: 0, biYPelsPerMeter: 0, biClrUsed: 0, biClrImportant: 0
+        };
+
+        let mut bits: *mut c_void = ptr::null_mut();
+        let bitmap = CreateDIBSection(ptr::null_mut(), &header, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+        assert!(!bitmap.is_null(), "Failed to create the bitmap");
+        let pixels = slice::from_raw_parts(bits as *const u8, 16 * 16 * 4);
+
+        let hdc = CreateCompatibleDC(ptr::null_mut());
+        let old = SelectObject(hdc, bitmap as _);
+        let rect = RECT{left: 0, top: 0, right: 16, bottom: 16};
+
+        // (red, green, blue) of the pixel at x, y. The pixels are stored in the BGRA format.
+        let pixel = |x: usize, y: usize| { let i = (y*16+x)*4; (pixels[i+2], pixels[i+1], pixels[i]) };
+
+        // Horizontal gradient from red to blue
+        assert!(gradient_fill(hdc, &rect, (255, 0, 0), (0, 0, 255), GradientDirection::Horizontal), "Failed to fill the rect");
+        GdiFlush();
+        assert!(pixel(0, 0).0 > 200 && pixel(0, 0).2 < 55, "The gradient should start with the first color");
+        assert!(pixel(15, 0).0 < 55 && pixel(15, 0).2 > 200, "The gradient should end with the second color");
+        assert!((1..16).all(|x| pixel(x, 0).0 <= pixel(x-1, 0).0 && pixel(x, 0).2 >= pixel(x-1, 0).2), "The gradient should go from left to right");
+        assert!((0..16).all(|y| pixel(8, y) == pixel(8, 0)), "A horizontal gradient should not change vertically");
+
+        // Vertical gradient from black to white
+        assert!(gradient_fill(hdc, &rect, (0, 0, 0), (255, 255, 255), GradientDirection::Vertical), "Failed to fill the rect");
+        GdiFlush();
+        assert!(pixel(0, 0).1 < 55 && pixel(0, 15).1 > 200, "The gradient should go from the first color to the second color");
+        assert!((1..16).all(|y| pixel(0, y).1 >= pixel(0, y-1).1), "The gradient should go from top to bottom");
+        assert!((0..16).all(|x| pixel(x, 8) == pixel(0, 8)), "A vertical gradient should not change horizontally");
+
+        // Two identical colors fill the rect
+        assert!(gradient_fill(hdc, &rect, (10, 20, 30), (10, 20, 30), GradientDirection::Horizontal), "Failed to fill the rect");
+        GdiFlush();
+        assert!((0..16).all(|y| (0..16).all(|x| pixel(x, y) == (10, 20, 30))), "The rect should be filled with a solid color");
+
+        SelectObject(hdc, old);
+        DeleteDC(hdc);
+        DeleteObject(bitmap as _);
+    }
+}
+
+#[test]
+#[cfg(feature = "tree-view")]
+fn test_tree_view() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let ui = setup_ui();
+
+    let tv_t = TreeViewT{position: (0, 0), size: (300, 100), visible: true, disabled: false, tab_stop: true, group: false, lines: true, buttons: true, parent: 1000, font: None, auto_tooltip: false};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, tv_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, TreeView);
+    test_position!(ui, &1001, TreeView);
+    test_size!(ui, &1001, TreeView);
+    test_enabled!(ui, &1001, TreeView);
+
+    let selected = Rc::new(Cell::new(0u32));
+    let count = selected.clone();
+    ui.bind(&1001, &5000, Event::TreeItemSelected, move |_, _, _, _|{ count.set(count.get()+1); } );
+    ui.commit().expect("Commit was not successful");
+
+    {
+        let tv = ui.get::<TreeView>(&1001).expect("Control not found");
+
+        let root = tv.insert_item(None, "Root", Some(42u32));
+        let child1 = tv.insert_item(Some(root), "Child 1", Some("data".to_string()));
+        let child2 = tv.insert_item::<()>(Some(root), "Child 2", None);
+        assert!(!root.is_null() && !child1.is_null() && !child2.is_null(), "Items were not inserted");
+        assert!(tv.len() == 3, "Length is not 3");
+        assert!(tv.children(None) == vec![root], "Root items do not match");
+        assert!(tv.children(Some(root)) == vec![child1, child2], "Children do not match");
+
+        assert!(tv.item_text(child1).as_str() == "Child 1", "Item text do not match");
+        tv.set_item_text(child1, "Renamed");
+        assert!(tv.item_text(child1).as_str() == "Renamed", "Item text do not match");
+
+        assert!(tv.item_data::<u32>(root) == Some(42), "Item data do not match");
+        assert!(tv.item_data::<String>(child1) == Some("data".to_string()), "Item data do not match");
+        assert!(tv.item_data::<u32>(child1).is_none(), "Item data type must be checked");
+        assert!(tv.item_data::<u32>(child2).is_none(), "Item should not have data");
+
+        tv.expand(root);
+        assert!(tv.is_expanded(root), "Root should be expanded");
+        tv.collapse(root);
+        assert!(!tv.is_expanded(root), "Root should be collapsed");
+
+        assert!(tv.selected_item().is_none(), "No item should be selected");
+        tv.set_selected_item(Some(child2));
+        assert!(tv.selected_item() == Some(child2), "Selected item do not match");
+        assert!(selected.get() == 1, "TreeItemSelected was not raised");
+
+        tv.remove_item(child1);
+        assert!(tv.len() == 2, "Length is not 2");
+
+        tv.clear();
+        assert!(tv.len() == 0, "Length is not 0");
+    }
+}
+
+#[test]
+fn test_list_view() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let ui = setup_ui();
+
+    let lv_t = ListViewT{position: (0, 0), size: (300, 100), visible: true, disabled: false, tab_stop: true, group: false, style: ListViewStyle::Report, parent: 1000, font: None, auto_tooltip: false};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, lv_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, ListView);
+    test_position!(ui, &1001, ListView);
+    test_size!(ui, &1001, ListView);
+    test_enabled!(ui, &1001, ListView);
+
+    let selected = Rc::new(Cell::new(0u32));
+    let count = selected.clone();
+    ui.bind(&1001, &5000, Event::SelectionChanged, move |_, _, _, _|{ count.set(count.get()+1); } );
+    ui.commit().expect("Commit was not successful");
+
+    {
+        let lv = ui.get::<ListView>(&1001).expect("Control not found");
+
+        lv.add_column(0, 100, "Name");
+        lv.add_column(1, 100, "Size");
+
+        lv.insert_item(0, 0, "beta");
+        lv.insert_item(0, 1, "2");
+        lv.insert_item(1, 0, "Alpha");
+        lv.insert_item(1, 1, "1");
+        lv.insert_item(2, 0, "gamma");
+        lv.insert_item(2, 1, "3");
+        assert!(lv.len() == 3, "Item count is not 3");
+        assert!(lv.get_item_text(1, 0).as_str() == "Alpha", "Item text do not match");
+        assert!(lv.get_item_text(1, 1).as_str() == "1", "Sub item text do not match");
+
+        lv.sort_column(0, true);
+        assert!(lv.get_item_text(0, 0).as_str() == "Alpha", "Items were not sorted");
+        assert!(lv.get_item_text(0, 1).as_str() == "1", "Sub items were not sorted with their item");
+        assert!(lv.get_item_text(2, 0).as_str() == "gamma", "Items were not sorted");
+
+        lv.sort_column(1, false);
+        assert!(lv.get_item_text(0, 0).as_str() == "gamma", "Items were not sorted in descending order");
+
+        assert!(lv.get_selected_indexes().len() == 0, "No item should be selected");
+        lv.set_selected(0, true);
+        lv.set_selected(2, true);
+        assert!(lv.get_selected_indexes() == vec![0, 2], "Selected items do not match");
+        assert!(lv.get_selected_index() == Some(0), "First selected item do not match");
+        assert!(selected.get() == 2, "SelectionChanged was not raised");
+
+        assert!(lv.get_style() == ListViewStyle::Report, "Style is not Report");
+        lv.set_style(ListViewStyle::List);
+        assert!(lv.get_style() == ListViewStyle::List, "Style is not List");
+
+        lv.remove_item(0);
+        assert!(lv.len() == 2, "Item count is not 2");
+
+        lv.clear();
+        assert!(lv.len() == 0, "Item count is not 0");
+    }
+}
+
+#[test]
+fn test_tab_control() {
+    let ui = setup_ui();
+
+    let tc_t = TabControlT{position: (0, 0), size: (300, 200), visible: true, disabled: false, tab_stop: true, group: false, multi_line: false, buttons: false, parent: 1000, font: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, tc_t);
+    ui.pack_control(&1002, TabItemT{label: "General", parent: 1001});
+    ui.pack_control(&1003, TabItemT{label: "Advanced", parent: 1001});
+    ui.pack_control(&1004, ButtonT{text: "Button", position: (10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1003, font: None, command_link: false, note: None});
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, TabControl);
+    test_position!(ui, &1001, TabControl);
+    test_size!(ui, &1001, TabControl);
+    test_enabled!(ui, &1001, TabControl);
+
+    {
+        use nwg::custom::{AnyHandle, get_window_long};
+
+        let tc_t = TabControlT{position: (0, 0), size: (300, 200), visible: true, disabled: false, tab_stop: false, group: true, multi_line: false, buttons: false, parent: 1000, font: None};
+        ui.pack_control(&1010, tc_t);
+        ui.pack_control(&1011, ListViewT{position: (0, 0), size: (300, 100), visible: true, disabled: false, tab_stop: true, group: true,
+          style: ListViewStyle::Report, parent: 1000, font: None, auto_tooltip: false});
+        ui.commit().expect("Commit was not successful");
+
+        let style = |id: u64| match ui.handle_of(&id) {
+            Ok(AnyHandle::HWND(h)) => get_window_long(h, winapi::GWL_STYLE) as u32,
+            _ => panic!("Bad handle")
+        };
+
+        assert!(style(1001) & (winapi::WS_TABSTOP | winapi::WS_GROUP) == winapi::WS_TABSTOP, "Tab control should use the template flags");
+        assert!(style(1010) & (winapi::WS_TABSTOP | winapi::WS_GROUP) == winapi::WS_GROUP, "Tab control should use the template flags");
+        assert!(style(1011) & winapi::WS_GROUP == winapi::WS_GROUP, "List view should use the template group flag");
+
+        ui.unpack(&1010);
+        ui.unpack(&1011);
+        ui.commit().expect("Commit was not successful");
+    }
+
+    // The parent of a tab item must be a tab control
+    ui.pack_control(&1005, TabItemT{label: "Bad", parent: 1000});
+    let r = ui.commit();
+    assert!(r.is_err(), "A tab item should require a tab control parent");
+
+    {
+        let tc = ui.get::<TabControl>(&1001).expect("Control not found");
+        let general = ui.get::<TabItem>(&1002).expect("Control not found");
+        let advanced = ui.get::<TabItem>(&1003).expect("Control not found");
+
+        assert!(tc.tab_count() == 2, "Tab count is not 2");
+        assert!(tc.tab_label(1).as_str() == "Advanced", "Tab label do not match");
+        assert!(general.index() == Some(0) && advanced.index() == Some(1), "Tab indices do not match");
+        assert!(tc.selected_tab() == Some(0), "The first tab should be selected");
+        assert!(general.is_selected() && !advanced.is_selected(), "The first page should be displayed");
+
+        tc.set_selected_tab(1);
+        assert!(tc.selected_tab() == Some(1), "The second tab should be selected");
+        assert!(advanced.is_selected(), "The second page should be displayed");
+        
+        advanced.set_label("Other");
+        assert!(advanced.get_label().as_str() == "Other", "Tab label do not match");
+
+        let index = tc.add_tab("Custom").expect("Failed to add a tab");
+        assert!(index == 2 && tc.tab_count() == 3, "The tab was not added");
+        assert!(tc.tab_label(2).as_str() == "Custom", "Tab label do not match");
+        assert!(tc.page(2).is_some() && tc.page(3).is_none(), "Tab pages do not match");
+
+        tc.remove_tab(2);
+        assert!(tc.tab_count() == 2, "The tab was not removed");
+    }
+
+    // Unpacking a tab item removes its tab
+    ui.unpack(&1003);
+    ui.commit().expect("Commit was not successful");
+    assert!(!ui.has_id(&1004), "The children of the page should be unpacked");
+
+    {
+        let tc = ui.get::<TabControl>(&1001).expect("Control not found");
+        assert!(tc.tab_count() == 1, "The tab was not removed");
+        assert!(tc.selected_tab() == Some(0), "The remaining tab should be selected");
+    }
+}
+
+#[test]
+fn test_rich_text_box() {
+    let ui = setup_ui();
+
+    let rtb_t = RichTextBoxT{text: "Hello", position: (0, 0), size: (200, 100), visible: true, disabled: false, tab_stop: true, readonly: false, scrollbars: (false, true), parent: 1000, font: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, rtb_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, RichTextBox);
+    test_position!(ui, &1001, RichTextBox);
+    test_size!(ui, &1001, RichTextBox);
+    test_enabled!(ui, &1001, RichTextBox);
+
+    {
+        let rtb = ui.get::<RichTextBox>(&1001).expect("Control not found");
+
+        assert!(rtb.get_text().as_str() == "Hello", "Text do not match");
+        rtb.append_text(" World");
+        assert!(rtb.get_text().as_str() == "Hello World", "Text do not match");
+
+        rtb.set_readonly(true);
+        assert!(rtb.get_readonly(), "The rich text box should be readonly");
+        rtb.set_readonly(false);
+
+        // Format the word "World"
+        rtb.set_selection(6, 11);
+        assert!(rtb.get_selection() == (6, 11), "Selection do not match");
+        assert!(!rtb.selection_bold() && !rtb.selection_italic() && !rtb.selection_underline(), "Text should not be formatted");
+        assert!(rtb.selection_color().is_none(), "Text should use the default color");
+
+        rtb.set_selection_bold(true);
+        rtb.set_selection_italic(true);
+        rtb.set_selection_underline(true);
+        rtb.set_selection_color(Some((255, 0, 0)));
+        assert!(rtb.selection_bold() && rtb.selection_italic() && rtb.selection_underline(), "Text should be formatted");
+        assert!(rtb.selection_color() == Some((255, 0, 0)), "Color do not match");
+
+        rtb.set_selection(0, 5);
+        assert!(!rtb.selection_bold(), "Only the selected text should be formatted");
+
+        // Round trip through RTF
+        let rtf = rtb.save_rtf().expect("Could not save the document");
+        assert!(rtf.starts_with("{\\rtf"), "Document is not a RTF document");
+
+        rtb.set_text("");
+        rtb.load_rtf(&rtf).expect("Could not load the document");
+        assert!(rtb.get_text().as_str() == "Hello World", "Text do not match");
+
+        rtb.set_selection(6, 11);
+        assert!(rtb.selection_bold() && rtb.selection_color() == Some((255, 0, 0)), "Format was not loaded");
+
+        // Round trip of a document with non ASCII characters
+        rtb.set_text("Héllo Wörld ñ € 日本");
+        let rtf = rtb.save_rtf().expect("Could not save the document");
+        rtb.set_text("");
+        rtb.load_rtf(&rtf).expect("Could not load the document");
+        assert!(rtb.get_text().as_str() == "Héllo Wörld ñ € 日本", "Non ASCII text do not match");
+        assert!(rtb.save_rtf().expect("Could not save the document") == rtf, "Document do not match");
+
+        // Characters that do not fit in a byte are escaped
+        rtb.load_rtf("{\\rtf1\\ansi 日本 café}").expect("Could not load the document");
+        assert!(rtb.get_text().as_str() == "日本 café", "Unicode text do not match");
+    }
+}
+
+#[test]
+fn test_date_time_picker() {
+    let ui = setup_ui();
+
+    let value = PickerDateTime{year: 2017, month: 5, day: 12, hour: 14, minute: 30, second: 5};
+    let dtp_t = DateTimePickerT{value: Some(value.clone()), position: (0, 0), size: (150, 30), visible: true, disabled: false, tab_stop: true, parent: 1000, font: None, flags: DateTimePickerFlags::DateTime, format: None::<&'static str>, optional: false};
+    let time_t = DateTimePickerT{value: None, position: (0, 40), size: (150, 30), visible: true, disabled: false, tab_stop: true, parent: 1000, font: None, flags: DateTimePickerFlags::Time, format: Some("HH':'mm"), optional: true};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, dtp_t);
+    ui.pack_control(&1002, time_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, DateTimePicker);
+    test_position!(ui, &1001, DateTimePicker);
+    test_size!(ui, &1001, DateTimePicker);
+    test_enabled!(ui, &1001, DateTimePicker);
+
+    {
+        let dtp = ui.get::<DateTimePicker>(&1001).expect("Control not found");
+        let time = ui.get::<DateTimePicker>(&1002).expect("Control not found");
+
+        assert!(dtp.get_value() == Some(value.clone()), "Value do not match");
+        assert!(dtp.get_value_string().as_str() == "2017-05-12 14:30:05", "The default format should show the date and the time");
+        assert!(dtp.is_checked(), "A picker that is not optional is always checked");
+
+        assert!(!time.is_checked() && time.get_value().is_none(), "An optional picker without value should not be checked");
+        time.set_value(&Some(value.clone()));
+        assert!(time.is_checked(), "The picker should be checked after a value is set");
+        assert!(time.get_value_string().as_str() == "14:30", "Format do not match");
+
+        time.set_value(&None);
+        assert!(!time.is_checked(), "The picker should be cleared");
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_date_time_picker_chrono() {
+    extern crate chrono;
+    use chrono::NaiveDate;
+
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, DateTimePickerT{value: None, position: (0, 0), size: (150, 30), visible: true, disabled: false, tab_stop: true, parent: 1000, font: None, flags: DateTimePickerFlags::DateTime, format: None::<&'static str>, optional: false});
+    ui.commit().expect("Commit was not successful");
+
+    let dtp = ui.get::<DateTimePicker>(&1001).expect("Control not found");
+    let value = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap().and_hms_opt(23, 59, 58).unwrap();
+
+    dtp.set_naive_value(Some(value));
+    assert!(dtp.value() == Some(value), "Value do not match");
+}
+
+#[test]
+fn test_month_calendar() {
+    let ui = setup_ui();
+
+    let date = |y, m, d| PickerDate{year: y, month: m, day: d};
+    let cal_t = MonthCalendarT{value: Some(date(2017, 5, 12)), position: (0, 0), size: (250, 200), visible: true, disabled: false, tab_stop: true, parent: 1000, font: None, multi_select: false, week_numbers: false, range: (Some(date(2017, 1, 1)), None)};
+    let multi_t = MonthCalendarT{value: None, position: (0, 0), size: (250, 200), visible: true, disabled: false, tab_stop: true, parent: 1000, font: None, multi_select: true, week_numbers: true, range: (None, None)};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, cal_t);
+    ui.pack_control(&1002, multi_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, MonthCalendar);
+    test_position!(ui, &1001, MonthCalendar);
+    test_size!(ui, &1001, MonthCalendar);
+    test_enabled!(ui, &1001, MonthCalendar);
+
+    {
+        let cal = ui.get::<MonthCalendar>(&1001).expect("Control not found");
+        let multi = ui.get::<MonthCalendar>(&1002).expect("Control not found");
+
+        assert!(!cal.is_multi_select() && multi.is_multi_select(), "Multi select style do not match");
+        assert!(cal.selected_date() == date(2017, 5, 12), "Selected date do not match");
+        assert!(cal.min_date() == Some(date(2017, 1, 1)) && cal.max_date().is_none(), "Range do not match");
+
+        cal.set_selected_date(&date(2016, 12, 31));
+        assert!(cal.selected_date() == date(2017, 5, 12), "A date outside of the range should not be selected");
+
+        cal.set_range(&None, &Some(date(2018, 1, 1)));
+        assert!(cal.min_date().is_none() && cal.max_date() == Some(date(2018, 1, 1)), "Range do not match");
+
+        cal.set_today(&date(2017, 6, 1));
+        assert!(cal.today() == date(2017, 6, 1), "Today do not match");
+
+        multi.set_selected_range(&date(2017, 5, 1), &date(2017, 5, 5));
+        assert!(multi.selected_range() == (date(2017, 5, 1), date(2017, 5, 5)), "Selected range do not match");
+        assert!(multi.selected_date() == date(2017, 5, 1), "Selected date should be the start of the range");
+
+        multi.set_selected_range(&date(2017, 5, 1), &date(2017, 5, 20));
+        assert!(multi.selected_range() == (date(2017, 5, 1), date(2017, 5, 5)), "The range should be limited to 7 days");
+
+        multi.set_max_selection(31);
+        multi.set_selected_range(&date(2017, 5, 1), &date(2017, 5, 20));
+        assert!(multi.selected_range() == (date(2017, 5, 1), date(2017, 5, 20)), "Selected range do not match");
+    }
+}
+
+#[test]
+fn test_numeric_updown() {
+    let ui = setup_ui();
+
+    let ud_t = NumericUpDownT{value: 5, min: -10, max: 10, step: 2, wrap: false, position: (10, 10), size: (100, 25), visible: true, disabled: false, tab_stop: true, parent: 1000, font: None};
+    let bad_t = NumericUpDownT{value: 0, min: 10, max: -10, step: 1, wrap: false, position: (10, 10), size: (100, 25), visible: true, disabled: false, tab_stop: true, parent: 1000, font: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, ud_t);
+    ui.commit().expect("Commit was not successful");
+
+    ui.pack_control(&1002, bad_t.clone());
+    assert!(ui.commit().is_err(), "A numeric up-down with an invalid range should not be created");
+
+    ui.pack_control(&1003, NumericUpDownT{min: -10, max: 10, step: 0, ..bad_t});
+    assert!(ui.commit().is_err(), "A numeric up-down with a step of 0 should not be created");
+
+    // methods test
+    test_visibility!(ui, &1001, NumericUpDown);
+    test_position!(ui, &1001, NumericUpDown);
+    test_size!(ui, &1001, NumericUpDown);
+    test_enabled!(ui, &1001, NumericUpDown);
+
+    {
+        let ud = ui.get::<NumericUpDown>(&1001).expect("Control not found");
+
+        assert!(ud.value() == 5 && ud.get_text().as_str() == "5", "Value do not match");
+        assert!(ud.min() == -10 && ud.max() == 10, "Range do not match");
+        assert!(ud.step() == 2, "Step do not match");
+        assert!(!ud.get_wrap(), "Control should not wrap");
+
+        ud.set_value(-3);
+        assert!(ud.value() == -3 && ud.get_text().as_str() == "-3", "Value do not match");
+
+        ud.set_value(50);
+        assert!(ud.value() == 10, "Value should be clamped to the range");
+
+        ud.set_range(0, 5);
+        assert!(ud.min() == 0 && ud.max() == 5 && ud.value() == 5, "Value should be clamped to the new range");
+
+        ud.set_step(3);
+        assert!(ud.step() == 3, "Step do not match");
+
+        ud.set_step(-2);
+        assert!(ud.step() == 1, "Step should be at least 1");
+    }
+}
+
+#[test]
+fn test_scroll_bar() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use nwg::custom::AnyHandle;
+    use winapi::{WM_HSCROLL, WM_VSCROLL, SB_LINEDOWN, SB_PAGEUP, WPARAM, LPARAM};
+    use user32::SendMessageW;
+
+    let ui = setup_ui();
+
+    let hscroll_t = HorizontalScrollBarT{position: (10, 10), size: (100, 17), visible: true, disabled: false, range: (0, 100), value: 20, page_size: 10, parent: 1000};
+    let vscroll_t = VerticalScrollBarT{position: (10, 30), size: (17, 100), visible: true, disabled: false, range: (10, 50), value: 0, page_size: 0, parent: 1000};
+    let bad_t = HorizontalScrollBarT{position: (10, 10), size: (100, 17), visible: true, disabled: false, range: (100, 0), value: 0, page_size: 0, parent: 1000};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, hscroll_t);
+    ui.pack_control(&1002, vscroll_t);
+    ui.commit().expect("Commit was not successful");
+
+    ui.pack_control(&1003, bad_t);
+    assert!(ui.commit().is_err(), "A scroll bar with an invalid range should not be created");
+
+    // methods test
+    test_visibility!(ui, &1001, HorizontalScrollBar);
+    test_position!(ui, &1001, HorizontalScrollBar);
+    test_size!(ui, &1001, HorizontalScrollBar);
+    test_enabled!(ui, &1001, HorizontalScrollBar);
+
+    test_visibility!(ui, &1002, VerticalScrollBar);
+    test_position!(ui, &1002, VerticalScrollBar);
+    test_size!(ui, &1002, VerticalScrollBar);
+    test_enabled!(ui, &1002, VerticalScrollBar);
+
+    {
+        let hscroll = ui.get::<HorizontalScrollBar>(&1001).expect("Control not found");
+        let vscroll = ui.get::<VerticalScrollBar>(&1002).expect("Control not found");
+
+        assert!(hscroll.value() == 20, "Value do not match");
+        assert!(hscroll.range() == (0, 100), "Range do not match");
+        assert!(hscroll.page_size() == 10, "Page size do not match");
+        assert!(vscroll.value() == 10, "Value should be clamped to the range");
+
+        hscroll.set_value(200);
+        assert!(hscroll.value() == 91, "Value should be clamped to the range minus the page size");
+
+        hscroll.set_page_size(0);
+        hscroll.set_value(100);
+        assert!(hscroll.value() == 100, "Value do not match");
+
+        assert!(hscroll.set_range(10, 0).is_err(), "Setting an invalid range should fail");
+        hscroll.set_range(0, 50).expect("Failed to set the range");
+        assert!(hscroll.range() == (0, 50) && hscroll.value() == 50, "Value should be clamped to the new range");
+
+        vscroll.set_value(30);
+        assert!(vscroll.value() == 30, "Value do not match");
+
+        hscroll.set_value(20);
+        vscroll.set_page_size(5);
+    }
+
+    // Scroll events
+    let scrolls: Rc<RefCell<Vec<(u64, ScrollType, u32)>>> = Rc::new(RefCell::new(Vec::new()));
+    for id in [1001u64, 1002].iter() {
+        let scrolls = scrolls.clone();
+        ui.bind(id, &1004, Event::Scroll, move |_, id, _, args| {
+            if let &EventArgs::Scroll{kind, value} = args {
+                scrolls.borrow_mut().push((*id, kind, value));
+            }
+        });
+    }
+
+    let (window, hscroll, vscroll) = match (ui.handle_of(&1000), ui.handle_of(&1001), ui.handle_of(&1002)) {
+        (Ok(AnyHandle::HWND(w)), Ok(AnyHandle::HWND(h)), Ok(AnyHandle::HWND(v))) => (w, h, v),
+        _ => panic!("Bad handles")
+    };
+
+    unsafe {
+        SendMessageW(window, WM_HSCROLL, SB_LINEDOWN as WPARAM, hscroll as LPARAM);
+        SendMessageW(window, WM_VSCROLL, SB_PAGEUP as WPARAM, vscroll as LPARAM);
+    }
+
+    assert_eq!(*scrolls.borrow(), vec![(1001, ScrollType::SmallInc, 21), (1002, ScrollType::LargeDec, 25)]);
+    assert!(ui.get::<HorizontalScrollBar>(&1001).unwrap().value() == 21, "Value should be updated by the scroll");
+    assert!(ui.get::<VerticalScrollBar>(&1002).unwrap().value() == 25, "Value should be updated by the scroll");
+
+    // Track info
+    {
+        let hscroll = ui.get::<HorizontalScrollBar>(&1001).expect("Control not found");
+        assert!(hscroll.set_track_info(10, 0, 0, 0).is_err(), "Setting an invalid range should fail");
+
+        hscroll.set_track_info(0, 200, 150, 20).expect("Failed to set the track info");
+        assert!(hscroll.range() == (0, 200) && hscroll.value() == 150 && hscroll.page_size() == 20, "Track info do not match");
+
+        hscroll.set_track_info(0, 100, 150, 20).expect("Failed to set the track info");
+        assert!(hscroll.value() == 81, "Value should be clamped to the range minus the page size");
+
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        assert!(window.set_track_info(true, 10, 0, 0, 0).is_err(), "Setting an invalid range should fail");
+
+        window.set_track_info(true, 0, 500, 100, 50).expect("Failed to set the track info");
+        window.set_track_info(false, 0, 300, 20, 30).expect("Failed to set the track info");
+        assert_eq!(window.track_info(true), (0, 500, 100, 50));
+        assert_eq!(window.track_info(false), (0, 300, 20, 30));
+    }
+}
+
+#[test]
+fn test_build_window_errors() {
+    use nwg::custom::{build_window, WindowParams, AnyHandle};
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    let parent = match ui.handle_of(&1000) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let params = WindowParams{title: "", class_name: "NWG_CLASS_NOT_REGISTERED", position: (0, 0), size: (10, 10), flags: 0, ex_flags: Some(0), parent: parent};
+    match unsafe{ build_window(params) } {
+        Err(SystemError::SystemCall{function, ..}) => assert!(function.contains("NWG_CLASS_NOT_REGISTERED"), "The error should hold the class name"),
+        Err(e) => panic!("Should have returned SystemError::SystemCall, got {:?}", e),
+        Ok(_) => panic!("An unregistered class should not create a window")
+    }
+
+    ui.unpack(&1000);
+    ui.commit().expect("Commit was not successful");
+
+    let params = WindowParams{title: "", class_name: "STATIC", position: (0, 0), size: (10, 10), flags: 0, ex_flags: Some(0), parent: parent};
+    match unsafe{ build_window(params) } {
+        Err(SystemError::ParentNotFound) => {},
+        Err(e) => panic!("Should have returned SystemError::ParentNotFound, got {:?}", e),
+        Ok(_) => panic!("A window with a destroyed parent should not be created")
+    }
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_nwg_id_derive() {
+    #[derive(NwgId)]
+    enum AppId { MainWindow, OkButton }
+
+    let ui: Ui<AppId> = Ui::new().unwrap();
+    ui.pack_control(&AppId::MainWindow, WindowT{title: "", position:(-600,-600), size:(100, 100), resizable:true, visible:true, disabled:false, exit_on_close:true, icon_big: None, icon_small: None});
+    ui.pack_control(&AppId::OkButton, ButtonT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: AppId::MainWindow, font: None, command_link: false, note: None});
+    ui.commit().expect("Commit was not successful");
+
+    assert!(ui.get::<Button>(&AppId::OkButton).is_ok(), "Control not found");
+}
+
+#[test]
+fn test_resources_drop() {
+    use nwg::custom::{Resource, AnyHandle};
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    let window = ui.handle_of(&1000).expect("Control not found");
+    let snapshot = Bitmap::capture(&window).expect("Window capture failed");
+    let bitmap_handle = match snapshot.handle() { AnyHandle::HBITMAP(h) => h, _ => panic!("Bad handle") };
+
+    // A resource that does not own its handle must not release it when dropped
+    drop(Bitmap::from_hbitmap(bitmap_handle, false));
+    let copy = Bitmap::clone_hbitmap(bitmap_handle).expect("A borrowed bitmap should not be deleted when dropped");
+
+    let icon = Icon::from_bitmap(&snapshot, None).expect("Failed to create an icon from a bitmap");
+    let icon_handle = match icon.handle() { AnyHandle::HICON(h) => h, _ => panic!("Bad handle") };
+    drop(Icon::from_hicon(icon_handle, false));
+    assert!(Icon::clone_hicon(icon_handle).is_ok(), "A borrowed icon should not be destroyed when dropped");
+
+    let cursor = Cursor::from_bitmap(&snapshot, None, (5, 5)).expect("Failed to create a cursor from a bitmap");
+    let cursor_handle = match cursor.handle() { AnyHandle::HCURSOR(h) => h, _ => panic!("Bad handle") };
+
+    // Owned handles are released when the resources are dropped
+    drop(snapshot);
+    drop(icon);
+    drop(cursor);
+    assert!(Bitmap::clone_hbitmap(bitmap_handle).is_err(), "An owned bitmap should be deleted when dropped");
+    assert!(Icon::clone_hicon(icon_handle).is_err(), "An owned icon should be destroyed when dropped");
+    assert!(Icon::clone_hicon(unsafe{ ::std::mem::transmute(cursor_handle) }).is_err(), "An owned cursor should be destroyed when dropped");
+
+    drop(copy);
+}
+
+#[test]
+#[cfg(feature = "drag-drop")]
+fn test_list_view_drag() {
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, ListViewT{position: (0, 0), size: (300, 100), visible: true, disabled: false, tab_stop: true, group: false, style: ListViewStyle::Report, parent: 1000, font: None, auto_tooltip: false});
+    ui.commit().expect("Commit was not successful");
+
+    let lv = ui.get::<ListView>(&1001).expect("Control not found");
+    lv.add_column(0, 100, "Name");
+    lv.insert_item(0, 0, "file.txt");
+
+    // Without a selection, the drag operation is not started
+    assert!(lv.get_selected_indexes().is_empty());
+    match lv.drag_selection() { Ok(false) => {}, _ => panic!("A list view without selection should not start a drag operation") }
+}
+
+#[test]
+fn test_docking() {
+    let ui = setup_ui();
+
+    let btn_t = ButtonT{text: "TEST", position:(10, 10), size: (50, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, btn_t.clone());
+    ui.pack_control(&1002, btn_t.clone());
+    ui.pack_control(&1003, btn_t.clone());
+    ui.commit().expect("Commit was not successful");
+
+    {
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        window.set_size(200, 150);
+
+        // The controls created last are docked first
+        let fill = ui.handle_of(&1001).expect("Control not found");
+        assert!(fill.dock().is_none(), "Controls should not be docked by default");
+        assert!(fill.set_dock(Some(Dock::Fill)));
+        assert!(ui.handle_of(&1002).expect("Control not found").set_dock(Some(Dock::Top)));
+        assert!(ui.handle_of(&1003).expect("Control not found").set_dock(Some(Dock::Top)));
+        assert!(fill.dock() == Some(Dock::Fill));
+
+        let (first, second, fill) = (ui.get::<Button>(&1003).unwrap(), ui.get::<Button>(&1002).unwrap(), ui.get::<Button>(&1001).unwrap());
+        assert!(first.get_position() == (0, 0) && first.get_size() == (200, 30), "First top control is not docked");
+        assert!(second.get_position() == (0, 30) && second.get_size() == (200, 30), "Controls docked to the same edge should stack");
+        assert!(fill.get_position() == (0, 60) && fill.get_size() == (200, 90), "Fill control should take the remaining space");
+
+        window.set_size(300, 200);
+        assert!(second.get_size() == (300, 30), "Docked control should be resized with the window");
+        assert!(fill.get_size() == (300, 140), "Fill control should be resized with the window");
+    }
+
+    assert!(ui.handle_of(&1001).unwrap().set_dock(None));
+    assert!(ui.handle_of(&1001).unwrap().dock().is_none());
+}
+
+#[test]
+fn test_relative_placement() {
+    let ui = setup_ui();
+
+    let btn_t = ButtonT{text: "TEST", position:(10, 10), size: (50, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1000, font: None, command_link: false, note: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, btn_t);
+    ui.commit().expect("Commit was not successful");
+
+    let window = ui.get::<Window>(&1000).expect("Control not found");
+    window.set_size(200, 100);
+
+    let placement = RelativePlacement{x: Length::Percent(50.0), y: Length::Px(10), width: Length::Percent(25.0), height: Length::Px(30)};
+    let handle = ui.handle_of(&1001).expect("Control not found");
+    assert!(handle.relative_placement().is_none());
+    assert!(handle.set_relative_placement(Some(placement)));
+    assert!(handle.relative_placement() == Some(placement));
+
+    let btn = ui.get::<Button>(&1001).expect("Control not found");
+    assert!(btn.get_position() == (100, 10) && btn.get_size() == (50, 30), "Relative placement should be applied immediately");
+
+    window.set_size(400, 100);
+    assert!(btn.get_position() == (200, 10) && btn.get_size() == (100, 30), "Relative placement should be updated when the parent is resized");
+
+    assert!(handle.set_relative_placement(None));
+    window.set_size(200, 100);
+    assert!(btn.get_position() == (200, 10), "Control should keep its position once the relative placement is removed");
+}
+
+#[test]
+#[cfg(feature = "testing")]
+fn test_nested_layouts() {
+    use nwg::custom::AnyHandle;
+    use nwg::testing::get_window_layout_passes;
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+
+    // 5 nested group boxes that fill their parent
+    for i in 0..5u64 {
+        let parent = if i == 0 { 1000 } else { 1000 + i };
+        ui.pack_control(&(1001 + i), GroupBoxT{text: "", position: (0, 0), size: (50, 50), visible: true, disabled: false, align: HTextAlign::Left, parent: parent, font: None});
+    }
+    ui.commit().expect("Commit was not successful");
+
+    for i in 0..5u64 {
+        assert!(ui.handle_of(&(1001 + i)).unwrap().set_dock(Some(Dock::Fill)));
+    }
+
+    let ids: Vec<u64> = (1000..1006).collect();
+    let handles: Vec<_> = ids.iter().map(|id| match ui.handle_of(id) { Ok(AnyHandle::HWND(h)) => h, _ => panic!("Bad handle") }).collect();
+    let before: Vec<usize> = handles.iter().map(|&h| unsafe{ get_window_layout_passes(h) }).collect();
+
+    {
+        let window = ui.get::<Window>(&1000).expect("Control not found");
+        window.set_size(300, 200);
+    }
+
+    for (i, &h) in handles.iter().enumerate() {
+        let passes = unsafe{ get_window_layout_passes(h) };
+        assert!(passes == before[i] + 1, "Layout {} was computed {} times for one resize", i, passes - before[i]);
+    }
+
+    let inner = ui.get::<GroupBox>(&1005).expect("Control not found");
+    assert!(inner.get_size().0 > 0, "Innermost group box should fill its parent");
+}
+
+#[test]
+fn test_form_state() {
+    let ui = setup_ui();
+
+    let ti_t = TextInputT::<_, u64, _> {
+        text: "TEST",
+        position: (0, 0), size: (100, 30), 
+        visible: true, disabled: false, tab_stop: true, group: false, readonly: false, password: false,
+        limit: 10,
+        placeholder: None,
+        parent: 1000,
+        font: None
+    };
+    let cb_t = CheckBoxT{text: "TEST", position:(10, 10), size: (100, 30), visible: true, disabled: false, tab_stop: true, group: false, checkstate: CheckState::Checked, tristate: false, parent: 1000, font: None};
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, ti_t);
+    ui.pack_control(&1002, cb_t);
+    ui.pack_control(&2000, window());
+    ui.commit().expect("Commit was not successful");
+
+    let state = ui.form_state(&1000).expect("Failed to read the form state");
+    assert!(state.len() == 2, "Form state should only contain the input controls of the window");
+    assert!(state.iter().any(|&(id, ref v)| id == 1001 && *v == FormValue::Text("TEST".to_string())));
+    assert!(state.iter().any(|&(id, ref v)| id == 1002 && *v == FormValue::Check(CheckState::Checked)));
+    assert!(ui.form_state(&2000).expect("Failed to read the form state").is_empty());
+
+    {
+        ui.get::<TextInput>(&1001).unwrap().set_text("OTHER");
+        ui.get::<CheckBox>(&1002).unwrap().set_checkstate(CheckState::Unchecked);
+    }
+
+    // Values of controls that are no longer in the ui are skipped
+    let mut saved = state.clone();
+    saved.push((1003, FormValue::Text("GONE".to_string())));
+    assert!(ui.restore_form_state(&saved) == 2, "Only the existing controls should be restored");
+
+    assert!(&ui.get::<TextInput>(&1001).unwrap().get_text() == "TEST");
+    assert!(ui.get::<CheckBox>(&1002).unwrap().get_checkstate() == CheckState::Checked);
+
+    match ui.form_state(&9999) { Err(Error::KeyNotFound) => {}, r => panic!("Should have returned Error::KeyNotFound, got {:?}", r) }
+}
+
+#[test]
+fn test_build_ui() {
+    use nwg::custom::{AnyHandle, get_window_long};
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    let specs = [
+        ControlSpec{kind: ControlKind::Label, id: 1001, text: "Name", position: (5, 5), size: (80, 25), visible: true, disabled: false,
+          tab_stop: false, group: false, placement: None},
+        ControlSpec{kind: ControlKind::TextInput, id: 1002, text: "", position: (90, 5), size: (100, 25), visible: true, disabled: false,
+          tab_stop: true, group: true, placement: None},
+        ControlSpec{kind: ControlKind::CheckBox, id: 1003, text: "Remember", position: (5, 35), size: (100, 25), visible: true, disabled: true,
+          tab_stop: false, group: false, placement: None},
+        ControlSpec{kind: ControlKind::Button, id: 1004, text: "Ok", position: (5, 65), size: (100, 25), visible: true, disabled: false,
+          tab_stop: true, group: false, placement: Some(RelativePlacement{x: Length::Percent(50.0), y: Length::Px(65), width: Length::Percent(25.0), height: Length::Px(25)})},
+    ];
+
+    let handles = ui.build_ui(&1000, &specs).expect("Failed to build the controls");
+    assert!(handles.iter().map(|&(id, _)| id).collect::<Vec<u64>>() == vec![1001, 1002, 1003, 1004], "Handles should follow the specs order");
+
+    let window = ui.handle_of(&1000).unwrap();
+    assert!(handles.iter().all(|&(_, ref h)| h.is_child_of(&window)), "Controls should be created in the parent");
+
+    assert!(&ui.get::<Label>(&1001).unwrap().get_text() == "Name");
+    assert!(ui.get::<TextInput>(&1002).unwrap().get_position() == (90, 5));
+    assert!(!ui.get::<CheckBox>(&1003).unwrap().get_enabled());
+    assert!(&ui.get::<Button>(&1004).unwrap().get_text() == "Ok");
+
+    let style = |id: u64| match ui.handle_of(&id) {
+        Ok(AnyHandle::HWND(h)) => get_window_long(h, winapi::GWL_STYLE) as u32,
+        _ => panic!("Bad handle")
+    };
+
+    assert!(style(1002) & (winapi::WS_TABSTOP | winapi::WS_GROUP) == winapi::WS_TABSTOP | winapi::WS_GROUP, "The flags should be applied");
+    assert!(style(1003) & winapi::WS_TABSTOP == 0, "The flags should be applied");
+
+    let (_, _, w, _) = window.client_rect().unwrap();
+    assert!(handles[3].1.relative_placement().is_some(), "The placement should be applied");
+    assert!(ui.get::<Button>(&1004).unwrap().get_position() == ((w / 2) as i32, 65), "The button should be placed in its layout cell");
+    assert!(ui.get::<Button>(&1004).unwrap().get_size() == (w / 4, 25), "The button should be placed in its layout cell");
+
+    // The commands waiting in the queue are not executed
+    ui.pack_value(&2000, 5u32);
+    let lists = [
+        ControlSpec{kind: ControlKind::ComboBox, id: 1005, text: "A\nB", position: (5, 95), size: (100, 25), visible: true, disabled: false,
+          tab_stop: true, group: false, placement: None},
+        ControlSpec{kind: ControlKind::ListBox, id: 1006, text: "A\nB\nC", position: (5, 125), size: (100, 60), visible: true, disabled: false,
+          tab_stop: true, group: false, placement: None},
+    ];
+
+    ui.build_ui(&1000, &lists).expect("Failed to build the controls");
+    assert!(!ui.has_id(&2000), "build_ui should not commit the queued commands");
+    assert!(ui.get::<ComboBox<String>>(&1005).unwrap().len() == 2, "Each line of the text should be an item");
+    assert!(ui.get::<ListBox<String>>(&1006).unwrap().len() == 3, "Each line of the text should be an item");
+
+    // All or nothing
+    let mut invalid = lists.to_vec();
+    invalid[0].id = 1007;
+    invalid[1].id = 1001;
+    match ui.build_ui(&1000, &invalid) { Err(Error::KeyExists) => {}, r => panic!("Should have returned Error::KeyExists, got {:?}", r) }
+    assert!(!ui.has_id(&1007), "The controls created before the failure should be removed");
+    assert!(ui.has_id(&1001), "The existing controls should be kept");
+
+    match ui.build_ui(&9999, &lists[0..1]) { Err(Error::KeyNotFound) => {}, r => panic!("Should have returned Error::KeyNotFound, got {:?}", r) }
+
+    ui.commit().expect("Commit was not successful");
+    assert!(ui.has_id(&2000));
+}
+
+#[test]
+fn test_label() {
+    let ui = setup_ui();
+
+    let lbl_t = LabelT{text: "A label with a text that is way too long", position: (0, 0), size: (50, 25), visible: true, disabled: false,
+      align: HTextAlign::Left, parent: 1000, font: None, auto_tooltip: true};
+
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1001, lbl_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, Label);
+    test_position!(ui, &1001, Label);
+    test_size!(ui, &1001, Label);
+    test_enabled!(ui, &1001, Label);
+
+    {
+        let lbl = ui.get::<Label>(&1001).expect("Control not found");
+        assert!(&lbl.get_text() == "A label with a text that is way too long", "Label text do not match");
+    }
+
+    ui.unpack(&1001).expect("Unpack was not successful");
+}
+
+#[test]
+fn test_auto_tooltip() {
+    use std::mem;
+    use nwg::custom::{AnyHandle, auto_tooltip_text};
+    use winapi::{HWND, RECT, LPARAM, WPARAM, WM_MOUSEMOVE, LVM_GETSUBITEMRECT, LVIR_LABEL};
+    use user32::SendMessageW;
+
+    const LONG_TEXT: &'static str = "A text that is way too long to fit in the control";
+
+    fn handle(ui: &Ui<u64>, id: u64) -> HWND {
+        match ui.handle_of(&id) {
+            Ok(AnyHandle::HWND(h)) => h,
+            _ => panic!("Bad handle")
+        }
+    }
+
+    // Move the mouse over the point and return the text of the tooltip
+    fn hover(handle: HWND, x: i32, y: i32) -> Option<String> {
+        unsafe {
+            SendMessageW(handle, WM_MOUSEMOVE, 0, (((y as u32) << 16) | ((x as u32) & 0xFFFF)) as LPARAM);
+            auto_tooltip_text(handle)
+        }
+    }
+
+    // The middle of the left side of a list view cell
+    fn cell_point(handle: HWND, row: i32, col: i32) -> (i32, i32) {
+        let mut r = RECT{left: LVIR_LABEL, top: col, right: 0, bottom: 0};
+        unsafe{ SendMessageW(handle, LVM_GETSUBITEMRECT, row as WPARAM, mem::transmute(&mut r)); }
+        (r.left + 2, (r.top + r.bottom) / 2)
+    }
+
+    let ui = setup_ui();
+
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, LabelT{text: LONG_TEXT, position: (0, 0), size: (50, 25), visible: true, disabled: false,
+      align: HTextAlign::Left, parent: 1000, font: None, auto_tooltip: true});
+    ui.pack_control(&1002, LabelT{text: "A", position: (0, 30), size: (50, 25), visible: true, disabled: false,
+      align: HTextAlign::Left, parent: 1000, font: None, auto_tooltip: true});
+    ui.pack_control(&1003, ListBoxT{collection: vec![LONG_TEXT, "A"], position: (0, 60), size: (60, 90), visible: true, disabled: false,
+      tab_stop: true, group: false, readonly: false, multi_select: false, tab_stops: false, parent: 1000, font: None, auto_tooltip: true});
+    ui.pack_control(&1004, ListViewT{position: (0, 0), size: (100, 100), visible: true, disabled: false, tab_stop: true, group: false,
+      style: ListViewStyle::Report, parent: 1000, font: None, auto_tooltip: true});
+    ui.pack_control(&1005, MultiColumnListBoxT{position: (0, 0), size: (100, 100), visible: true, disabled: false, tab_stop: true,
+      group: false, multi_select: false, parent: 1000, font: None, auto_tooltip: true});
+    ui.pack_control(&1006, LabelT{text: LONG_TEXT, position: (0, 0), size: (50, 25), visible: true, disabled: false,
+      align: HTextAlign::Left, parent: 1000, font: None, auto_tooltip: false});
+    ui.commit().expect("Commit was not successful");
+
+    // Labels
+    assert_eq!(hover(handle(&ui, 1001), 5, 5), Some(LONG_TEXT.to_string()));
+    assert_eq!(hover(handle(&ui, 1002), 5, 5), Some("".to_string()));
+    assert_eq!(hover(handle(&ui, 1006), 5, 5), None, "A control without auto_tooltip should not have a tooltip");
+
+    // Listbox: the first item is truncated, not the second one
+    let listbox = handle(&ui, 1003);
+    assert_eq!(hover(listbox, 5, 5), Some(LONG_TEXT.to_string()));
+    assert_eq!(hover(listbox, 5, 60), Some("".to_string()), "The tooltip should be cleared when the mouse is not over a truncated item");
+
+    // List view: the first column is truncated, not the second one
+    let list_view = handle(&ui, 1004);
+    {
+        let lv = ui.get::<ListView>(&1004).expect("Control not found");
+        lv.add_column(0, 40, "First");
+        lv.add_column(1, 50, "Second");
+        lv.insert_item(0, 0, LONG_TEXT);
+        lv.insert_item(0, 1, "A");
+    }
+
+    let (x, y) = cell_point(list_view, 0, 1);
+    assert_eq!(hover(list_view, x, y), Some("".to_string()));
+    let (x, y) = cell_point(list_view, 0, 0);
+    assert_eq!(hover(list_view, x, y), Some(LONG_TEXT.to_string()));
+
+    // Multi column listbox
+    let listbox = handle(&ui, 1005);
+    {
+        let lb = ui.get::<MultiColumnListBox>(&1005).expect("Control not found");
+        lb.add_column(0, 40, "First");
+        lb.add_column(1, 50, "Second");
+        lb.add_item(vec!["A".to_string(), LONG_TEXT.to_string()]);
+    }
+
+    let (x, y) = cell_point(listbox, 0, 1);
+    assert_eq!(hover(listbox, x, y), Some(LONG_TEXT.to_string()));
+    let (x, y) = cell_point(listbox, 0, 0);
+    assert_eq!(hover(listbox, x, y), Some("".to_string()));
+}
+
+#[test]
+#[cfg(feature = "tree-view")]
+fn test_tree_view_auto_tooltip() {
+    use std::mem;
+    use nwg::custom::{AnyHandle, auto_tooltip_text};
+    use winapi::{RECT, HTREEITEM, LPARAM, WPARAM, TRUE, WM_MOUSEMOVE, TVM_GETITEMRECT};
+    use user32::SendMessageW;
+
+    const LONG_TEXT: &'static str = "A text that is way too long to fit in the control";
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.pack_control(&1001, TreeViewT{position: (0, 0), size: (80, 100), visible: true, disabled: false, tab_stop: true, group: false, lines: true,
+      buttons: true, parent: 1000, font: None, auto_tooltip: true});
+    ui.commit().expect("Commit was not successful");
+
+    let (long, short) = {
+        let tv = ui.get::<TreeView>(&1001).expect("Control not found");
+        (tv.insert_item(None, LONG_TEXT, None::<u32>), tv.insert_item(None, "A", None::<u32>))
+    };
+
+    let tree_view = match ui.handle_of(&1001) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
+
+    let hover = |item: HTREEITEM| -> Option<String> {
+        unsafe {
+            let mut r: RECT = mem::zeroed();
+            *(&mut r as *mut RECT as *mut HTREEITEM) = item;
+            SendMessageW(tree_view, TVM_GETITEMRECT, TRUE as WPARAM, mem::transmute(&mut r));
+
+            let (x, y) = (r.left + 2, (r.top + r.bottom) / 2);
+            SendMessageW(tree_view, WM_MOUSEMOVE, 0, (((y as u32) << 16) | ((x as u32) & 0xFFFF)) as LPARAM);
+            auto_tooltip_text(tree_view)
+        }
+    };
+
+    assert_eq!(hover(long.0), Some(LONG_TEXT.to_string()));
+    assert_eq!(hover(short.0), Some("".to_string()));
+}
+
+#[test]
+fn test_message_window() {
+    use std::thread;
+
+    let worker = thread::spawn(|| {
+        let ui = setup_ui();
+
+        ui.pack_control(&1000, MessageWindowT{title: "TEST"});
+        ui.commit().expect("Commit was not successful");
+
+        let window = ui.get::<MessageWindow>(&1000).expect("Control not found");
+        assert!(&window.get_title() == "TEST", "Title do not match");
+
+        window.set_title("OTHER");
+        assert!(&window.get_title() == "OTHER", "Title do not match");
+    });
+
+    worker.join().expect("Message window thread panicked");
+}
+
+#[test]
+fn test_hotkey_input() {
+    let ui = setup_ui();
+
+    let hk_t = HotkeyInputT{position: (0, 0), size: (100, 25), visible: true, disabled: false, tab_stop: true, group: false, hotkey: None, parent: 1000, font: None};
+
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1001, hk_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, HotkeyInput);
+    test_position!(ui, &1001, HotkeyInput);
+    test_size!(ui, &1001, HotkeyInput);
+    test_enabled!(ui, &1001, HotkeyInput);
+
+    {
+        let hk = ui.get::<HotkeyInput>(&1001).expect("Control not found");
+        let shortcut = Shortcut{key: 0x41, ctrl: true, alt: false, shift: true};
+
+        assert!(hk.get_hotkey() == None, "Hotkey should be empty");
+        hk.set_hotkey(Some(shortcut));
+        assert!(hk.get_hotkey() == Some(shortcut), "Hotkey do not match");
+        hk.set_hotkey(None);
+        assert!(hk.get_hotkey() == None, "Hotkey should be empty");
+    }
+}
+
+#[test]
+fn test_ip_address_input() {
+    let ui = setup_ui();
+
+    let ip_t = IpAddressInputT{position: (0, 0), size: (150, 25), visible: true, disabled: false, tab_stop: true, group: false, address: Some([127, 0, 0, 1]), parent: 1000, font: None};
+
+    ui.pack_control(&1000, window());
+
+    // pack test
+    ui.pack_control(&1001, ip_t);
+    ui.commit().expect("Commit was not successful");
+
+    // methods test
+    test_visibility!(ui, &1001, IpAddressInput);
+    test_position!(ui, &1001, IpAddressInput);
+    test_size!(ui, &1001, IpAddressInput);
+    test_enabled!(ui, &1001, IpAddressInput);
 
     {
-        let checkbox = ui.get::<CheckBox>(&1010).expect("Control not found");
+        let ip = ui.get::<IpAddressInput>(&1001).expect("Control not found");
 
-        assert!(checkbox.get_checkstate() == CheckState::Checked);
-        checkbox.set_checkstate(CheckState::Unchecked);
-        assert!(checkbox.get_checkstate() == CheckState::Unchecked);
+        assert!(ip.get_address() == [127, 0, 0, 1], "Address do not match");
+        ip.set_address([192, 168, 1, 254]);
+        assert!(ip.get_address() == [192, 168, 1, 254], "Address do not match");
 
-        assert!(checkbox.get_text().as_str() == "TEST");
-        checkbox.set_text("Waloo");
-        assert!(checkbox.get_text().as_str() == "Waloo");
+        ip.clear();
+        assert!(ip.is_blank(), "Address should be blank");
+
+        assert!(ip.set_field_range(0, 10, 20).is_ok());
+        assert!(ip.set_field_range(4, 10, 20).is_err());
+        assert!(ip.set_field_range(0, 20, 10).is_err());
     }
 }
 
 #[test]
-fn test_listbox() {
+fn test_animation() {
     let ui = setup_ui();
 
-    let col = vec!["Foo", "FooBar", "Excelsior", "wOOsh"];
-    let mut lb_t = ListBoxT {
-        collection: col.clone(),
-        position:(10, 50), size: (100, 90),
-        visible: true, disabled: false,  readonly: false, multi_select: false,
-        parent: 1000,
-        font: None 
-    };
+    let anim_t = AnimationT{source: "", position: (0, 0), size: (100, 100), visible: true, disabled: false, autoplay: false, center: true, transparent: true, parent: 1000};
 
     ui.pack_control(&1000, window());
 
     // pack test
-    ui.pack_control(&1002, lb_t.clone());
-    lb_t.multi_select = true;
-    ui.pack_control(&1003, lb_t);
+    ui.pack_control(&1001, anim_t.clone());
     ui.commit().expect("Commit was not successful");
 
+    let mut bad_anim_t = anim_t.clone();
+    bad_anim_t.source = "this_file_does_not_exist.avi";
+    ui.pack_control(&1002, bad_anim_t);
+    match ui.commit() { Err(Error::System(SystemError::AnimationOpen)) => {}, r => panic!("Should have returned SystemError::AnimationOpen, got {:?}", r) }
+
     // methods test
-    test_visibility!(ui, &1002, ListBox<&'static str>);
-    test_position!(ui, &1002, ListBox<&'static str>);
-    test_size!(ui, &1002, ListBox<&'static str>, (200, 192)); // Listbox height is forced to be rounded to match the items height.
-    test_enabled!(ui, &1002, ListBox<&'static str>);
+    test_visibility!(ui, &1001, Animation);
+    test_position!(ui, &1001, Animation);
+    test_size!(ui, &1001, Animation);
+    test_enabled!(ui, &1001, Animation);
 
     {
-        let mut lb = ui.get_mut::<ListBox<&'static str>>(&1002).expect("Control not found!");
+        let anim = ui.get::<Animation>(&1001).expect("Control not found");
+        assert!(anim.open_file("this_file_does_not_exist.avi").is_err());
+        assert!(!anim.is_playing());
+    }
+}
 
-        assert!(lb.get_readonly() == false, "Listbox should not be readonly");
-        assert!(lb.get_multi_select() == false, "Listbox should not be multi select");
+#[test]
+fn test_pager() {
+    let ui = setup_ui();
 
-        lb.set_readonly(true);
-        assert!(lb.get_readonly() == true, "Listbox should be readonly");
-        lb.set_readonly(false);
+    let pager_t = PagerT{position: (0, 0), size: (100, 30), visible: true, disabled: false, vertical: false, autoscroll: false, parent: 1000};
+    let btn_t = ButtonT{text: "TEST", position:(0, 0), size: (300, 30), visible: true, disabled: false, tab_stop: true, group: false, parent: 1001, font: None, command_link: false, note: None};
 
-        lb.set_multi_select(true);
-        assert!(lb.get_multi_select() == true, "Listbox should be multi-select");
-        lb.set_multi_select(false);
+    ui.pack_control(&1000, window());
 
-        assert!(lb.collection() == &col, "Collection do not match");
-        assert!(lb.collection_mut() == &col, "Collection do not match");
-        assert!(lb.len() == 4, "Collection length should be 4");
+    // pack test
+    ui.pack_control(&1001, pager_t);
+    ui.pack_control(&1002, btn_t);
+    ui.commit().expect("Commit was not successful");
 
-        lb.push("Foohoy!");
-        assert!(lb.collection() == &["Foo", "FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
-        assert!(lb.get_string(4).unwrap().as_str() == "Foohoy!", "Item text do not match");
-        assert!(lb.len() == 5, "Collection length should be 5");
+    // methods test
+    test_visibility!(ui, &1001, Pager);
+    test_position!(ui, &1001, Pager);
+    test_size!(ui, &1001, Pager);
+    test_enabled!(ui, &1001, Pager);
 
-        lb.remove(0);
-        assert!(lb.collection() == &["FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
-        assert!(lb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match");
+    {
+        let pager = ui.get::<Pager>(&1001).expect("Control not found");
+        let btn = ui.handle_of(&1002).expect("Control not found");
+        let font = nwg::custom::AnyHandle::HFONT(::std::ptr::null_mut());
 
-        assert!(lb.get_selected_index().is_none(), "No index should be selected");
-        assert!(lb.get_selected_indexes().len() == 0, "Indexes vector length should be 0");
+        assert!(pager.set_child(&btn).is_ok());
+        assert!(pager.set_child(&font).is_err());
 
-        lb.set_selected_index(1);
+        pager.set_button_size(15);
+        assert!(pager.get_button_size() == 15, "Button size do not match");
+    }
+}
 
-        assert!(lb.get_selected_index() == Some(1), "Current index is not 1");
-        assert!(lb.get_selected_indexes().len() == 0, "Indexes vector length should be 0");
-        assert!(lb.index_selected(1), "Index 1 is not selected");
-        assert!(lb.index_selected(2) == false, "Index 2 is selected");
-        assert!(lb.index_selected(665) == false, "Index 665 is selected");
-        assert!(lb.len_selected() == 1, "Selected length is not 1");
+#[test]
+fn test_rebar() {
+    let ui = setup_ui();
 
-        lb.set_selected_index(usize::max_value());
-        assert!(lb.get_selected_index().is_none(), "No index should be selected");
+    let rebar_t = RebarT{position: (0, 0), size: (300, 30), visible: true, disabled: false, fixed_order: false, band_borders: true, parent: 1000};
+    let btn_t = ButtonT{text: "TEST", position:(0, 0), size: (100, 25), visible: true, disabled: false, tab_stop: true, group: false, parent: 1001, font: None, command_link: false, note: None};
 
-        assert!(lb.find_string("foo", false) == Some(0), "find_string shoud have returned 0");
-        assert!(lb.find_string("foo", true) == None, "find_string shoud have returned None");
-        assert!(lb.find_string("Foohoy!", true) == Some(3), "find_string shoud have returned None");
+    ui.pack_control(&1000, window());
 
-        assert!(lb.get_string(100) == None, "Item text should be None");
+    // pack test
+    ui.pack_control(&1001, rebar_t);
+    ui.pack_control(&1002, btn_t.clone());
+    ui.pack_control(&1003, btn_t);
+    ui.commit().expect("Commit was not successful");
 
-        lb.collection_mut().remove(0);
-        assert!(lb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match"); // Ui and inner collection not synced
-        lb.sync();
-        assert!(lb.get_string(0).unwrap().as_str() == "Excelsior", "Item text do not match"); // Ui and inner collection synced
+    // methods test
+    test_visibility!(ui, &1001, Rebar);
+    test_position!(ui, &1001, Rebar);
+    test_enabled!(ui, &1001, Rebar);
 
-        lb.clear();
-        assert!(lb.len() == 0, "Length is not 0");
+    {
+        let rebar = ui.get::<Rebar>(&1001).expect("Control not found");
+        let btn1 = ui.handle_of(&1002).expect("Control not found");
+        let btn2 = ui.handle_of(&1003).expect("Control not found");
+
+        assert!(rebar.add_band(&btn1, "First", false).is_ok());
+        assert!(rebar.add_band(&btn2, "Second", true).is_ok());
+        assert!(rebar.band_count() == 2, "Band count do not match");
+        assert!(rebar.row_count() == 2, "Row count do not match");
+
+        rebar.remove_band(1);
+        assert!(rebar.band_count() == 1, "Band count do not match");
     }
+}
 
-    {
-        let lb = ui.get::<ListBox<&'static str>>(&1003).expect("Control not found!");
+#[test]
+fn test_pump_until() {
+    use std::time::Duration;
 
-        assert!(lb.get_multi_select() == true, "Listbox should not be multi select");
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
 
-        assert!(lb.get_selected_indexes().len() == 0, "Indexes vector length should be 0");
+    assert!(pump_until(|| true, Duration::from_millis(0)), "Predicate should succeed immediately");
+    assert!(!pump_until(|| false, Duration::from_millis(20)), "Predicate should time out");
 
-        lb.set_index_selected(0, true);
-        lb.set_index_selected(2, true);
-        assert!(lb.len_selected() == 2, "Selected length is not 2");
+    let mut calls = 0;
+    assert!(pump_until(|| { calls += 1; calls == 3 }, Duration::from_secs(5)), "Predicate should succeed on the third call");
+}
 
-        assert!(lb.get_selected_indexes() == [0, 2], "Selected indexes do not match");
+#[test]
+fn test_visual_styles_thread() {
+    use std::thread;
+
+    // The activation context is per thread, so a worker thread must be able to enable the visual styles too
+    assert!(enable_visual_styles().is_ok(), "Failed to enable the visual styles");
+    let worker = thread::spawn(|| {
+        let first = enable_visual_styles().is_ok();
+        let second = enable_visual_styles().is_ok();
+        let ui = setup_ui();
+        ui.pack_control(&1000, window());
+        first && second && ui.commit().is_ok()
+    });
 
-        lb.set_index_selected(0, false);
-        assert!(lb.len_selected() == 1, "Selected length is not 1");
-        assert!(lb.get_selected_indexes() == [2], "Selected indexes do not match");
+    assert!(worker.join().unwrap(), "Failed to enable the visual styles on a worker thread");
+}
 
-        lb.set_index_selected(usize::max_value(), true);
-        assert!(lb.len_selected() == 4, "Selected length is not 4");
-        assert!(lb.get_selected_indexes() == [0,1,2,3], "Selected indexes do not match");
+#[test]
+fn test_visual_styles_from_resource() {
+    use std::thread;
 
-        lb.set_range_selected(0, 2, false);
-        assert!(lb.len_selected() == 1, "Selected length is not 1");
-        
-        lb.set_range_selected(0, 6, true);
-        assert!(lb.len_selected() == 4, "Selected length is not 4");
-        assert!(lb.get_selected_indexes() == [0,1,2,3], "Selected indexes do not match");
-    }
-    
+    // The test executable has no manifest with this ID
+    let missing = thread::spawn(|| enable_visual_styles_from_resource(0x7FFF).is_err());
+    assert!(missing.join().unwrap(), "Activating a missing manifest should fail");
+
+    // The context is activated per thread, so every thread gets the same result
+    let first = thread::spawn(|| enable_visual_styles_from_resource(1).is_ok()).join().unwrap();
+    let second = thread::spawn(|| enable_visual_styles_from_resource(1).is_ok()).join().unwrap();
+    assert!(first == second, "Every thread should be able to activate the manifest");
+
+    // Once the visual styles are enabled on a thread, the function has no effect
+    let enabled = thread::spawn(|| enable_visual_styles().is_ok() && enable_visual_styles_from_resource(0x7FFF).is_ok());
+    assert!(enabled.join().unwrap(), "The visual styles should already be enabled");
 }
 
 #[test]
-fn test_timer() {
-    // TODO
+fn test_keep_awake() {
+    assert!(keep_awake_state().is_none(), "The system should be allowed to sleep by default");
+
+    {
+        let _guard = keep_awake(KeepAwake::System);
+        assert!(keep_awake_state() == Some(KeepAwake::System), "Keep awake state do not match");
+
+        {
+            let _guard = keep_awake(KeepAwake::Display);
+            assert!(keep_awake_state() == Some(KeepAwake::Display), "Keep awake state do not match");
+        }
+
+        assert!(keep_awake_state() == Some(KeepAwake::System), "The previous state should be restored");
+    }
+
+    assert!(keep_awake_state().is_none(), "The system should be allowed to sleep again");
+
+    let guard = keep_awake(KeepAwake::System);
+    allow_sleep();
+    assert!(keep_awake_state().is_none(), "allow_sleep should cancel the request");
+    drop(guard);
 }
 
 #[test]
-fn test_combobox() {
-    let ui = setup_ui();
+fn test_truncate_text() {
+    assert!(truncate_text("Hello", 5, true).as_str() == "Hello", "Text that fits should not be truncated");
+    assert!(truncate_text("Hello World", 5, false).as_str() == "Hello", "Text do not match");
+    assert!(truncate_text("Hello World", 6, true).as_str() == "Hello\u{2026}", "Text do not match");
+    assert!(truncate_text("Hello", 0, true).as_str() == "", "Text do not match");
 
-    let col = vec!["Foo", "FooBar", "Excelsior", "wOOsh"];
-    let cb_t = ComboBoxT {
-        collection: col.clone(),
-        position:(10, 50), size: (100, 90),
-        visible: true, disabled: false,
-        placeholder: Some("TEST"),
-        parent: 1000,
-        font: None 
-    };
+    // An emoji is a surrogate pair (2 UTF-16 units) and must not be split
+    assert!(truncate_text("ab\u{1F600}cd", 3, false).as_str() == "ab", "A surrogate pair should not be split");
+    assert!(truncate_text("ab\u{1F600}cd", 4, false).as_str() == "ab\u{1F600}", "Text do not match");
+    assert!(truncate_text("ab\u{1F600}cd", 4, true).as_str() == "ab\u{2026}", "A surrogate pair should not be split");
+
+    // CJK characters are one UTF-16 unit
+    assert!(truncate_text("\u{6F22}\u{5B57}\u{6F22}\u{5B57}", 3, true).as_str() == "\u{6F22}\u{5B57}\u{2026}", "Text do not match");
 
+    let ui = setup_ui();
     ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
 
-    // pack test
-    ui.pack_control(&1002, cb_t);
+    let window = ui.get::<Window>(&1000).expect("Control not found");
+    window.set_title_truncated("C:\\\u{6F22}\u{5B57}\\\u{1F600}\u{1F600}.txt", 6, true);
+    assert!(window.get_title().as_str() == "C:\\\u{6F22}\u{5B57}\u{2026}", "Title do not match");
+}
+
+#[test]
+fn test_dpi_awareness_context() {
+    let before = thread_dpi_awareness();
+
+    match dpi_awareness_context(DpiAwareness::PerMonitorAware) {
+        Ok(guard) => {
+            assert_eq!(thread_dpi_awareness(), Some(DpiAwareness::PerMonitorAware));
+            drop(guard);
+            assert_eq!(thread_dpi_awareness(), before, "The previous awareness should be restored");
+        },
+        Err(_) => {
+            // Per thread awareness is not supported by this version of Windows
+            assert_eq!(before, None);
+        }
+    }
+}
+
+#[test]
+fn test_dpi_changed() {
+    use nwg::custom::AnyHandle;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let ui = setup_ui();
+    ui.pack_control(&1000, window());
     ui.commit().expect("Commit was not successful");
 
-    // methods test
-    test_visibility!(ui, &1002, ComboBox<&'static str>);
-    test_position!(ui, &1002, ComboBox<&'static str>);
-    test_enabled!(ui, &1002, ComboBox<&'static str>);
+    let window = match ui.handle_of(&1000) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
+    };
 
-    {
-        let mut cb = ui.get_mut::<ComboBox<&'static str>>(&1002).expect("Control not found!");
+    let received: Rc<RefCell<Vec<(u32, f32, (i32, i32), (u32, u32))>>> = Rc::new(RefCell::new(Vec::new()));
+    let log = received.clone();
+    ui.bind(&1000, &5000, Event::DpiChanged, move |_, _, _, args| {
+        if let &EventArgs::Dpi{dpi, scale, position, size} = args {
+            log.borrow_mut().push((dpi, scale, position, size));
+        }
+    });
 
-        assert!(cb.collection() == &col, "Collection do not match");
-        assert!(cb.collection_mut() == &col, "Collection do not match");
-        assert!(cb.len() == 4, "Collection length should be 4");
+    // The system sends the new DPI in both words of `w` and the suggested window rectangle in `l`
+    let rect = winapi::RECT{left: 10, top: 20, right: 310, bottom: 220};
+    unsafe{ user32::SendMessageW(window, winapi::WM_DPICHANGED, (144 << 16) | 144, &rect as *const winapi::RECT as winapi::LPARAM); }
 
-        cb.push("Foohoy!");
-        assert!(cb.collection() == &["Foo", "FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
-        assert!(cb.get_string(4).unwrap().as_str() == "Foohoy!", "Item text do not match");
-        assert!(cb.len() == 5, "Collection length should be 5");
+    assert!(*received.borrow() == vec![(144, 1.5, (10, 20), (300, 200))], "Dpi event do not match: {:?}", *received.borrow());
+}
 
-        cb.remove(0);
-        assert!(cb.collection() == &["FooBar", "Excelsior", "wOOsh", "Foohoy!"], "Collection do not match");
-        assert!(cb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match");
+#[test]
+fn test_gdi_cache() {
+    use nwg::custom::{cached_pen, cached_brush, cached_font, clear_gdi_cache, gdi_cache_len, gradient_fill, draw_text_cached};
+    use gdi32::{CreateCompatibleDC, DeleteDC};
+    use winapi::RECT;
+
+    unsafe {
+        let pen = cached_pen((255, 0, 0), 2);
+        assert!(!pen.is_null(), "Failed to create a pen");
+        assert!(pen == cached_pen((255, 0, 0), 2), "The pen should be reused");
+        assert!(pen != cached_pen((255, 0, 0), 3), "Pens with different widths should not be shared");
+
+        let font = cached_font("Arial", 12, FONT_WEIGHT_BOLD, FONT_DECO_ITALIC, FontQuality::ClearType);
+        assert!(!font.is_null() && font == cached_font("Arial", 12, FONT_WEIGHT_BOLD, FONT_DECO_ITALIC, FontQuality::ClearType), "The font should be reused");
+        assert!(gdi_cache_len() == 3);
+
+        let grayscale = cached_font("Arial", 12, FONT_WEIGHT_BOLD, FONT_DECO_ITALIC, FontQuality::Grayscale);
+        assert!(!grayscale.is_null() && grayscale != font, "Fonts with different qualities should not be shared");
+        assert!(gdi_cache_len() == 4);
+
+        // The drawing helpers use the cache
+        let hdc = CreateCompatibleDC(::std::ptr::null_mut());
+        let rect = RECT{left: 0, top: 0, right: 50, bottom: 50};
+        assert!(gradient_fill(hdc, &rect, (10, 20, 30), (10, 20, 30), GradientDirection::Vertical), "Failed to fill the rect");
+        assert!(gdi_cache_len() == 5, "A solid fill should use a cached brush");
+
+        let text_font = FontT{ family: "Arial", size: 12, weight: FONT_WEIGHT_BOLD, decoration: FONT_DECO_ITALIC, quality: FontQuality::ClearType };
+        assert!(draw_text_cached(hdc, &text_font, "Hello", &rect, TEXT_LEFT) > 0, "Failed to draw the text");
+        assert!(gdi_cache_len() == 5, "The cached font should be reused to draw the text");
+        DeleteDC(hdc);
+
+        // The cache is capped, the least recently used objects are deleted
+        for i in 0..100u8 {
+            assert!(!cached_brush((i, i, i)).is_null(), "Failed to create a brush");
+        }
+        assert!(gdi_cache_len() == 64, "The cache should be capped");
 
-        assert!(cb.get_selected_index().is_none(), "No index should be selected");
-        assert!(cb.get_selected_text().as_str() == "");
+        clear_gdi_cache();
+        assert!(gdi_cache_len() == 0, "The cache should be empty");
+    }
+}
 
-        cb.set_selected_index(1);
-        assert!(cb.get_selected_text().as_str() == "Excelsior");
-        assert!(cb.get_selected_index() == Some(1), "Current index is not 1");
+#[test]
+fn test_draw_bitmap_alpha() {
+    use nwg::custom::draw_bitmap_alpha;
+    use gdi32::{CreateDIBSection, CreateCompatibleDC, SelectObject, DeleteDC};
+    use winapi::{BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DWORD, RECT, BOOL, c_void};
+    use std::{mem, ptr, slice};
+
+    // The drawing is batched, so it must be flushed before the pixels are read
+    extern "system" { fn GdiFlush() -> BOOL; }
+
+    // Create a 4x1 32 bits bitmap filled with `color` (BGRA). Return the bitmap and its pixels.
+    unsafe fn dib(color: [u8; 4]) -> (Bitmap, &'static mut [u8]) {
+        let mut header: BITMAPINFO = mem::zeroed();
+        header.bmiHeader = BITMAPINFOHEADER {
+            biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD, biWidth: 4, biHeight: -1, biPlanes: 1, biBitCount: 32,
+            biCompression: BI_RGB, biSizeImage: 0, biXPelsPerMeter: 0, biYPelsPerMeter: 0, biClrUsed: 0, biClrImportant: 0
+        };
+
+        let mut bits: *mut c_void = ptr::null_mut();
+        let handle = CreateDIBSection(ptr::null_mut(), &header, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+        assert!(!handle.is_null(), "Failed to create the bitmap");
+
+        let pixels = slice::from_raw_parts_mut(bits as *mut u8, 16);
+        for px in pixels.chunks_mut(4) { px.copy_from_slice(&color); }
+        (Bitmap::from_hbitmap(handle, true), pixels)
+    }
 
-        cb.set_selected_index(usize::max_value());
-        assert!(cb.get_selected_index().is_none(), "No index should be selected");
+    unsafe {
+        let (dest, dest_pixels) = dib([0, 0, 0, 0]);
+        let dest_handle = match dest.handle() { nwg::custom::AnyHandle::HBITMAP(h) => h, _ => unreachable!() };
+        let hdc = CreateCompatibleDC(ptr::null_mut());
+        let old = SelectObject(hdc, dest_handle as _);
+        let rect = RECT{left: 0, top: 0, right: 4, bottom: 1};
+
+        // Without per-pixel alpha, a 32 bits bitmap with an empty alpha channel is drawn as opaque
+        let (no_alpha, _) = dib([255, 255, 255, 0]);
+        assert!(draw_bitmap_alpha(hdc, &rect, &no_alpha, &rect, 255, false), "Failed to draw the bitmap");
+        GdiFlush();
+        assert!(dest_pixels[0..3] == [255, 255, 255], "A bitmap drawn without per-pixel alpha should be opaque");
+
+        // With per-pixel alpha, the alpha channel of the bitmap is used
+        for b in dest_pixels.iter_mut() { *b = 0; }
+        let (transparent, src_pixels) = dib([255, 255, 255, 255]);
+        src_pixels[4..8].copy_from_slice(&[0, 0, 0, 0]);
+        assert!(draw_bitmap_alpha(hdc, &rect, &transparent, &rect, 255, true), "Failed to draw the bitmap");
+        GdiFlush();
+        assert!(dest_pixels[0..3] == [255, 255, 255], "Opaque pixels should be drawn");
+        assert!(dest_pixels[4..7] == [0, 0, 0], "Transparent pixels should not be drawn");
+
+        SelectObject(hdc, old);
+        DeleteDC(hdc);
+    }
+}
 
-        assert!(cb.find_string("foo", false) == Some(0), "find_string shoud have returned 0");
-        assert!(cb.find_string("foo", true) == None, "find_string shoud have returned None");
-        assert!(cb.find_string("Foohoy!", true) == Some(3), "find_string shoud have returned None");
+#[test]
+fn test_draw_text() {
+    use nwg::custom::draw_text;
+    use gdi32::{CreateDIBSection, CreateCompatibleDC, SelectObject, DeleteDC, DeleteObject, SetBkMode, GetBkMode, SetBkColor, SetTextColor, GetCurrentObject};
+    use winapi::{BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DWORD, RECT, BOOL, OPAQUE, OBJ_FONT, c_void};
+    use std::{mem, ptr, slice};
 
-        assert!(cb.get_string(100) == None, "Item text should be None");
+    extern "system" { fn GdiFlush() -> BOOL; }
 
-        cb.collection_mut().remove(0);
-        assert!(cb.get_string(0).unwrap().as_str() == "FooBar", "Item text do not match"); // Ui and inner collection not synced
-        cb.sync();
-        assert!(cb.get_string(0).unwrap().as_str() == "Excelsior", "Item text do not match"); // Ui and inner collection synced
+    const WHITE: [u8; 3] = [255, 255, 255];
+    const RED: [u8; 3] = [0, 0, 255];
 
-        cb.clear();
-        assert!(cb.len() == 0, "Length is not 0");
+    let ui = setup_ui();
+    ui.pack_resource(&1000, FontT{ family: "Arial", size: 16, weight: FONT_WEIGHT_BOLD, decoration: FONT_DECO_NORMAL, quality: FontQuality::None });
+    ui.commit().expect("Commit was not successful");
+    let font = ui.get::<Font>(&1000).expect("Failed to get the font");
+
+    unsafe {
+        // A 64x20 white 32 bits bitmap
+        let mut header: BITMAPINFO = mem::zeroed();
+        header.bmiHeader = BITMAPINFOHEADER {
+            biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD, biWidth: 64, biHeight: -20, biPlanes: 1, biBitCount: 32,
+            biCompression: BI_RGB, biSizeImage: 0, biXPelsPerMeter: 0, biYPelsPerMeter: 0, biClrUsed: 0, biClrImportant: 0
+        };
+
+        let mut bits: *mut c_void = ptr::null_mut();
+        let bitmap = CreateDIBSection(ptr::null_mut(), &header, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+        assert!(!bitmap.is_null(), "Failed to create the bitmap");
+        let pixels = slice::from_raw_parts_mut(bits as *mut u8, 64 * 20 * 4);
+        for b in pixels.iter_mut() { *b = 255; }
+
+        let hdc = CreateCompatibleDC(ptr::null_mut());
+        let old_bitmap = SelectObject(hdc, bitmap as _);
+        let old_font = GetCurrentObject(hdc, OBJ_FONT);
+
+        // An opaque red background would be visible if the text background was filled
+        SetBkMode(hdc, OPAQUE);
+        SetBkColor(hdc, 0x0000FF);
+        SetTextColor(hdc, 0x000000);
+
+        let rect = RECT{left: 0, top: 0, right: 32, bottom: 20};
+        let height = draw_text(hdc, &font, "WWWWWWWWWW", &rect, TEXT_LEFT | TEXT_SINGLE_LINE);
+        GdiFlush();
+
+        assert!(height > 0, "Failed to draw the text");
+        assert!(GetBkMode(hdc) == OPAQUE, "The background mode should be restored");
+        assert!(GetCurrentObject(hdc, OBJ_FONT) == old_font, "The font should be restored");
+
+        let pixel = |x: usize, y: usize| [pixels[(y*64+x)*4], pixels[(y*64+x)*4+1], pixels[(y*64+x)*4+2]];
+        let inside: Vec<[u8; 3]> = (0..20).flat_map(|y| (0..32).map(move |x| (x, y))).map(|(x, y)| pixel(x, y)).collect();
+        let outside: Vec<[u8; 3]> = (0..20).flat_map(|y| (32..64).map(move |x| (x, y))).map(|(x, y)| pixel(x, y)).collect();
+
+        assert!(inside.iter().any(|px| *px != WHITE), "The text should be drawn in the rect");
+        assert!(inside.iter().all(|px| *px != RED), "The background of the text should not be filled");
+        assert!(outside.iter().all(|px| *px == WHITE), "The text should be clipped to the rect");
+
+        SelectObject(hdc, old_bitmap);
+        DeleteDC(hdc);
+        DeleteObject(bitmap as _);
     }
 }
 
 #[test]
-fn text_textinput() {
+fn test_work_area() {
+    use nwg::custom::{AnyHandle, work_area, fit_maximized_window, get_window_client_rect};
+
     let ui = setup_ui();
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
 
-    let ti_t = TextInputT::<_, &'static str, _> {
-        text: "TEST",
-        position: (0, 0), size: (100, 30), 
-        visible: true, disabled: false, readonly: false, password: false,
-        limit: 10,
-        placeholder: None,
-        parent: 1000,
-        font: None
+    let handle = match ui.handle_of(&1000) {
+        Ok(AnyHandle::HWND(h)) => h,
+        _ => panic!("Bad handle")
     };
 
-    ui.pack_control(&1000, window());
+    let window = ui.get::<Window>(&1000).expect("Control not found");
+    let (_, _, w, h) = unsafe{ work_area(handle) };
+    assert!(w > 0 && h > 0, "Work area should not be empty");
 
-    // pack test
-    ui.pack_control(&1001, ti_t);
-    ui.commit().expect("Commit was not successful");
+    assert!(!unsafe{ fit_maximized_window(handle) }, "A window that is not maximized should not be resized");
 
-    // methods test
-    test_visibility!(ui, &1001, TextInput);
-    test_position!(ui, &1001, TextInput);
-    test_size!(ui, &1001, TextInput);
-    test_enabled!(ui, &1001, TextInput);
+    let placement = WindowPlacement{state: PlacementState::Maximized, position: (10, 10), size: (200, 150), restore_maximized: false};
+    window.set_placement(&placement);
+    let client_before = unsafe{ get_window_client_rect(handle) };
 
-    {
-        let tinput = ui.get::<TextInput>(&1001).expect("Control not found");
+    assert!(unsafe{ fit_maximized_window(handle) }, "A maximized window should be resized");
+    assert!(unsafe{ get_window_client_rect(handle) } == client_before, "The maximized window should keep the same size if the work area did not change");
+}
 
-        assert!(tinput.get_text().as_str() == "TEST");
-        tinput.set_text("Waloo");
-        assert!(tinput.get_text().as_str() == "Waloo");
+#[test]
+fn test_mouse_settings() {
+    use nwg::mouse::{system_double_click_time, system_drag_threshold};
 
-        assert!(tinput.get_readonly() == false);
-        tinput.set_readonly(true);
-        assert!(tinput.get_readonly() == true);
+    assert!(system_double_click_time() > 0, "Double click time should not be 0");
 
-        assert!(tinput.get_password() == false);
-        tinput.set_password(true);
-        assert!(tinput.get_password() == true);
+    let (x, y) = system_drag_threshold();
+    assert!(x > 0 && y > 0, "Drag threshold should not be 0");
+}
 
-        assert!(tinput.get_limit() == 10);
-        tinput.set_limit(10_000);
-        assert!(tinput.get_limit() == 10_000);
+#[test]
+fn test_ui_brushes() {
+    use nwg::custom::{AnyHandle, clear_gdi_cache};
+    use user32::SendMessageW;
+    use gdi32::GetObjectType;
+    use winapi::{WM_THEMECHANGED, HGDIOBJ};
+
+    let ui = setup_ui();
+
+    let brush = ui.brush((255, 0, 0));
+    assert!(!brush.is_null(), "Failed to create a brush");
+    assert!(brush == ui.brush((255, 0, 0)), "The brush should be reused");
+    assert!(brush != ui.brush((0, 255, 0)), "Brushes with different colors should not be shared");
+
+    // The brushes of a ui are never evicted and do not depend on the GDI cache of the thread
+    for i in 0..100u8 {
+        assert!(!ui.brush((i, i, i)).is_null(), "Failed to create a brush");
     }
-}
\ No newline at end of file
+    unsafe{ clear_gdi_cache(); }
+    assert!(brush == ui.brush((255, 0, 0)), "The brush should still be cached");
+
+    // A theme change deletes the brushes
+    ui.pack_control(&1000, window());
+    ui.commit().expect("Commit was not successful");
+
+    let handle = match ui.handle_of(&1000) { Ok(AnyHandle::HWND(h)) => h, _ => panic!("Bad handle") };
+    unsafe{ SendMessageW(handle, WM_THEMECHANGED, 0, 0); }
+    assert!(unsafe{ GetObjectType(brush as HGDIOBJ) } == 0, "The brush should be deleted after a theme change");
+    assert!(!ui.brush((255, 0, 0)).is_null(), "The brush should be created again after a theme change");
+}